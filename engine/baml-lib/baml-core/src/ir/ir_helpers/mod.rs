@@ -342,6 +342,27 @@ impl IRHelper for IntermediateRepr {
             }
             BamlValue::Media(_) => anyhow::bail!("Could not unify Media with {:?}", field_type),
 
+            BamlValue::Temporal(t)
+                if FieldType::Primitive(TypeValue::Temporal(t.temporal_type()))
+                    .is_subtype_of(&field_type) =>
+            {
+                Ok(BamlValueWithMeta::Temporal(t, field_type))
+            }
+            BamlValue::Temporal(t) => {
+                anyhow::bail!(
+                    "Could not unify {} with {:?}",
+                    t.temporal_type(),
+                    field_type
+                )
+            }
+
+            BamlValue::Message(m)
+                if FieldType::Primitive(TypeValue::Message).is_subtype_of(&field_type) =>
+            {
+                Ok(BamlValueWithMeta::Message(m, field_type))
+            }
+            BamlValue::Message(_) => anyhow::bail!("Could not unify Message with {:?}", field_type),
+
             BamlValue::Enum(name, val) => {
                 if FieldType::Enum(name.clone()).is_subtype_of(&field_type) {
                     Ok(BamlValueWithMeta::Enum(name, val, field_type))
@@ -482,6 +503,10 @@ pub fn infer_type(value: &BamlValue) -> Option<FieldType> {
             Some(FieldType::List(Box::new(item_ty)))
         }
         BamlValue::Media(m) => Some(FieldType::Primitive(TypeValue::Media(m.media_type))),
+        BamlValue::Temporal(t) => {
+            Some(FieldType::Primitive(TypeValue::Temporal(t.temporal_type())))
+        }
+        BamlValue::Message(_) => Some(FieldType::Primitive(TypeValue::Message)),
         BamlValue::Enum(enum_name, _) => Some(FieldType::Enum(enum_name.clone())),
         BamlValue::Class(class_name, _) => Some(FieldType::Class(class_name.clone())),
     };
@@ -1,9 +1,10 @@
 use baml_types::{
-    BamlMap, BamlValue, BamlValueWithMeta, Constraint, ConstraintLevel, FieldType, LiteralValue,
-    TypeValue,
+    BamlMap, BamlMessage, BamlMessagePart, BamlTemporal, BamlValue, BamlValueWithMeta, Constraint,
+    ConstraintLevel, FieldType, LiteralValue, TypeValue,
 };
 use core::result::Result;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use crate::ir::IntermediateRepr;
 
@@ -68,6 +69,37 @@ impl ArgCoercer {
                 },
                 TypeValue::Bool if matches!(value, BamlValue::Bool(_)) => Ok(value.clone()),
                 TypeValue::Null if matches!(value, BamlValue::Null) => Ok(value.clone()),
+                TypeValue::Temporal(temporal_type) => {
+                    let parsed = match value {
+                        BamlValue::Temporal(v) => v.cast_to(*temporal_type).ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Expected a {temporal_type}, got a {}",
+                                v.temporal_type()
+                            )
+                        }),
+                        BamlValue::String(s) => BamlTemporal::parse(*temporal_type, s),
+                        BamlValue::Int(i) => BamlTemporal::from_number(*temporal_type, *i as f64),
+                        BamlValue::Float(f) => BamlTemporal::from_number(*temporal_type, *f),
+                        _ => Err(anyhow::anyhow!("Expected type {:?}, got `{}`", t, value)),
+                    };
+                    match parsed {
+                        Ok(v) => Ok(BamlValue::Temporal(v)),
+                        Err(e) => {
+                            scope.push_error(e.to_string());
+                            Err(())
+                        }
+                    }
+                }
+                TypeValue::Message => match value {
+                    BamlValue::Message(v) => Ok(BamlValue::Message(v.clone())),
+                    BamlValue::Map(kv) => {
+                        self.coerce_message(ir, kv, scope).map(BamlValue::Message)
+                    }
+                    _ => {
+                        scope.push_error(format!("Expected type {:?}, got `{}`", t, value));
+                        Err(())
+                    }
+                },
                 TypeValue::Media(media_type) => match value {
                     BamlValue::Media(v) => Ok(BamlValue::Media(v.clone())),
                     BamlValue::Map(kv) => {
@@ -356,6 +388,128 @@ impl ArgCoercer {
             None => Ok(value),
         }
     }
+
+    /// Builds a `message` from a map like
+    /// `{ role "user", content ["Hi", { type "image", url "..." }] }`. A
+    /// `content` with a single part can be given without the list.
+    fn coerce_message(
+        &self,
+        ir: &IntermediateRepr,
+        kv: &BamlMap<String, BamlValue>,
+        scope: &mut ScopeStack,
+    ) -> Result<BamlMessage, ()> {
+        for key in kv.keys() {
+            if !["role", "content", "name", "metadata"].contains(&key.as_str()) {
+                scope.push_error(format!(
+                    "Invalid property `{}` on message: expected `role`, `content`, `name` or `metadata`",
+                    key
+                ));
+            }
+        }
+
+        let role = match kv.get("role") {
+            Some(BamlValue::String(role)) => role.clone(),
+            Some(v) => {
+                scope.push_error(format!(
+                    "Invalid property `role` on message: expected string, got {}",
+                    v.r#type()
+                ));
+                return Err(());
+            }
+            None => {
+                scope.push_error("Missing property `role` on message".to_string());
+                return Err(());
+            }
+        };
+
+        let parts = match kv.get("content") {
+            Some(BamlValue::List(parts)) => parts.iter().collect::<Vec<_>>(),
+            Some(part) => vec![part],
+            None => {
+                scope.push_error("Missing property `content` on message".to_string());
+                return Err(());
+            }
+        };
+        let mut content = Vec::with_capacity(parts.len());
+        for part in parts {
+            match part {
+                BamlValue::String(text) => content.push(BamlMessagePart::Text(text.clone())),
+                BamlValue::Media(media) => content.push(BamlMessagePart::Media(media.clone())),
+                BamlValue::Map(part) => {
+                    let Some(TypeValue::Media(media_type)) = part
+                        .get("type")
+                        .and_then(|t| t.as_str())
+                        .and_then(|t| TypeValue::from_str(t).ok())
+                    else {
+                        scope.push_error(format!(
+                            "Invalid message part: expected `type` to be image, audio, pdf or video, got `{}`",
+                            BamlValue::Map(part.clone())
+                        ));
+                        return Err(());
+                    };
+                    let media = part
+                        .iter()
+                        .filter(|(key, _)| key.as_str() != "type")
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect();
+                    if let BamlValue::Media(media) = self.coerce_arg(
+                        ir,
+                        &FieldType::Primitive(TypeValue::Media(media_type)),
+                        &BamlValue::Map(media),
+                        scope,
+                    )? {
+                        content.push(BamlMessagePart::Media(media));
+                    }
+                }
+                _ => {
+                    scope.push_error(format!(
+                        "Invalid message part: expected a string or a media map, got `{}`",
+                        part
+                    ));
+                    return Err(());
+                }
+            }
+        }
+
+        let name = match kv.get("name") {
+            Some(BamlValue::String(name)) => Some(name.clone()),
+            Some(BamlValue::Null) | None => None,
+            Some(v) => {
+                scope.push_error(format!(
+                    "Invalid property `name` on message: expected string, got {}",
+                    v.r#type()
+                ));
+                return Err(());
+            }
+        };
+
+        let metadata = match kv.get("metadata") {
+            Some(BamlValue::Map(metadata)) => metadata
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        key.clone(),
+                        serde_json::to_value(value).unwrap_or(serde_json::Value::Null),
+                    )
+                })
+                .collect(),
+            Some(BamlValue::Null) | None => BamlMap::new(),
+            Some(v) => {
+                scope.push_error(format!(
+                    "Invalid property `metadata` on message: expected map, got {}",
+                    v.r#type()
+                ));
+                return Err(());
+            }
+        };
+
+        Ok(BamlMessage {
+            role,
+            content,
+            name,
+            metadata,
+        })
+    }
 }
 
 /// Search a potentially deeply-nested `BamlValue` for any failing asserts,
@@ -399,7 +553,7 @@ fn first_failing_assert_nested<'a>(
 
 #[cfg(test)]
 mod tests {
-    use baml_types::JinjaExpression;
+    use baml_types::{BamlMediaType, JinjaExpression};
 
     use crate::ir::repr::make_test_ir;
 
@@ -439,4 +593,58 @@ mod tests {
         let res = arg_coercer.coerce_arg(&ir, &type_, &value, &mut ScopeStack::new());
         assert!(res.is_err());
     }
+
+    #[test]
+    fn test_message_arg() {
+        let ir = make_test_ir("").unwrap();
+        let arg_coercer = ArgCoercer {
+            span_path: None,
+            allow_implicit_cast_to_string: false,
+        };
+        let message_type = FieldType::Primitive(TypeValue::Message);
+        let image = BamlMap::from([
+            ("type".to_string(), BamlValue::String("image".to_string())),
+            (
+                "url".to_string(),
+                BamlValue::String("https://example.com/cat.png".to_string()),
+            ),
+        ]);
+        let value = BamlValue::Map(BamlMap::from([
+            ("role".to_string(), BamlValue::String("user".to_string())),
+            (
+                "content".to_string(),
+                BamlValue::List(vec![
+                    BamlValue::String("What is this?".to_string()),
+                    BamlValue::Map(image),
+                ]),
+            ),
+        ]));
+
+        let mut scope = ScopeStack::new();
+        let res = arg_coercer.coerce_arg(&ir, &message_type, &value, &mut scope);
+        let message = match res {
+            Ok(BamlValue::Message(message)) => message,
+            other => panic!("Expected a message, got {:?}", other),
+        };
+        assert_eq!(message.role, "user");
+        assert_eq!(
+            message.content[0],
+            BamlMessagePart::Text("What is this?".to_string())
+        );
+        let BamlMessagePart::Media(image) = &message.content[1] else {
+            panic!("Expected an image, got {:?}", message.content[1]);
+        };
+        assert_eq!(image.media_type, BamlMediaType::Image);
+
+        // A message needs a role.
+        let value = BamlValue::Map(BamlMap::from([(
+            "content".to_string(),
+            BamlValue::String("Hi".to_string()),
+        )]));
+        let mut scope = ScopeStack::new();
+        assert!(arg_coercer
+            .coerce_arg(&ir, &message_type, &value, &mut scope)
+            .is_err());
+        assert!(scope.has_errors());
+    }
 }
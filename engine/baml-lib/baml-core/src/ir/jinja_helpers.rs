@@ -49,7 +49,20 @@ pub fn render_expression(
     expression: &JinjaExpression,
     ctx: &HashMap<String, minijinja::Value>,
 ) -> anyhow::Result<String> {
-    let env = get_env();
+    render_expression_with_globals(expression, ctx, vec![])
+}
+
+/// Like `render_expression`, with `globals` added to the environment, e.g.
+/// functions that the expression can call.
+pub fn render_expression_with_globals(
+    expression: &JinjaExpression,
+    ctx: &HashMap<String, minijinja::Value>,
+    globals: Vec<(String, minijinja::Value)>,
+) -> anyhow::Result<String> {
+    let mut env = get_env();
+    for (name, value) in globals {
+        env.add_global(name, value);
+    }
     // In rust string literals, `{` is escaped as `{{`.
     // So producing the string `{{}}` requires writing the literal `"{{{{}}}}"`
     let template = format!(r#"{{{{ {} }}}}"#, expression.0);
@@ -57,6 +70,21 @@ pub fn render_expression(
     Ok(env.render_str(&template, &args_dict)?)
 }
 
+/// Evaluate a bare minijinja expression to a value, e.g. `tickets[0]` with
+/// context `{"tickets": [...]}` returns the first ticket.
+pub fn evaluate_expression(
+    expression: &JinjaExpression,
+    ctx: &HashMap<String, minijinja::Value>,
+) -> anyhow::Result<BamlValue> {
+    use serde::Deserialize;
+
+    let env = get_env();
+    let value = env
+        .compile_expression(&expression.0)?
+        .eval(minijinja::Value::from_serialize(ctx))?;
+    Ok(BamlValue::deserialize(value)?)
+}
+
 // TODO: (Greg) better error handling.
 // TODO: (Greg) Upstream, typecheck the expression.
 pub fn evaluate_predicate(
@@ -163,4 +191,44 @@ mod tests {
             "3.5"
         );
     }
+
+    #[test]
+    fn test_evaluate_expression() {
+        let ctx = vec![(
+            "email".to_string(),
+            BamlValue::Map(
+                vec![
+                    (
+                        "subject".to_string(),
+                        BamlValue::String("Refund".to_string()),
+                    ),
+                    (
+                        "tags".to_string(),
+                        BamlValue::List(vec![
+                            BamlValue::String("billing".to_string()),
+                            BamlValue::String("urgent".to_string()),
+                        ]),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .into(),
+        )]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            evaluate_expression(&JinjaExpression("email.subject".to_string()), &ctx).unwrap(),
+            BamlValue::String("Refund".to_string())
+        );
+        assert_eq!(
+            evaluate_expression(&JinjaExpression("email.tags[1:]".to_string()), &ctx).unwrap(),
+            BamlValue::List(vec![BamlValue::String("urgent".to_string())])
+        );
+        assert_eq!(
+            evaluate_expression(&JinjaExpression("email.tags|length".to_string()), &ctx).unwrap(),
+            BamlValue::Int(2)
+        );
+    }
 }
@@ -1,6 +1,6 @@
 // JSON Schema
 
-use baml_types::TypeValue;
+use baml_types::{BamlTemporalType, TypeValue};
 use serde_json::json;
 
 use super::{
@@ -175,6 +175,14 @@ impl WithJsonSchema for FieldType {
                 TypeValue::Null => json!({
                     "type": "null",
                 }),
+                TypeValue::Temporal(t) => json!({
+                    "type": "string",
+                    "format": match t {
+                        BamlTemporalType::Date => "date",
+                        BamlTemporalType::DateTime => "date-time",
+                        BamlTemporalType::Duration => "duration",
+                    },
+                }),
                 TypeValue::Media(_) => json!({
                     // anyOf either an object that has a uri, or it has a base64 string
                     "type": "object",
@@ -185,6 +193,27 @@ impl WithJsonSchema for FieldType {
                     },
                     "required": ["url"],
                 }),
+                TypeValue::Message => json!({
+                    "type": "object",
+                    "properties": {
+                        "role": {
+                            "type": "string",
+                        },
+                        "content": {
+                            "anyOf": [
+                                { "type": "string" },
+                                { "type": "array" },
+                            ],
+                        },
+                        "name": {
+                            "type": "string",
+                        },
+                        "metadata": {
+                            "type": "object",
+                        },
+                    },
+                    "required": ["role", "content"],
+                }),
             },
             FieldType::List(item) => json!({
                 "type": "array",
@@ -0,0 +1,721 @@
+// JSON Schema -> BAML
+//
+// The inverse of `json_schema.rs`: converts a JSON Schema document (or the
+// `components.schemas` of an OpenAPI document) into BAML classes and enums.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use baml_types::{FieldType, LiteralValue};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+/// The classes and enums found in a JSON Schema document.
+#[derive(Debug, Default)]
+pub struct JsonSchemaImport {
+    pub classes: IndexMap<String, ImportedClass>,
+    pub enums: IndexMap<String, ImportedEnum>,
+    /// The type described by the root of the document, if it describes one
+    /// (as opposed to only holding `definitions`).
+    pub root: Option<FieldType>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportedClass {
+    pub description: Option<String>,
+    pub fields: Vec<ImportedField>,
+}
+
+#[derive(Debug)]
+pub struct ImportedField {
+    /// A valid BAML identifier.
+    pub name: String,
+    /// The original property name, if it is not a valid BAML identifier.
+    pub alias: Option<String>,
+    pub r#type: FieldType,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportedEnum {
+    pub description: Option<String>,
+    pub values: Vec<ImportedEnumValue>,
+}
+
+#[derive(Debug)]
+pub struct ImportedEnumValue {
+    /// A valid BAML identifier.
+    pub name: String,
+    /// The original value, if it is not a valid BAML identifier.
+    pub alias: Option<String>,
+}
+
+impl JsonSchemaImport {
+    /// Convert a JSON Schema document. `root_name` names the class generated
+    /// for the root schema when it has no `title`.
+    pub fn from_json_schema(document: &Value, root_name: &str) -> Result<Self> {
+        Self::from_json_schema_with_taken_names(document, root_name, &HashSet::new())
+    }
+
+    /// Like [`Self::from_json_schema`], but types are renamed (`Name2`,
+    /// `Name3`, ...) rather than take a name in `taken`, e.g. the name of a
+    /// class that already exists.
+    pub fn from_json_schema_with_taken_names(
+        document: &Value,
+        root_name: &str,
+        taken: &HashSet<String>,
+    ) -> Result<Self> {
+        let mut importer = Importer {
+            document,
+            result: JsonSchemaImport::default(),
+            refs: HashMap::new(),
+            in_progress: HashSet::new(),
+            used_names: taken.clone(),
+        };
+
+        for container in ["/definitions", "/$defs", "/components/schemas"] {
+            let Some(Value::Object(definitions)) = document.pointer(container) else {
+                continue;
+            };
+            for key in definitions.keys() {
+                importer.resolve_ref(&format!("#{}/{}", container, escape_pointer(key)))?;
+            }
+        }
+
+        // OpenAPI documents only describe types through their components.
+        if document.get("openapi").is_none() && describes_type(document) {
+            let name = document
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or(root_name);
+            let root = importer
+                .convert(document, &type_name(name), true)
+                .context("Failed to import the root schema")?;
+            importer.result.root = Some(root);
+        }
+
+        Ok(importer.result)
+    }
+
+    /// Render the imported types as BAML source code.
+    pub fn to_baml(&self) -> String {
+        let mut out = String::new();
+
+        for (name, e) in self.enums.iter() {
+            out.push_str(&format!("enum {name} {{\n"));
+            for value in e.values.iter() {
+                out.push_str(&format!("  {}", value.name));
+                if let Some(alias) = &value.alias {
+                    out.push_str(&format!(" @alias({})", string_literal(alias)));
+                }
+                out.push('\n');
+            }
+            if let Some(description) = &e.description {
+                out.push_str(&format!(
+                    "  @@description({})\n",
+                    string_literal(description)
+                ));
+            }
+            out.push_str("}\n\n");
+        }
+
+        for (name, c) in self.classes.iter() {
+            out.push_str(&format!("class {name} {{\n"));
+            for field in c.fields.iter() {
+                out.push_str(&format!("  {} {}", field.name, field.r#type));
+                if let Some(alias) = &field.alias {
+                    out.push_str(&format!(" @alias({})", string_literal(alias)));
+                }
+                if let Some(description) = &field.description {
+                    out.push_str(&format!(" @description({})", string_literal(description)));
+                }
+                out.push('\n');
+            }
+            if let Some(description) = &c.description {
+                out.push_str(&format!(
+                    "  @@description({})\n",
+                    string_literal(description)
+                ));
+            }
+            out.push_str("}\n\n");
+        }
+
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+}
+
+struct Importer<'a> {
+    document: &'a Value,
+    result: JsonSchemaImport,
+    /// `$ref` pointers that have already been converted.
+    refs: HashMap<String, FieldType>,
+    /// `$ref` pointers currently being converted, to detect recursive aliases.
+    in_progress: HashSet<String>,
+    used_names: HashSet<String>,
+}
+
+impl Importer<'_> {
+    fn resolve_ref(&mut self, pointer: &str) -> Result<FieldType> {
+        if let Some(t) = self.refs.get(pointer) {
+            return Ok(t.clone());
+        }
+        let Some(path) = pointer.strip_prefix('#') else {
+            anyhow::bail!("Only local $refs are supported, got {pointer}");
+        };
+        let target = self
+            .document
+            .pointer(path)
+            .with_context(|| format!("Could not resolve $ref {pointer}"))?;
+        if !self.in_progress.insert(pointer.to_string()) {
+            anyhow::bail!("Recursive $ref {pointer} must point to an object schema");
+        }
+
+        let name = path
+            .rsplit('/')
+            .next()
+            .map(unescape_pointer)
+            .unwrap_or_default();
+        let result = if is_class_schema(target) {
+            // Register the class before converting its fields so that
+            // recursive references resolve to it.
+            let name = self.claim_name(&type_name(&name));
+            self.refs
+                .insert(pointer.to_string(), FieldType::class(&name));
+            self.fill_class(target, &name)
+        } else {
+            self.convert(target, &type_name(&name), true)
+        }
+        .with_context(|| format!("Failed to import {pointer}"));
+
+        self.in_progress.remove(pointer);
+        let t = result?;
+        self.refs.insert(pointer.to_string(), t.clone());
+        Ok(t)
+    }
+
+    /// Convert a schema to a BAML type. Objects always become classes; enums
+    /// become named enums only when `named` is set, and are otherwise inlined
+    /// as unions of literals.
+    fn convert(&mut self, schema: &Value, name: &str, named: bool) -> Result<FieldType> {
+        let Value::Object(obj) = schema else {
+            anyhow::bail!("Expected a schema object, got {schema}");
+        };
+
+        let t = self.convert_non_nullable(schema, name, named)?;
+        if obj.get("nullable").and_then(Value::as_bool) == Some(true) {
+            return Ok(make_optional(t));
+        }
+        Ok(t)
+    }
+
+    fn convert_non_nullable(
+        &mut self,
+        schema: &Value,
+        name: &str,
+        named: bool,
+    ) -> Result<FieldType> {
+        if let Some(pointer) = schema.get("$ref").and_then(Value::as_str) {
+            return self.resolve_ref(pointer);
+        }
+
+        if let Some(value) = schema.get("const") {
+            return literal(value).map(FieldType::Literal);
+        }
+
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            return self.convert_enum(schema, values, name, named);
+        }
+
+        for key in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(variants)) = schema.get(key) {
+                let variants = variants
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| self.convert(v, &format!("{name}Option{}", i + 1), false))
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(make_union(variants));
+            }
+        }
+
+        if let Some(Value::Array(parts)) = schema.get("allOf") {
+            return match parts.as_slice() {
+                [single] => self.convert(single, name, named),
+                _ => {
+                    let merged = self.merge_all_of(schema, parts)?;
+                    self.convert_class(&merged, name)
+                }
+            };
+        }
+
+        match schema.get("type") {
+            Some(Value::String(t)) => self.convert_typed(schema, t, name),
+            Some(Value::Array(types)) => {
+                let variants = types
+                    .iter()
+                    .map(|t| match t.as_str() {
+                        Some(t) => self.convert_typed(schema, t, name),
+                        None => anyhow::bail!("Expected a type name, got {t}"),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(make_union(variants))
+            }
+            Some(other) => anyhow::bail!("Expected a type name, got {other}"),
+            None if schema.get("properties").is_some() => self.convert_class(schema, name),
+            None => anyhow::bail!("Schemas without a type are not supported: {schema}"),
+        }
+    }
+
+    fn convert_typed(&mut self, schema: &Value, t: &str, name: &str) -> Result<FieldType> {
+        Ok(match t {
+            "string" => FieldType::string(),
+            "integer" => FieldType::int(),
+            "number" => FieldType::float(),
+            "boolean" => FieldType::bool(),
+            "null" => FieldType::null(),
+            "array" => match schema.get("items") {
+                Some(items @ Value::Object(_)) => {
+                    FieldType::list(self.convert(items, &format!("{name}Item"), false)?)
+                }
+                Some(_) => anyhow::bail!("Tuple-style `items` are not supported"),
+                None => anyhow::bail!("Arrays must specify `items`"),
+            },
+            "object" => {
+                if schema.get("properties").is_some() {
+                    self.convert_class(schema, name)?
+                } else {
+                    match schema.get("additionalProperties") {
+                        Some(values @ Value::Object(_)) => FieldType::map(
+                            FieldType::string(),
+                            self.convert(values, &format!("{name}Value"), false)?,
+                        ),
+                        _ => anyhow::bail!(
+                            "Objects must specify `properties` or an `additionalProperties` schema"
+                        ),
+                    }
+                }
+            }
+            other => anyhow::bail!("Unsupported type: {other}"),
+        })
+    }
+
+    fn convert_enum(
+        &mut self,
+        schema: &Value,
+        values: &[Value],
+        name: &str,
+        named: bool,
+    ) -> Result<FieldType> {
+        let (nulls, values): (Vec<_>, Vec<_>) = values.iter().partition(|v| v.is_null());
+        let strings = values.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>();
+
+        let t = if named && !strings.is_empty() && strings.len() == values.len() {
+            let name = self.claim_name(name);
+            let mut used = HashSet::new();
+            let values = strings
+                .iter()
+                .map(|v| {
+                    let ident = unique(enum_value_name(v), &mut used);
+                    ImportedEnumValue {
+                        alias: (ident != *v).then(|| v.to_string()),
+                        name: ident,
+                    }
+                })
+                .collect();
+            self.result.enums.insert(
+                name.clone(),
+                ImportedEnum {
+                    description: description(schema),
+                    values,
+                },
+            );
+            FieldType::r#enum(&name)
+        } else {
+            make_union(
+                values
+                    .iter()
+                    .map(|v| literal(v).map(FieldType::Literal))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        };
+
+        Ok(if nulls.is_empty() {
+            t
+        } else {
+            make_optional(t)
+        })
+    }
+
+    fn convert_class(&mut self, schema: &Value, name: &str) -> Result<FieldType> {
+        let name = self.claim_name(name);
+        self.fill_class(schema, &name)
+    }
+
+    fn fill_class(&mut self, schema: &Value, name: &str) -> Result<FieldType> {
+        // Reserve the position of the class ahead of the classes its fields
+        // pull in.
+        self.result
+            .classes
+            .insert(name.to_string(), ImportedClass::default());
+
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect::<HashSet<_>>())
+            .unwrap_or_default();
+
+        let mut used = HashSet::new();
+        let mut fields = vec![];
+        if let Some(Value::Object(properties)) = schema.get("properties") {
+            for (key, property) in properties {
+                let ident = unique(field_name(key), &mut used);
+                let t = self
+                    .convert(property, &format!("{name}{}", pascal_case(key)), false)
+                    .with_context(|| format!("Failed to import {name}.{key}"))?;
+                fields.push(ImportedField {
+                    alias: (ident != *key).then(|| key.clone()),
+                    name: ident,
+                    r#type: if required.contains(key.as_str()) {
+                        t
+                    } else {
+                        make_optional(t)
+                    },
+                    description: description(property),
+                });
+            }
+        }
+
+        self.result.classes.insert(
+            name.to_string(),
+            ImportedClass {
+                description: description(schema),
+                fields,
+            },
+        );
+        Ok(FieldType::class(name))
+    }
+
+    /// Flatten `allOf: [A, B, ...]` into a single object schema.
+    fn merge_all_of(&self, schema: &Value, parts: &[Value]) -> Result<Value> {
+        let mut properties = serde_json::Map::new();
+        let mut required = vec![];
+        for part in parts {
+            let part = match part.get("$ref").and_then(Value::as_str) {
+                Some(pointer) => pointer
+                    .strip_prefix('#')
+                    .and_then(|path| self.document.pointer(path))
+                    .with_context(|| format!("Could not resolve $ref {pointer}"))?,
+                None => part,
+            };
+            let Some(Value::Object(props)) = part.get("properties") else {
+                anyhow::bail!("allOf is only supported over object schemas");
+            };
+            properties.extend(props.clone());
+            if let Some(Value::Array(r)) = part.get("required") {
+                required.extend(r.iter().cloned());
+            }
+        }
+
+        let mut merged = serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        if let Some(d) = schema.get("description") {
+            merged["description"] = d.clone();
+        }
+        Ok(merged)
+    }
+
+    fn claim_name(&mut self, name: &str) -> String {
+        unique(name.to_string(), &mut self.used_names)
+    }
+}
+
+/// Whether `schema` is a plain object schema, i.e. one that becomes a class.
+fn is_class_schema(schema: &Value) -> bool {
+    schema.get("properties").is_some()
+        && matches!(schema.get("type"), None | Some(Value::String(_)))
+        && schema
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("object")
+            == "object"
+        && ["$ref", "anyOf", "oneOf", "allOf", "enum", "nullable"]
+            .iter()
+            .all(|key| schema.get(key).is_none())
+}
+
+fn describes_type(schema: &Value) -> bool {
+    [
+        "type",
+        "properties",
+        "$ref",
+        "anyOf",
+        "oneOf",
+        "allOf",
+        "enum",
+    ]
+    .iter()
+    .any(|key| schema.get(key).is_some())
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+}
+
+fn literal(value: &Value) -> Result<LiteralValue> {
+    Ok(match value {
+        Value::String(s) => LiteralValue::String(s.clone()),
+        Value::Bool(b) => LiteralValue::Bool(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => LiteralValue::Int(i),
+            None => anyhow::bail!("Only integer literals are supported, got {n}"),
+        },
+        other => anyhow::bail!("Unsupported literal: {other}"),
+    })
+}
+
+fn make_optional(t: FieldType) -> FieldType {
+    if t.is_optional() {
+        t
+    } else {
+        t.as_optional()
+    }
+}
+
+/// Build a union, pulling `null` variants out into an optional.
+fn make_union(variants: Vec<FieldType>) -> FieldType {
+    let (nulls, mut rest): (Vec<_>, Vec<_>) = variants.into_iter().partition(FieldType::is_null);
+    let t = match rest.len() {
+        0 => return FieldType::null(),
+        1 => rest.remove(0),
+        _ => FieldType::union(rest),
+    };
+    if nulls.is_empty() {
+        t
+    } else {
+        make_optional(t)
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Replace every character that can't appear in a BAML identifier with `_`.
+fn sanitize(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn field_name(key: &str) -> String {
+    if is_identifier(key) {
+        return key.to_string();
+    }
+    let name = sanitize(key);
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        Some(_) => format!("field_{name}"),
+        None => "field".to_string(),
+    }
+}
+
+fn enum_value_name(value: &str) -> String {
+    if is_identifier(value) && value.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return value.to_string();
+    }
+    let name = sanitize(value).to_ascii_uppercase();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        Some(_) => format!("V_{name}"),
+        None => "EMPTY".to_string(),
+    }
+}
+
+fn pascal_case(s: &str) -> String {
+    sanitize(s)
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn type_name(s: &str) -> String {
+    let name = pascal_case(s);
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        Some(_) => format!("T{name}"),
+        None => "Unnamed".to_string(),
+    }
+}
+
+fn unique(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut i = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{name}{i}");
+        i += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+fn escape_pointer(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_pointer(s: &str) -> String {
+    s.replace("~1", "/").replace("~0", "~")
+}
+
+/// Quote `s` as a BAML string, falling back to a raw string when needed.
+fn string_literal(s: &str) -> String {
+    if !s.contains('"') && !s.contains('\n') {
+        return format!("\"{s}\"");
+    }
+    let mut hashes = "#".to_string();
+    while s.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("{hashes}\"{s}\"{hashes}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn import_definitions() {
+        let schema = json!({
+            "title": "resume",
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "Full name" },
+                "years-of-experience": { "type": "integer" },
+                "status": { "$ref": "#/definitions/Status" },
+                "links": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" }
+                },
+                "contact": {
+                    "anyOf": [{ "type": "string" }, { "type": "null" }]
+                },
+            },
+            "required": ["name", "status", "links"],
+            "definitions": {
+                "Status": {
+                    "enum": ["active", "INACTIVE"],
+                    "description": "Employment status"
+                }
+            }
+        });
+
+        let import = JsonSchemaImport::from_json_schema(&schema, "Root").unwrap();
+        assert_eq!(import.root, Some(FieldType::class("Resume")));
+        assert_eq!(
+            import.to_baml(),
+            r#"enum Status {
+  ACTIVE @alias("active")
+  INACTIVE
+  @@description("Employment status")
+}
+
+class Resume {
+  name string @description("Full name")
+  years_of_experience int? @alias("years-of-experience")
+  status Status
+  links map<string, string>
+  contact string?
+}
+"#
+        );
+    }
+
+    #[test]
+    fn import_openapi_components() {
+        let schema = json!({
+            "openapi": "3.0.0",
+            "components": {
+                "schemas": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "value": { "type": "number", "nullable": true },
+                            "children": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/Node" }
+                            },
+                            "kind": { "enum": ["leaf", "branch"] }
+                        },
+                        "required": ["value", "children", "kind"]
+                    }
+                }
+            }
+        });
+
+        let import = JsonSchemaImport::from_json_schema(&schema, "Root").unwrap();
+        assert_eq!(import.root, None);
+        assert_eq!(
+            import.to_baml(),
+            r#"class Node {
+  value float?
+  children Node[]
+  kind ("leaf" | "branch")
+}
+"#
+        );
+    }
+
+    #[test]
+    fn import_avoids_taken_names() {
+        let schema = json!({
+            "title": "Person",
+            "type": "object",
+            "properties": {
+                "role": { "$ref": "#/$defs/Role" }
+            },
+            "required": ["role"],
+            "$defs": {
+                "Role": { "enum": ["admin"] }
+            }
+        });
+
+        let taken = HashSet::from(["Person".to_string(), "Role".to_string()]);
+        let import =
+            JsonSchemaImport::from_json_schema_with_taken_names(&schema, "Root", &taken).unwrap();
+        assert_eq!(import.root, Some(FieldType::class("Person2")));
+        assert_eq!(
+            import.to_baml(),
+            r#"enum Role2 {
+  ADMIN @alias("admin")
+}
+
+class Person2 {
+  role Role2
+}
+"#
+        );
+    }
+}
@@ -1,6 +1,7 @@
 mod ir_helpers;
 pub mod jinja_helpers;
 mod json_schema;
+pub mod json_schema_import;
 pub mod repr;
 mod walker;
 
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 
 use anyhow::{anyhow, Result};
-use baml_types::{Constraint, ConstraintLevel, FieldType, StringOr, UnresolvedValue};
+use baml_types::{
+    Constraint, ConstraintLevel, FieldType, ParserConfig, StringOr, TypeValue, UnresolvedValue,
+};
 use either::Either;
 use indexmap::{IndexMap, IndexSet};
 use internal_baml_parser_database::{
@@ -224,6 +226,8 @@ pub struct NodeAttributes {
     ///
     ///   - @skip becomes ("skip", bool)
     ///   - @alias(...) becomes ("alias", ...)
+    ///   - @stream.done becomes ("stream.done", bool), and likewise for the
+    ///     other @stream.* attributes
     meta: IndexMap<String, UnresolvedValue<()>>,
 
     pub constraints: Vec<Constraint>,
@@ -260,6 +264,9 @@ fn to_ir_attributes(
             dynamic_type,
             skip,
             constraints,
+            stream_done,
+            stream_not_null,
+            stream_with_state,
         } = attributes;
 
         let description = description
@@ -285,10 +292,46 @@ fn to_ir_attributes(
             }
         });
 
-        let meta = vec![description, alias, dynamic_type, skip]
-            .into_iter()
-            .flatten()
-            .collect();
+        let stream_done = stream_done.as_ref().and_then(|v| {
+            if *v {
+                Some(("stream.done".to_string(), UnresolvedValue::Bool(true, ())))
+            } else {
+                None
+            }
+        });
+        let stream_not_null = stream_not_null.as_ref().and_then(|v| {
+            if *v {
+                Some((
+                    "stream.not_null".to_string(),
+                    UnresolvedValue::Bool(true, ()),
+                ))
+            } else {
+                None
+            }
+        });
+        let stream_with_state = stream_with_state.as_ref().and_then(|v| {
+            if *v {
+                Some((
+                    "stream.with_state".to_string(),
+                    UnresolvedValue::Bool(true, ()),
+                ))
+            } else {
+                None
+            }
+        });
+
+        let meta = vec![
+            description,
+            alias,
+            dynamic_type,
+            skip,
+            stream_done,
+            stream_not_null,
+            stream_with_state,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
         (meta, constraints.clone())
     })
 }
@@ -773,6 +816,16 @@ impl Function {
     pub fn configs(&self) -> Option<&Vec<FunctionConfig>> {
         Some(&self.configs)
     }
+
+    pub fn parser_config(&self) -> &ParserConfig {
+        &self.parser_config
+    }
+
+    /// Empty unless the function calls other functions instead of having a
+    /// prompt.
+    pub fn steps(&self) -> &Vec<PipelineStep> {
+        &self.steps
+    }
 }
 
 #[derive(Debug)]
@@ -783,6 +836,21 @@ pub struct Function {
     pub tests: Vec<Node<TestCase>>,
     pub configs: Vec<FunctionConfig>,
     pub default_config: String,
+    pub parser_config: ParserConfig,
+    pub steps: Vec<PipelineStep>,
+}
+
+/// One step of a function declared with `steps { ... }`. The output of the
+/// last step is the output of the function.
+#[derive(Debug)]
+pub struct PipelineStep {
+    pub name: String,
+    pub function: FunctionId,
+    /// Calls the function once per element of this list, bound to `item`.
+    pub for_each: Option<JinjaExpression>,
+    /// Strings holding a `JinjaExpression` are evaluated against the
+    /// parameters, the outputs of earlier steps and `item`.
+    pub args: IndexMap<String, UnresolvedValue<()>>,
 }
 
 #[derive(Debug)]
@@ -791,6 +859,9 @@ pub struct FunctionConfig {
     pub prompt_template: String,
     pub prompt_span: ast::Span,
     pub client: ClientSpec,
+    /// The percentage of calls that use this config. The weights of the
+    /// configs of a function add up to 100.
+    pub weight: u32,
 }
 
 // impl std::fmt::Display for ClientSpec {
@@ -856,39 +927,84 @@ impl WithRepr<Function> for FunctionWalker<'_> {
     }
 
     fn repr(&self, db: &ParserDatabase) -> Result<Function> {
+        let variants = &self.metadata().variants;
+        // Pipelines have no prompt or client of their own.
+        let (configs, steps) = match self.steps() {
+            Some(steps) => (
+                vec![],
+                steps
+                    .iter()
+                    .map(|step| PipelineStep {
+                        name: step.name.0.clone(),
+                        function: step.function.0.clone(),
+                        for_each: step.for_each.as_ref().map(|(expr, _)| expr.clone()),
+                        args: step
+                            .args
+                            .iter()
+                            .map(|(name, (_, value))| (name.clone(), value.without_meta()))
+                            .collect(),
+                    })
+                    .collect(),
+            ),
+            None => (
+                std::iter::once(Ok(FunctionConfig {
+                    name: "default_config".to_string(),
+                    prompt_template: self.jinja_prompt().to_string(),
+                    prompt_span: self.ast_function().span().clone(),
+                    client: match self.client_spec() {
+                        Ok(spec) => spec,
+                        Err(e) => anyhow::bail!("{}", e.message()),
+                    },
+                    // Calls not taken by a variant use the function's own prompt.
+                    weight: 100u32.saturating_sub(variants.iter().map(|v| v.weight).sum()),
+                }))
+                .chain(variants.iter().map(|variant| {
+                    Ok(FunctionConfig {
+                        name: variant.name.0.clone(),
+                        prompt_template: self.variant_jinja_prompt(variant).to_string(),
+                        prompt_span: variant.span.clone(),
+                        client: match self.variant_client_spec(variant) {
+                            Ok(spec) => spec,
+                            Err(e) => anyhow::bail!("{}", e.message()),
+                        },
+                        weight: variant.weight,
+                    })
+                }))
+                .collect::<Result<Vec<_>>>()?,
+                vec![],
+            ),
+        };
+        let inputs = self
+            .ast_function()
+            .input()
+            .expect("msg")
+            .args
+            .iter()
+            .map(|arg| {
+                let field_type = arg.1.field_type.repr(db)?;
+                Ok((arg.0.to_string(), field_type))
+            })
+            .collect::<Result<Vec<_>>>()?;
         Ok(Function {
             name: self.name().to_string(),
-            inputs: self
-                .ast_function()
-                .input()
-                .expect("msg")
-                .args
-                .iter()
-                .map(|arg| {
-                    let field_type = arg.1.field_type.repr(db)?;
-                    Ok((arg.0.to_string(), field_type))
-                })
-                .collect::<Result<Vec<_>>>()?,
             output: self
                 .ast_function()
                 .output()
                 .expect("need block arg")
                 .field_type
                 .repr(db)?,
-            configs: vec![FunctionConfig {
-                name: "default_config".to_string(),
-                prompt_template: self.jinja_prompt().to_string(),
-                prompt_span: self.ast_function().span().clone(),
-                client: match self.client_spec() {
-                    Ok(spec) => spec,
-                    Err(e) => anyhow::bail!("{}", e.message()),
-                },
-            }],
+            configs,
             default_config: "default_config".to_string(),
+            parser_config: self.metadata().parser_config.clone(),
+            steps,
             tests: self
                 .walk_tests()
-                .map(|e| e.node(db))
-                .collect::<Result<Vec<_>>>()?,
+                .map(|e| test_case_nodes(&e, &inputs, db))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+            inputs,
         })
     }
 }
@@ -979,6 +1095,9 @@ pub struct TestCase {
     pub functions: Vec<Node<TestCaseFunction>>,
     pub args: IndexMap<String, UnresolvedValue<()>>,
     pub constraints: Vec<Constraint>,
+    /// The row of the dataset this case was expanded from, by column. Empty
+    /// for tests without a dataset.
+    pub row: IndexMap<String, UnresolvedValue<()>>,
 }
 
 impl WithRepr<TestCaseFunction> for (&ConfigurationWalker<'_>, usize) {
@@ -1035,6 +1154,7 @@ impl WithRepr<TestCase> for ConfigurationWalker<'_> {
                 .map(|(k, (_, v))| Ok((k.clone(), v.without_meta())))
                 .collect::<Result<IndexMap<_, _>>>()?,
             functions,
+            row: IndexMap::new(),
             constraints: <AstWalker<'_, (ValExpId, &str)> as WithRepr<TestCase>>::attributes(
                 self, db,
             )
@@ -1044,6 +1164,71 @@ impl WithRepr<TestCase> for ConfigurationWalker<'_> {
         })
     }
 }
+/// A test case with a dataset becomes one case per row, named `Test[0]`,
+/// `Test[1]`, etc. after the index of the row.
+fn test_case_nodes(
+    walker: &ConfigurationWalker<'_>,
+    inputs: &[(String, FieldType)],
+    db: &ParserDatabase,
+) -> Result<Vec<Node<TestCase>>> {
+    let Some(dataset) = &walker.test_case().dataset else {
+        return Ok(vec![walker.node(db)?]);
+    };
+    let is_csv = dataset.path.0.ends_with(".csv");
+    dataset
+        .rows
+        .iter()
+        .map(|row| {
+            let mut node: Node<TestCase> = walker.node(db)?;
+            node.elem.name = format!("{}[{}]", node.elem.name, row.index);
+            for (arg, (column, _)) in &dataset.columns {
+                if let Some(value) = row.values.get(column) {
+                    let value = match inputs.iter().find(|(name, _)| name == arg) {
+                        Some((_, field_type)) if is_csv => {
+                            csv_value_as(value.without_meta(), field_type)
+                        }
+                        _ => value.without_meta(),
+                    };
+                    node.elem.args.insert(arg.clone(), value);
+                }
+            }
+            node.elem.row = row
+                .values
+                .iter()
+                .map(|(column, value)| (column.clone(), value.without_meta()))
+                .collect();
+            Ok(node)
+        })
+        .collect()
+}
+
+/// CSV values are all strings. Converts the ones passed to `int`, `float` and
+/// `bool` parameters, and empty values passed to optional parameters. Other
+/// values are left as strings, to be checked like any other test argument.
+fn csv_value_as(value: UnresolvedValue<()>, field_type: &FieldType) -> UnresolvedValue<()> {
+    let UnresolvedValue::String(StringOr::Value(text), meta) = &value else {
+        return value;
+    };
+    match field_type {
+        FieldType::Optional(_) if text.is_empty() => UnresolvedValue::Null(*meta),
+        FieldType::Optional(base) | FieldType::Constrained { base, .. } => {
+            csv_value_as(value, base)
+        }
+        FieldType::Primitive(TypeValue::Int) if text.trim().parse::<i64>().is_ok() => {
+            UnresolvedValue::Numeric(text.trim().to_string(), *meta)
+        }
+        FieldType::Primitive(TypeValue::Float) if text.trim().parse::<f64>().is_ok() => {
+            UnresolvedValue::Numeric(text.trim().to_string(), *meta)
+        }
+        FieldType::Primitive(TypeValue::Bool) => match text.trim() {
+            "true" | "True" | "TRUE" => UnresolvedValue::Bool(true, *meta),
+            "false" | "False" | "FALSE" => UnresolvedValue::Bool(false, *meta),
+            _ => value,
+        },
+        _ => value,
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub enum Prompt {
     // The prompt stirng, and a list of input replacer keys (raw key w/ magic string, and key to replace with)
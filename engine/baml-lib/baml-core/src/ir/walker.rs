@@ -61,12 +61,26 @@ impl<'a> Walker<'a, &'a FunctionNode> {
         None
     }
 
-    pub fn required_env_vars(&'a self) -> Result<HashSet<String>> {
-        if let Some(c) = self.elem().configs.first() {
+    /// The environment variables used by the clients of every variant, or
+    /// by the functions that the steps of a pipeline call.
+    pub fn required_env_vars(&self) -> Result<HashSet<String>> {
+        if !self.elem().steps.is_empty() {
+            let mut env_vars = HashSet::new();
+            for step in &self.elem().steps {
+                env_vars.extend(self.db.find_function(&step.function)?.required_env_vars()?);
+            }
+            return Ok(env_vars);
+        }
+        if self.elem().configs.is_empty() {
+            anyhow::bail!("Function {} has no client", self.name())
+        }
+
+        let mut env_vars = HashSet::new();
+        for c in &self.elem().configs {
             match &c.client {
                 ClientSpec::Named(n) => {
                     let client: super::ClientWalker<'a> = self.db.find_client(n)?;
-                    Ok(client.required_env_vars())
+                    env_vars.extend(client.required_env_vars());
                 }
                 ClientSpec::Shorthand(provider, model) => {
                     let options = IndexMap::from_iter([(
@@ -80,17 +94,15 @@ impl<'a> Walker<'a, &'a FunctionNode> {
                         ),
                     )]);
                     let properties = internal_llm_client::PropertyHandler::<()>::new(options, ());
+                    // We likely can't make a shorthand client from the given provider
+                    // if this fails.
                     if let Ok(client) = provider.parse_client_property(properties) {
-                        Ok(client.required_env_vars())
-                    } else {
-                        // We likely can't make a shorthand client from the given provider
-                        Ok(HashSet::new())
+                        env_vars.extend(client.required_env_vars());
                     }
                 }
             }
-        } else {
-            anyhow::bail!("Function {} has no client", self.name())
         }
+        Ok(env_vars)
     }
 
     pub fn walk_impls(
@@ -250,6 +262,18 @@ impl<'a> Walker<'a, (&'a FunctionNode, &'a TestCase)> {
             .collect()
     }
 
+    /// The row of the dataset this case was expanded from, by column.
+    pub fn test_case_row(
+        &self,
+        ctx: &EvaluationContext<'_>,
+    ) -> Result<IndexMap<String, BamlValue>> {
+        self.test_case()
+            .row
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), v.resolve_serde::<BamlValue>(ctx)?)))
+            .collect()
+    }
+
     pub fn function(&'a self) -> Walker<'a, &'a FunctionNode> {
         Walker {
             db: self.db,
@@ -301,6 +325,11 @@ impl<'a> Walker<'a, &'a Class> {
     pub fn inputs(&self) -> &'a Vec<(String, baml_types::FieldType)> {
         self.elem().inputs()
     }
+
+    /// Whether values of this class are only streamed once fully parsed.
+    pub fn stream_done(&self) -> bool {
+        self.item.attributes.get("stream.done").is_some()
+    }
 }
 
 impl<'a> Walker<'a, &'a Client> {
@@ -402,6 +431,22 @@ impl<'a> Walker<'a, &'a Field> {
             .transpose()
     }
 
+    /// Whether the field is only streamed once fully parsed.
+    pub fn stream_done(&self) -> bool {
+        self.item.attributes.get("stream.done").is_some()
+    }
+
+    /// Whether the parent object is held back while streaming until this
+    /// field has a value.
+    pub fn stream_not_null(&self) -> bool {
+        self.item.attributes.get("stream.not_null").is_some()
+    }
+
+    /// Whether the streamed field is wrapped with its completion state.
+    pub fn stream_with_state(&self) -> bool {
+        self.item.attributes.get("stream.with_state").is_some()
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
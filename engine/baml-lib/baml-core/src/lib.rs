@@ -50,6 +50,15 @@ pub fn validate(root_path: &Path, files: Vec<SourceFile>) -> ValidatedSchema {
         let diagnostics = Mutex::new(&mut diagnostics);
         let db = Mutex::new(&mut db);
         files.par_iter().for_each(|file| {
+            // Data files are read by the blocks that refer to them.
+            if file.path_buf().extension().is_some_and(|ext| {
+                internal_baml_parser_database::DATA_FILE_EXTENSIONS
+                    .iter()
+                    .any(|data_ext| ext == *data_ext)
+            }) {
+                db.lock().unwrap().add_data_file(file.clone());
+                return;
+            }
             match internal_baml_schema_ast::parse_schema(root_path, file) {
                 Ok((ast, err)) => {
                     let mut diagnostics = diagnostics.lock().unwrap();
@@ -4,6 +4,7 @@ mod configurations;
 mod cycle;
 mod enums;
 mod functions;
+mod pipelines;
 mod template_strings;
 mod tests;
 mod types;
@@ -20,6 +21,7 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
     enums::validate(ctx);
     classes::validate(ctx);
     functions::validate(ctx);
+    pipelines::validate(ctx);
     clients::validate(ctx);
     template_strings::validate(ctx);
     configurations::validate(ctx);
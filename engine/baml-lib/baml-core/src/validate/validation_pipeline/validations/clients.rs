@@ -28,7 +28,8 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
             | internal_llm_client::UnresolvedClientProperty::Anthropic(_)
             | internal_llm_client::UnresolvedClientProperty::AWSBedrock(_)
             | internal_llm_client::UnresolvedClientProperty::Vertex(_)
-            | internal_llm_client::UnresolvedClientProperty::GoogleAI(_) => {}
+            | internal_llm_client::UnresolvedClientProperty::GoogleAI(_)
+            | internal_llm_client::UnresolvedClientProperty::Mock(_) => {}
             internal_llm_client::UnresolvedClientProperty::RoundRobin(options) => {
                 validate_strategy(options, ctx);
             }
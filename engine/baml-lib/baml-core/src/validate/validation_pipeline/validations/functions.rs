@@ -5,7 +5,10 @@ use crate::validate::validation_pipeline::context::Context;
 use either::Either;
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span};
 
-use internal_baml_schema_ast::ast::{FieldType, TypeExpId, WithIdentifier, WithName, WithSpan};
+use internal_baml_jinja_types::PredefinedTypes;
+use internal_baml_schema_ast::ast::{
+    FieldType, RawString, TypeExpId, WithIdentifier, WithName, WithSpan,
+};
 
 use super::types::validate_type;
 
@@ -45,52 +48,7 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                 defined_types.add_variable(name.name(), ctx.db.to_jinja_type(&t.field_type))
             });
         }
-        match internal_baml_jinja_types::validate_template(
-            template.name(),
-            prompt.raw_value(),
-            &mut defined_types,
-        ) {
-            Ok(_) => {}
-            Err(e) => {
-                let pspan = prompt.span();
-                if let Some(e) = e.parsing_errors {
-                    let range = match e.range() {
-                        Some(range) => range,
-                        None => {
-                            ctx.push_error(DatamodelError::new_validation_error(
-                                &format!("Error parsing jinja template: {}", e),
-                                pspan.clone(),
-                            ));
-                            continue;
-                        }
-                    };
-
-                    let start_offset = pspan.start + range.start;
-                    let end_offset = pspan.start + range.end;
-
-                    let span = Span::new(
-                        pspan.file.clone(),
-                        start_offset as usize,
-                        end_offset as usize,
-                    );
-
-                    ctx.push_error(DatamodelError::new_validation_error(
-                        &format!("Error parsing jinja template: {}", e),
-                        span,
-                    ))
-                } else {
-                    e.errors.iter().for_each(|t| {
-                        let span = t.span();
-                        let span = Span::new(
-                            pspan.file.clone(),
-                            pspan.start + span.start_offset as usize,
-                            pspan.start + span.end_offset as usize,
-                        );
-                        ctx.push_warning(DatamodelWarning::new(t.message().to_string(), span))
-                    })
-                }
-            }
-        }
+        validate_prompt(ctx, template.name(), prompt, &mut defined_types);
         defined_types.end_scope();
         defined_types.errors_mut().clear();
     }
@@ -114,6 +72,11 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
             }
         }
 
+        // Functions with steps are checked in `pipelines`.
+        if func.steps().is_some() {
+            continue;
+        }
+
         // Ensure the client is correct.
         // TODO: message to the user that it should be either a client ref OR an inline client
         match func.client_spec() {
@@ -167,54 +130,71 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
 
             defined_types.add_variable(&name, field_type);
         });
-        match internal_baml_jinja_types::validate_template(
-            func.name(),
-            prompt.raw_value(),
-            &mut defined_types,
-        ) {
-            Ok(_) => {}
-            Err(e) => {
-                let pspan = prompt.span();
-                if let Some(e) = e.parsing_errors {
-                    let range = match e.range() {
-                        Some(range) => range,
-                        None => {
-                            ctx.push_error(DatamodelError::new_validation_error(
-                                &format!("Error parsing jinja template: {}", e),
-                                pspan.clone(),
-                            ));
-                            continue;
-                        }
-                    };
+        validate_prompt(ctx, func.name(), prompt, &mut defined_types);
+
+        for variant in &func.metadata().variants {
+            if variant.client.is_some() {
+                if let Err(e) = func.variant_client_spec(variant) {
+                    ctx.push_error(e);
+                }
+            }
+            if let Some(prompt) = &variant.prompt {
+                defined_types.errors_mut().clear();
+                validate_prompt(ctx, func.name(), prompt, &mut defined_types);
+            }
+        }
+        defined_types.end_scope();
+        defined_types.errors_mut().clear();
+    }
+}
+
+fn validate_prompt(
+    ctx: &mut Context<'_>,
+    name: &str,
+    prompt: &RawString,
+    defined_types: &mut PredefinedTypes,
+) {
+    match internal_baml_jinja_types::validate_template(name, prompt.raw_value(), defined_types) {
+        Ok(_) => {}
+        Err(e) => {
+            let pspan = prompt.span();
+            if let Some(e) = e.parsing_errors {
+                let range = match e.range() {
+                    Some(range) => range,
+                    None => {
+                        ctx.push_error(DatamodelError::new_validation_error(
+                            &format!("Error parsing jinja template: {}", e),
+                            pspan.clone(),
+                        ));
+                        return;
+                    }
+                };
 
-                    let start_offset = pspan.start + range.start;
-                    let end_offset = pspan.start + range.end;
+                let start_offset = pspan.start + range.start;
+                let end_offset = pspan.start + range.end;
 
+                let span = Span::new(
+                    pspan.file.clone(),
+                    start_offset as usize,
+                    end_offset as usize,
+                );
+
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!("Error parsing jinja template: {}", e),
+                    span,
+                ))
+            } else {
+                e.errors.iter().for_each(|t| {
+                    let span = t.span();
                     let span = Span::new(
                         pspan.file.clone(),
-                        start_offset as usize,
-                        end_offset as usize,
+                        pspan.start + span.start_offset as usize,
+                        pspan.start + span.end_offset as usize,
                     );
-
-                    ctx.push_error(DatamodelError::new_validation_error(
-                        &format!("Error parsing jinja template: {}", e),
-                        span,
-                    ))
-                } else {
-                    e.errors.iter().for_each(|t| {
-                        let span = t.span();
-                        let span = Span::new(
-                            pspan.file.clone(),
-                            pspan.start + span.start_offset as usize,
-                            pspan.start + span.end_offset as usize,
-                        );
-                        ctx.push_warning(DatamodelWarning::new(t.message().to_string(), span))
-                    })
-                }
+                    ctx.push_warning(DatamodelWarning::new(t.message().to_string(), span))
+                })
             }
         }
-        defined_types.end_scope();
-        defined_types.errors_mut().clear();
     }
 }
 
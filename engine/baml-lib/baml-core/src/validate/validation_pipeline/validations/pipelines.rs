@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use baml_types::{StringOr, UnresolvedValue};
+use internal_baml_diagnostics::{DatamodelError, Span};
+use internal_baml_jinja_types::{expression_type, JinjaContext, PredefinedTypes, Type};
+use internal_baml_parser_database::walkers::FunctionWalker;
+use internal_baml_schema_ast::ast::{WithName, WithSpan};
+
+use crate::validate::validation_pipeline::context::Context;
+
+/// Checks that the steps of each function call existing functions with
+/// arguments of the right types, and that the last step returns the output
+/// of the function.
+pub(super) fn validate(ctx: &mut Context<'_>) {
+    for func in ctx.db.walk_functions() {
+        let Some(steps) = func.steps() else {
+            continue;
+        };
+
+        let mut defined_types = PredefinedTypes::default(JinjaContext::Parsing);
+        ctx.db
+            .walk_classes()
+            .for_each(|c| c.add_to_types(&mut defined_types));
+        let mut names = HashSet::new();
+        for arg in func.walk_input_args() {
+            if let Some(name) = arg.ast_arg().0 {
+                defined_types.add_variable(name.name(), ctx.db.to_jinja_type(arg.field_type()));
+                names.insert(name.name());
+            }
+        }
+        let output = match func.ast_function().output() {
+            Some(output) => ctx.db.to_jinja_type(&output.field_type),
+            None => Type::Unknown,
+        };
+
+        for (idx, step) in steps.iter().enumerate() {
+            let (name, name_span) = &step.name;
+            if name == "item" || !names.insert(name.as_str()) {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!("Step `{name}` must not have the name of a parameter, an earlier step or `item`"),
+                    name_span.clone(),
+                ));
+            }
+
+            let Some(callee) = ctx.db.find_function_by_name(&step.function.0) else {
+                ctx.push_error(DatamodelError::not_found_error(
+                    "Function",
+                    &step.function.0,
+                    step.function.1.clone(),
+                    ctx.db
+                        .walk_functions()
+                        .map(|f| f.name().to_string())
+                        .collect(),
+                    false,
+                ));
+                defined_types.add_variable(name, Type::Unknown);
+                continue;
+            };
+            if calls(callee, func.name(), &mut HashSet::new()) {
+                ctx.push_error(DatamodelError::new_validation_error(
+                    &format!("`{}` calls itself through its steps", func.name()),
+                    step.function.1.clone(),
+                ));
+            }
+
+            defined_types.start_scope();
+            if let Some((expr, span)) = &step.for_each {
+                let item = match check_expression(ctx, &expr.0, span, &mut defined_types) {
+                    Some(Type::List(item)) => *item,
+                    Some(Type::Unknown) | None => Type::Unknown,
+                    Some(t) => {
+                        ctx.push_error(DatamodelError::new_validation_error(
+                            &format!("`for_each` must be a list, not `{}`", t.name()),
+                            span.clone(),
+                        ));
+                        Type::Unknown
+                    }
+                };
+                defined_types.add_variable("item", item);
+            }
+            for (arg, (arg_span, value)) in &step.args {
+                let Some(param) = callee.find_input_arg_by_name(arg) else {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &format!("`{}` has no parameter `{arg}`", callee.name()),
+                        arg_span.clone(),
+                    ));
+                    continue;
+                };
+                match value {
+                    UnresolvedValue::String(StringOr::JinjaExpression(expr), span) => {
+                        let expected = ctx.db.to_jinja_type(param.field_type());
+                        match check_expression(ctx, &expr.0, span, &mut defined_types) {
+                            Some(t) if !t.is_subtype_of(&expected) => {
+                                ctx.push_error(DatamodelError::new_validation_error(
+                                    &format!(
+                                        "`{arg}` of `{}` must be `{}`, not `{}`",
+                                        callee.name(),
+                                        expected.name(),
+                                        t.name()
+                                    ),
+                                    span.clone(),
+                                ))
+                            }
+                            _ => {}
+                        }
+                    }
+                    // Constants are coerced to the parameter type when the
+                    // step runs, but expressions nested in them still need
+                    // to be valid.
+                    other => check_nested_expressions(ctx, other, &mut defined_types),
+                }
+            }
+            defined_types.end_scope();
+
+            for param in callee.walk_input_args() {
+                let Some(param_name) = param.ast_arg().0 else {
+                    continue;
+                };
+                if !param.is_optional() && !step.args.contains_key(param_name.name()) {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "Step `{name}` is missing `{}`, a parameter of `{}`",
+                            param_name.name(),
+                            callee.name()
+                        ),
+                        step.span.clone(),
+                    ));
+                }
+            }
+
+            let step_output = match callee.ast_function().output() {
+                Some(output) => ctx.db.to_jinja_type(&output.field_type),
+                None => Type::Unknown,
+            };
+            if idx == steps.len() - 1 {
+                if let Some((_, span)) = &step.for_each {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "The last step cannot use `for_each`: its output is the output of `{}`",
+                            func.name()
+                        ),
+                        span.clone(),
+                    ));
+                } else if !step_output.is_subtype_of(&output) {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "The last step returns `{}`, but `{}` returns `{}`",
+                            step_output.name(),
+                            func.name(),
+                            output.name()
+                        ),
+                        step.function.1.clone(),
+                    ));
+                }
+            }
+            let step_output = match step.for_each {
+                Some(_) => Type::List(Box::new(step_output)),
+                None => step_output,
+            };
+            defined_types.add_variable(name, step_output);
+        }
+    }
+}
+
+/// Whether `function`, or any function its steps call, calls `target`.
+fn calls<'db>(
+    function: FunctionWalker<'db>,
+    target: &str,
+    visited: &mut HashSet<&'db str>,
+) -> bool {
+    if function.name() == target {
+        return true;
+    }
+    if !visited.insert(function.name()) {
+        return false;
+    }
+    function.steps().unwrap_or_default().iter().any(|step| {
+        function
+            .db
+            .find_function_by_name(&step.function.0)
+            .map_or(false, |callee| calls(callee, target, visited))
+    })
+}
+
+fn check_expression(
+    ctx: &mut Context<'_>,
+    expression: &str,
+    span: &Span,
+    defined_types: &mut PredefinedTypes,
+) -> Option<Type> {
+    match expression_type(expression, defined_types) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            match e.parsing_errors {
+                Some(e) => ctx.push_error(DatamodelError::new_validation_error(
+                    &format!("Error parsing jinja expression: {}", e),
+                    span.clone(),
+                )),
+                None => e.errors.iter().for_each(|t| {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        t.message(),
+                        span.clone(),
+                    ))
+                }),
+            }
+            None
+        }
+    }
+}
+
+fn check_nested_expressions(
+    ctx: &mut Context<'_>,
+    value: &UnresolvedValue<Span>,
+    defined_types: &mut PredefinedTypes,
+) {
+    match value {
+        UnresolvedValue::String(StringOr::JinjaExpression(expr), span) => {
+            check_expression(ctx, &expr.0, span, defined_types);
+        }
+        UnresolvedValue::Array(values, _) => values
+            .iter()
+            .for_each(|v| check_nested_expressions(ctx, v, defined_types)),
+        UnresolvedValue::Map(values, _) => values
+            .values()
+            .for_each(|(_, v)| check_nested_expressions(ctx, v, defined_types)),
+        _ => {}
+    }
+}
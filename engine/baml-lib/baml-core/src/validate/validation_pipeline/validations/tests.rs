@@ -9,6 +9,7 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
     tests.iter().for_each(|walker| {
         let constraints = &walker.test_case().constraints;
         let args = &walker.test_case().args;
+        let dataset = walker.test_case().dataset.as_ref();
         let mut check_names: Vec<String> = Vec::new();
         for (
             Constraint {
@@ -22,6 +23,13 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
         {
             let mut defined_types = PredefinedTypes::default(JinjaContext::Parsing);
             defined_types.add_variable("this", Type::Unknown);
+            // Any function can be called as a judge.
+            ctx.db
+                .walk_classes()
+                .for_each(|c| c.add_to_types(&mut defined_types));
+            ctx.db
+                .walk_functions()
+                .for_each(|f| f.add_to_types(&mut defined_types));
             defined_types.add_class(
                 "Checks",
                 check_names
@@ -29,18 +37,37 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                     .map(|check_name| (check_name.clone(), Type::Unknown))
                     .collect(),
             );
+            if let Some(dataset) = dataset {
+                defined_types.add_class(
+                    "Row",
+                    dataset
+                        .rows
+                        .iter()
+                        .flat_map(|row| row.values.keys())
+                        .map(|column| (column.clone(), Type::Unknown))
+                        .collect(),
+                );
+            }
             defined_types.add_class(
                 "_",
                 vec![
                     ("checks".to_string(), Type::ClassRef("Checks".to_string())),
                     ("result".to_string(), Type::Unknown),
                     ("latency_ms".to_string(), Type::Number),
+                    (
+                        "row".to_string(),
+                        match dataset {
+                            Some(_) => Type::ClassRef("Row".to_string()),
+                            None => Type::None,
+                        },
+                    ),
                 ]
                 .into_iter()
                 .collect(),
             );
             defined_types.add_variable("_", Type::ClassRef("_".to_string()));
             args.keys()
+                .chain(dataset.iter().flat_map(|d| d.columns.keys()))
                 .for_each(|arg_name| defined_types.add_variable(arg_name, Type::Unknown));
             if let (ConstraintLevel::Check, Some(check_name)) = (level, label) {
                 check_names.push(check_name.to_string());
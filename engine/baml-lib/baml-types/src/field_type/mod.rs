@@ -1,4 +1,5 @@
 use crate::BamlMediaType;
+use crate::BamlTemporalType;
 use crate::Constraint;
 
 mod builder;
@@ -12,6 +13,8 @@ pub enum TypeValue {
     // Char,
     Null,
     Media(BamlMediaType),
+    Temporal(BamlTemporalType),
+    Message,
 }
 
 impl std::str::FromStr for TypeValue {
@@ -26,6 +29,12 @@ impl std::str::FromStr for TypeValue {
             "null" => TypeValue::Null,
             "image" => TypeValue::Media(BamlMediaType::Image),
             "audio" => TypeValue::Media(BamlMediaType::Audio),
+            "pdf" => TypeValue::Media(BamlMediaType::Pdf),
+            "video" => TypeValue::Media(BamlMediaType::Video),
+            "date" => TypeValue::Temporal(BamlTemporalType::Date),
+            "datetime" => TypeValue::Temporal(BamlTemporalType::DateTime),
+            "duration" => TypeValue::Temporal(BamlTemporalType::Duration),
+            "message" => TypeValue::Message,
             _ => return Err(()),
         })
     }
@@ -41,6 +50,10 @@ impl std::fmt::Display for TypeValue {
             TypeValue::Null => write!(f, "null"),
             TypeValue::Media(BamlMediaType::Image) => write!(f, "image"),
             TypeValue::Media(BamlMediaType::Audio) => write!(f, "audio"),
+            TypeValue::Media(BamlMediaType::Pdf) => write!(f, "pdf"),
+            TypeValue::Media(BamlMediaType::Video) => write!(f, "video"),
+            TypeValue::Temporal(temporal_type) => write!(f, "{temporal_type}"),
+            TypeValue::Message => write!(f, "message"),
         }
     }
 }
@@ -340,5 +353,11 @@ mod tests {
     fn subtype_media() {
         let x = FieldType::Primitive(TypeValue::Media(BamlMediaType::Audio));
         assert!(x.is_subtype_of(&x));
+
+        let pdf = FieldType::Primitive(TypeValue::Media(BamlMediaType::Pdf));
+        let video = FieldType::Primitive(TypeValue::Media(BamlMediaType::Video));
+        assert!(pdf.is_subtype_of(&pdf));
+        assert!(!pdf.is_subtype_of(&video));
+        assert!(!x.is_subtype_of(&pdf));
     }
 }
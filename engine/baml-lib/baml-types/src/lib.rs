@@ -1,7 +1,10 @@
 mod constraint;
 mod map;
 mod media;
+mod message;
 mod minijinja;
+mod parser_config;
+mod temporal;
 
 mod baml_value;
 mod field_type;
@@ -14,5 +17,10 @@ pub use field_type::{FieldType, LiteralValue, TypeValue};
 pub use generator::{GeneratorDefaultClientMode, GeneratorOutputType};
 pub use map::Map as BamlMap;
 pub use media::{BamlMedia, BamlMediaContent, BamlMediaType, MediaBase64, MediaUrl};
+pub use message::{BamlMessage, BamlMessagePart};
 pub use minijinja::JinjaExpression;
-pub use value_expr::{EvaluationContext, GetEnvVar, ResolvedValue, StringOr, UnresolvedValue};
+pub use parser_config::ParserConfig;
+pub use temporal::{BamlTemporal, BamlTemporalType};
+pub use value_expr::{
+    EvaluationContext, GetEnvVar, ResolveEnvVar, ResolvedValue, StringOr, UnresolvedValue,
+};
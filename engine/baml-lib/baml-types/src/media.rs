@@ -7,6 +7,19 @@ use std::{borrow::Cow, fmt, path::PathBuf};
 pub enum BamlMediaType {
     Image,
     Audio,
+    Pdf,
+    Video,
+}
+
+impl BamlMediaType {
+    /// Best-effort mime type for a file with the given extension, e.g. "png"
+    /// becomes "image/png" and "pdf" becomes "application/pdf".
+    pub fn mime_type_for_extension(&self, ext: &str) -> String {
+        match self {
+            BamlMediaType::Pdf => "application/pdf".to_string(),
+            _ => format!("{}/{}", self, ext),
+        }
+    }
 }
 
 impl fmt::Display for BamlMediaType {
@@ -14,6 +27,8 @@ impl fmt::Display for BamlMediaType {
         match *self {
             BamlMediaType::Image => write!(f, "image"),
             BamlMediaType::Audio => write!(f, "audio"),
+            BamlMediaType::Pdf => write!(f, "pdf"),
+            BamlMediaType::Video => write!(f, "video"),
         }
     }
 }
@@ -28,8 +43,8 @@ impl fmt::Display for BamlMediaType {
 pub struct BamlMedia {
     pub media_type: BamlMediaType,
 
-    /// Explicitly specified by the 'media_type' field on img and audio structs in BAML files.
-    /// example: "image/png", "image/jpeg", "audio/mp3"
+    /// Explicitly specified by the 'media_type' field on media structs in BAML files.
+    /// example: "image/png", "image/jpeg", "audio/mp3", "application/pdf", "video/mp4"
     pub mime_type: Option<String>,
     pub content: BamlMediaContent,
 }
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{BamlMap, BamlMedia};
+
+/// A `message` value: one chat message, e.g. a turn of a conversation
+/// history, that is spliced into a prompt as a message of its own.
+///
+/// Like [`BamlMedia`], prompt rendering relies on its serialization: the
+/// message is written into the rendered string as JSON and read back when the
+/// string is split into chat messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BamlMessage {
+    pub role: String,
+    pub content: Vec<BamlMessagePart>,
+    /// The name of the participant, for providers that support one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Passed along with every part of the message, like the keyword
+    /// arguments of `_.role()`. Providers only send the keys that the client
+    /// allows in `allowed_role_metadata`.
+    #[serde(default, skip_serializing_if = "BamlMap::is_empty")]
+    pub metadata: BamlMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BamlMessagePart {
+    Text(String),
+    Media(BamlMedia),
+}
+
+impl BamlMessage {
+    pub fn new(role: String, content: Vec<BamlMessagePart>) -> BamlMessage {
+        BamlMessage {
+            role,
+            content,
+            name: None,
+            metadata: BamlMap::new(),
+        }
+    }
+
+    pub fn text(role: String, text: String) -> BamlMessage {
+        Self::new(role, vec![BamlMessagePart::Text(text)])
+    }
+
+    /// The text parts of the message, joined by newlines.
+    pub fn text_content(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|part| match part {
+                BamlMessagePart::Text(t) => Some(t.as_str()),
+                BamlMessagePart::Media(_) => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BamlMediaType;
+
+    #[test]
+    fn test_round_trip() {
+        let mut message = BamlMessage::new(
+            "user".to_string(),
+            vec![
+                BamlMessagePart::Text("What is in this image?".to_string()),
+                BamlMessagePart::Media(BamlMedia::url(
+                    BamlMediaType::Image,
+                    "https://example.com/cat.png".to_string(),
+                    None,
+                )),
+            ],
+        );
+        message.name = Some("ada".to_string());
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["content"][0], "What is in this image?");
+        assert!(json.get("metadata").is_none());
+        assert_eq!(
+            serde_json::from_value::<BamlMessage>(json).unwrap(),
+            message
+        );
+    }
+}
@@ -29,6 +29,8 @@ impl From<BamlValue> for minijinja::Value {
                 minijinja::Value::from(list)
             }
             BamlValue::Media(i) => i.into(),
+            BamlValue::Temporal(t) => minijinja::Value::from(t.to_iso8601()),
+            BamlValue::Message(m) => minijinja::Value::from_serialize(&m),
             BamlValue::Enum(_, v) => minijinja::Value::from(v),
             BamlValue::Class(_, m) => {
                 let map = m.into_iter().map(|(k, v)| (k, minijinja::Value::from(v)));
@@ -0,0 +1,67 @@
+/// How strictly the output of a function is parsed, set with
+/// `@@parser({ ... })` on the function. Unset options keep the lenient
+/// defaults.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct ParserConfig {
+    /// Look for JSON inside markdown code blocks.
+    pub allow_markdown_json: Option<bool>,
+    /// Repair malformed JSON, e.g. unquoted keys or missing brackets.
+    pub allow_fixes: Option<bool>,
+    /// Fall back to the whole output as a string.
+    pub allow_as_string: Option<bool>,
+    /// Search the output for every JSON object in it.
+    pub find_all_json_objects: Option<bool>,
+    /// Also parse YAML, XML-tagged and TOML outputs. Off unless the prompt
+    /// asks for one of them with `output_format(style=...)`.
+    pub allow_alternate_formats: Option<bool>,
+    /// The highest coercion score accepted. Lower scores mean fewer
+    /// coercions were needed.
+    pub max_score: Option<i32>,
+    /// Coercion flags that fail the parse when they fire.
+    pub deny: Vec<String>,
+}
+
+impl ParserConfig {
+    /// Options accepted by `@@parser`.
+    pub const OPTIONS: &'static [&'static str] = &[
+        "allow_markdown_json",
+        "allow_fixes",
+        "allow_as_string",
+        "find_all_json_objects",
+        "allow_alternate_formats",
+        "max_score",
+        "deny",
+    ];
+
+    /// Coercion flags that can be listed in `deny`.
+    pub const FLAGS: &'static [&'static str] = &[
+        "ObjectFromMarkdown",
+        "ObjectFromFixedJson",
+        "DefaultButHadUnparseableValue",
+        "ObjectToString",
+        "ObjectToPrimitive",
+        "ObjectToMap",
+        "ExtraKey",
+        "StrippedNonAlphaNumeric",
+        "SubstringMatch",
+        "SingleToArray",
+        "ArrayItemParseError",
+        "MapKeyParseError",
+        "MapValueParseError",
+        "JsonToString",
+        "ImpliedKey",
+        "InferedObject",
+        "FirstMatch",
+        "StrMatchOneFromMany",
+        "DefaultFromNoValue",
+        "DefaultButHadValue",
+        "OptionalDefaultFromNoValue",
+        "StringToBool",
+        "StringToNull",
+        "StringToChar",
+        "StringToTemporal",
+        "FloatToInt",
+        "NumberToTemporal",
+        "NoFields",
+    ];
+}
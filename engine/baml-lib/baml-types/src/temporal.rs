@@ -0,0 +1,553 @@
+use std::fmt;
+
+use anyhow::Result;
+use chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, TimeDelta, Utc,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum BamlTemporalType {
+    Date,
+    DateTime,
+    Duration,
+}
+
+impl fmt::Display for BamlTemporalType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BamlTemporalType::Date => write!(f, "date"),
+            BamlTemporalType::DateTime => write!(f, "datetime"),
+            BamlTemporalType::Duration => write!(f, "duration"),
+        }
+    }
+}
+
+/// A `date`, `datetime` or `duration` value.
+///
+/// These serialize (and render into prompts) as ISO-8601 strings:
+/// `2024-03-03`, `2024-03-03T14:30:00+00:00` and `PT2H30M`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BamlTemporal {
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+    Duration(TimeDelta),
+}
+
+impl BamlTemporal {
+    pub fn temporal_type(&self) -> BamlTemporalType {
+        match self {
+            BamlTemporal::Date(_) => BamlTemporalType::Date,
+            BamlTemporal::DateTime(_) => BamlTemporalType::DateTime,
+            BamlTemporal::Duration(_) => BamlTemporalType::Duration,
+        }
+    }
+
+    pub fn to_iso8601(&self) -> String {
+        match self {
+            BamlTemporal::Date(d) => d.format("%Y-%m-%d").to_string(),
+            BamlTemporal::DateTime(dt) => dt.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            BamlTemporal::Duration(d) => format_iso8601_duration(d),
+        }
+    }
+
+    /// Parses the ISO-8601 form written by [`BamlTemporal::to_iso8601`].
+    /// Datetimes without an offset are taken to be in UTC.
+    pub fn parse_iso8601(temporal_type: BamlTemporalType, s: &str) -> Option<Self> {
+        let s = s.trim();
+        match temporal_type {
+            BamlTemporalType::Date => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .map(BamlTemporal::Date),
+            BamlTemporalType::DateTime => parse_iso8601_datetime(s).map(BamlTemporal::DateTime),
+            BamlTemporalType::Duration => parse_iso8601_duration(s).map(BamlTemporal::Duration),
+        }
+    }
+
+    /// Parses ISO-8601 as well as the looser forms models tend to write, e.g.
+    /// `March 3rd 2024`, `03/04/2024`, `2024-03-03 14:30` or `2h 30m`.
+    ///
+    /// Numeric dates with the year last are read month first, whatever the
+    /// separator: `03/04/2024`, `03-04-2024` and `03.04.2024` are all March
+    /// 4th. Bare numbers are read as by [`BamlTemporal::from_bare_number`].
+    pub fn parse(temporal_type: BamlTemporalType, s: &str) -> Result<Self> {
+        if let Some(value) = Self::parse_iso8601(temporal_type, s) {
+            return Ok(value);
+        }
+        let s = s.trim();
+        let parsed = match temporal_type {
+            BamlTemporalType::Date => parse_loose_date(s)
+                .or_else(|| {
+                    parse_iso8601_datetime(s)
+                        .or_else(|| parse_loose_datetime(s))
+                        .map(|dt| dt.date_naive())
+                })
+                .map(BamlTemporal::Date),
+            BamlTemporalType::DateTime => parse_loose_datetime(s).map(BamlTemporal::DateTime),
+            BamlTemporalType::Duration => parse_loose_duration(s).map(BamlTemporal::Duration),
+        };
+        if let Some(parsed) = parsed {
+            return Ok(parsed);
+        }
+        if let Ok(n) = s.parse::<f64>() {
+            return Self::from_bare_number(temporal_type, n);
+        }
+        anyhow::bail!("Could not parse {s:?} as a {temporal_type}")
+    }
+
+    /// Like [`BamlTemporal::from_number`], for a number found where a temporal
+    /// was expected, e.g. in a model's output. Dates and datetimes are only
+    /// read from Unix timestamps of at least 10 digits, so that e.g. a year
+    /// like `2024` is not taken for seconds after 1970.
+    pub fn from_bare_number(temporal_type: BamlTemporalType, n: f64) -> Result<Self> {
+        if temporal_type != BamlTemporalType::Duration && n.abs() < MIN_BARE_TIMESTAMP {
+            anyhow::bail!("{n} is not a Unix timestamp, so it can't be read as a {temporal_type}");
+        }
+        Self::from_number(temporal_type, n)
+    }
+
+    /// Interprets a number as seconds since the Unix epoch for dates and
+    /// datetimes, or as a number of seconds for durations.
+    pub fn from_number(temporal_type: BamlTemporalType, seconds: f64) -> Result<Self> {
+        if !seconds.is_finite() {
+            anyhow::bail!("{seconds} is not a valid {temporal_type}");
+        }
+        let millis = (seconds * 1000.0).round() as i64;
+        Ok(match temporal_type {
+            BamlTemporalType::Date | BamlTemporalType::DateTime => {
+                let dt = DateTime::<Utc>::from_timestamp_millis(millis).ok_or_else(|| {
+                    anyhow::anyhow!("{seconds} is out of range for a {temporal_type}")
+                })?;
+                let dt = dt.fixed_offset();
+                match temporal_type {
+                    BamlTemporalType::Date => BamlTemporal::Date(dt.date_naive()),
+                    _ => BamlTemporal::DateTime(dt),
+                }
+            }
+            BamlTemporalType::Duration => {
+                BamlTemporal::Duration(TimeDelta::try_milliseconds(millis).ok_or_else(|| {
+                    anyhow::anyhow!("{seconds} is out of range for a {temporal_type}")
+                })?)
+            }
+        })
+    }
+
+    /// Converts to another temporal type where that loses no meaning: a
+    /// datetime to its date, or a date to midnight UTC.
+    pub fn cast_to(self, temporal_type: BamlTemporalType) -> Option<Self> {
+        match (self, temporal_type) {
+            (v, t) if v.temporal_type() == t => Some(v),
+            (BamlTemporal::DateTime(dt), BamlTemporalType::Date) => {
+                Some(BamlTemporal::Date(dt.date_naive()))
+            }
+            (BamlTemporal::Date(d), BamlTemporalType::DateTime) => Some(BamlTemporal::DateTime(
+                d.and_time(NaiveTime::MIN).and_utc().fixed_offset(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BamlTemporal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_iso8601())
+    }
+}
+
+impl Serialize for BamlTemporal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_iso8601())
+    }
+}
+
+fn parse_iso8601_datetime(s: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt);
+    }
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .map(|dt| dt.and_utc().fixed_offset())
+}
+
+fn format_iso8601_duration(d: &TimeDelta) -> String {
+    let sign = if *d < TimeDelta::zero() { "-" } else { "" };
+    let d = d.abs();
+
+    let days = d.num_days();
+    let hours = d.num_hours() % 24;
+    let minutes = d.num_minutes() % 60;
+    let seconds = d.num_seconds() % 60;
+    let nanos = d.subsec_nanos();
+
+    let mut out = format!("{sign}P");
+    if days > 0 {
+        out += &format!("{days}D");
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 || days == 0 {
+        out.push('T');
+        if hours > 0 {
+            out += &format!("{hours}H");
+        }
+        if minutes > 0 {
+            out += &format!("{minutes}M");
+        }
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            out += &format!("{seconds}.{}S", fraction.trim_end_matches('0'));
+        } else if seconds > 0 || (hours == 0 && minutes == 0) {
+            out += &format!("{seconds}S");
+        }
+    }
+    out
+}
+
+/// Parses `PnWnDTnHnMnS`. Years and months are rejected, since their length
+/// depends on the date they are added to.
+fn parse_iso8601_duration(s: &str) -> Option<TimeDelta> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let s = s.strip_prefix(['P', 'p'])?;
+    if s.is_empty() {
+        return None;
+    }
+
+    let (date_part, time_part) = match s.split_once(['T', 't']) {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (s, None),
+    };
+
+    let mut total = TimeDelta::zero();
+    for (number, unit) in iso8601_components(date_part)? {
+        total += match unit.to_ascii_uppercase() {
+            'W' => seconds_to_delta(number * 7.0 * 86400.0)?,
+            'D' => seconds_to_delta(number * 86400.0)?,
+            _ => return None,
+        };
+    }
+    if let Some(time_part) = time_part {
+        for (number, unit) in iso8601_components(time_part)? {
+            total += match unit.to_ascii_uppercase() {
+                'H' => seconds_to_delta(number * 3600.0)?,
+                'M' => seconds_to_delta(number * 60.0)?,
+                'S' => seconds_to_delta(number)?,
+                _ => return None,
+            };
+        }
+    }
+
+    Some(if negative { -total } else { total })
+}
+
+/// Splits e.g. `1DT` into `[(1.0, 'D')]`.
+fn iso8601_components(s: &str) -> Option<Vec<(f64, char)>> {
+    let mut components = vec![];
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() || c == '.' || c == ',' {
+            number.push(if c == ',' { '.' } else { c });
+        } else {
+            if number.is_empty() {
+                return None;
+            }
+            components.push((number.parse().ok()?, c));
+            number.clear();
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(components)
+}
+
+fn seconds_to_delta(seconds: f64) -> Option<TimeDelta> {
+    if !seconds.is_finite() {
+        return None;
+    }
+    TimeDelta::try_milliseconds((seconds * 1000.0).round() as i64)
+}
+
+/// Lowercases, drops ordinal suffixes ("3rd" -> "3"), filler words and
+/// punctuation, so that e.g. "Sunday, March 3rd, 2024" becomes "march 3 2024".
+fn normalize_loose(s: &str) -> String {
+    let s = s.to_lowercase().replace([',', '.'], " ");
+    s.split_whitespace()
+        .filter(|word| !matches!(*word, "of" | "the" | "on" | "at"))
+        .filter(|word| !WEEKDAYS.contains(word))
+        .map(|word| {
+            let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            if !digits.is_empty()
+                && digits.chars().all(|c| c.is_ascii_digit())
+                && matches!(&word[digits.len()..], "st" | "nd" | "rd" | "th")
+            {
+                digits
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+const WEEKDAYS: &[&str] = &[
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+    "mon",
+    "tue",
+    "tues",
+    "wed",
+    "thu",
+    "thur",
+    "thurs",
+    "fri",
+    "sat",
+    "sun",
+];
+
+/// The smallest number read as a Unix timestamp when a date or datetime is
+/// expected: 2001-09-09.
+const MIN_BARE_TIMESTAMP: f64 = 1e9;
+
+/// Numeric dates with the year last are month first for every separator.
+const LOOSE_DATE_FORMATS: &[&str] = &[
+    "%Y/%m/%d", "%m/%d/%Y", "%B %d %Y", "%b %d %Y", "%d %B %Y", "%d %b %Y", "%Y %B %d", "%Y %b %d",
+    "%Y-%m-%d", "%m-%d-%Y", "%m %d %Y",
+];
+
+/// Dates without a year (e.g. "March 3rd") are rejected rather than guessed.
+fn parse_loose_date(s: &str) -> Option<NaiveDate> {
+    // Normalizing also turns "03.03.2024" into "03 03 2024".
+    [s.to_string(), normalize_loose(s)]
+        .iter()
+        .find_map(|candidate| {
+            LOOSE_DATE_FORMATS
+                .iter()
+                .find_map(|fmt| NaiveDate::parse_from_str(candidate, fmt).ok())
+        })
+}
+
+fn parse_loose_datetime(s: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(dt) = DateTime::parse_from_rfc2822(s) {
+        return Some(dt);
+    }
+    for fmt in [
+        "%Y-%m-%d %H:%M:%S%.f%:z",
+        "%Y-%m-%d %H:%M:%S%.f%z",
+        "%Y-%m-%dT%H:%M:%S%.f%z",
+        "%Y-%m-%d %H:%M%:z",
+    ] {
+        if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+            return Some(dt);
+        }
+    }
+
+    let s = s.trim_end_matches(['Z', 'z']).trim_end();
+    let s = s
+        .strip_suffix(" UTC")
+        .or_else(|| s.strip_suffix(" GMT"))
+        .unwrap_or(s);
+    for fmt in [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y/%m/%d %H:%M:%S",
+        "%Y/%m/%d %H:%M",
+        "%m/%d/%Y %H:%M:%S",
+        "%m/%d/%Y %H:%M",
+        "%m/%d/%Y %I:%M %p",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Some(dt.and_utc().fixed_offset());
+        }
+    }
+
+    // A loose date followed by a time, e.g. "March 3rd 2024 at 2:30 pm".
+    let normalized = normalize_loose(s);
+    let words = normalized.split(' ').collect::<Vec<_>>();
+    for split in (1..=words.len()).rev() {
+        let (date, time) = words.split_at(split);
+        let Some(date) = parse_loose_date(&date.join(" ")) else {
+            continue;
+        };
+        let time = time.join(" ");
+        if time.is_empty() {
+            return Some(date.and_time(NaiveTime::MIN).and_utc().fixed_offset());
+        }
+        let time = time.replace(' ', "");
+        return ["%H:%M:%S", "%H:%M", "%I:%M%p", "%I%p", "%I:%M:%S%p"]
+            .iter()
+            .find_map(|fmt| NaiveTime::parse_from_str(&time, fmt).ok())
+            .map(|t| date.and_time(t).and_utc().fixed_offset());
+    }
+    None
+}
+
+/// Parses durations like `2h 30m`, `1.5 hours`, `2 days and 3 hours` or
+/// `02:30:00`.
+fn parse_loose_duration(s: &str) -> Option<TimeDelta> {
+    let normalized = s.to_lowercase().replace(',', " ");
+    let normalized = normalized.trim();
+    let (negative, normalized) = match normalized.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, normalized),
+    };
+
+    let total = if let Some(total) = parse_clock_duration(normalized) {
+        total
+    } else {
+        let mut total = TimeDelta::zero();
+        let mut rest = normalized;
+        let mut matched = false;
+        while !rest.is_empty() {
+            rest = rest.trim_start();
+            if let Some(r) = rest.strip_prefix("and ") {
+                rest = r;
+                continue;
+            }
+            let number_len = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            if number_len == 0 {
+                return None;
+            }
+            let number: f64 = rest[..number_len].parse().ok()?;
+            rest = rest[number_len..].trim_start();
+            let unit_len = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let seconds_per_unit = match &rest[..unit_len] {
+                "w" | "wk" | "wks" | "week" | "weeks" => 7.0 * 86400.0,
+                "d" | "day" | "days" => 86400.0,
+                "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+                "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+                "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+                "ms" | "millisecond" | "milliseconds" => 0.001,
+                _ => return None,
+            };
+            total += seconds_to_delta(number * seconds_per_unit)?;
+            rest = &rest[unit_len..];
+            matched = true;
+        }
+        if !matched {
+            return None;
+        }
+        total
+    };
+
+    Some(if negative { -total } else { total })
+}
+
+/// Parses `H:MM:SS`.
+fn parse_clock_duration(s: &str) -> Option<TimeDelta> {
+    let parts = s.split(':').collect::<Vec<_>>();
+    let [hours, minutes, seconds] = parts.as_slice() else {
+        return None;
+    };
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    let seconds: f64 = seconds.parse().ok()?;
+    if minutes >= 60 || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    seconds_to_delta(hours as f64 * 3600.0 + minutes as f64 * 60.0 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(temporal_type: BamlTemporalType, s: &str) -> String {
+        BamlTemporal::parse(temporal_type, s).unwrap().to_iso8601()
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        for s in [
+            "2024-03-03",
+            "March 3rd 2024",
+            "Sunday, March 3rd, 2024",
+            "3rd of March 2024",
+            "03/03/2024",
+            "1709424000",
+            "2024-03-03T10:00:00Z",
+        ] {
+            assert_eq!(parse(BamlTemporalType::Date, s), "2024-03-03", "{s}");
+        }
+        assert!(BamlTemporal::parse(BamlTemporalType::Date, "March 3rd").is_err());
+    }
+
+    #[test]
+    fn test_numeric_dates_are_month_first() {
+        for s in ["03/04/2024", "03-04-2024", "03.04.2024"] {
+            assert_eq!(parse(BamlTemporalType::Date, s), "2024-03-04", "{s}");
+        }
+        assert!(BamlTemporal::parse(BamlTemporalType::Date, "13.04.2024").is_err());
+    }
+
+    #[test]
+    fn test_small_numbers_are_not_timestamps() {
+        for s in ["2024", "0", "-5"] {
+            assert!(
+                BamlTemporal::parse(BamlTemporalType::Date, s).is_err(),
+                "{s}"
+            );
+            assert!(
+                BamlTemporal::parse(BamlTemporalType::DateTime, s).is_err(),
+                "{s}"
+            );
+        }
+        assert_eq!(parse(BamlTemporalType::Duration, "2024"), "PT33M44S");
+        // Values passed in from code are always timestamps.
+        assert_eq!(
+            BamlTemporal::from_number(BamlTemporalType::Date, 0.0)
+                .unwrap()
+                .to_iso8601(),
+            "1970-01-01"
+        );
+    }
+
+    #[test]
+    fn test_parse_datetimes() {
+        assert_eq!(
+            parse(BamlTemporalType::DateTime, "2024-03-03T14:30:00Z"),
+            "2024-03-03T14:30:00+00:00"
+        );
+        assert_eq!(
+            parse(BamlTemporalType::DateTime, "2024-03-03T14:30:00+02:00"),
+            "2024-03-03T14:30:00+02:00"
+        );
+        assert_eq!(
+            parse(BamlTemporalType::DateTime, "March 3rd 2024 at 2:30 pm"),
+            "2024-03-03T14:30:00+00:00"
+        );
+        assert_eq!(
+            parse(BamlTemporalType::DateTime, "1709424000.5"),
+            "2024-03-03T00:00:00.500+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_durations() {
+        for s in [
+            "PT2H30M",
+            "2h 30m",
+            "2 hours and 30 minutes",
+            "2.5h",
+            "02:30:00",
+            "9000",
+        ] {
+            assert_eq!(parse(BamlTemporalType::Duration, s), "PT2H30M", "{s}");
+        }
+        assert_eq!(parse(BamlTemporalType::Duration, "P2W"), "P14D");
+        assert_eq!(parse(BamlTemporalType::Duration, "500ms"), "PT0.5S");
+        assert_eq!(parse(BamlTemporalType::Duration, "-PT5M"), "-PT5M");
+        assert!(BamlTemporal::parse(BamlTemporalType::Duration, "P1Y").is_err());
+    }
+}
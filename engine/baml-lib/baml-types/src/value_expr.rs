@@ -208,19 +208,38 @@ pub trait GetEnvVar {
     fn set_allow_missing_env_var(&self, allow: bool) -> Self;
 }
 
+/// Resolves `env.X` references through something other than the map of
+/// environment variables, e.g. a `.env` file or a secret manager.
+pub trait ResolveEnvVar {
+    /// Returns the value of `key`, or `None` if none of the sources know it.
+    /// `env_vars` are the environment variables of the evaluation context.
+    fn resolve_env_var(
+        &self,
+        key: &str,
+        env_vars: &HashMap<String, String>,
+    ) -> Result<Option<String>>;
+}
+
 pub struct EvaluationContext<'a> {
     env_vars: Option<&'a HashMap<String, String>>,
+    resolver: Option<&'a dyn ResolveEnvVar>,
     fill_missing_env_vars: bool,
 }
 
 impl<'a> GetEnvVar for EvaluationContext<'a> {
     fn get_env_var(&self, key: &str) -> Result<String> {
-        match self
-            .env_vars
-            .as_ref()
-            .and_then(|env_vars| env_vars.get(key))
-        {
-            Some(v) => Ok(v.to_string()),
+        let value = match self.resolver {
+            Some(resolver) => {
+                resolver.resolve_env_var(key, self.env_vars.unwrap_or(&HashMap::new()))?
+            }
+            None => self
+                .env_vars
+                .and_then(|env_vars| env_vars.get(key))
+                .cloned(),
+        };
+
+        match value {
+            Some(v) => Ok(v),
             None => {
                 if self.fill_missing_env_vars {
                     Ok(format!("${key}"))
@@ -234,6 +253,7 @@ impl<'a> GetEnvVar for EvaluationContext<'a> {
     fn set_allow_missing_env_var(&self, allow: bool) -> Self {
         Self {
             env_vars: self.env_vars,
+            resolver: self.resolver,
             fill_missing_env_vars: allow,
         }
     }
@@ -243,15 +263,26 @@ impl<'a> EvaluationContext<'a> {
     pub fn new(env_vars: &'a HashMap<String, String>, fill_missing_env_vars: bool) -> Self {
         Self {
             env_vars: Some(env_vars),
+            resolver: None,
             fill_missing_env_vars,
         }
     }
+
+    /// Looks up environment variables through `resolver` instead of reading
+    /// them from the map directly.
+    pub fn with_resolver(self, resolver: &'a dyn ResolveEnvVar) -> Self {
+        Self {
+            resolver: Some(resolver),
+            ..self
+        }
+    }
 }
 
 impl<'db> Default for EvaluationContext<'db> {
     fn default() -> Self {
         Self {
             env_vars: None,
+            resolver: None,
             fill_missing_env_vars: true,
         }
     }
@@ -435,6 +466,10 @@ impl crate::BamlValue {
                 Resolvable::Array(values, ())
             }
             crate::BamlValue::Media(m) => m.to_resolvable()?,
+            crate::BamlValue::Temporal(t) => {
+                Resolvable::String(StringOr::Value(t.to_iso8601()), ())
+            }
+            crate::BamlValue::Message(m) => m.to_resolvable()?,
             crate::BamlValue::Null => Resolvable::Null(()),
         })
     }
@@ -464,3 +499,29 @@ impl crate::BamlMedia {
         Ok(Resolvable::Map(index_map, ()))
     }
 }
+
+impl crate::BamlMessage {
+    pub fn to_resolvable(&self) -> Result<Resolvable<StringOr, ()>> {
+        let string = |s: &str| ((), Resolvable::String(StringOr::Value(s.to_string()), ()));
+        let mut index_map = IndexMap::default();
+        index_map.insert("role".to_string(), string(&self.role));
+        let content = self
+            .content
+            .iter()
+            .map(|part| match part {
+                crate::BamlMessagePart::Text(t) => Ok(string(t).1),
+                crate::BamlMessagePart::Media(m) => m.to_resolvable(),
+            })
+            .collect::<Result<_>>()?;
+        index_map.insert("content".to_string(), ((), Resolvable::Array(content, ())));
+        if let Some(name) = &self.name {
+            index_map.insert("name".to_string(), string(name));
+        }
+        if !self.metadata.is_empty() {
+            let metadata: crate::BamlValue =
+                serde_json::from_value(serde_json::to_value(&self.metadata)?)?;
+            index_map.insert("metadata".to_string(), ((), metadata.to_resolvable()?));
+        }
+        Ok(Resolvable::Map(index_map, ()))
+    }
+}
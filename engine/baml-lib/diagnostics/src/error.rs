@@ -423,6 +423,8 @@ impl DatamodelError {
                 "string".to_string(),
                 "image".to_string(),
                 "audio".to_string(),
+                "pdf".to_string(),
+                "video".to_string(),
                 "null".to_string(),
             ];
             names.extend(primitives);
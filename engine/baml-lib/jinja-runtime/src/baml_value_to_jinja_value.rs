@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use baml_types::EvaluationContext;
+use baml_types::{BamlMessage, BamlMessagePart, EvaluationContext};
 use indexmap::IndexMap;
 use internal_baml_core::ir::repr::IntermediateRepr;
 use internal_baml_core::ir::IRHelper;
@@ -32,6 +32,18 @@ impl IntoMiniJinjaValue for BamlValue {
                     .map(|(k, v)| (k.as_str(), v.to_minijinja_value(ir, eval_ctx)));
                 minijinja::Value::from_iter(map)
             }
+            // Rendered without the brackets and commas of a list, so that
+            // `{{ history }}` splices in one chat message per element.
+            BamlValue::List(l)
+                if !l.is_empty() && l.iter().all(|v| matches!(v, BamlValue::Message(_))) =>
+            {
+                minijinja::Value::from_object(MinijinjaBamlMessages {
+                    messages: l
+                        .iter()
+                        .map(|v| v.to_minijinja_value(ir, eval_ctx))
+                        .collect(),
+                })
+            }
             BamlValue::List(l) => {
                 let list: Vec<minijinja::Value> = l
                     .iter()
@@ -40,6 +52,10 @@ impl IntoMiniJinjaValue for BamlValue {
                 minijinja::Value::from(list)
             }
             BamlValue::Media(i) => i.to_minijinja_value(ir, eval_ctx),
+            BamlValue::Temporal(t) => minijinja::Value::from(t.to_iso8601()),
+            BamlValue::Message(m) => {
+                minijinja::Value::from_object(MinijinjaBamlMessage { message: m.clone() })
+            }
             // For enums and classes we compute the aliases from the IR, and generate custom jinja structs that print out the alias if stringified.
             BamlValue::Enum(_name, value) => {
                 minijinja::Value::from(value.clone())
@@ -138,6 +154,99 @@ impl minijinja::value::Object for MinijinjaBamlMedia {
     }
 }
 
+// Messages
+
+struct MinijinjaBamlMessage {
+    message: BamlMessage,
+}
+
+const MAGIC_MESSAGE_DELIMITER: &str = "BAML_MESSAGE_MAGIC_STRING_DELIMITER";
+
+impl std::fmt::Display for MinijinjaBamlMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{MAGIC_MESSAGE_DELIMITER}:baml-start-message:{}:baml-end-message:{MAGIC_MESSAGE_DELIMITER}",
+            serde_json::json!(self.message)
+        )
+    }
+}
+
+// Like media, nested messages have to render as the magic string too.
+impl std::fmt::Debug for MinijinjaBamlMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl minijinja::value::Object for MinijinjaBamlMessage {
+    fn kind(&self) -> minijinja::value::ObjectKind<'_> {
+        minijinja::value::ObjectKind::Struct(self)
+    }
+}
+
+impl minijinja::value::StructObject for MinijinjaBamlMessage {
+    fn get_field(&self, name: &str) -> Option<minijinja::Value> {
+        match name {
+            "role" => Some(minijinja::Value::from(self.message.role.clone())),
+            "content" => Some(minijinja::Value::from(
+                self.message
+                    .content
+                    .iter()
+                    .map(|part| match part {
+                        BamlMessagePart::Text(t) => minijinja::Value::from(t.clone()),
+                        BamlMessagePart::Media(m) => {
+                            minijinja::Value::from_object(MinijinjaBamlMedia::from(m.clone()))
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            "name" => Some(minijinja::Value::from(self.message.name.clone())),
+            "metadata" => Some(minijinja::Value::from_serialize(&self.message.metadata)),
+            _ => None,
+        }
+    }
+
+    fn static_fields(&self) -> Option<&'static [&'static str]> {
+        Some(&["role", "content", "name", "metadata"])
+    }
+}
+
+struct MinijinjaBamlMessages {
+    messages: Vec<minijinja::Value>,
+}
+
+impl std::fmt::Display for MinijinjaBamlMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for message in &self.messages {
+            write!(f, "{message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for MinijinjaBamlMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl minijinja::value::Object for MinijinjaBamlMessages {
+    fn kind(&self) -> minijinja::value::ObjectKind<'_> {
+        minijinja::value::ObjectKind::Seq(self)
+    }
+}
+
+impl minijinja::value::SeqObject for MinijinjaBamlMessages {
+    fn get_item(&self, idx: usize) -> Option<minijinja::Value> {
+        self.messages.get(idx).cloned()
+    }
+
+    fn item_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
 // Enums
 
 struct MinijinjaBamlEnum {
@@ -1,4 +1,4 @@
-use baml_types::{BamlMedia, BamlValue, EvaluationContext};
+use baml_types::{BamlMedia, BamlMessage, BamlMessagePart, BamlValue, EvaluationContext};
 use colored::*;
 mod chat_message_part;
 
@@ -7,6 +7,7 @@ use internal_baml_core::ir::jinja_helpers::get_env;
 use internal_baml_core::ir::repr::IntermediateRepr;
 pub use output_format::types;
 mod baml_value_to_jinja_value;
+pub mod tokenizer;
 
 use minijinja::{self, value::Kwargs};
 use minijinja::{context, ErrorKind};
@@ -18,6 +19,7 @@ use std::collections::HashMap;
 use crate::baml_value_to_jinja_value::IntoMiniJinjaValue;
 pub use crate::chat_message_part::ChatMessagePart;
 use crate::output_format::OutputFormat;
+use crate::tokenizer::Tokenizer;
 
 #[allow(non_camel_case_types)]
 #[derive(Clone, Debug, Serialize)]
@@ -26,6 +28,8 @@ pub struct RenderContext_Client {
     pub provider: String,
     pub default_role: String,
     pub allowed_roles: Vec<String>,
+    /// The model the client calls, if it is known before the request is made.
+    pub model: Option<String>,
 }
 
 #[derive(Debug)]
@@ -43,6 +47,8 @@ pub struct TemplateStringMacro {
 
 const MAGIC_CHAT_ROLE_DELIMITER: &str = "BAML_CHAT_ROLE_MAGIC_STRING_DELIMITER";
 const MAGIC_MEDIA_DELIMITER: &str = "BAML_MEDIA_MAGIC_STRING_DELIMITER";
+const MAGIC_MESSAGE_DELIMITER: &str = "BAML_MESSAGE_MAGIC_STRING_DELIMITER";
+const MAGIC_CACHE_DELIMITER: &str = "BAML_CACHE_MAGIC_STRING_DELIMITER";
 
 fn render_minijinja(
     template: &str,
@@ -54,6 +60,13 @@ fn render_minijinja(
 ) -> Result<RenderedPrompt, minijinja::Error> {
     let mut env = get_env();
 
+    // Counted with the tokenizer of the model the prompt is rendered for.
+    let tokenizer = Tokenizer::for_model(ctx.client.model.as_deref());
+    env.add_filter("token_count", move |text: String| tokenizer.count(&text));
+    env.add_filter("truncate_tokens", move |text: String, max_tokens: usize| {
+        tokenizer.truncate(&text, max_tokens)
+    });
+
     // dedent
     let whitespace_length = template
         .split('\n')
@@ -94,6 +107,7 @@ fn render_minijinja(
 
     env.add_template("prompt", &template)?;
     let client = ctx.client.clone();
+    let client_name = client.name.clone();
     let tags = std::mem::take(&mut ctx.tags);
     let formatter = OutputFormat::new(ctx);
     env.add_global(
@@ -158,11 +172,26 @@ fn render_minijinja(
         },
     );
 
+    // Marks everything before it as a prompt cache prefix, by setting
+    // `cache_control` on the part that precedes it.
+    let cache_breakpoint_fn = minijinja::Value::from_function(
+        |kwargs: Kwargs| -> Result<String, minijinja::Error> {
+            let mut cache_control = json!({ "type": "ephemeral" });
+            if let Some(ttl) = kwargs.get::<Option<String>>("ttl")? {
+                cache_control["ttl"] = ttl.into();
+            }
+            kwargs.assert_all_used()?;
+
+            Ok(format!("{MAGIC_CACHE_DELIMITER}:baml-start-cache:{cache_control}:baml-end-cache:{MAGIC_CACHE_DELIMITER}"))
+        },
+    );
+
     env.add_global(
         "_",
         context! {
             chat => role_fn,
-            role => role_fn
+            role => role_fn,
+            cache_breakpoint => cache_breakpoint_fn
         },
     );
 
@@ -170,7 +199,11 @@ fn render_minijinja(
 
     let rendered = tmpl.render(args)?;
 
-    if !rendered.contains(MAGIC_CHAT_ROLE_DELIMITER) && !rendered.contains(MAGIC_MEDIA_DELIMITER) {
+    if !rendered.contains(MAGIC_CHAT_ROLE_DELIMITER)
+        && !rendered.contains(MAGIC_MEDIA_DELIMITER)
+        && !rendered.contains(MAGIC_MESSAGE_DELIMITER)
+        && !rendered.contains(MAGIC_CACHE_DELIMITER)
+    {
         return Ok(RenderedPrompt::Completion(rendered));
     }
 
@@ -178,7 +211,10 @@ fn render_minijinja(
     let mut role = None;
     let mut meta = None;
     let mut allow_duplicate_role = false;
-    for chunk in rendered.split(MAGIC_CHAT_ROLE_DELIMITER) {
+    for chunk in rendered
+        .split(MAGIC_CHAT_ROLE_DELIMITER)
+        .flat_map(|chunk| chunk.split(MAGIC_MESSAGE_DELIMITER))
+    {
         if chunk.starts_with(":baml-start-baml:") && chunk.ends_with(":baml-end-baml:") {
             let parsed = chunk
                 .strip_prefix(":baml-start-baml:")
@@ -203,11 +239,31 @@ fn render_minijinja(
                     meta = Some(parsed);
                 }
             }
+        } else if let Some(message) = chunk
+            .strip_prefix(":baml-start-message:")
+            .and_then(|c| c.strip_suffix(":baml-end-message:"))
+        {
+            // Text after the message keeps the role it would have had
+            // without it.
+            if let Some(message) = spliced_message(message, &allowed_roles, &client_name)? {
+                chat_messages.push(message);
+            }
         } else if role.is_none() && chunk.is_empty() {
             // If there's only whitespace before the first `_.chat()` directive, we discard that chunk
         } else {
             let mut parts = vec![];
-            for part in chunk.split(MAGIC_MEDIA_DELIMITER) {
+            for part in chunk
+                .split(MAGIC_MEDIA_DELIMITER)
+                .flat_map(|part| part.split(MAGIC_CACHE_DELIMITER))
+            {
+                if let Some(cache_control) = part
+                    .strip_prefix(":baml-start-cache:")
+                    .and_then(|p| p.strip_suffix(":baml-end-cache:"))
+                {
+                    mark_cache_breakpoint(&mut parts, &mut chat_messages, cache_control)?;
+                    continue;
+                }
+
                 let part = if part.starts_with(":baml-start-media:")
                     && part.ends_with(":baml-end-media:")
                 {
@@ -257,6 +313,104 @@ fn render_minijinja(
     Ok(RenderedPrompt::Chat(chat_messages))
 }
 
+/// Sets `cache_control` on the last part rendered before a
+/// `_.cache_breakpoint()`: the last part of the current message, or of the
+/// previous one if the current message has none yet.
+fn mark_cache_breakpoint(
+    parts: &mut [ChatMessagePart],
+    chat_messages: &mut [RenderedChatMessage],
+    cache_control: &str,
+) -> Result<(), minijinja::Error> {
+    let cache_control = serde_json::from_str::<serde_json::Value>(cache_control).map_err(|_| {
+        minijinja::Error::new(
+            ErrorKind::CannotUnpack,
+            format!(
+                "Cache breakpoint had unrecognizable data: {}",
+                cache_control
+            ),
+        )
+    })?;
+    let last = match parts.last_mut() {
+        Some(part) => part,
+        None => chat_messages
+            .last_mut()
+            .and_then(|m| m.parts.last_mut())
+            .ok_or_else(|| {
+                minijinja::Error::new(
+                    ErrorKind::InvalidOperation,
+                    "cache_breakpoint() must come after the content to cache",
+                )
+            })?,
+    };
+    let part = std::mem::replace(last, ChatMessagePart::Text(String::new()));
+    *last = part.with_meta(HashMap::from([(
+        "cache_control".to_string(),
+        cache_control,
+    )]));
+    Ok(())
+}
+
+/// Reads back a `message` value that was rendered into the prompt. Unlike
+/// `_.role()`, a role the client does not allow is an error rather than
+/// falling back to the default role.
+fn spliced_message(
+    data: &str,
+    allowed_roles: &[String],
+    client_name: &str,
+) -> Result<Option<RenderedChatMessage>, minijinja::Error> {
+    let message = serde_json::from_str::<BamlMessage>(data).map_err(|_| {
+        minijinja::Error::new(
+            ErrorKind::CannotUnpack,
+            format!("Message variable had unrecognizable data: {}", data),
+        )
+    })?;
+    if !allowed_roles.contains(&message.role) {
+        return Err(minijinja::Error::new(
+            ErrorKind::InvalidOperation,
+            format!(
+                "Message role '{}' is not allowed by client {}. Allowed roles: {}",
+                message.role,
+                client_name,
+                allowed_roles.join(", ")
+            ),
+        ));
+    }
+
+    let mut meta = message
+        .metadata
+        .into_iter()
+        .collect::<HashMap<String, serde_json::Value>>();
+    if let Some(name) = message.name {
+        meta.insert("name".to_string(), name.into());
+    }
+    let parts = message
+        .content
+        .into_iter()
+        .map(|part| {
+            let part = match part {
+                BamlMessagePart::Text(t) => ChatMessagePart::Text(t),
+                BamlMessagePart::Media(m) => ChatMessagePart::Media(m),
+            };
+            if meta.is_empty() {
+                part
+            } else {
+                part.with_meta(meta.clone())
+            }
+        })
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return Ok(None);
+    }
+
+    // Consecutive messages with the same role stay separate, as they were
+    // passed in.
+    Ok(Some(RenderedChatMessage {
+        role: message.role,
+        allow_duplicate_role: true,
+        parts,
+    }))
+}
+
 #[derive(Debug, PartialEq, Serialize, Clone)]
 pub struct RenderedChatMessage {
     pub role: String,
@@ -514,6 +668,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -542,6 +697,306 @@ mod render_tests {
         Ok(())
     }
 
+    #[test]
+    fn render_messages() -> anyhow::Result<()> {
+        setup_logging();
+
+        let mut answer = BamlMessage::text("assistant".to_string(), "A cat.".to_string());
+        answer.name = Some("bot".to_string());
+        let args = BamlValue::Map(BamlMap::from([
+            (
+                "history".to_string(),
+                BamlValue::List(vec![
+                    BamlValue::Message(BamlMessage::new(
+                        "user".to_string(),
+                        vec![
+                            BamlMessagePart::Text("What is this?".to_string()),
+                            BamlMessagePart::Media(BamlMedia::url(
+                                BamlMediaType::Image,
+                                "https://example.com/cat.jpg".to_string(),
+                                None,
+                            )),
+                        ],
+                    )),
+                    BamlValue::Message(answer),
+                ]),
+            ),
+            (
+                "question".to_string(),
+                BamlValue::String("And now?".to_string()),
+            ),
+        ]));
+
+        let ir = make_test_ir("")?;
+        let render_ctx = |allowed_roles: &[&str]| RenderContext {
+            client: RenderContext_Client {
+                name: "gpt4".to_string(),
+                provider: "openai".to_string(),
+                default_role: "user".to_string(),
+                allowed_roles: allowed_roles.iter().map(|r| r.to_string()).collect(),
+                model: None,
+            },
+            output_format: OutputFormatContent::new_string(),
+            tags: HashMap::new(),
+        };
+
+        let rendered = render_prompt(
+            "{{ _.role(\"system\") }}
+            You describe images.
+            {{ history }}
+            {{ _.role(\"user\") }}
+            {{ question }}",
+            &args,
+            render_ctx(&["system", "user", "assistant"]),
+            &[],
+            &ir,
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(
+            rendered,
+            RenderedPrompt::Chat(vec![
+                RenderedChatMessage {
+                    role: "system".to_string(),
+                    allow_duplicate_role: false,
+                    parts: vec![ChatMessagePart::Text("You describe images.".to_string())],
+                },
+                RenderedChatMessage {
+                    role: "user".to_string(),
+                    allow_duplicate_role: true,
+                    parts: vec![
+                        ChatMessagePart::Text("What is this?".to_string()),
+                        ChatMessagePart::Media(BamlMedia::url(
+                            BamlMediaType::Image,
+                            "https://example.com/cat.jpg".to_string(),
+                            None
+                        )),
+                    ],
+                },
+                RenderedChatMessage {
+                    role: "assistant".to_string(),
+                    allow_duplicate_role: true,
+                    parts: vec![ChatMessagePart::Text("A cat.".to_string())
+                        .with_meta(HashMap::from([("name".to_string(), "bot".into())]))],
+                },
+                RenderedChatMessage {
+                    role: "user".to_string(),
+                    allow_duplicate_role: false,
+                    parts: vec![ChatMessagePart::Text("And now?".to_string())],
+                },
+            ])
+        );
+
+        // Roles of messages are checked against the roles the client allows.
+        let rendered = render_prompt(
+            "{{ history }}",
+            &args,
+            render_ctx(&["system", "user"]),
+            &[],
+            &ir,
+            &HashMap::new(),
+        );
+        assert!(rendered
+            .unwrap_err()
+            .to_string()
+            .contains("Message role 'assistant' is not allowed by client gpt4"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_cache_breakpoints() -> anyhow::Result<()> {
+        setup_logging();
+
+        let args = BamlValue::Map(BamlMap::from([(
+            "doc".to_string(),
+            BamlValue::String("A long document.".to_string()),
+        )]));
+
+        let ir = make_test_ir("")?;
+        let render_ctx = || RenderContext {
+            client: RenderContext_Client {
+                name: "claude".to_string(),
+                provider: "anthropic".to_string(),
+                default_role: "user".to_string(),
+                allowed_roles: vec!["system".to_string(), "user".to_string()],
+                model: None,
+            },
+            output_format: OutputFormatContent::new_string(),
+            tags: HashMap::new(),
+        };
+        let cache_control = |ttl: Option<&str>| {
+            let mut cache_control = json!({ "type": "ephemeral" });
+            if let Some(ttl) = ttl {
+                cache_control["ttl"] = ttl.into();
+            }
+            HashMap::from([("cache_control".to_string(), cache_control)])
+        };
+
+        let rendered = render_prompt(
+            "{{ _.role(\"system\") }}
+            You answer questions about documents.
+            {{ _.cache_breakpoint(ttl=\"1h\") }}
+            {{ _.role(\"user\") }}
+            {{ doc }}
+            {{ _.cache_breakpoint() }}
+            What is it about?",
+            &args,
+            render_ctx(),
+            &[],
+            &ir,
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(
+            rendered,
+            RenderedPrompt::Chat(vec![
+                RenderedChatMessage {
+                    role: "system".to_string(),
+                    allow_duplicate_role: false,
+                    parts: vec![ChatMessagePart::Text(
+                        "You answer questions about documents.".to_string()
+                    )
+                    .with_meta(cache_control(Some("1h")))],
+                },
+                RenderedChatMessage {
+                    role: "user".to_string(),
+                    allow_duplicate_role: false,
+                    parts: vec![
+                        ChatMessagePart::Text("A long document.".to_string())
+                            .with_meta(cache_control(None)),
+                        ChatMessagePart::Text("What is it about?".to_string()),
+                    ],
+                },
+            ])
+        );
+
+        // A breakpoint at the start of a message marks the end of the
+        // previous one.
+        let rendered = render_prompt(
+            "{{ _.role(\"system\") }}
+            You answer questions about documents.
+            {{ _.role(\"user\") }}
+            {{ _.cache_breakpoint() }}
+            {{ doc }}",
+            &args,
+            render_ctx(),
+            &[],
+            &ir,
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(
+            rendered,
+            RenderedPrompt::Chat(vec![
+                RenderedChatMessage {
+                    role: "system".to_string(),
+                    allow_duplicate_role: false,
+                    parts: vec![ChatMessagePart::Text(
+                        "You answer questions about documents.".to_string()
+                    )
+                    .with_meta(cache_control(None))],
+                },
+                RenderedChatMessage {
+                    role: "user".to_string(),
+                    allow_duplicate_role: false,
+                    parts: vec![ChatMessagePart::Text("A long document.".to_string())],
+                },
+            ])
+        );
+
+        let rendered = render_prompt(
+            "{{ _.cache_breakpoint() }} {{ doc }}",
+            &args,
+            render_ctx(),
+            &[],
+            &ir,
+            &HashMap::new(),
+        );
+        assert!(rendered
+            .unwrap_err()
+            .to_string()
+            .contains("cache_breakpoint() must come after the content to cache"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_pdf_and_video() -> anyhow::Result<()> {
+        setup_logging();
+
+        let args = BamlValue::Map(BamlMap::from([
+            (
+                "doc".to_string(),
+                BamlValue::Media(BamlMedia::base64(
+                    BamlMediaType::Pdf,
+                    "JVBERi0xLjQK".to_string(),
+                    Some("application/pdf".to_string()),
+                )),
+            ),
+            (
+                "clip".to_string(),
+                BamlValue::Media(BamlMedia::url(
+                    BamlMediaType::Video,
+                    "https://example.com/clip.mp4".to_string(),
+                    Some("video/mp4".to_string()),
+                )),
+            ),
+        ]));
+
+        let ir = make_test_ir(
+            "
+            class C {
+                
+            }
+            ",
+        )?;
+
+        let rendered = render_prompt(
+            "{{ _.chat(\"user\") }}
+            Summarize {{ doc }} and describe {{ clip }}",
+            &args,
+            RenderContext {
+                client: RenderContext_Client {
+                    name: "gemini".to_string(),
+                    provider: "google-ai".to_string(),
+                    default_role: "user".to_string(),
+                    allowed_roles: vec!["user".to_string()],
+                    model: None,
+                },
+                output_format: OutputFormatContent::new_string(),
+                tags: HashMap::new(),
+            },
+            &[],
+            &ir,
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(
+            rendered,
+            RenderedPrompt::Chat(vec![RenderedChatMessage {
+                role: "user".to_string(),
+                allow_duplicate_role: false,
+                parts: vec![
+                    ChatMessagePart::Text("Summarize".to_string()),
+                    ChatMessagePart::Media(BamlMedia::base64(
+                        BamlMediaType::Pdf,
+                        "JVBERi0xLjQK".to_string(),
+                        Some("application/pdf".to_string()),
+                    )),
+                    ChatMessagePart::Text("and describe".to_string()),
+                    ChatMessagePart::Media(BamlMedia::url(
+                        BamlMediaType::Video,
+                        "https://example.com/clip.mp4".to_string(),
+                        Some("video/mp4".to_string()),
+                    )),
+                ]
+            },])
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn render_image_nested() -> anyhow::Result<()> {
         setup_logging();
@@ -575,6 +1030,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -634,6 +1090,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -702,6 +1159,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string(), "john doe".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -780,6 +1238,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -831,6 +1290,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -871,6 +1331,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -911,6 +1372,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -951,6 +1413,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -1013,6 +1476,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -1073,6 +1537,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string(), "john doe".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -1158,6 +1623,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string(), "user".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -1236,6 +1702,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::from([("ROLE".to_string(), BamlValue::String("john doe".into()))]),
@@ -1291,6 +1758,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1342,6 +1810,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1389,6 +1858,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1409,6 +1879,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1452,6 +1923,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1472,6 +1944,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1515,6 +1988,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1589,6 +2063,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1683,6 +2158,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1789,6 +2265,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1866,6 +2343,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -1926,6 +2404,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
@@ -2031,6 +2510,7 @@ mod render_tests {
                     provider: "openai".to_string(),
                     default_role: "system".to_string(),
                     allowed_roles: vec!["system".to_string()],
+                    model: None,
                 },
                 output_format: OutputFormatContent::new_string(),
                 tags: HashMap::new(),
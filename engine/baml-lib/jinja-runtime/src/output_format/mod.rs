@@ -163,10 +163,32 @@ impl minijinja::value::Object for OutputFormat {
             None
         };
 
+        let style = if kwargs.has("style") {
+            match kwargs
+                .get::<String>("style")
+                .map_err(|e| e.to_string())
+                .and_then(|s| types::OutputStyle::from_str(s.as_str()).map_err(|e| e.to_string()))
+            {
+                Ok(style) => Some(style),
+                Err(e) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!(
+                            "Invalid value for style (expected one of {}): {}",
+                            types::OutputStyle::VARIANTS.join(", "),
+                            e
+                        ),
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+
         let Ok(_) = kwargs.assert_all_used() else {
             return Err(Error::new(
                 ErrorKind::TooManyArguments,
-                "output_format() got an unexpected keyword argument (only 'prefix', 'always_host_enums', 'enum_value_prefix', and 'or_splitter' are allowed)",
+                "output_format() got an unexpected keyword argument (only 'prefix', 'always_hoist_enums', 'enum_value_prefix', 'or_splitter', 'map_style', 'hoisted_class_prefix' and 'style' are allowed)",
             ));
         };
 
@@ -177,6 +199,7 @@ impl minijinja::value::Object for OutputFormat {
             always_hoist_enums,
             map_style,
             hoisted_class_prefix,
+            style,
         ))?;
 
         match content {
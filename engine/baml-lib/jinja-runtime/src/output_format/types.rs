@@ -1,7 +1,7 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 use anyhow::Result;
-use baml_types::{Constraint, FieldType, TypeValue};
+use baml_types::{BamlTemporalType, Constraint, FieldType, TypeValue};
 use indexmap::{IndexMap, IndexSet};
 
 #[derive(Debug)]
@@ -141,6 +141,53 @@ pub(crate) enum MapStyle {
     ObjectLiteral,
 }
 
+/// Serialization format the model is asked to answer in. `jsonish` can parse
+/// all of them back into the same value tree.
+#[derive(Clone, Copy, PartialEq, Eq, strum::EnumString, strum::VariantNames)]
+pub(crate) enum OutputStyle {
+    #[strum(serialize = "json")]
+    Json,
+
+    #[strum(serialize = "yaml")]
+    Yaml,
+
+    #[strum(serialize = "xml")]
+    Xml,
+
+    #[strum(serialize = "toml")]
+    Toml,
+}
+
+impl OutputStyle {
+    fn display_name(&self) -> &'static str {
+        match self {
+            OutputStyle::Json => "JSON",
+            OutputStyle::Yaml => "YAML",
+            OutputStyle::Xml => "XML",
+            OutputStyle::Toml => "TOML",
+        }
+    }
+}
+
+/// Whether a prompt template calls `output_format` with a literal `style`
+/// other than JSON, in which case the output is also parsed in that style.
+pub fn requests_alternate_style(template: &str) -> bool {
+    template
+        .match_indices("output_format(")
+        .any(|(start, call)| {
+            let args = &template[start + call.len()..];
+            let args = &args[..args.find(')').unwrap_or(args.len())];
+            args.split(',').any(|arg| {
+                let Some((key, value)) = arg.split_once('=') else {
+                    return false;
+                };
+                let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                key.trim() == "style"
+                    && OutputStyle::from_str(value).is_ok_and(|style| style != OutputStyle::Json)
+            })
+        })
+}
+
 pub(crate) struct RenderOptions {
     prefix: RenderSetting<String>,
     pub(crate) or_splitter: String,
@@ -148,6 +195,7 @@ pub(crate) struct RenderOptions {
     hoisted_class_prefix: RenderSetting<String>,
     always_hoist_enums: RenderSetting<bool>,
     map_style: MapStyle,
+    style: OutputStyle,
 }
 
 impl Default for RenderOptions {
@@ -159,6 +207,7 @@ impl Default for RenderOptions {
             hoisted_class_prefix: RenderSetting::Auto,
             always_hoist_enums: RenderSetting::Auto,
             map_style: MapStyle::TypeParameters,
+            style: OutputStyle::Json,
         }
     }
 }
@@ -174,6 +223,7 @@ impl RenderOptions {
         always_hoist_enums: Option<bool>,
         map_style: Option<MapStyle>,
         hoisted_class_prefix: Option<Option<String>>,
+        style: Option<OutputStyle>,
     ) -> Self {
         Self {
             prefix: prefix.map_or(RenderSetting::Auto, |p| {
@@ -189,6 +239,7 @@ impl RenderOptions {
             hoisted_class_prefix: hoisted_class_prefix.map_or(RenderSetting::Auto, |p| {
                 p.map_or(RenderSetting::Never, RenderSetting::Always)
             }),
+            style: style.unwrap_or(OutputStyle::Json),
         }
     }
 
@@ -244,6 +295,7 @@ struct ClassRender {
     #[allow(dead_code)]
     name: String,
     values: Vec<ClassFieldRender>,
+    style: OutputStyle,
 }
 
 struct ClassFieldRender {
@@ -254,19 +306,84 @@ struct ClassFieldRender {
 
 impl std::fmt::Display for ClassRender {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{{")?;
-        for value in &self.values {
-            if let Some(desc) = &value.description {
-                writeln!(f, "  // {}", desc.replace("\n", "\n  // "))?;
+        match self.style {
+            OutputStyle::Json => {
+                writeln!(f, "{{")?;
+                for value in &self.values {
+                    if let Some(desc) = &value.description {
+                        writeln!(f, "  // {}", desc.replace("\n", "\n  // "))?;
+                    }
+                    writeln!(
+                        f,
+                        "  {}: {},",
+                        value.name,
+                        value.r#type.replace('\n', "\n  ")
+                    )?;
+                }
+                write!(f, "}}")
+            }
+            OutputStyle::Yaml => {
+                let lines = self
+                    .values
+                    .iter()
+                    .map(|value| {
+                        let mut field = String::new();
+                        if let Some(desc) = &value.description {
+                            field.push_str(&format!("# {}\n", desc.replace('\n', "\n# ")));
+                        }
+                        // Nested objects and lists go on their own indented block.
+                        if value.r#type.contains('\n') || value.r#type.starts_with("- ") {
+                            field.push_str(&format!(
+                                "{}:\n  {}",
+                                value.name,
+                                value.r#type.replace('\n', "\n  ")
+                            ));
+                        } else {
+                            field.push_str(&format!("{}: {}", value.name, value.r#type));
+                        }
+                        field
+                    })
+                    .collect::<Vec<_>>();
+                write!(f, "{}", lines.join("\n"))
+            }
+            OutputStyle::Xml => {
+                let lines = self
+                    .values
+                    .iter()
+                    .map(|value| {
+                        let mut field = String::new();
+                        if let Some(desc) = &value.description {
+                            field.push_str(&format!("<!-- {} -->\n", desc));
+                        }
+                        if value.r#type.contains('\n') {
+                            field.push_str(&format!(
+                                "<{name}>\n  {}\n</{name}>",
+                                value.r#type.replace('\n', "\n  "),
+                                name = value.name,
+                            ));
+                        } else {
+                            field.push_str(&format!(
+                                "<{name}>{}</{name}>",
+                                value.r#type,
+                                name = value.name,
+                            ));
+                        }
+                        field
+                    })
+                    .collect::<Vec<_>>();
+                write!(f, "{}", lines.join("\n"))
+            }
+            // Nested TOML tables are rendered inline, see
+            // `OutputFormatContent::toml_table` for the top level.
+            OutputStyle::Toml => {
+                let fields = self
+                    .values
+                    .iter()
+                    .map(|value| format!("{} = {}", value.name, value.r#type))
+                    .collect::<Vec<_>>();
+                write!(f, "{{ {} }}", fields.join(", "))
             }
-            writeln!(
-                f,
-                "  {}: {},",
-                value.name,
-                value.r#type.replace('\n', "\n  ")
-            )?;
         }
-        write!(f, "}}")
     }
 }
 
@@ -331,16 +448,23 @@ impl OutputFormatContent {
                         "\n"
                     };
 
-                    Some(format!("Answer in JSON using this {type_prefix}:{end}"))
+                    Some(format!(
+                        "Answer in {} using this {type_prefix}:{end}",
+                        options.style.display_name()
+                    ))
                 }
-                FieldType::List(_) => Some(String::from(
-                    "Answer with a JSON Array using this schema:\n",
+                FieldType::List(_) => Some(format!(
+                    "Answer with a {} Array using this schema:\n",
+                    options.style.display_name()
+                )),
+                FieldType::Union(_) => Some(format!(
+                    "Answer in {} using any of these schemas:\n",
+                    options.style.display_name()
+                )),
+                FieldType::Optional(_) | FieldType::Map(_, _) => Some(format!(
+                    "Answer in {} using this schema:\n",
+                    options.style.display_name()
                 )),
-                FieldType::Union(_) => {
-                    Some(String::from("Answer in JSON using any of these schemas:\n"))
-                }
-                FieldType::Optional(_) => Some(String::from("Answer in JSON using this schema:\n")),
-                FieldType::Map(_, _) => Some(String::from("Answer in JSON using this schema:\n")),
                 FieldType::Tuple(_) => None,
                 FieldType::Constrained { base, .. } => {
                     auto_prefix(base, options, output_format_content)
@@ -415,12 +539,25 @@ impl OutputFormatContent {
                 TypeValue::Float => "float".to_string(),
                 TypeValue::Bool => "bool".to_string(),
                 TypeValue::Null => "null".to_string(),
+                TypeValue::Temporal(BamlTemporalType::Date) => "date (YYYY-MM-DD)".to_string(),
+                TypeValue::Temporal(BamlTemporalType::DateTime) => {
+                    "datetime (ISO 8601, e.g. 2024-03-01T14:30:00Z)".to_string()
+                }
+                TypeValue::Temporal(BamlTemporalType::Duration) => {
+                    "duration (ISO 8601, e.g. PT2H30M)".to_string()
+                }
                 TypeValue::Media(media_type) => {
                     return Err(minijinja::Error::new(
                         minijinja::ErrorKind::BadSerialization,
                         format!("type '{media_type}' is not supported in outputs"),
                     ))
                 }
+                TypeValue::Message => {
+                    return Err(minijinja::Error::new(
+                        minijinja::ErrorKind::BadSerialization,
+                        "type 'message' is not supported in outputs",
+                    ))
+                }
             },
             FieldType::Literal(v) => v.to_string(),
             FieldType::Constrained { base, .. } => {
@@ -478,6 +615,7 @@ impl OutputFormatContent {
                             })
                         })
                         .collect::<Result<_, minijinja::Error>>()?,
+                    style: options.style,
                 }
                 .to_string()
             }
@@ -490,14 +628,23 @@ impl OutputFormatContent {
                 let inner_str =
                     self.render_possibly_recursive_type(options, inner, render_state, false)?;
 
-                if !is_recursive
+                let is_block = !is_recursive
                     && match inner.as_ref() {
                         FieldType::Primitive(_) => false,
                         FieldType::Optional(t) => !t.is_primitive(),
                         FieldType::Enum(_e) => inner_str.len() > 15,
                         _ => true,
+                    };
+
+                if is_block && options.style == OutputStyle::Yaml {
+                    format!("- {}", inner_str.replace('\n', "\n  "))
+                } else if is_block && options.style == OutputStyle::Xml {
+                    if inner_str.contains('\n') {
+                        format!("<item>\n  {}\n</item>", inner_str.replace('\n', "\n  "))
+                    } else {
+                        format!("<item>{inner_str}</item>")
                     }
-                {
+                } else if is_block {
                     format!("[\n  {}\n]", inner_str.replace('\n', "\n  "))
                 } else if matches!(inner.as_ref(), FieldType::Optional(_)) {
                     format!("({})[]", inner_str)
@@ -541,6 +688,35 @@ impl OutputFormatContent {
         })
     }
 
+    /// Top level TOML classes are rendered as a table of `key = type` lines;
+    /// nested classes stay inline since they would need `[section]` headers.
+    fn toml_table(
+        &self,
+        options: &RenderOptions,
+        cls: &str,
+        render_state: &mut RenderState,
+    ) -> Result<String, minijinja::Error> {
+        let Some(class) = self.classes.get(cls) else {
+            return Err(minijinja::Error::new(
+                minijinja::ErrorKind::BadSerialization,
+                format!("Class {cls} not found"),
+            ));
+        };
+
+        let mut lines = Vec::new();
+        for (name, field_type, description) in &class.fields {
+            if let Some(desc) = description {
+                lines.push(format!("# {}", desc.replace('\n', "\n# ")));
+            }
+            lines.push(format!(
+                "{} = {}",
+                name.rendered_name(),
+                self.render_possibly_recursive_type(options, field_type, render_state, false)?
+            ));
+        }
+        Ok(lines.join("\n"))
+    }
+
     pub(crate) fn render(
         &self,
         options: RenderOptions,
@@ -563,6 +739,25 @@ impl OutputFormatContent {
 
                 Some(self.enum_to_string(enm, &options))
             }
+            FieldType::Class(cls)
+                if options.style == OutputStyle::Toml && !self.recursive_classes.contains(cls) =>
+            {
+                Some(self.toml_table(&options, cls, &mut render_state)?)
+            }
+            FieldType::Class(cls)
+                if options.style == OutputStyle::Xml && !self.recursive_classes.contains(cls) =>
+            {
+                let fields =
+                    self.inner_type_render(&options, &self.target, &mut render_state, false)?;
+                let root = self
+                    .classes
+                    .get(cls)
+                    .map_or(cls.as_str(), |c| c.name.rendered_name());
+                Some(format!(
+                    "<{root}>\n  {}\n</{root}>",
+                    fields.replace('\n', "\n  ")
+                ))
+            }
             _ => Some(self.inner_type_render(&options, &self.target, &mut render_state, false)?),
         };
 
@@ -2214,4 +2409,115 @@ Answer in JSON using this schema:
             ))
         );
     }
+
+    fn person_and_order_classes() -> Vec<Class> {
+        vec![
+            Class {
+                name: Name::new("Person".to_string()),
+                fields: vec![
+                    (
+                        Name::new("name".to_string()),
+                        FieldType::string(),
+                        Some("The person's name".to_string()),
+                    ),
+                    (Name::new("age".to_string()), FieldType::int(), None),
+                ],
+                constraints: Vec::new(),
+            },
+            Class {
+                name: Name::new("Order".to_string()),
+                fields: vec![
+                    (
+                        Name::new("buyer".to_string()),
+                        FieldType::class("Person"),
+                        None,
+                    ),
+                    (
+                        Name::new("items".to_string()),
+                        FieldType::list(FieldType::string()),
+                        None,
+                    ),
+                ],
+                constraints: Vec::new(),
+            },
+        ]
+    }
+
+    fn render_with_style(target: FieldType, style: OutputStyle) -> Option<String> {
+        let content = OutputFormatContent::target(target)
+            .classes(person_and_order_classes())
+            .build();
+        content
+            .render(RenderOptions {
+                style,
+                ..Default::default()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn render_class_as_yaml() {
+        assert_eq!(
+            render_with_style(FieldType::class("Order"), OutputStyle::Yaml),
+            Some(String::from(
+                "Answer in YAML using this schema:\nbuyer:\n  # The person's name\n  name: string\n  age: int\nitems: string[]"
+            ))
+        );
+    }
+
+    #[test]
+    fn render_list_of_classes_as_yaml() {
+        assert_eq!(
+            render_with_style(
+                FieldType::list(FieldType::class("Person")),
+                OutputStyle::Yaml
+            ),
+            Some(String::from(
+                "Answer with a YAML Array using this schema:\n- # The person's name\n  name: string\n  age: int"
+            ))
+        );
+    }
+
+    #[test]
+    fn render_class_as_xml() {
+        assert_eq!(
+            render_with_style(FieldType::class("Order"), OutputStyle::Xml),
+            Some(String::from(
+                "Answer in XML using this schema:\n<Order>\n  <buyer>\n    <!-- The person's name -->\n    <name>string</name>\n    <age>int</age>\n  </buyer>\n  <items>string[]</items>\n</Order>"
+            ))
+        );
+    }
+
+    #[test]
+    fn render_class_as_toml() {
+        assert_eq!(
+            render_with_style(FieldType::class("Order"), OutputStyle::Toml),
+            Some(String::from(
+                "Answer in TOML using this schema:\nbuyer = { name = string, age = int }\nitems = string[]"
+            ))
+        );
+        assert_eq!(
+            render_with_style(FieldType::class("Person"), OutputStyle::Toml),
+            Some(String::from(
+                "Answer in TOML using this schema:\n# The person's name\nname = string\nage = int"
+            ))
+        );
+    }
+
+    #[test]
+    fn detects_alternate_style() {
+        assert!(requests_alternate_style(
+            "{{ ctx.output_format(prefix=none, style=\"yaml\") }}"
+        ));
+        assert!(requests_alternate_style(
+            "{{ ctx.output_format(style='xml') }}"
+        ));
+        assert!(!requests_alternate_style(
+            "{{ ctx.output_format(style=\"json\") }}"
+        ));
+        assert!(!requests_alternate_style("{{ ctx.output_format }}"));
+        assert!(!requests_alternate_style(
+            "style=\"yaml\" {{ ctx.output_format() }}"
+        ));
+    }
 }
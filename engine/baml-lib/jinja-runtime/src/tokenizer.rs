@@ -0,0 +1,169 @@
+use crate::{ChatMessagePart, RenderedPrompt};
+
+/// Counts tokens locally, without a round trip to the provider.
+///
+/// OpenAI models are counted exactly with their BPE vocabulary. Other models
+/// don't publish theirs, so they get an approximation of ~4 characters per
+/// token, which is also what wasm builds use to avoid bundling the
+/// vocabularies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tokenizer {
+    #[cfg(not(target_arch = "wasm32"))]
+    Bpe(tiktoken_rs::tokenizer::Tokenizer),
+    Approximate,
+}
+
+const CHARS_PER_TOKEN: usize = 4;
+
+// Tokens every chat message costs on top of its content, for the role and
+// the delimiters around it.
+const TOKENS_PER_MESSAGE: usize = 4;
+
+impl Tokenizer {
+    pub fn for_model(model: Option<&str>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(model) = model {
+            use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer as Bpe};
+
+            // Azure and proxies often prefix the model, e.g. `openai/gpt-4o`.
+            let model = model.rsplit('/').next().unwrap_or(model);
+            let bpe = get_tokenizer(model).or_else(|| {
+                (model.starts_with("o1") || model.starts_with("o3")).then_some(Bpe::O200kBase)
+            });
+            if let Some(bpe) = bpe {
+                return Tokenizer::Bpe(bpe);
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        let _ = model;
+
+        Tokenizer::Approximate
+    }
+
+    /// Whether counts are exact rather than approximate.
+    pub fn is_exact(&self) -> bool {
+        !matches!(self, Tokenizer::Approximate)
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Tokenizer::Bpe(bpe) => with_bpe(*bpe, |bpe| bpe.encode_ordinary(text).len()),
+            Tokenizer::Approximate => text.chars().count().div_ceil(CHARS_PER_TOKEN),
+        }
+    }
+
+    /// The longest prefix of `text` that fits in `max_tokens`.
+    pub fn truncate(&self, text: &str, max_tokens: usize) -> String {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Tokenizer::Bpe(bpe) => with_bpe(*bpe, |bpe| {
+                let tokens = bpe.encode_ordinary(text);
+                if tokens.len() <= max_tokens {
+                    return text.to_string();
+                }
+                // Cutting between the bytes of a multi-byte character makes
+                // decoding fail, so back off until it doesn't.
+                (0..=max_tokens)
+                    .rev()
+                    .find_map(|n| bpe.decode(tokens[..n].to_vec()).ok())
+                    .unwrap_or_default()
+            }),
+            Tokenizer::Approximate => text
+                .chars()
+                .take(max_tokens.saturating_mul(CHARS_PER_TOKEN))
+                .collect(),
+        }
+    }
+
+    /// Counts the text of a prompt. Media is not counted, since how many
+    /// tokens an image or a file costs depends on the provider.
+    pub fn count_prompt(&self, prompt: &RenderedPrompt) -> usize {
+        match prompt {
+            RenderedPrompt::Completion(text) => self.count(text),
+            RenderedPrompt::Chat(messages) => messages
+                .iter()
+                .map(|message| {
+                    TOKENS_PER_MESSAGE
+                        + message
+                            .parts
+                            .iter()
+                            .filter_map(ChatMessagePart::as_text)
+                            .map(|text| self.count(text))
+                            .sum::<usize>()
+                })
+                .sum(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn with_bpe<T>(
+    tokenizer: tiktoken_rs::tokenizer::Tokenizer,
+    f: impl FnOnce(&tiktoken_rs::CoreBPE) -> T,
+) -> T {
+    use tiktoken_rs::tokenizer::Tokenizer as Bpe;
+
+    // Building a vocabulary takes a while, so each one is built once and shared.
+    let bpe = match tokenizer {
+        Bpe::O200kBase => tiktoken_rs::o200k_base_singleton(),
+        Bpe::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+        Bpe::P50kBase => tiktoken_rs::p50k_base_singleton(),
+        Bpe::P50kEdit => tiktoken_rs::p50k_edit_singleton(),
+        Bpe::R50kBase | Bpe::Gpt2 => tiktoken_rs::r50k_base_singleton(),
+    };
+    let bpe = bpe.lock();
+    f(&bpe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RenderedChatMessage;
+
+    #[test]
+    fn test_approximate() {
+        let tokenizer = Tokenizer::for_model(Some("claude-3-5-sonnet-latest"));
+        assert_eq!(tokenizer, Tokenizer::Approximate);
+        assert!(!tokenizer.is_exact());
+        assert_eq!(tokenizer.count("abcdefghi"), 3);
+        assert_eq!(tokenizer.truncate("abcdefghi", 1), "abcd");
+    }
+
+    #[test]
+    fn test_openai_models_use_bpe() {
+        let tokenizer = Tokenizer::for_model(Some("gpt-4o-mini"));
+        assert!(matches!(tokenizer, Tokenizer::Bpe(_)));
+        assert!(tokenizer.is_exact());
+        assert_eq!(tokenizer.count("hello world"), 2);
+        assert_eq!(tokenizer.truncate("hello world", 1), "hello");
+        assert_eq!(tokenizer.truncate("hello world", 10), "hello world");
+
+        assert!(matches!(
+            Tokenizer::for_model(Some("o3-mini")),
+            Tokenizer::Bpe(_)
+        ));
+    }
+
+    #[test]
+    fn test_count_prompt() {
+        let tokenizer = Tokenizer::Approximate;
+        let prompt = RenderedPrompt::Chat(vec![
+            RenderedChatMessage {
+                role: "system".to_string(),
+                allow_duplicate_role: false,
+                parts: vec![ChatMessagePart::Text("12345678".to_string())],
+            },
+            RenderedChatMessage {
+                role: "user".to_string(),
+                allow_duplicate_role: false,
+                parts: vec![ChatMessagePart::Text("1234".to_string())],
+            },
+        ]);
+        assert_eq!(
+            tokenizer.count_prompt(&prompt),
+            2 + 1 + 2 * TOKENS_PER_MESSAGE
+        );
+    }
+}
@@ -210,9 +210,11 @@ fn tracker_visit_expr(
                 "split",
                 "sum",
                 "title",
+                "token_count",
                 "tojson",
                 "json",
                 "trim",
+                "truncate_tokens",
                 "unique",
                 "urlencode",
             ];
@@ -303,8 +305,20 @@ fn tracker_visit_expr(
                     }
                 },
                 "title" => Type::String,
+                "token_count" => {
+                    if !inner.is_subtype_of(&Type::String) {
+                        ensure_type("string");
+                    }
+                    Type::Int
+                }
                 "tojson" | "json" => Type::String,
                 "trim" => Type::String,
+                "truncate_tokens" => {
+                    if !inner.is_subtype_of(&Type::String) {
+                        ensure_type("string");
+                    }
+                    Type::String
+                }
                 "unique" => Type::Unknown,
                 "urlencode" => Type::String,
                 other => {
@@ -270,8 +270,49 @@ fn test_output_format() {
     );
 
     assert_eq!(
-        assert_fails_to!("ctx.output_format(prefix='1', unknown=1)", &types),
-        vec!["Function 'baml::OutputFormat' does not have an argument 'unknown'. Did you mean one of these: 'always_hoist_enums', 'enum_value_prefix', 'or_splitter'?"]
+        assert_fails_to!(
+            "ctx.output_format(prefix='1', or_splitter='1', unknown=1)",
+            &types
+        ),
+        vec!["Function 'baml::OutputFormat' does not have an argument 'unknown'. Did you mean one of these: 'always_hoist_enums', 'enum_value_prefix', 'style'?"]
+    );
+
+    assert_eq!(
+        assert_evaluates_to!("ctx.output_format(style='yaml')", &types),
+        Type::String
+    );
+}
+
+#[test]
+fn cache_breakpoint() {
+    let types = PredefinedTypes::default(JinjaContext::Prompt);
+    assert_eq!(
+        assert_evaluates_to!("_.cache_breakpoint()", &types),
+        Type::String
+    );
+    assert_eq!(
+        assert_evaluates_to!("_.cache_breakpoint(ttl='1h')", &types),
+        Type::String
+    );
+    assert_eq!(
+        assert_fails_to!("_.cache_breakpoint(ttl=1)", &types),
+        vec!["Function 'baml::CacheBreakpoint' expects argument 'ttl' to be of type (none | string), but got literal[1]"]
+    );
+}
+
+#[test]
+fn token_filters() {
+    let mut types = PredefinedTypes::default(JinjaContext::Prompt);
+    types.add_variable("text", Type::String);
+    types.add_variable("n", Type::Int);
+    assert_eq!(assert_evaluates_to!("text|token_count", &types), Type::Int);
+    assert_eq!(
+        assert_evaluates_to!("text|truncate_tokens(100)", &types),
+        Type::String
+    );
+    assert_eq!(
+        assert_fails_to!("n|token_count", &types),
+        vec!["'n' is a int, expected string"]
     );
 }
 
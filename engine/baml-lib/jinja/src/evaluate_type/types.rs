@@ -35,6 +35,8 @@ pub enum Type {
     FunctionRef(String),
     Image,
     Audio,
+    Pdf,
+    Video,
 }
 
 impl Type {
@@ -94,6 +96,8 @@ impl Type {
             (Type::FunctionRef(_), _) => false,
             (Type::Image, _) => false,
             (Type::Audio, _) => false,
+            (Type::Pdf, _) => false,
+            (Type::Video, _) => false,
             (Type::String, _) => false,
             (Type::Bool, _) => false,
         }
@@ -149,6 +153,8 @@ impl Type {
             Type::FunctionRef(name) => format!("function {name}"),
             Type::Image => "image".into(),
             Type::Audio => "audio".into(),
+            Type::Pdf => "pdf".into(),
+            Type::Video => "video".into(),
         }
     }
 
@@ -256,6 +262,13 @@ impl PredefinedTypes {
                     "baml::Chat".into(),
                     (Type::String, vec![("role".into(), Type::String)]),
                 ),
+                (
+                    "baml::CacheBreakpoint".into(),
+                    (
+                        Type::String,
+                        vec![("ttl".into(), Type::merge(vec![Type::String, Type::None]))],
+                    ),
+                ),
                 (
                     "baml::OutputFormat".into(),
                     (
@@ -278,6 +291,7 @@ impl PredefinedTypes {
                                 "hoisted_class_prefix".into(),
                                 Type::merge(vec![Type::String, Type::None]),
                             ),
+                            ("style".into(), Type::merge(vec![Type::String, Type::None])),
                         ],
                     ),
                 ),
@@ -288,6 +302,10 @@ impl PredefinedTypes {
                     HashMap::from([
                         ("name".into(), Type::String),
                         ("provider".into(), Type::String),
+                        (
+                            "model".into(),
+                            Type::merge([Type::String, Type::None]),
+                        ),
                     ]),
                 ),
                 (
@@ -312,6 +330,10 @@ impl PredefinedTypes {
                     HashMap::from([
                         ("chat".into(), Type::FunctionRef("baml::Chat".into())),
                         ("role".into(), Type::FunctionRef("baml::Chat".into())),
+                        (
+                            "cache_breakpoint".into(),
+                            Type::FunctionRef("baml::CacheBreakpoint".into()),
+                        ),
                     ]),
                 ),
                 (
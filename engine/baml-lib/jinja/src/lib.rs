@@ -24,6 +24,14 @@ pub fn validate_expression(
     expression: &str,
     types: &mut PredefinedTypes,
 ) -> Result<(), ValidationError> {
+    expression_type(expression, types).map(|_| ())
+}
+
+/// Like `validate_expression`, returning the type the expression evaluates to.
+pub fn expression_type(
+    expression: &str,
+    types: &mut PredefinedTypes,
+) -> Result<Type, ValidationError> {
     let parsed = match minijinja::machinery::parse_expr(expression) {
         Ok(parsed) => parsed,
         Err(err) => {
@@ -34,14 +42,10 @@ pub fn validate_expression(
         }
     };
 
-    let expr_type = evaluate_type::evaluate_type(&parsed, types);
-    match expr_type {
-        Ok(_) => Ok(()),
-        Err(err) => Err(ValidationError {
-            errors: err,
-            parsing_errors: None,
-        }),
-    }
+    evaluate_type::evaluate_type(&parsed, types).map_err(|err| ValidationError {
+        errors: err,
+        parsing_errors: None,
+    })
 }
 
 pub fn validate_template(
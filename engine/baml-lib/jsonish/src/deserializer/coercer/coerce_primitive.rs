@@ -1,5 +1,5 @@
 use anyhow::Result;
-use baml_types::BamlMediaType;
+use baml_types::{BamlMediaType, BamlTemporal, BamlTemporalType};
 use internal_baml_core::ir::{FieldType, TypeValue};
 
 use crate::deserializer::{
@@ -40,6 +40,12 @@ impl TypeCoercer for TypeValue {
             TypeValue::Null => coerce_null(ctx, target, value),
             TypeValue::Media(BamlMediaType::Image) => Err(ctx.error_image_not_supported()),
             TypeValue::Media(BamlMediaType::Audio) => Err(ctx.error_audio_not_supported()),
+            TypeValue::Media(BamlMediaType::Pdf) => Err(ctx.error_pdf_not_supported()),
+            TypeValue::Media(BamlMediaType::Video) => Err(ctx.error_video_not_supported()),
+            TypeValue::Message => Err(ctx.error_message_not_supported()),
+            TypeValue::Temporal(temporal_type) => {
+                coerce_temporal(ctx, target, *temporal_type, value)
+            }
         }
     }
 }
@@ -215,6 +221,49 @@ fn coerce_float(
     }
 }
 
+fn coerce_temporal(
+    ctx: &ParsingContext,
+    target: &FieldType,
+    temporal_type: BamlTemporalType,
+    value: Option<&crate::jsonish::Value>,
+) -> Result<BamlValueWithFlags, ParsingError> {
+    if let Some(value) = value {
+        match value {
+            crate::jsonish::Value::String(s) => {
+                let s = s.trim().trim_end_matches(',');
+                if let Some(t) = BamlTemporal::parse_iso8601(temporal_type, s) {
+                    Ok(BamlValueWithFlags::Temporal(t.into()))
+                } else if let Ok(t) = BamlTemporal::parse(temporal_type, s) {
+                    Ok(BamlValueWithFlags::Temporal(
+                        (t, Flag::StringToTemporal(s.to_string())).into(),
+                    ))
+                } else {
+                    Err(ctx.error_unexpected_type(target, value))
+                }
+            }
+            crate::jsonish::Value::Number(n) => {
+                let Some(n) = n.as_f64() else {
+                    return Err(ctx.error_unexpected_type(target, value));
+                };
+                match BamlTemporal::from_bare_number(temporal_type, n) {
+                    Ok(t) => Ok(BamlValueWithFlags::Temporal(
+                        (t, Flag::NumberToTemporal(n)).into(),
+                    )),
+                    Err(_) => Err(ctx.error_unexpected_type(target, value)),
+                }
+            }
+            crate::jsonish::Value::Array(items) => {
+                coerce_array_to_singular(ctx, target, &items.iter().collect::<Vec<_>>(), &|value| {
+                    coerce_temporal(ctx, target, temporal_type, Some(value))
+                })
+            }
+            _ => Err(ctx.error_unexpected_type(target, value)),
+        }
+    } else {
+        Err(ctx.error_unexpected_null(target))
+    }
+}
+
 pub(super) fn coerce_bool(
     ctx: &ParsingContext,
     target: &FieldType,
@@ -74,6 +74,11 @@ impl TypeCoercer for FieldType {
                 v.add_flag(Flag::ObjectFromFixedJson(fixes.to_vec()));
                 Ok(v)
             }
+            Some(crate::jsonish::Value::Incomplete(v)) => {
+                let mut v = self.coerce(ctx, target, Some(v))?;
+                v.add_flag(Flag::Incomplete);
+                Ok(v)
+            }
             _ => match self {
                 FieldType::Primitive(p) => p.coerce(ctx, target, value),
                 FieldType::Enum(e) => IrRef::Enum(e).coerce(ctx, target, value),
@@ -38,15 +38,60 @@ pub enum Flag {
     StringToBool(String),
     StringToNull(String),
     StringToChar(String),
+    /// A date, datetime or duration that was not written in ISO 8601.
+    StringToTemporal(String),
 
     // Number -> X convertions.
     FloatToInt(f64),
+    NumberToTemporal(f64),
 
     // X -> Object convertions.
     NoFields(Option<crate::jsonish::Value>),
 
     /// Constraint results (only contains checks)
     ConstraintResults(Vec<(String, JinjaExpression, bool)>),
+
+    /// The value was cut off by the end of the input, e.g. while streaming.
+    Incomplete,
+}
+
+impl Flag {
+    /// The name of the flag, as used in `@@parser({ deny [...] })`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flag::ObjectFromMarkdown(..) => "ObjectFromMarkdown",
+            Flag::ObjectFromFixedJson(..) => "ObjectFromFixedJson",
+            Flag::DefaultButHadUnparseableValue(..) => "DefaultButHadUnparseableValue",
+            Flag::ObjectToString(..) => "ObjectToString",
+            Flag::ObjectToPrimitive(..) => "ObjectToPrimitive",
+            Flag::ObjectToMap(..) => "ObjectToMap",
+            Flag::ExtraKey(..) => "ExtraKey",
+            Flag::StrippedNonAlphaNumeric(..) => "StrippedNonAlphaNumeric",
+            Flag::SubstringMatch(..) => "SubstringMatch",
+            Flag::SingleToArray => "SingleToArray",
+            Flag::ArrayItemParseError(..) => "ArrayItemParseError",
+            Flag::MapKeyParseError(..) => "MapKeyParseError",
+            Flag::MapValueParseError(..) => "MapValueParseError",
+            Flag::JsonToString(..) => "JsonToString",
+            Flag::ImpliedKey(..) => "ImpliedKey",
+            Flag::InferedObject(..) => "InferedObject",
+            Flag::FirstMatch(..) => "FirstMatch",
+            Flag::UnionMatch(..) => "UnionMatch",
+            Flag::StrMatchOneFromMany(..) => "StrMatchOneFromMany",
+            Flag::DefaultFromNoValue => "DefaultFromNoValue",
+            Flag::DefaultButHadValue(..) => "DefaultButHadValue",
+            Flag::OptionalDefaultFromNoValue => "OptionalDefaultFromNoValue",
+            Flag::StringToBool(..) => "StringToBool",
+            Flag::StringToNull(..) => "StringToNull",
+            Flag::StringToChar(..) => "StringToChar",
+            Flag::StringToTemporal(..) => "StringToTemporal",
+            Flag::FloatToInt(..) => "FloatToInt",
+            Flag::NumberToTemporal(..) => "NumberToTemporal",
+            Flag::NoFields(..) => "NoFields",
+            Flag::ConstraintResults(..) => "ConstraintResults",
+            Flag::Incomplete => "Incomplete",
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -91,11 +136,14 @@ impl DeserializerConditions {
                 Flag::StringToBool(_) => None,
                 Flag::StringToNull(_) => None,
                 Flag::StringToChar(_) => None,
+                Flag::StringToTemporal(_) => None,
                 Flag::FloatToInt(_) => None,
+                Flag::NumberToTemporal(_) => None,
                 Flag::NoFields(_) => None,
                 Flag::UnionMatch(_idx, _) => None,
                 Flag::DefaultButHadUnparseableValue(e) => Some(e.clone()),
                 Flag::ConstraintResults(_) => None,
+                Flag::Incomplete => None,
             })
             .collect::<Vec<_>>()
     }
@@ -235,9 +283,15 @@ impl std::fmt::Display for Flag {
             Flag::StringToChar(value) => {
                 write!(f, "String to char: {}", value)?;
             }
+            Flag::StringToTemporal(value) => {
+                write!(f, "String to temporal: {}", value)?;
+            }
             Flag::FloatToInt(value) => {
                 write!(f, "Float to int: {}", value)?;
             }
+            Flag::NumberToTemporal(value) => {
+                write!(f, "Number to temporal: {}", value)?;
+            }
             Flag::NoFields(value) => {
                 write!(f, "No fields: ")?;
                 if let Some(value) = value {
@@ -256,6 +310,9 @@ impl std::fmt::Display for Flag {
                     )?;
                 }
             }
+            Flag::Incomplete => {
+                write!(f, "Incomplete value")?;
+            }
         }
         Ok(())
     }
@@ -1,5 +1,8 @@
 pub mod coercer;
 pub mod deserialize_flags;
+pub mod parse_report;
 // pub mod schema;
 mod score;
+pub mod streaming;
+pub(crate) mod strictness;
 pub mod types;
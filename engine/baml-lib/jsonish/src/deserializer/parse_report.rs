@@ -0,0 +1,86 @@
+use super::{deserialize_flags::Flag, score::WithScore, types::BamlValueWithFlags};
+
+/// The fixes and coercions the parser applied to a value, e.g. to monitor
+/// how much fixing the output of a function needs.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct ParseReport {
+    /// The score of the whole value. Lower is better, and 0 means the value
+    /// needed no coercions.
+    pub score: i32,
+    /// Every value that has flags, in document order.
+    pub entries: Vec<ParseReportEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct ParseReportEntry {
+    /// JSON path of the value, e.g. `$.items[0].name`.
+    pub path: String,
+    /// Names of the flags of the value, e.g. `SubstringMatch`.
+    pub flags: Vec<String>,
+    /// The score of these flags alone.
+    pub score: i32,
+}
+
+impl ParseReport {
+    pub fn new(value: &BamlValueWithFlags) -> Self {
+        let mut entries = vec![];
+        collect(value, "$", &mut entries);
+        Self {
+            score: value.score(),
+            entries,
+        }
+    }
+}
+
+fn collect(value: &BamlValueWithFlags, path: &str, entries: &mut Vec<ParseReportEntry>) {
+    push(path, value.conditions().flags().iter(), entries);
+    match value {
+        BamlValueWithFlags::List(_, items) => {
+            for (idx, item) in items.iter().enumerate() {
+                collect(item, &format!("{path}[{idx}]"), entries);
+            }
+        }
+        BamlValueWithFlags::Map(_, kv) => {
+            for (key, (key_flags, item)) in kv {
+                let item_path = format!("{path}[{key:?}]");
+                push(&item_path, key_flags.flags().iter(), entries);
+                collect(item, &item_path, entries);
+            }
+        }
+        BamlValueWithFlags::Class(_, _, fields) => {
+            for (name, field) in fields {
+                collect(field, &format!("{path}.{name}"), entries);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push<'a>(
+    path: &str,
+    flags: impl Iterator<Item = &'a Flag>,
+    entries: &mut Vec<ParseReportEntry>,
+) {
+    // Check results are reported with the value itself.
+    let flags = flags
+        .filter(|flag| !matches!(flag, Flag::ConstraintResults(_)))
+        .collect::<Vec<_>>();
+    if flags.is_empty() {
+        return;
+    }
+
+    let score = flags.iter().map(|flag| flag.score()).sum();
+    let flags = flags.iter().map(|flag| flag.name().to_string());
+    match entries.last_mut() {
+        // Map keys and values share a path.
+        Some(entry) if entry.path == path => {
+            entry.flags.extend(flags);
+            entry.score += score;
+        }
+        _ => entries.push(ParseReportEntry {
+            path: path.to_string(),
+            flags: flags.collect(),
+            score,
+        }),
+    }
+}
@@ -27,6 +27,7 @@ impl WithScore for BamlValueWithFlags {
             }
             BamlValueWithFlags::Null(s) => s.score(),
             BamlValueWithFlags::Media(s) => s.score(),
+            BamlValueWithFlags::Temporal(s) => s.score(),
         }
     }
 }
@@ -64,10 +65,14 @@ impl WithScore for Flag {
             Flag::StringToBool(_) => 1,
             Flag::StringToNull(_) => 1,
             Flag::StringToChar(_) => 1,
+            Flag::StringToTemporal(_) => 1,
             Flag::FloatToInt(_) => 1,
+            Flag::NumberToTemporal(_) => 1,
             Flag::NoFields(_) => 1,
             // No scores for contraints
             Flag::ConstraintResults(_) => 0,
+            // Being cut off is not a coercion
+            Flag::Incomplete => 0,
         }
     }
 }
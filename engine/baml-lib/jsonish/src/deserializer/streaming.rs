@@ -0,0 +1,107 @@
+use baml_types::BamlMap;
+use internal_baml_core::ir::{repr::IntermediateRepr, IRHelper};
+
+use super::{
+    deserialize_flags::{DeserializerConditions, Flag},
+    types::{BamlValueWithFlags, CompletionState},
+};
+
+/// Name of the class used to wrap fields marked with `@stream.with_state`.
+pub const STREAM_STATE_CLASS: &str = "StreamState";
+
+/// Applies the `@stream.*` attributes of the classes in `ir` to a partial
+/// value:
+///
+///   - `@stream.done` fields, and values of `@@stream.done` classes, stay
+///     null until they are complete.
+///   - `@stream.not_null` fields hold back their parent object until they
+///     have a value.
+///   - `@stream.with_state` fields are wrapped in a `StreamState` class
+///     carrying the value and its completion state.
+///
+/// Held back objects become null, or are left out of lists and maps.
+pub fn process_partial(ir: &IntermediateRepr, value: BamlValueWithFlags) -> BamlValueWithFlags {
+    process_value(ir, value).unwrap_or_else(pending_null)
+}
+
+fn pending_null() -> BamlValueWithFlags {
+    BamlValueWithFlags::Null(
+        DeserializerConditions::new().with_flag(Flag::OptionalDefaultFromNoValue),
+    )
+}
+
+/// Returns `None` if the value must be held back.
+fn process_value(ir: &IntermediateRepr, value: BamlValueWithFlags) -> Option<BamlValueWithFlags> {
+    let state = value.completion_state();
+    match value {
+        BamlValueWithFlags::Class(name, flags, fields) => {
+            let Ok(class) = ir.find_class(&name) else {
+                // Dynamic classes have no attributes.
+                return Some(BamlValueWithFlags::Class(name, flags, fields));
+            };
+            if class.stream_done() && state != CompletionState::Complete {
+                return None;
+            }
+
+            let mut processed = BamlMap::new();
+            for (field_name, field_value) in fields {
+                let Some(field) = class.find_field(&field_name) else {
+                    processed.insert(field_name, field_value);
+                    continue;
+                };
+
+                let field_state = field_value.completion_state();
+                let field_value = if field.stream_done() && field_state != CompletionState::Complete
+                {
+                    pending_null()
+                } else {
+                    process_value(ir, field_value).unwrap_or_else(pending_null)
+                };
+
+                if field.stream_not_null()
+                    && field_state != CompletionState::Complete
+                    && matches!(field_value, BamlValueWithFlags::Null(_))
+                {
+                    return None;
+                }
+
+                let field_value = if field.stream_with_state() {
+                    with_state(field_value, field_state)
+                } else {
+                    field_value
+                };
+                processed.insert(field_name, field_value);
+            }
+
+            Some(BamlValueWithFlags::Class(name, flags, processed))
+        }
+        BamlValueWithFlags::List(flags, items) => Some(BamlValueWithFlags::List(
+            flags,
+            items
+                .into_iter()
+                .filter_map(|item| process_value(ir, item))
+                .collect(),
+        )),
+        BamlValueWithFlags::Map(flags, kv) => Some(BamlValueWithFlags::Map(
+            flags,
+            kv.into_iter()
+                .filter_map(|(k, (v_flags, v))| process_value(ir, v).map(|v| (k, (v_flags, v))))
+                .collect(),
+        )),
+        _ => Some(value),
+    }
+}
+
+fn with_state(value: BamlValueWithFlags, state: CompletionState) -> BamlValueWithFlags {
+    let mut fields = BamlMap::new();
+    fields.insert("value".to_string(), value);
+    fields.insert(
+        "state".to_string(),
+        BamlValueWithFlags::String(state.as_str().to_string().into()),
+    );
+    BamlValueWithFlags::Class(
+        STREAM_STATE_CLASS.to_string(),
+        DeserializerConditions::new(),
+        fields,
+    )
+}
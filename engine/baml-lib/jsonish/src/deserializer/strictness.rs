@@ -0,0 +1,34 @@
+use anyhow::Result;
+use baml_types::ParserConfig;
+
+use super::types::BamlValueWithFlags;
+
+/// Fails if `value` goes over the `max_score` of `config`, or if any of its
+/// flags is listed in `deny`.
+pub(crate) fn check(value: &BamlValueWithFlags, config: &ParserConfig) -> Result<()> {
+    let mut violations = vec![];
+
+    if let Some(max_score) = config.max_score {
+        let score = value.score();
+        if score > max_score {
+            violations.push(format!("score {score} is above max_score {max_score}"));
+        }
+    }
+
+    let mut denied: Vec<&str> = vec![];
+    for flag in value.all_flags() {
+        let name = flag.name();
+        if config.deny.iter().any(|d| d == name) && !denied.contains(&name) {
+            denied.push(name);
+        }
+    }
+    if !denied.is_empty() {
+        violations.push(format!("denied flags fired: {}", denied.join(", ")));
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!("Parser strictness violated: {}", violations.join("; "))
+    }
+}
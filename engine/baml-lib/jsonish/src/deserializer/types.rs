@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 
-use baml_types::{BamlMap, BamlMedia, BamlValue, BamlValueWithMeta, Constraint, JinjaExpression};
+use baml_types::{
+    BamlMap, BamlMedia, BamlTemporal, BamlValue, BamlValueWithMeta, Constraint, JinjaExpression,
+};
 use serde_json::json;
 use strsim::jaro;
 
@@ -30,6 +32,7 @@ pub enum BamlValueWithFlags {
     ),
     Null(DeserializerConditions),
     Media(ValueWithFlags<BamlMedia>),
+    Temporal(ValueWithFlags<BamlTemporal>),
 }
 
 impl BamlValueWithFlags {
@@ -54,6 +57,7 @@ impl BamlValueWithFlags {
             }
             BamlValueWithFlags::Null(f) => f.score(),
             BamlValueWithFlags::Media(f) => f.score(),
+            BamlValueWithFlags::Temporal(f) => f.score(),
         }
     }
 
@@ -69,6 +73,65 @@ impl BamlValueWithFlags {
             BamlValueWithFlags::Class(_, v, _) => v,
             BamlValueWithFlags::Null(v) => v,
             BamlValueWithFlags::Media(v) => &v.flags,
+            BamlValueWithFlags::Temporal(v) => &v.flags,
+        }
+    }
+
+    /// The flags of this value and of every value nested in it.
+    pub fn all_flags(&self) -> Vec<&Flag> {
+        let mut flags = self.conditions().flags().iter().collect::<Vec<_>>();
+        match self {
+            BamlValueWithFlags::List(_, items) => {
+                items.iter().for_each(|item| flags.extend(item.all_flags()))
+            }
+            BamlValueWithFlags::Map(_, kv) => kv.values().for_each(|(f, v)| {
+                flags.extend(f.flags());
+                flags.extend(v.all_flags());
+            }),
+            BamlValueWithFlags::Class(_, _, fields) => fields
+                .values()
+                .for_each(|field| flags.extend(field.all_flags())),
+            _ => {}
+        }
+        flags
+    }
+}
+
+/// How much of a value has been seen in a (possibly partial) response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionState {
+    /// Nothing has been parsed for the value yet.
+    Pending,
+    /// The value has started but the input ended before it was closed.
+    Incomplete,
+    /// The value is fully parsed and won't change.
+    Complete,
+}
+
+impl CompletionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompletionState::Pending => "Pending",
+            CompletionState::Incomplete => "Incomplete",
+            CompletionState::Complete => "Complete",
+        }
+    }
+}
+
+impl BamlValueWithFlags {
+    pub fn completion_state(&self) -> CompletionState {
+        let flags = self.conditions().flags();
+        if flags.iter().any(|f| matches!(f, Flag::Incomplete)) {
+            CompletionState::Incomplete
+        } else if flags.iter().any(|f| {
+            matches!(
+                f,
+                Flag::OptionalDefaultFromNoValue | Flag::DefaultFromNoValue
+            )
+        }) {
+            CompletionState::Pending
+        } else {
+            CompletionState::Complete
         }
     }
 }
@@ -223,6 +286,16 @@ impl BamlValueWithFlags {
                     });
                 }
             }
+            BamlValueWithFlags::Temporal(v) => {
+                let causes = v.flags.explanation();
+                if !causes.is_empty() {
+                    expls.push(ParsingError {
+                        scope: scope.clone(),
+                        reason: format!("error while parsing {}", v.value.temporal_type()),
+                        causes,
+                    });
+                }
+            }
         }
     }
 }
@@ -293,6 +366,7 @@ impl From<BamlValueWithFlags> for BamlValue {
             }
             BamlValueWithFlags::Null(_) => BamlValue::Null,
             BamlValueWithFlags::Media(i) => BamlValue::Media(i.value),
+            BamlValueWithFlags::Temporal(t) => BamlValue::Temporal(t.value),
         }
     }
 }
@@ -317,6 +391,7 @@ impl From<&BamlValueWithFlags> for BamlValue {
             ),
             BamlValueWithFlags::Null(_) => BamlValue::Null,
             BamlValueWithFlags::Media(i) => BamlValue::Media(i.value.clone()),
+            BamlValueWithFlags::Temporal(t) => BamlValue::Temporal(t.value),
         }
     }
 }
@@ -334,6 +409,7 @@ impl BamlValueWithFlags {
             BamlValueWithFlags::Class(_, v, _) => v.add_flag(flag),
             BamlValueWithFlags::Null(v) => v.add_flag(flag),
             BamlValueWithFlags::Media(v) => v.flags.add_flag(flag),
+            BamlValueWithFlags::Temporal(v) => v.flags.add_flag(flag),
         }
     }
 
@@ -358,6 +434,7 @@ impl BamlValueWithFlags {
             BamlValueWithFlags::Class(c, _, _) => format!("Class {c}"),
             BamlValueWithFlags::Null(_) => "Null".to_string(),
             BamlValueWithFlags::Media(_) => "Image".to_string(),
+            BamlValueWithFlags::Temporal(t) => t.value.temporal_type().to_string(),
         }
     }
 }
@@ -427,6 +504,12 @@ impl std::fmt::Display for BamlValueWithFlags {
                     write!(f, "\n  {}", flags.to_string().replace("\n", "\n  "))?;
                 }
             }
+            BamlValueWithFlags::Temporal(v) => {
+                write!(f, "{}", v.value)?;
+                if !v.flags.flags.is_empty() {
+                    write!(f, "\n  {}", v.flags.to_string().replace("\n", "\n  "))?;
+                }
+            }
             BamlValueWithFlags::Media(v) => {
                 write!(f, "{:#?}", v.value)?;
                 if !v.flags.flags.is_empty() {
@@ -456,6 +539,7 @@ impl From<BamlValueWithFlags> for BamlValueWithMeta<Vec<(String, JinjaExpression
                 BamlValueWithMeta::List(values.into_iter().map(|v| v.into()).collect(), c)
             }
             Media(ValueWithFlags { value, .. }) => BamlValueWithMeta::Media(value, c),
+            Temporal(ValueWithFlags { value, .. }) => BamlValueWithMeta::Temporal(value, c),
             Enum(enum_name, ValueWithFlags { value, .. }) => {
                 BamlValueWithMeta::Enum(enum_name, value, c)
             }
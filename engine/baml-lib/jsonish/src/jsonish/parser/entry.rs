@@ -4,7 +4,7 @@ use crate::jsonish::{
     parser::{
         fixing_parser,
         markdown_parser::{self, MarkdownResult},
-        multi_json_parser,
+        multi_json_parser, toml_parser, xml_parser, yaml_parser,
     },
     value::Fixes,
     Value,
@@ -29,6 +29,75 @@ pub fn parse(str: &str, mut options: ParseOptions) -> Result<Value> {
         }
     };
 
+    let alternatives = if options.allow_alternate_formats {
+        alternate_formats(str, &options)
+    } else {
+        vec![]
+    };
+
+    // A document that is a YAML list (`- name: John`) can't be JSON. The JSON
+    // parsers would only grep fragments out of its items, like the `[]` of
+    // `tags: []`, and an empty list would beat the actual items.
+    if alternatives.iter().any(is_yaml_list) {
+        return Ok(Value::AnyOf(alternatives, str.to_string()));
+    }
+
+    let res = parse_jsonish(str, options);
+    if alternatives.is_empty() {
+        return res;
+    }
+
+    // The JSON candidates go first so that they win any ties.
+    Ok(match res {
+        Ok(Value::AnyOf(items, s)) => {
+            Value::AnyOf(items.into_iter().chain(alternatives).collect(), s)
+        }
+        Ok(v) => Value::AnyOf(
+            std::iter::once(v).chain(alternatives).collect(),
+            str.to_string(),
+        ),
+        Err(e) => {
+            log::debug!("Only found non-JSON candidates: {:?}", e);
+            Value::AnyOf(alternatives, str.to_string())
+        }
+    })
+}
+
+type FormatParser = fn(&str, &ParseOptions) -> Result<Vec<Value>>;
+
+/// Model outputs in other formats (YAML, XML tags, TOML), converted to the
+/// same `Value` tree as JSON.
+fn alternate_formats(str: &str, options: &ParseOptions) -> Vec<Value> {
+    let parsers: [(FormatParser, Fixes); 3] = [
+        (yaml_parser::parse, Fixes::ParsedAsYaml),
+        (xml_parser::parse, Fixes::ParsedAsXml),
+        (toml_parser::parse, Fixes::ParsedAsToml),
+    ];
+
+    parsers
+        .into_iter()
+        .flat_map(|(parser, fix)| match parser(str, options) {
+            Ok(items) => items
+                .into_iter()
+                .map(|v| Value::FixedJson(v.into(), vec![fix.clone()]))
+                .collect(),
+            Err(e) => {
+                log::debug!("Error parsing as {:?}: {:?}", fix, e);
+                vec![]
+            }
+        })
+        .collect()
+}
+
+fn is_yaml_list(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::FixedJson(v, fixes)
+            if matches!(**v, Value::Array(_)) && fixes.contains(&Fixes::ParsedAsYaml)
+    )
+}
+
+fn parse_jsonish(str: &str, options: ParseOptions) -> Result<Value> {
     if options.allow_markdown_json {
         match markdown_parser::parse(str, &options) {
             Ok(items) => match items.len() {
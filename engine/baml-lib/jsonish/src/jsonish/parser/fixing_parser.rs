@@ -8,7 +8,7 @@ use self::json_parse_state::JsonParseState;
 use super::ParseOptions;
 use anyhow::Result;
 
-pub fn parse(str: &str, _options: &ParseOptions) -> Result<Vec<(Value, Vec<Fixes>)>> {
+pub fn parse(str: &str, options: &ParseOptions) -> Result<Vec<(Value, Vec<Fixes>)>> {
     // Try to fix some common JSON issues
     // - Unquoted single word strings
     // - Single quoted strings
@@ -46,7 +46,11 @@ pub fn parse(str: &str, _options: &ParseOptions) -> Result<Vec<(Value, Vec<Fixes
 
     // If we still have a collection open, close it
     while !state.collection_stack.is_empty() {
-        state.complete_collection();
+        if options.mark_incomplete {
+            state.complete_unterminated_collection();
+        } else {
+            state.complete_collection();
+        }
     }
 
     // Determine what to return.
@@ -162,4 +166,27 @@ mod tests {
             _ => panic!("Expected object"),
         }
     }
+
+    #[test]
+    fn test_partial_object_mark_incomplete() {
+        let opts = ParseOptions::default().mark_incomplete(true);
+        let vals = parse(r#"{"a": "x", "b": ["y", "z"#, &opts).unwrap();
+        let Value::Incomplete(object) = &vals[0].0 else {
+            panic!("Expected incomplete object");
+        };
+        let Value::Object(fields) = object.as_ref() else {
+            panic!("Expected object");
+        };
+        assert_eq!(fields[0], ("a".to_string(), Value::String("x".to_string())));
+        let Value::Incomplete(array) = &fields[1].1 else {
+            panic!("Expected incomplete array");
+        };
+        assert_eq!(
+            **array,
+            Value::Array(vec![
+                Value::String("y".to_string()),
+                Value::Incomplete(Value::String("z".to_string()).into()),
+            ])
+        );
+    }
 }
@@ -21,6 +21,16 @@ impl JsonParseState {
     }
 
     pub fn complete_collection(&mut self) {
+        self.complete_collection_with(|value| value)
+    }
+
+    /// Completes a collection that the input ended in, marking its value as
+    /// [`Value::Incomplete`].
+    pub fn complete_unterminated_collection(&mut self) {
+        self.complete_collection_with(|value| Value::Incomplete(value.into()))
+    }
+
+    fn complete_collection_with(&mut self, mark: impl FnOnce(Value) -> Value) {
         let (collection, fixes) = match self.collection_stack.pop() {
             Some(collection) => collection,
             None => return,
@@ -43,11 +53,11 @@ impl JsonParseState {
                             _ => keys.push(value.to_string()),
                         }
                     } else {
-                        values.push(value);
+                        values.push(mark(value));
                     }
                 }
                 JsonCollection::Array(values) => {
-                    values.push(value);
+                    values.push(mark(value));
                 }
                 _ => {
                     // TODO: this should never happen as we should only be pushing objects and arrays
@@ -58,7 +68,7 @@ impl JsonParseState {
                 }
             }
         } else {
-            self.completed_values.push((name, value, fixes));
+            self.completed_values.push((name, mark(value), fixes));
         }
     }
 
@@ -2,6 +2,11 @@ mod entry;
 mod fixing_parser;
 mod markdown_parser;
 mod multi_json_parser;
+mod toml_parser;
+mod xml_parser;
+mod yaml_parser;
+
+use baml_types::ParserConfig;
 
 pub use entry::parse;
 
@@ -11,6 +16,8 @@ pub struct ParseOptions {
     allow_markdown_json: bool,
     allow_fixes: bool,
     allow_as_string: bool,
+    allow_alternate_formats: bool,
+    mark_incomplete: bool,
     depth: usize,
 }
 
@@ -21,15 +28,49 @@ impl Default for ParseOptions {
             allow_markdown_json: true,
             allow_fixes: true,
             allow_as_string: true,
+            // Opt-in, since e.g. any "key: value" line of prose is valid YAML.
+            allow_alternate_formats: false,
+            mark_incomplete: false,
             depth: 0,
         }
     }
 }
 
+impl ParseOptions {
+    /// The default options, with the overrides from a function's
+    /// `@@parser({ ... })` attribute.
+    pub fn from_config(config: &ParserConfig) -> Self {
+        let default = Self::default();
+        Self {
+            all_finding_all_json_objects: config
+                .find_all_json_objects
+                .unwrap_or(default.all_finding_all_json_objects),
+            allow_markdown_json: config
+                .allow_markdown_json
+                .unwrap_or(default.allow_markdown_json),
+            allow_fixes: config.allow_fixes.unwrap_or(default.allow_fixes),
+            allow_as_string: config.allow_as_string.unwrap_or(default.allow_as_string),
+            allow_alternate_formats: config
+                .allow_alternate_formats
+                .unwrap_or(default.allow_alternate_formats),
+            mark_incomplete: default.mark_incomplete,
+            depth: default.depth,
+        }
+    }
+
+    /// Whether to wrap values the input ends in (e.g. mid-stream) in
+    /// [`Value::Incomplete`](super::Value::Incomplete).
+    pub fn mark_incomplete(mut self, mark_incomplete: bool) -> Self {
+        self.mark_incomplete = mark_incomplete;
+        self
+    }
+}
+
 pub(super) enum ParsingMode {
     JsonMarkdown,
     JsonMarkdownString,
     AllJsonObjects,
+    XmlTagContent,
 }
 
 impl ParseOptions {
@@ -39,6 +80,7 @@ impl ParseOptions {
             ParsingMode::JsonMarkdownString => {
                 new.allow_markdown_json = false;
                 new.allow_as_string = true;
+                new.allow_alternate_formats = false;
             }
             ParsingMode::JsonMarkdown => {
                 new.allow_markdown_json = false;
@@ -47,6 +89,11 @@ impl ParseOptions {
             ParsingMode::AllJsonObjects => {
                 new.all_finding_all_json_objects = false;
                 new.allow_as_string = false;
+                new.allow_alternate_formats = false;
+            }
+            ParsingMode::XmlTagContent => {
+                new.allow_as_string = true;
+                new.allow_alternate_formats = false;
             }
         }
         new
@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+use crate::jsonish::Value;
+
+use super::ParseOptions;
+
+/// Parses a TOML document (e.g. `name = "John"\n[address]\ncity = "Paris"`)
+/// into a `Value::Object`.
+pub fn parse(str: &str, _options: &ParseOptions) -> Result<Vec<Value>> {
+    let content = str.trim();
+    if !looks_like_toml(content) {
+        return Ok(vec![]);
+    }
+
+    let parsed = match basic_toml::from_str::<serde_json::Value>(content) {
+        Ok(v) => v,
+        Err(e) => {
+            log::debug!("Invalid TOML: {:?}", e);
+            // While streaming, the last line is often incomplete (e.g. `age = `),
+            // so give the document without it another try.
+            let Some((head, _)) = content.rsplit_once('\n') else {
+                return Ok(vec![]);
+            };
+            match basic_toml::from_str::<serde_json::Value>(head) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::debug!("Invalid TOML (without last line): {:?}", e);
+                    return Ok(vec![]);
+                }
+            }
+        }
+    };
+
+    match parsed {
+        serde_json::Value::Object(ref o) if !o.is_empty() => {
+            Ok(vec![serde_json::from_value(parsed)?])
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Only treat the input as TOML if it starts with a `key =` entry or a
+/// `[table]` header.
+fn looks_like_toml(content: &str) -> bool {
+    let Some(first_line) = content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+    else {
+        return false;
+    };
+
+    let Ok(toml_re) = regex::Regex::new(
+        r#"^(\[\[?\s*["']?[A-Za-z_][\w\-.]*["']?\s*\]\]?|["']?[A-Za-z_][\w\-.]*["']?\s*=([^=]|$))"#,
+    ) else {
+        return false;
+    };
+
+    toml_re.is_match(first_line)
+}
@@ -0,0 +1,288 @@
+use anyhow::Result;
+
+use crate::jsonish::{
+    parser::{entry, ParsingMode},
+    Value,
+};
+
+use super::ParseOptions;
+
+/// A tag found in the model output, e.g. `<answer>...</answer>`.
+#[derive(Debug)]
+struct Element<'a> {
+    name: &'a str,
+    // None for self-closing tags (`<answer />`).
+    body: Option<&'a str>,
+    closed: bool,
+}
+
+/// Parses XML-tagged output (e.g. `<name>John</name><age>30</age>`) into
+/// `Value`s. This is intentionally lenient: attributes, comments and
+/// processing instructions are ignored, text outside of tags is dropped and
+/// unclosed tags run until the end of the input (which is what a streaming
+/// response looks like).
+///
+/// Returns the candidates:
+///  - All the tags as an object (or a list, if they are all `<item>`s)
+///  - The contents of each tag individually
+pub fn parse(str: &str, options: &ParseOptions) -> Result<Vec<Value>> {
+    let roots = elements(str);
+
+    if roots.is_empty() || !(roots.iter().any(|e| e.closed) || str.trim_start().starts_with('<')) {
+        return Ok(vec![]);
+    }
+
+    let values = roots
+        .iter()
+        .map(|e| (e.name.to_string(), element_value(e, options)))
+        .collect::<Vec<_>>();
+
+    let grouped = group(values.clone());
+    let mut candidates = vec![grouped];
+    candidates.extend(values.into_iter().map(|(_, v)| v));
+    Ok(candidates)
+}
+
+fn element_value(element: &Element, options: &ParseOptions) -> Value {
+    let Some(body) = element.body else {
+        return Value::Null;
+    };
+
+    let children = elements(body);
+    if children.is_empty() {
+        return leaf_value(body, options);
+    }
+
+    group(
+        children
+            .iter()
+            .map(|e| (e.name.to_string(), element_value(e, options)))
+            .collect(),
+    )
+}
+
+/// Sibling tags become an object keyed by tag name (repeated tags are
+/// collected into a list), unless they are all the same tag, in which case
+/// they are a list: `<item>a</item><item>b</item>`.
+fn group(children: Vec<(String, Value)>) -> Value {
+    let is_list = match children.first() {
+        Some((first, _)) => {
+            children.iter().all(|(name, _)| name == first)
+                && (children.len() > 1 || first == "item")
+        }
+        None => false,
+    };
+    if is_list {
+        return Value::Array(children.into_iter().map(|(_, v)| v).collect());
+    }
+
+    let mut fields: Vec<(String, Value)> = vec![];
+    for (name, value) in children {
+        match fields.iter_mut().find(|(k, _)| k == &name) {
+            Some((_, Value::Array(items))) => items.push(value),
+            Some((_, existing)) => {
+                let first = std::mem::replace(existing, Value::Null);
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => fields.push((name, value)),
+        }
+    }
+    Value::Object(fields)
+}
+
+fn leaf_value(body: &str, options: &ParseOptions) -> Value {
+    let text = text_content(body);
+    let text = text.trim();
+
+    if text.is_empty() {
+        return Value::String(String::new());
+    }
+
+    if text.starts_with('{') || text.starts_with('[') || text.starts_with("```") {
+        match entry::parse(text, options.next_from_mode(ParsingMode::XmlTagContent)) {
+            Ok(v) => return v,
+            Err(e) => log::debug!("Error parsing tag content: {:?}", e),
+        }
+    }
+
+    // Numbers, booleans and null.
+    match serde_json::from_str::<Value>(text) {
+        Ok(v) => Value::AnyOf(vec![v], text.to_string()),
+        Err(_) => Value::String(text.to_string()),
+    }
+}
+
+/// Finds the top-level tags in `s`.
+fn elements(s: &str) -> Vec<Element<'_>> {
+    let mut result = vec![];
+    let mut pos = 0;
+
+    while let Some(offset) = s[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &s[start..];
+
+        if let Some(skip) = skip_non_element(rest) {
+            pos = start + skip;
+            continue;
+        }
+
+        let Some(name) = tag_name(&rest[1..]) else {
+            // Just a `<` in the text (e.g. `a < b`).
+            pos = start + 1;
+            continue;
+        };
+
+        let Some(open_end) = rest.find('>') else {
+            // The opening tag itself is still being streamed.
+            break;
+        };
+        let body_start = start + open_end + 1;
+
+        if rest[..open_end].ends_with('/') {
+            result.push(Element {
+                name,
+                body: None,
+                closed: true,
+            });
+            pos = body_start;
+            continue;
+        }
+
+        match find_closing_tag(s, name, body_start) {
+            Some((close_start, close_end)) => {
+                result.push(Element {
+                    name,
+                    body: Some(&s[body_start..close_start]),
+                    closed: true,
+                });
+                pos = close_end;
+            }
+            None => {
+                result.push(Element {
+                    name,
+                    body: Some(strip_partial_tag(&s[body_start..])),
+                    closed: false,
+                });
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the number of bytes to skip if `s` starts with a comment, CDATA,
+/// processing instruction, doctype or stray closing tag.
+fn skip_non_element(s: &str) -> Option<usize> {
+    let skip_to = |end: &str| s.find(end).map(|i| i + end.len()).unwrap_or(s.len());
+
+    if s.starts_with("<!--") {
+        Some(skip_to("-->"))
+    } else if s.starts_with("<![CDATA[") {
+        Some(skip_to("]]>"))
+    } else if s.starts_with("<?") {
+        Some(skip_to("?>"))
+    } else if s.starts_with("<!") || s.starts_with("</") {
+        Some(skip_to(">"))
+    } else {
+        None
+    }
+}
+
+fn tag_name(s: &str) -> Option<&str> {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, c)) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return None,
+    }
+    let end = chars
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+        .map(|(i, _)| i)
+        .unwrap_or(s.len());
+
+    // The tag name must be followed by the end of the tag or its attributes.
+    match s[end..].chars().next() {
+        None | Some('>') | Some('/') => Some(&s[..end]),
+        Some(c) if c.is_whitespace() => Some(&s[..end]),
+        _ => None,
+    }
+}
+
+/// Finds the `</name>` matching an already opened `<name>`, accounting for
+/// nested tags with the same name.
+fn find_closing_tag(s: &str, name: &str, from: usize) -> Option<(usize, usize)> {
+    let mut depth = 1;
+    let mut pos = from;
+
+    while let Some(offset) = s[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &s[start..];
+
+        if rest.starts_with("<!--") || rest.starts_with("<![CDATA[") {
+            pos = start + skip_non_element(rest).unwrap_or(rest.len());
+            continue;
+        }
+
+        if let Some(after) = rest.strip_prefix("</") {
+            if tag_name(after) == Some(name) {
+                let close_end = start + rest.find('>')? + 1;
+                depth -= 1;
+                if depth == 0 {
+                    return Some((start, close_end));
+                }
+                pos = close_end;
+                continue;
+            }
+        } else if tag_name(&rest[1..]) == Some(name) {
+            let open_end = rest.find('>')?;
+            if !rest[..open_end].ends_with('/') {
+                depth += 1;
+            }
+            pos = start + open_end + 1;
+            continue;
+        }
+
+        pos = start + 1;
+    }
+
+    None
+}
+
+/// Drops a tag that is cut off at the end of a streaming response
+/// (e.g. `John</na`).
+fn strip_partial_tag(s: &str) -> &str {
+    match s.rfind('<') {
+        Some(i) if !s[i..].contains('>') => &s[..i],
+        _ => s,
+    }
+}
+
+/// Unescapes entities, except inside CDATA sections which are kept verbatim.
+fn text_content(s: &str) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("<![CDATA[") {
+        result.push_str(&unescape(&rest[..start]));
+        let content = &rest[start + "<![CDATA[".len()..];
+        match content.find("]]>") {
+            Some(end) => {
+                result.push_str(&content[..end]);
+                rest = &content[end + "]]>".len()..];
+            }
+            None => {
+                result.push_str(content);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(&unescape(rest));
+    result
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
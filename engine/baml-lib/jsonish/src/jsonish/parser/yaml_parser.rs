@@ -0,0 +1,114 @@
+use anyhow::Result;
+
+use crate::jsonish::Value;
+
+use super::ParseOptions;
+
+/// Parses a YAML document (e.g. `name: John\nage: 30`) into a `Value`.
+///
+/// Only mappings and sequences are accepted; a bare YAML scalar is just text
+/// and is already handled by the string fallback of the JSON parser.
+pub fn parse(str: &str, _options: &ParseOptions) -> Result<Vec<Value>> {
+    let content = strip_document_markers(str);
+    if !looks_like_yaml(content) {
+        return Ok(vec![]);
+    }
+
+    let parsed = match serde_yaml::from_str::<serde_yaml::Value>(content) {
+        Ok(v) => v,
+        Err(e) => {
+            log::debug!("Invalid YAML: {:?}", e);
+            // While streaming, the last line is often cut off mid-token (e.g. an
+            // unterminated quote), so give the document without it another try.
+            let Some((head, _)) = content.trim_end().rsplit_once('\n') else {
+                return Ok(vec![]);
+            };
+            match serde_yaml::from_str::<serde_yaml::Value>(head) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::debug!("Invalid YAML (without last line): {:?}", e);
+                    return Ok(vec![]);
+                }
+            }
+        }
+    };
+
+    match parsed {
+        serde_yaml::Value::Mapping(_) | serde_yaml::Value::Sequence(_) => {
+            Ok(vec![from_yaml(parsed)])
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+fn strip_document_markers(str: &str) -> &str {
+    let content = str.trim();
+    let content = content.strip_prefix("---").unwrap_or(content);
+    content
+        .strip_suffix("\n...")
+        .unwrap_or(content)
+        .trim_matches('\n')
+}
+
+/// YAML happily accepts almost any text as a scalar, so only treat the input as
+/// YAML if it starts with a `key:` entry or a `- ` list item.
+fn looks_like_yaml(content: &str) -> bool {
+    let Some(first_line) = content
+        .lines()
+        .map(str::trim_end)
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+    else {
+        return false;
+    };
+
+    let Ok(key_re) = regex::Regex::new(r#"^\s*(-\s+)?["']?[A-Za-z_][\w\-]*["']?\s*:(\s|$)"#) else {
+        return false;
+    };
+
+    key_re.is_match(first_line) || first_line.starts_with("- ")
+}
+
+fn from_yaml(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Boolean(b),
+        serde_yaml::Value::Number(n) => number_from_yaml(&n),
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(from_yaml).collect())
+        }
+        serde_yaml::Value::Mapping(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (key_from_yaml(k), from_yaml(v)))
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => from_yaml(tagged.value),
+    }
+}
+
+fn number_from_yaml(n: &serde_yaml::Number) -> Value {
+    if let Some(i) = n.as_i64() {
+        Value::Number(i.into())
+    } else if let Some(u) = n.as_u64() {
+        Value::Number(u.into())
+    } else {
+        match n.as_f64().and_then(serde_json::Number::from_f64) {
+            Some(f) => Value::Number(f),
+            // NaN and infinities have no JSON representation.
+            None => Value::String(n.to_string()),
+        }
+    }
+}
+
+fn key_from_yaml(key: serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s,
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Tagged(tagged) => key_from_yaml(tagged.value),
+        other => serde_yaml::to_string(&other)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
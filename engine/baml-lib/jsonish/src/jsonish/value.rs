@@ -9,6 +9,9 @@ use baml_types::BamlMap;
 pub enum Fixes {
     GreppedForJSON,
     InferredArray,
+    ParsedAsYaml,
+    ParsedAsXml,
+    ParsedAsToml,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,6 +30,10 @@ pub enum Value {
     Markdown(String, Box<Value>),
     FixedJson(Box<Value>, Vec<Fixes>),
     AnyOf(Vec<Value>, String),
+
+    // The input ended before the value was closed. Only produced for partial
+    // parses.
+    Incomplete(Box<Value>),
 }
 
 impl Hash for Value {
@@ -54,6 +61,7 @@ impl Hash for Value {
                 v.hash(state);
             }
             Value::FixedJson(v, _) => v.hash(state),
+            Value::Incomplete(v) => v.hash(state),
             Value::AnyOf(items, _) => {
                 for item in items {
                     item.hash(state);
@@ -97,6 +105,7 @@ impl Value {
             Value::FixedJson(inner, fixes) => {
                 format!("{} ({} fixes)", inner.r#type(), fixes.len())
             }
+            Value::Incomplete(inner) => format!("{} (incomplete)", inner.r#type()),
             Value::AnyOf(items, _) => {
                 let mut s = "AnyOf[".to_string();
                 for item in items {
@@ -139,6 +148,7 @@ impl std::fmt::Display for Value {
             }
             Value::Markdown(s, v) => write!(f, "{}\n{}", s, v),
             Value::FixedJson(v, _) => write!(f, "{}", v),
+            Value::Incomplete(v) => write!(f, "{}", v),
             Value::AnyOf(items, s) => {
                 write!(f, "AnyOf[{},", s)?;
                 for item in items {
@@ -5,10 +5,11 @@ use anyhow::Result;
 pub mod deserializer;
 mod jsonish;
 
-use baml_types::FieldType;
+use baml_types::{FieldType, ParserConfig};
 use deserializer::coercer::{ParsingContext, TypeCoercer};
 
-pub use deserializer::types::BamlValueWithFlags;
+pub use deserializer::parse_report::{ParseReport, ParseReportEntry};
+pub use deserializer::types::{BamlValueWithFlags, CompletionState};
 use internal_baml_core::ir::TypeValue;
 use internal_baml_jinja::types::OutputFormatContent;
 
@@ -20,13 +21,41 @@ pub fn from_str(
     target: &FieldType,
     raw_string: &str,
     allow_partials: bool,
+) -> Result<BamlValueWithFlags> {
+    from_str_with_config(
+        of,
+        target,
+        raw_string,
+        allow_partials,
+        &ParserConfig::default(),
+    )
+}
+
+/// Like [`from_str`], following a function's `@@parser({ ... })` options.
+///
+/// `max_score` and `deny` are only checked for final values, since partial
+/// values are expected to be missing fields.
+pub fn from_str_with_config(
+    of: &OutputFormatContent,
+    target: &FieldType,
+    raw_string: &str,
+    allow_partials: bool,
+    config: &ParserConfig,
 ) -> Result<BamlValueWithFlags> {
     if matches!(target, FieldType::Primitive(TypeValue::String)) {
-        return Ok(BamlValueWithFlags::String(raw_string.to_string().into()));
+        // While streaming, more of the string may still come in.
+        return Ok(BamlValueWithFlags::String(if allow_partials {
+            (raw_string.to_string(), Flag::Incomplete).into()
+        } else {
+            raw_string.to_string().into()
+        }));
     }
 
     // When the schema is just a string, i should really just return the raw_string w/o parsing it.
-    let mut value = jsonish::parse(raw_string, jsonish::ParseOptions::default())?;
+    let mut value = jsonish::parse(
+        raw_string,
+        jsonish::ParseOptions::from_config(config).mark_incomplete(allow_partials),
+    )?;
     // let schema = deserializer::schema::from_jsonish_value(&value, None);
 
     // See Note [Streaming Number Invalidation]
@@ -59,6 +88,10 @@ pub fn from_str(
                 anyhow::bail!("Failed to coerce value: {:?}", v.conditions().flags());
             }
 
+            if !allow_partials {
+                deserializer::strictness::check(&v, config)?;
+            }
+
             Ok(v)
         }
         Err(e) => anyhow::bail!("Failed to coerce value: {}", e),
@@ -117,6 +150,7 @@ fn last_value_as_number(value: &mut Value) -> Vec<&mut Value> {
             .unwrap_or_default(),
         Value::Markdown(_, sub_value) => last_value_as_number(sub_value),
         Value::FixedJson(fixed_val, _fixes) => last_value_as_number(fixed_val),
+        Value::Incomplete(val) => last_value_as_number(val),
         Value::AnyOf(variants, _) => variants
             .iter_mut()
             .flat_map(|variant| last_value_as_number(variant))
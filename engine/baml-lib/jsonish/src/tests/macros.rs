@@ -111,3 +111,33 @@ macro_rules! test_partial_deserializer {
         }
     };
 }
+
+/// Like `test_partial_deserializer`, but also applies the `@stream.*`
+/// attributes of the schema to the partial value.
+macro_rules! test_streaming_deserializer {
+    ($name:ident, $file_content:expr, $raw_string:expr, $target_type:expr, $($json:tt)+) => {
+        #[test_log::test]
+        fn $name() {
+            let ir = load_test_ir($file_content);
+            let target = render_output_format(&ir, &$target_type, &Default::default()).unwrap();
+
+            let result = from_str(
+                &target,
+                &$target_type,
+                $raw_string,
+                true,
+            );
+
+            assert!(result.is_ok(), "Failed to parse: {:?}", result);
+
+            let value = crate::deserializer::streaming::process_partial(&ir, result.unwrap());
+            let value: BamlValue = value.into();
+            log::info!("{}", value);
+            let json_value = json!(value);
+
+            let expected = serde_json::json!($($json)+);
+
+            assert_json_diff::assert_json_eq!(json_value, expected);
+        }
+    };
+}
@@ -4,6 +4,7 @@ use internal_baml_jinja::types::{Class, Enum, Name, OutputFormatContent};
 #[macro_use]
 pub mod macros;
 
+mod test_alt_formats;
 mod test_basics;
 mod test_class;
 mod test_class_2;
@@ -13,7 +14,11 @@ mod test_enum;
 mod test_lists;
 mod test_literals;
 mod test_maps;
+mod test_parse_report;
+mod test_parser_config;
 mod test_partials;
+mod test_streaming;
+mod test_temporal;
 mod test_unions;
 
 use indexmap::IndexSet;
@@ -0,0 +1,223 @@
+use baml_types::ParserConfig;
+
+use super::*;
+use crate::BamlValueWithFlags;
+
+/// Other formats are only parsed when a function opts in, so the test macros
+/// in this file parse with `@@parser({ allow_alternate_formats true })`.
+fn from_str(
+    of: &OutputFormatContent,
+    target: &FieldType,
+    raw_string: &str,
+    allow_partials: bool,
+) -> Result<BamlValueWithFlags> {
+    crate::from_str_with_config(
+        of,
+        target,
+        raw_string,
+        allow_partials,
+        &ParserConfig {
+            allow_alternate_formats: Some(true),
+            ..Default::default()
+        },
+    )
+}
+
+const PERSON_FILE: &str = r#"
+class Person {
+  name string
+  age int?
+  tags string[]
+}
+
+class Order {
+  buyer Person
+  items string[]
+}
+"#;
+
+test_deserializer!(
+    test_yaml_class,
+    PERSON_FILE,
+    r#"name: John Doe
+age: 30
+tags:
+  - admin
+  - "on call"
+"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John Doe", "age": 30, "tags": ["admin", "on call"]}
+);
+
+test_deserializer!(
+    test_yaml_in_markdown,
+    PERSON_FILE,
+    r#"Here is the person:
+
+```yaml
+name: John Doe
+tags: [admin]
+```
+"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John Doe", "age": null, "tags": ["admin"]}
+);
+
+test_deserializer!(
+    test_yaml_nested_class,
+    PERSON_FILE,
+    r#"---
+buyer:
+  name: Jane
+  tags: []
+items:
+  - apples
+  - pears
+"#,
+    FieldType::Class("Order".to_string()),
+    {"buyer": {"name": "Jane", "age": null, "tags": []}, "items": ["apples", "pears"]}
+);
+
+test_deserializer!(
+    test_yaml_list_of_classes,
+    PERSON_FILE,
+    r#"- name: John
+  age: 30
+  tags: []
+- name: Jane
+  tags: [admin]
+"#,
+    FieldType::List(FieldType::Class("Person".to_string()).into()),
+    [
+        {"name": "John", "age": 30, "tags": []},
+        {"name": "Jane", "age": null, "tags": ["admin"]}
+    ]
+);
+
+test_deserializer!(
+    test_xml_tags_class,
+    PERSON_FILE,
+    r#"<name>John Doe</name>
+<age>30</age>
+<tags>
+  <item>admin</item>
+  <item>on call</item>
+</tags>"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John Doe", "age": 30, "tags": ["admin", "on call"]}
+);
+
+test_deserializer!(
+    test_xml_tags_with_root_and_text,
+    PERSON_FILE,
+    r#"Sure! Here is the order:
+
+<Order>
+  <buyer>
+    <name>Jane &amp; Co</name>
+    <tags><item>vip</item></tags>
+  </buyer>
+  <items>
+    <item>apples</item>
+    <item><![CDATA[<pears>]]></item>
+  </items>
+</Order>
+
+Let me know if you need anything else."#,
+    FieldType::Class("Order".to_string()),
+    {"buyer": {"name": "Jane & Co", "age": null, "tags": ["vip"]}, "items": ["apples", "<pears>"]}
+);
+
+test_deserializer!(
+    test_xml_answer_tag_after_thinking,
+    PERSON_FILE,
+    r#"<thinking>
+The user mentioned they are 30 and that a < b.
+</thinking>
+<answer>
+  <name>John</name>
+  <age>30</age>
+  <tags><item>admin</item></tags>
+</answer>"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John", "age": 30, "tags": ["admin"]}
+);
+
+test_deserializer!(
+    test_xml_tag_with_json_content,
+    PERSON_FILE,
+    r#"<answer>{"name": "John", "tags": ["admin"]}</answer>"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John", "age": null, "tags": ["admin"]}
+);
+
+test_deserializer!(
+    test_toml_class,
+    PERSON_FILE,
+    r#"items = ["apples", "pears"]
+
+[buyer]
+name = "Jane"
+age = 41
+tags = ["vip"]
+"#,
+    FieldType::Class("Order".to_string()),
+    {"buyer": {"name": "Jane", "age": 41, "tags": ["vip"]}, "items": ["apples", "pears"]}
+);
+
+test_partial_deserializer!(
+    test_yaml_streaming,
+    PERSON_FILE,
+    r#"name: John Doe
+tags: [admin]
+age: 3"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John Doe", "age": null, "tags": ["admin"]}
+);
+
+test_partial_deserializer!(
+    test_yaml_streaming_unterminated_quote,
+    PERSON_FILE,
+    r#"name: John Doe
+tags:
+  - admin
+  - "on ca"#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John Doe", "age": null, "tags": ["admin"]}
+);
+
+test_partial_deserializer!(
+    test_xml_streaming,
+    PERSON_FILE,
+    r#"<Order>
+  <buyer>
+    <name>Jane</name>
+    <tags><item>vip</item></tags>
+  </buyer>
+  <items>
+    <item>apples</item>
+    <item>pe</it"#,
+    FieldType::Class("Order".to_string()),
+    {"buyer": {"name": "Jane", "age": null, "tags": ["vip"]}, "items": ["apples", "pe"]}
+);
+
+test_partial_deserializer!(
+    test_toml_streaming,
+    PERSON_FILE,
+    r#"name = "John Doe"
+tags = ["admin"]
+age = "#,
+    FieldType::Class("Person".to_string()),
+    {"name": "John Doe", "age": null, "tags": ["admin"]}
+);
+
+#[test]
+fn test_alternate_formats_are_opt_in() {
+    let target_type = FieldType::Class("Person".to_string());
+    let ir = load_test_ir(PERSON_FILE);
+    let target = render_output_format(&ir, &target_type, &Default::default()).unwrap();
+
+    let raw_string = "name: John Doe\ntags: [admin]\n";
+    assert!(crate::from_str(&target, &target_type, raw_string, false).is_err());
+    assert!(from_str(&target, &target_type, raw_string, false).is_ok());
+}
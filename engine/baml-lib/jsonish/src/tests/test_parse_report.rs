@@ -0,0 +1,64 @@
+use super::*;
+use crate::{ParseReport, ParseReportEntry};
+
+const BAML_FILE: &str = r#"
+class Item {
+  name string
+  count int
+}
+"#;
+
+fn report(raw_string: &str, target_type: FieldType) -> ParseReport {
+    let ir = load_test_ir(BAML_FILE);
+    let target = render_output_format(&ir, &target_type, &Default::default()).unwrap();
+
+    let value = from_str(&target, &target_type, raw_string, false).unwrap();
+    ParseReport::new(&value)
+}
+
+#[test]
+fn test_report_paths() {
+    let report = report(
+        r#"[{"name": "Apple", "count": 1}, {"name": "Pear", "count": 2.5}]"#,
+        FieldType::list(FieldType::class("Item")),
+    );
+
+    assert!(report.score > 0);
+    assert!(
+        report.entries.contains(&ParseReportEntry {
+            path: "$[1].count".to_string(),
+            flags: vec!["FloatToInt".to_string()],
+            score: 1,
+        }),
+        "{report:#?}"
+    );
+    assert!(
+        !report.entries.iter().any(|e| e.path.starts_with("$[0]")),
+        "{report:#?}"
+    );
+}
+
+#[test]
+fn test_report_map_paths() {
+    let report = report(
+        r#"{"apple": 1.5}"#,
+        FieldType::map(FieldType::string(), FieldType::int()),
+    );
+
+    assert!(
+        report.entries.contains(&ParseReportEntry {
+            path: r#"$["apple"]"#.to_string(),
+            flags: vec!["FloatToInt".to_string()],
+            score: 1,
+        }),
+        "{report:#?}"
+    );
+}
+
+#[test]
+fn test_report_clean_output() {
+    let report = report(r#"{"name": "Apple", "count": 1}"#, FieldType::class("Item"));
+
+    assert_eq!(report.score, 0);
+    assert!(report.entries.iter().all(|e| e.score == 0), "{report:#?}");
+}
@@ -0,0 +1,101 @@
+use baml_types::ParserConfig;
+
+use super::*;
+use crate::from_str_with_config;
+
+const BAML_FILE: &str = r##"
+class Item {
+  name string
+  count int
+}
+
+function ExtractItem(text: string) -> Item {
+  client "openai/gpt-4o"
+  prompt #"
+    {{ text }}
+    {{ ctx.output_format }}
+  "#
+  @@parser({
+    allow_fixes false
+    allow_as_string false
+    max_score 10
+    deny [FloatToInt, SubstringMatch]
+  })
+}
+"##;
+
+fn parse(raw_string: &str, allow_partials: bool, config: &ParserConfig) -> Result<BamlValue> {
+    let ir = load_test_ir(BAML_FILE);
+    let target_type = FieldType::Class("Item".to_string());
+    let target = render_output_format(&ir, &target_type, &Default::default()).unwrap();
+
+    from_str_with_config(&target, &target_type, raw_string, allow_partials, config)
+        .map(|v| v.into())
+}
+
+fn function_config() -> ParserConfig {
+    let ir = load_test_ir(BAML_FILE);
+    let function = ir.find_function("ExtractItem").unwrap();
+    function.elem().parser_config().clone()
+}
+
+#[test]
+fn test_parser_attribute() {
+    assert_eq!(
+        function_config(),
+        ParserConfig {
+            allow_fixes: Some(false),
+            allow_as_string: Some(false),
+            max_score: Some(10),
+            deny: vec!["FloatToInt".to_string(), "SubstringMatch".to_string()],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_denied_flag() {
+    let raw_string = r#"{"name": "Apple", "count": 1.5}"#;
+
+    assert!(parse(raw_string, false, &ParserConfig::default()).is_ok());
+
+    let err = parse(raw_string, false, &function_config()).unwrap_err();
+    assert!(
+        err.to_string().contains("denied flags fired: FloatToInt"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_max_score() {
+    let raw_string = r#"{"name": "Apple", "count": 1.5}"#;
+    let config = ParserConfig {
+        max_score: Some(0),
+        ..Default::default()
+    };
+
+    let err = parse(raw_string, false, &config).unwrap_err();
+    assert!(err.to_string().contains("max_score 0"), "{err}");
+
+    let raw_string = r#"{"name": "Apple", "count": 1}"#;
+    assert!(parse(raw_string, false, &config).is_ok());
+}
+
+#[test]
+fn test_no_fixes() {
+    let raw_string = r#"{"name": "Apple", "count": 1"#;
+
+    assert!(parse(raw_string, false, &ParserConfig::default()).is_ok());
+    assert!(parse(raw_string, false, &function_config()).is_err());
+}
+
+#[test]
+fn test_partials_are_not_checked() {
+    let config = ParserConfig {
+        max_score: Some(0),
+        deny: vec!["DefaultFromNoValue".to_string()],
+        ..Default::default()
+    };
+
+    assert!(parse(r#"{"name": "Apple", "cou"#, true, &config).is_ok());
+}
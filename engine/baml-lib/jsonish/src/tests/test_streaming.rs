@@ -0,0 +1,137 @@
+use super::*;
+use crate::{deserializer::types::CompletionState, BamlValueWithFlags};
+
+const BAML_FILE: &str = r#"
+class Todo {
+  title string @stream.done
+  notes string
+}
+
+class Item {
+  id int @stream.not_null
+  name string
+}
+
+class Message {
+  text string @stream.with_state
+  count int @stream.with_state
+}
+
+class Point {
+  x int
+  y int
+  @@stream.done
+}
+
+class Shape {
+  points Point[]
+}
+"#;
+
+test_streaming_deserializer!(
+    test_stream_done_incomplete,
+    BAML_FILE,
+    r#"{"title": "Buy mi"#,
+    FieldType::Class("Todo".to_string()),
+    {"title": null, "notes": null}
+);
+
+test_streaming_deserializer!(
+    test_stream_done_complete,
+    BAML_FILE,
+    r#"{"title": "Buy milk", "notes": "Two liters of"#,
+    FieldType::Class("Todo".to_string()),
+    {"title": "Buy milk", "notes": "Two liters of"}
+);
+
+test_streaming_deserializer!(
+    test_stream_not_null,
+    BAML_FILE,
+    r#"[{"id": 1, "name": "Apple"}, {"name": "Ban"#,
+    FieldType::Class("Item".to_string()).as_list(),
+    [{"id": 1, "name": "Apple"}]
+);
+
+test_streaming_deserializer!(
+    test_stream_not_null_top_level,
+    BAML_FILE,
+    r#"{"name": "Apple"#,
+    FieldType::Class("Item".to_string()),
+    null
+);
+
+test_streaming_deserializer!(
+    test_stream_with_state,
+    BAML_FILE,
+    r#"{"text": "Hello, wor"#,
+    FieldType::Class("Message".to_string()),
+    {
+      "text": {"value": "Hello, wor", "state": "Incomplete"},
+      "count": {"value": null, "state": "Pending"}
+    }
+);
+
+test_streaming_deserializer!(
+    test_stream_with_state_complete,
+    BAML_FILE,
+    r#"{"text": "Hello, world", "count": 12"#,
+    FieldType::Class("Message".to_string()),
+    {
+      "text": {"value": "Hello, world", "state": "Complete"},
+      "count": {"value": null, "state": "Pending"}
+    }
+);
+
+test_streaming_deserializer!(
+    test_stream_done_class,
+    BAML_FILE,
+    r#"{"points": [{"x": 1, "y": 2}, {"x": 3, "y"#,
+    FieldType::Class("Shape".to_string()),
+    {"points": [{"x": 1, "y": 2}]}
+);
+
+#[test]
+fn test_completion_state() {
+    let ir = load_test_ir(BAML_FILE);
+    let target_type = FieldType::Class("Todo".to_string());
+    let target = render_output_format(&ir, &target_type, &Default::default()).unwrap();
+
+    let value = from_str(
+        &target,
+        &target_type,
+        r#"{"title": "Buy milk", "notes": "Tw"#,
+        true,
+    )
+    .unwrap();
+    assert_eq!(value.completion_state(), CompletionState::Incomplete);
+    let BamlValueWithFlags::Class(_, _, fields) = &value else {
+        panic!("Expected class, got {:?}", value);
+    };
+    assert_eq!(
+        fields["title"].completion_state(),
+        CompletionState::Complete
+    );
+    assert_eq!(
+        fields["notes"].completion_state(),
+        CompletionState::Incomplete
+    );
+
+    let value = from_str(
+        &target,
+        &target_type,
+        r#"{"title": "Buy milk", "notes": "Two"}"#,
+        false,
+    )
+    .unwrap();
+    assert_eq!(value.completion_state(), CompletionState::Complete);
+
+    // Final parses of cut off input aren't marked as incomplete.
+    let value = from_str(
+        &target,
+        &target_type,
+        r#"{"title": "Buy milk", "notes": "Tw"#,
+        false,
+    )
+    .unwrap();
+    assert_eq!(value.completion_state(), CompletionState::Complete);
+}
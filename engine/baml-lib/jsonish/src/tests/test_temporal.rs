@@ -0,0 +1,122 @@
+use baml_types::BamlTemporalType;
+
+use super::*;
+
+fn temporal(t: BamlTemporalType) -> FieldType {
+    FieldType::Primitive(TypeValue::Temporal(t))
+}
+
+test_deserializer!(
+    test_date_iso,
+    EMPTY_FILE,
+    "2024-03-01",
+    temporal(BamlTemporalType::Date),
+    "2024-03-01"
+);
+
+test_deserializer!(
+    test_date_loose,
+    EMPTY_FILE,
+    r#""March 3rd, 2024""#,
+    temporal(BamlTemporalType::Date),
+    "2024-03-03"
+);
+
+test_deserializer!(
+    test_date_from_datetime,
+    EMPTY_FILE,
+    r#""2024-03-01T14:30:00Z""#,
+    temporal(BamlTemporalType::Date),
+    "2024-03-01"
+);
+
+test_deserializer!(
+    test_datetime_with_offset,
+    EMPTY_FILE,
+    r#""2024-03-01T14:30:00+02:00""#,
+    temporal(BamlTemporalType::DateTime),
+    "2024-03-01T14:30:00+02:00"
+);
+
+test_deserializer!(
+    test_datetime_loose,
+    EMPTY_FILE,
+    r#""2024-03-01 14:30""#,
+    temporal(BamlTemporalType::DateTime),
+    "2024-03-01T14:30:00+00:00"
+);
+
+test_deserializer!(
+    test_duration_iso,
+    EMPTY_FILE,
+    r#""PT2H30M""#,
+    temporal(BamlTemporalType::Duration),
+    "PT2H30M"
+);
+
+test_deserializer!(
+    test_duration_loose,
+    EMPTY_FILE,
+    r#""2 hours and 30 minutes""#,
+    temporal(BamlTemporalType::Duration),
+    "PT2H30M"
+);
+
+test_deserializer!(
+    test_duration_from_seconds,
+    EMPTY_FILE,
+    "90",
+    temporal(BamlTemporalType::Duration),
+    "PT1M30S"
+);
+
+test_deserializer!(
+    test_datetime_from_timestamp,
+    EMPTY_FILE,
+    "1709424000",
+    temporal(BamlTemporalType::DateTime),
+    "2024-03-03T00:00:00+00:00"
+);
+
+test_failing_deserializer!(
+    test_date_from_year,
+    EMPTY_FILE,
+    "2024",
+    temporal(BamlTemporalType::Date)
+);
+
+test_failing_deserializer!(
+    test_date_without_year,
+    EMPTY_FILE,
+    r#""March 3rd""#,
+    temporal(BamlTemporalType::Date)
+);
+
+const TASK_FILE: &str = r#"
+class Task {
+  title string
+  due date
+  starts_at datetime?
+  estimate duration
+}
+"#;
+
+test_deserializer!(
+    test_class_with_temporal_fields,
+    TASK_FILE,
+    r#"
+    {
+      "title": "Write report",
+      "due": "2024-03-15",
+      "starts_at": null,
+      "estimate": "1h 15m"
+    }
+    "#,
+    FieldType::Class("Task".to_string()),
+    {
+      "title": "Write report",
+      "due": "2024-03-15",
+      "starts_at": null,
+      "estimate": "PT1H15M"
+    }
+);
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{AllowedRoleMetadata, FinishReasonFilter, RolesSelection, SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection};
+use crate::{AllowedRoleMetadata, FinishReasonFilter, ModelPricing, RateLimits, RolesSelection, SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection};
 use anyhow::Result;
 
 use baml_types::{EvaluationContext, StringOr, UnresolvedValue};
@@ -18,6 +18,9 @@ pub struct UnresolvedAnthropic<Meta> {
     headers: IndexMap<String, StringOr>,
     properties: IndexMap<String, (Meta, UnresolvedValue<Meta>)>,
     finish_reason_filter: UnresolvedFinishReasonFilter,
+    rate_limits: RateLimits,
+    pricing: ModelPricing,
+    context_window: Option<u32>,
 }
 
 impl<Meta> UnresolvedAnthropic<Meta> {
@@ -39,6 +42,9 @@ impl<Meta> UnresolvedAnthropic<Meta> {
                 .map(|(k, (_, v))| (k.clone(), ((), v.without_meta())))
                 .collect(),
             finish_reason_filter: self.finish_reason_filter.clone(),
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         }
     }
 }
@@ -53,6 +59,9 @@ pub struct ResolvedAnthropic {
     pub properties: IndexMap<String, serde_json::Value>,
     pub proxy_url: Option<String>,
     pub finish_reason_filter: FinishReasonFilter,
+    pub rate_limits: RateLimits,
+    pub pricing: ModelPricing,
+    pub context_window: Option<u32>,
 }
 
 impl ResolvedAnthropic {
@@ -132,6 +141,9 @@ impl<Meta: Clone> UnresolvedAnthropic<Meta> {
             properties,
             proxy_url: super::helpers::get_proxy_url(ctx),
             finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         })
     }
 
@@ -148,6 +160,9 @@ impl<Meta: Clone> UnresolvedAnthropic<Meta> {
         let supported_request_modes = properties.ensure_supported_request_modes();
         let headers = properties.ensure_headers().unwrap_or_default();
         let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let rate_limits = properties.ensure_rate_limits();
+        let pricing = properties.ensure_pricing();
+        let context_window = properties.ensure_context_window();
         let (properties, errors) = properties.finalize();
         if !errors.is_empty() {
             return Err(errors);
@@ -162,6 +177,9 @@ impl<Meta: Clone> UnresolvedAnthropic<Meta> {
             headers,
             properties,
             finish_reason_filter,
+            rate_limits,
+            pricing,
+            context_window,
         })
     }
 }
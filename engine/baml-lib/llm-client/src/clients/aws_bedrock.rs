@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{AllowedRoleMetadata, FinishReasonFilter, RolesSelection, SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection};
+use crate::{AllowedRoleMetadata, FinishReasonFilter, ModelPricing, RateLimits, RolesSelection, SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection};
 use anyhow::Result;
 
 use baml_types::{EvaluationContext, StringOr};
@@ -18,6 +18,9 @@ pub struct UnresolvedAwsBedrock {
     supported_request_modes: SupportedRequestModes,
     inference_config: Option<UnresolvedInferenceConfiguration>,
     finish_reason_filter: UnresolvedFinishReasonFilter,
+    rate_limits: RateLimits,
+    pricing: ModelPricing,
+    context_window: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +71,9 @@ pub struct ResolvedAwsBedrock {
     pub allowed_role_metadata: AllowedRoleMetadata,
     pub supported_request_modes: SupportedRequestModes,
     pub finish_reason_filter: FinishReasonFilter,
+    pub rate_limits: RateLimits,
+    pub pricing: ModelPricing,
+    pub context_window: Option<u32>,
 }
 
 impl ResolvedAwsBedrock {
@@ -129,6 +135,9 @@ impl UnresolvedAwsBedrock {
                 .map(|c| c.resolve(ctx))
                 .transpose()?,
             finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         })
     }
 
@@ -233,6 +242,9 @@ impl UnresolvedAwsBedrock {
             Some(inference_config)
         };
         let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let rate_limits = properties.ensure_rate_limits();
+        let pricing = properties.ensure_pricing();
+        let context_window = properties.ensure_context_window();
 
         // TODO: Handle inference_configuration
         let errors = properties.finalize_empty();
@@ -250,6 +262,9 @@ impl UnresolvedAwsBedrock {
             supported_request_modes,
             inference_config,
             finish_reason_filter,
+            rate_limits,
+            pricing,
+            context_window,
         })
     }
 }
@@ -3,7 +3,8 @@ use std::collections::HashSet;
 use crate::{AllowedRoleMetadata, SupportedRequestModes, UnresolvedAllowedRoleMetadata};
 use anyhow::Result;
 use crate::{
-    FinishReasonFilter, RolesSelection, UnresolvedFinishReasonFilter, UnresolvedRolesSelection
+    FinishReasonFilter, ModelPricing, RateLimits, RolesSelection, UnresolvedFinishReasonFilter,
+    UnresolvedRolesSelection,
 };
 
 use baml_types::{EvaluationContext, StringOr, UnresolvedValue};
@@ -21,6 +22,9 @@ pub struct UnresolvedGoogleAI<Meta> {
     allowed_metadata: UnresolvedAllowedRoleMetadata,
     supported_request_modes: SupportedRequestModes,
     finish_reason_filter: UnresolvedFinishReasonFilter,
+    rate_limits: RateLimits,
+    pricing: ModelPricing,
+    context_window: Option<u32>,
     properties: IndexMap<String, (Meta, UnresolvedValue<Meta>)>,
 }
 
@@ -44,6 +48,9 @@ impl<Meta> UnresolvedGoogleAI<Meta> {
                 .map(|(k, (_, v))| (k.clone(), ((), v.without_meta())))
                 .collect::<IndexMap<_, _>>(),
             finish_reason_filter: self.finish_reason_filter.clone(),
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         }
     }
 }
@@ -59,6 +66,9 @@ pub struct ResolvedGoogleAI {
     pub properties: IndexMap<String, serde_json::Value>,
     pub proxy_url: Option<String>,
     pub finish_reason_filter: FinishReasonFilter,
+    pub rate_limits: RateLimits,
+    pub pricing: ModelPricing,
+    pub context_window: Option<u32>,
 }
 
 impl ResolvedGoogleAI {
@@ -134,6 +144,9 @@ impl<Meta: Clone> UnresolvedGoogleAI<Meta> {
                 .collect::<Result<IndexMap<_, _>>>()?,
             proxy_url: super::helpers::get_proxy_url(ctx),
             finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         })
     }
 
@@ -153,6 +166,9 @@ impl<Meta: Clone> UnresolvedGoogleAI<Meta> {
         let supported_request_modes = properties.ensure_supported_request_modes();
         let headers = properties.ensure_headers().unwrap_or_default();
         let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let rate_limits = properties.ensure_rate_limits();
+        let pricing = properties.ensure_pricing();
+        let context_window = properties.ensure_context_window();
         let (properties, errors) = properties.finalize();
 
         if !errors.is_empty() {
@@ -169,6 +185,9 @@ impl<Meta: Clone> UnresolvedGoogleAI<Meta> {
             supported_request_modes,
             properties,
             finish_reason_filter,
+            rate_limits,
+            pricing,
+            context_window,
         })
     }
 }
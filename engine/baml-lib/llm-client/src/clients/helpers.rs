@@ -4,8 +4,8 @@ use baml_types::{GetEnvVar, StringOr, UnresolvedValue};
 use indexmap::IndexMap;
 
 use crate::{
-    SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter,
-    UnresolvedRolesSelection,
+    ModelPricing, RateLimits, SupportedRequestModes, UnresolvedAllowedRoleMetadata,
+    UnresolvedFinishReasonFilter, UnresolvedRolesSelection,
 };
 
 #[derive(Debug, Clone)]
@@ -160,6 +160,23 @@ impl<Meta: Clone> PropertyHandler<Meta> {
         result.map(|(key_span, value, meta)| (key_span.clone(), value, meta.clone()))
     }
 
+    pub fn ensure_float(&mut self, key: &str, required: bool) -> Option<(Meta, f64, Meta)> {
+        let result = match ensure_float(&mut self.options, key) {
+            Ok(result) => {
+                if required && result.is_none() {
+                    self.push_option_error(format!("Missing required property: {key}"));
+                }
+                result
+            }
+            Err(e) => {
+                self.errors.push(e);
+                return None;
+            }
+        };
+
+        result.map(|(key_span, value, meta)| (key_span.clone(), value, meta.clone()))
+    }
+
     fn ensure_allowed_roles(&mut self) -> Option<Vec<StringOr>> {
         self.ensure_array("allowed_roles", false)
             .map(|(_, value, value_span)| {
@@ -239,11 +256,38 @@ impl<Meta: Clone> PropertyHandler<Meta> {
         match result {
             Some((_, value, _)) => SupportedRequestModes {
                 stream: Some(value),
+                chat: None,
+                completion: None,
+            },
+            None => SupportedRequestModes {
+                stream: None,
+                chat: None,
+                completion: None,
             },
-            None => SupportedRequestModes { stream: None },
         }
     }
 
+    /// Reads `supports_chat` and `supports_completion` for providers that can
+    /// serve both the chat and the legacy completion endpoints.
+    pub fn ensure_supported_prompt_modes(&mut self, modes: &mut SupportedRequestModes) {
+        let chat = self.ensure_bool("supports_chat", false);
+        let completion = self.ensure_bool("supports_completion", false);
+
+        // Completion is off by default, so turning chat off without turning
+        // completion on would leave the client with no way to send a prompt.
+        if let Some((chat_span, false, _)) = &chat {
+            if !matches!(completion, Some((_, true, _))) {
+                self.push_error(
+                    "supports_chat can only be false if supports_completion is true",
+                    chat_span.clone(),
+                );
+            }
+        }
+
+        modes.chat = chat.map(|(_, value, _)| value);
+        modes.completion = completion.map(|(_, value, _)| value);
+    }
+
     pub fn ensure_finish_reason_filter(&mut self) -> UnresolvedFinishReasonFilter {
         let allow_list = self.ensure_array("finish_reason_allow_list", false);
         let deny_list = self.ensure_array("finish_reason_deny_list", false);
@@ -293,6 +337,53 @@ impl<Meta: Clone> PropertyHandler<Meta> {
         }
     }
 
+    fn ensure_positive_int(&mut self, key: &str) -> Option<u32> {
+        self.ensure_int(key, false)
+            .and_then(|(_, value, span)| match u32::try_from(value) {
+                Ok(value) if value > 0 => Some(value),
+                _ => {
+                    self.push_error(
+                        format!("{key} must be a positive integer. Got: {value}"),
+                        span,
+                    );
+                    None
+                }
+            })
+    }
+
+    pub fn ensure_rate_limits(&mut self) -> RateLimits {
+        RateLimits {
+            max_concurrent_requests: self.ensure_positive_int("max_concurrent_requests"),
+            requests_per_minute: self.ensure_positive_int("requests_per_minute"),
+            tokens_per_minute: self.ensure_positive_int("tokens_per_minute"),
+        }
+    }
+
+    /// Overrides the context window BAML assumes for the model, in tokens.
+    pub fn ensure_context_window(&mut self) -> Option<u32> {
+        self.ensure_positive_int("context_window")
+    }
+
+    pub fn ensure_pricing(&mut self) -> ModelPricing {
+        let mut ensure_price = |key: &str| {
+            self.ensure_float(key, false).and_then(|(_, value, span)| {
+                if value >= 0.0 {
+                    Some(value)
+                } else {
+                    self.push_error(format!("{key} must not be negative. Got: {value}"), span);
+                    None
+                }
+            })
+        };
+
+        ModelPricing {
+            input_per_million_tokens: ensure_price("input_cost_per_million_tokens"),
+            output_per_million_tokens: ensure_price("output_cost_per_million_tokens"),
+            cache_read_per_million_tokens: ensure_price("cache_read_cost_per_million_tokens"),
+            cache_write_per_million_tokens: ensure_price("cache_write_cost_per_million_tokens"),
+        }
+    }
+
     pub fn ensure_any(&mut self, key: &str) -> Option<(Meta, UnresolvedValue<Meta>)> {
         self.options.shift_remove(key)
     }
@@ -512,3 +603,26 @@ pub(crate) fn get_proxy_url(ctx: &impl GetEnvVar) -> Option<String> {
         None
     }
 }
+
+fn ensure_float<Meta: Clone>(
+    options: &mut IndexMap<String, (Meta, UnresolvedValue<Meta>)>,
+    key: &str,
+) -> Result<Option<(Meta, f64, Meta)>, Error<Meta>> {
+    if let Some((key_span, value)) = options.shift_remove(key) {
+        match value.into_numeric() {
+            Ok((n, meta)) => match n.parse::<f64>() {
+                Ok(n) => Ok(Some((key_span, n, meta))),
+                Err(_) => Err(Error {
+                    message: format!("{key} must be a number. Got: {n}"),
+                    span: meta,
+                }),
+            },
+            Err(other) => Err(Error {
+                message: format!("{} must be a number. Got: {}", key, other.r#type()),
+                span: other.meta().clone(),
+            }),
+        }
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashSet;
+
+use crate::{
+    AllowedRoleMetadata, FinishReasonFilter, ModelPricing, RateLimits, RolesSelection,
+    SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter,
+    UnresolvedRolesSelection,
+};
+use anyhow::{Context, Result};
+
+use baml_types::{EvaluationContext, StringOr, UnresolvedValue};
+use serde::Deserialize;
+
+use super::helpers::{Error, PropertyHandler};
+
+/// Keys allowed in each entry of a mock client's `responses`.
+const RESPONSE_KEYS: &[&str] = &[
+    "function",
+    "prompt_regex",
+    "content",
+    "error",
+    "message",
+    "finish_reason",
+    "latency_ms",
+];
+
+#[derive(Debug)]
+pub struct UnresolvedMock<Meta> {
+    model: Option<StringOr>,
+    responses: Vec<UnresolvedValue<Meta>>,
+    fixtures: Option<StringOr>,
+    chunk_size: Option<usize>,
+    latency_ms: Option<u64>,
+    chunk_latency_ms: Option<u64>,
+    role_selection: UnresolvedRolesSelection,
+    allowed_metadata: UnresolvedAllowedRoleMetadata,
+    supported_request_modes: SupportedRequestModes,
+    finish_reason_filter: UnresolvedFinishReasonFilter,
+    rate_limits: RateLimits,
+    pricing: ModelPricing,
+    context_window: Option<u32>,
+}
+
+/// A canned response, from a mock client's `responses` option or its
+/// fixtures file.
+///
+/// A response applies to a request if its `function` and `prompt_regex`
+/// (when set) both match. Responses without either apply to every request.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MockResponse {
+    pub function: Option<String>,
+    pub prompt_regex: Option<String>,
+    pub content: Option<String>,
+    pub error: Option<MockError>,
+    /// The error message to report with `error`.
+    pub message: Option<String>,
+    /// Defaults to `stop`.
+    pub finish_reason: Option<String>,
+    /// Overrides the client's `latency_ms` for this response.
+    pub latency_ms: Option<u64>,
+}
+
+/// An error to fail the request with instead of responding.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum MockError {
+    /// An HTTP status code, e.g. 429 or 500.
+    Status(u16),
+    Kind(MockErrorKind),
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MockErrorKind {
+    /// A 200 response whose body isn't a valid provider response.
+    MalformedResponse,
+}
+
+pub struct ResolvedMock {
+    pub model: String,
+    pub responses: Vec<MockResponse>,
+    /// Path to a JSON file of additional responses, relative to `baml_src`.
+    pub fixtures: Option<String>,
+    /// Number of characters per streamed chunk. Streams send the whole
+    /// response in one chunk if unset.
+    pub chunk_size: Option<usize>,
+    pub latency_ms: u64,
+    pub chunk_latency_ms: u64,
+    role_selection: RolesSelection,
+    pub allowed_metadata: AllowedRoleMetadata,
+    pub supported_request_modes: SupportedRequestModes,
+    pub finish_reason_filter: FinishReasonFilter,
+    pub rate_limits: RateLimits,
+    pub pricing: ModelPricing,
+    pub context_window: Option<u32>,
+}
+
+impl ResolvedMock {
+    pub fn allowed_roles(&self) -> Vec<String> {
+        self.role_selection.allowed_or_else(|| {
+            vec![
+                "system".to_string(),
+                "user".to_string(),
+                "assistant".to_string(),
+            ]
+        })
+    }
+
+    pub fn default_role(&self) -> String {
+        self.role_selection.default_or_else(|| "user".to_string())
+    }
+}
+
+impl<Meta: Clone> UnresolvedMock<Meta> {
+    pub fn without_meta(&self) -> UnresolvedMock<()> {
+        UnresolvedMock {
+            model: self.model.clone(),
+            responses: self.responses.iter().map(|r| r.without_meta()).collect(),
+            fixtures: self.fixtures.clone(),
+            chunk_size: self.chunk_size,
+            latency_ms: self.latency_ms,
+            chunk_latency_ms: self.chunk_latency_ms,
+            role_selection: self.role_selection.clone(),
+            allowed_metadata: self.allowed_metadata.clone(),
+            supported_request_modes: self.supported_request_modes.clone(),
+            finish_reason_filter: self.finish_reason_filter.clone(),
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
+        }
+    }
+
+    pub fn required_env_vars(&self) -> HashSet<String> {
+        let mut env_vars = HashSet::new();
+        if let Some(model) = self.model.as_ref() {
+            env_vars.extend(model.required_env_vars());
+        }
+        env_vars.extend(self.responses.iter().flat_map(|r| r.required_env_vars()));
+        if let Some(fixtures) = self.fixtures.as_ref() {
+            env_vars.extend(fixtures.required_env_vars());
+        }
+        env_vars.extend(self.role_selection.required_env_vars());
+        env_vars.extend(self.allowed_metadata.required_env_vars());
+        env_vars.extend(self.supported_request_modes.required_env_vars());
+        env_vars
+    }
+
+    pub fn resolve(&self, ctx: &EvaluationContext<'_>) -> Result<ResolvedMock> {
+        let responses = self
+            .responses
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                r.resolve_serde::<MockResponse>(ctx)
+                    .context(format!("Invalid mock response at index {i}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ResolvedMock {
+            model: self
+                .model
+                .as_ref()
+                .map(|m| m.resolve(ctx))
+                .transpose()?
+                .unwrap_or_else(|| "mock".to_string()),
+            responses,
+            fixtures: self.fixtures.as_ref().map(|f| f.resolve(ctx)).transpose()?,
+            chunk_size: self.chunk_size,
+            latency_ms: self.latency_ms.unwrap_or(0),
+            chunk_latency_ms: self.chunk_latency_ms.unwrap_or(0),
+            role_selection: self.role_selection.resolve(ctx)?,
+            allowed_metadata: self.allowed_metadata.resolve(ctx)?,
+            supported_request_modes: self.supported_request_modes.clone(),
+            finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
+        })
+    }
+
+    pub fn create_from(mut properties: PropertyHandler<Meta>) -> Result<Self, Vec<Error<Meta>>> {
+        let model = properties.ensure_string("model", false).map(|(_, v, _)| v);
+        let fixtures = properties
+            .ensure_string("fixtures", false)
+            .map(|(_, v, _)| v);
+
+        let responses = match properties.ensure_array("responses", false) {
+            Some((_, responses, _)) => {
+                for response in &responses {
+                    validate_response(&mut properties, response);
+                }
+                responses
+            }
+            None => {
+                if fixtures.is_none() {
+                    properties
+                        .push_option_error("mock clients need `responses`, `fixtures` or both");
+                }
+                vec![]
+            }
+        };
+
+        let chunk_size = ensure_non_negative(&mut properties, "chunk_size").and_then(|size| {
+            if size == 0 {
+                properties.push_option_error("chunk_size must be greater than 0");
+                None
+            } else {
+                Some(size as usize)
+            }
+        });
+        let latency_ms = ensure_non_negative(&mut properties, "latency_ms").map(|v| v as u64);
+        let chunk_latency_ms =
+            ensure_non_negative(&mut properties, "chunk_latency_ms").map(|v| v as u64);
+
+        let role_selection = properties.ensure_roles_selection();
+        let allowed_metadata = properties.ensure_allowed_metadata();
+        let supported_request_modes = properties.ensure_supported_request_modes();
+        let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let rate_limits = properties.ensure_rate_limits();
+        let pricing = properties.ensure_pricing();
+        let context_window = properties.ensure_context_window();
+
+        let errors = properties.finalize_empty();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            model,
+            responses,
+            fixtures,
+            chunk_size,
+            latency_ms,
+            chunk_latency_ms,
+            role_selection,
+            allowed_metadata,
+            supported_request_modes,
+            finish_reason_filter,
+            rate_limits,
+            pricing,
+            context_window,
+        })
+    }
+}
+
+fn ensure_non_negative<Meta: Clone>(
+    properties: &mut PropertyHandler<Meta>,
+    key: &str,
+) -> Option<i32> {
+    let (_, value, value_span) = properties.ensure_int(key, false)?;
+    if value < 0 {
+        properties.push_error(format!("{key} must not be negative"), value_span);
+        return None;
+    }
+    Some(value)
+}
+
+/// Checks the shape of a `responses` entry. Values are only type-checked
+/// once resolved, since they may come from environment variables.
+fn validate_response<Meta: Clone>(
+    properties: &mut PropertyHandler<Meta>,
+    response: &UnresolvedValue<Meta>,
+) {
+    let Some(map) = response.as_map() else {
+        properties.push_error(
+            format!(
+                "values in responses must be maps, e.g. {{ content \"...\" }}. Got: {}",
+                response.r#type()
+            ),
+            response.meta().clone(),
+        );
+        return;
+    };
+
+    for (key, (key_span, value)) in map {
+        if !RESPONSE_KEYS.contains(&key.as_str()) {
+            properties.push_error(
+                format!(
+                    "Unsupported mock response key: {key}. Expected one of: {}",
+                    RESPONSE_KEYS.join(", ")
+                ),
+                key_span.clone(),
+            );
+            continue;
+        }
+        if key == "error" {
+            let valid = match value {
+                UnresolvedValue::Numeric(n, _) => n.parse::<u16>().is_ok(),
+                UnresolvedValue::String(StringOr::Value(s), _) => s == "malformed_response",
+                _ => false,
+            };
+            if !valid {
+                properties.push_error(
+                    "error must be an HTTP status code or \"malformed_response\"",
+                    value.meta().clone(),
+                );
+            }
+        }
+    }
+
+    match (map.contains_key("content"), map.contains_key("error")) {
+        (true, true) => properties.push_error(
+            "A mock response cannot have both content and error",
+            response.meta().clone(),
+        ),
+        (false, false) => properties.push_error(
+            "A mock response needs either content or error",
+            response.meta().clone(),
+        ),
+        _ => {}
+    }
+}
@@ -11,6 +11,7 @@ pub mod anthropic;
 pub mod aws_bedrock;
 pub mod fallback;
 pub mod google_ai;
+pub mod mock;
 pub mod openai;
 pub mod round_robin;
 pub mod vertex;
@@ -22,6 +23,7 @@ pub enum UnresolvedClientProperty<Meta> {
     AWSBedrock(aws_bedrock::UnresolvedAwsBedrock),
     Vertex(vertex::UnresolvedVertex<Meta>),
     GoogleAI(google_ai::UnresolvedGoogleAI<Meta>),
+    Mock(mock::UnresolvedMock<Meta>),
     RoundRobin(round_robin::UnresolvedRoundRobin<Meta>),
     Fallback(fallback::UnresolvedFallback<Meta>),
 }
@@ -32,6 +34,7 @@ pub enum ResolvedClientProperty {
     AWSBedrock(aws_bedrock::ResolvedAwsBedrock),
     Vertex(vertex::ResolvedVertex),
     GoogleAI(google_ai::ResolvedGoogleAI),
+    Mock(mock::ResolvedMock),
     RoundRobin(round_robin::ResolvedRoundRobin),
     Fallback(fallback::ResolvedFallback),
 }
@@ -46,6 +49,7 @@ impl ResolvedClientProperty {
             ResolvedClientProperty::AWSBedrock(_) => "aws-bedrock",
             ResolvedClientProperty::Vertex(_) => "vertex",
             ResolvedClientProperty::GoogleAI(_) => "google-ai",
+            ResolvedClientProperty::Mock(_) => "mock",
         }
     }
 }
@@ -58,6 +62,7 @@ impl<Meta: Clone> UnresolvedClientProperty<Meta> {
             UnresolvedClientProperty::AWSBedrock(a) => a.required_env_vars(),
             UnresolvedClientProperty::Vertex(v) => v.required_env_vars(),
             UnresolvedClientProperty::GoogleAI(g) => g.required_env_vars(),
+            UnresolvedClientProperty::Mock(m) => m.required_env_vars(),
             UnresolvedClientProperty::RoundRobin(r) => r.required_env_vars(),
             UnresolvedClientProperty::Fallback(f) => f.required_env_vars(),
         }
@@ -84,6 +89,7 @@ impl<Meta: Clone> UnresolvedClientProperty<Meta> {
             UnresolvedClientProperty::GoogleAI(g) => {
                 g.resolve(ctx).map(ResolvedClientProperty::GoogleAI)
             }
+            UnresolvedClientProperty::Mock(m) => m.resolve(ctx).map(ResolvedClientProperty::Mock),
             UnresolvedClientProperty::RoundRobin(r) => {
                 r.resolve(ctx).map(ResolvedClientProperty::RoundRobin)
             }
@@ -110,6 +116,7 @@ impl<Meta: Clone> UnresolvedClientProperty<Meta> {
             UnresolvedClientProperty::GoogleAI(g) => {
                 UnresolvedClientProperty::GoogleAI(g.without_meta())
             }
+            UnresolvedClientProperty::Mock(m) => UnresolvedClientProperty::Mock(m.without_meta()),
             UnresolvedClientProperty::RoundRobin(r) => {
                 UnresolvedClientProperty::RoundRobin(r.without_meta())
             }
@@ -143,6 +150,9 @@ impl crate::ClientProvider {
             crate::ClientProvider::Vertex => {
                 UnresolvedClientProperty::Vertex(vertex::UnresolvedVertex::create_from(properties)?)
             }
+            crate::ClientProvider::Mock => {
+                UnresolvedClientProperty::Mock(mock::UnresolvedMock::create_from(properties)?)
+            }
             crate::ClientProvider::Strategy(s) => s.create_from(properties)?,
         })
     }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{AllowedRoleMetadata, FinishReasonFilter, RolesSelection, SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection};
+use crate::{AllowedRoleMetadata, FinishReasonFilter, ModelPricing, RateLimits, RolesSelection, SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection};
 use anyhow::Result;
 
 use baml_types::{GetEnvVar, StringOr, UnresolvedValue};
@@ -19,6 +19,9 @@ pub struct UnresolvedOpenAI<Meta> {
     properties: IndexMap<String, (Meta, UnresolvedValue<Meta>)>,
     query_params: IndexMap<String, StringOr>,
     finish_reason_filter: UnresolvedFinishReasonFilter,
+    rate_limits: RateLimits,
+    pricing: ModelPricing,
+    context_window: Option<u32>,
 }
 
 impl<Meta> UnresolvedOpenAI<Meta> {
@@ -45,6 +48,9 @@ impl<Meta> UnresolvedOpenAI<Meta> {
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect(),
             finish_reason_filter: self.finish_reason_filter.clone(),
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         }
     }
 }
@@ -60,6 +66,9 @@ pub struct ResolvedOpenAI {
     pub query_params: IndexMap<String, String>,
     pub proxy_url: Option<String>,
     pub finish_reason_filter: FinishReasonFilter,
+    pub rate_limits: RateLimits,
+    pub pricing: ModelPricing,
+    pub context_window: Option<u32>,
 }
 
 impl ResolvedOpenAI {
@@ -74,6 +83,16 @@ impl ResolvedOpenAI {
         }
     }
 
+    pub fn supports_chat(&self) -> bool {
+        self.supported_request_modes.chat.unwrap_or(true)
+    }
+
+    /// Whether completion prompts may be sent to the legacy `/completions`
+    /// endpoint, as served by vLLM, llama.cpp and TGI for base models.
+    pub fn supports_completion(&self) -> bool {
+        self.supported_request_modes.completion.unwrap_or(false)
+    }
+
     pub fn allowed_roles(&self) -> Vec<String> {
         self.role_selection.allowed_or_else(|| {
             if self.is_o1_model() {
@@ -202,6 +221,9 @@ impl<Meta: Clone> UnresolvedOpenAI<Meta> {
             query_params,
             proxy_url: super::helpers::get_proxy_url(ctx),
             finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            rate_limits: self.rate_limits.clone(),
+            pricing: self.pricing.clone(),
+            context_window: self.context_window,
         })
     }
 
@@ -316,9 +338,13 @@ impl<Meta: Clone> UnresolvedOpenAI<Meta> {
     ) -> Result<Self, Vec<Error<Meta>>> {
         let role_selection = properties.ensure_roles_selection();
         let allowed_metadata = properties.ensure_allowed_metadata();
-        let supported_request_modes = properties.ensure_supported_request_modes();
+        let mut supported_request_modes = properties.ensure_supported_request_modes();
+        properties.ensure_supported_prompt_modes(&mut supported_request_modes);
         let headers = properties.ensure_headers().unwrap_or_default();
         let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let rate_limits = properties.ensure_rate_limits();
+        let pricing = properties.ensure_pricing();
+        let context_window = properties.ensure_context_window();
         let (properties, errors) = properties.finalize();
 
         if !errors.is_empty() {
@@ -335,6 +361,9 @@ impl<Meta: Clone> UnresolvedOpenAI<Meta> {
             properties,
             query_params: IndexMap::new(),
             finish_reason_filter,
+            rate_limits,
+            pricing,
+            context_window,
         })
     }
 }
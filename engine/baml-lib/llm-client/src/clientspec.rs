@@ -42,6 +42,8 @@ pub enum ClientProvider {
     GoogleAi,
     /// The Vertex client provider variant
     Vertex,
+    /// The mock client provider variant, which responds without network access
+    Mock,
     /// The strategy client provider variant
     Strategy(StrategyClientProvider),
 }
@@ -76,6 +78,7 @@ impl std::fmt::Display for ClientProvider {
             ClientProvider::AwsBedrock => write!(f, "aws-bedrock"),
             ClientProvider::GoogleAi => write!(f, "google-ai"),
             ClientProvider::Vertex => write!(f, "vertex-ai"),
+            ClientProvider::Mock => write!(f, "mock"),
             ClientProvider::Strategy(variant) => write!(f, "{variant}"),
         }
     }
@@ -118,6 +121,7 @@ impl std::str::FromStr for ClientProvider {
             "aws-bedrock" => Ok(ClientProvider::AwsBedrock),
             "google-ai" => Ok(ClientProvider::GoogleAi),
             "vertex-ai" => Ok(ClientProvider::Vertex),
+            "mock" => Ok(ClientProvider::Mock),
             "fallback" => Ok(ClientProvider::Strategy(StrategyClientProvider::Fallback)),
             "baml-fallback" => Ok(ClientProvider::Strategy(StrategyClientProvider::Fallback)),
             "round-robin" => Ok(ClientProvider::Strategy(StrategyClientProvider::RoundRobin)),
@@ -172,6 +176,7 @@ impl ClientProvider {
             "google-ai",
             "vertex-ai",
             "aws-bedrock",
+            "mock",
         ]
     }
 }
@@ -189,6 +194,10 @@ impl std::fmt::Display for ClientSpec {
 pub struct SupportedRequestModes {
     // If unset, treat as auto
     pub stream: Option<bool>,
+    // If unset, treat as supported
+    pub chat: Option<bool>,
+    // If unset, treat as unsupported
+    pub completion: Option<bool>,
 }
 
 impl SupportedRequestModes {
@@ -197,6 +206,38 @@ impl SupportedRequestModes {
     }
 }
 
+/// Limits on how much traffic a client may send. These are shared by every
+/// call made through the same client in a runtime.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RateLimits {
+    // If unset, any number of requests may be in flight
+    pub max_concurrent_requests: Option<u32>,
+    // If unset, requests are not rate limited
+    pub requests_per_minute: Option<u32>,
+    // If unset, tokens are not rate limited
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl RateLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_concurrent_requests.is_none()
+            && self.requests_per_minute.is_none()
+            && self.tokens_per_minute.is_none()
+    }
+}
+
+/// Overrides for the built-in per-token prices of a client's model, in USD
+/// per million tokens.
+#[derive(Clone, Debug, Default)]
+pub struct ModelPricing {
+    pub input_per_million_tokens: Option<f64>,
+    pub output_per_million_tokens: Option<f64>,
+    /// Prompt tokens read from the provider's prompt cache.
+    pub cache_read_per_million_tokens: Option<f64>,
+    /// Prompt tokens written to the provider's prompt cache.
+    pub cache_write_per_million_tokens: Option<f64>,
+}
+
 #[derive(Clone, Debug)]
 pub enum UnresolvedFinishReasonFilter {
     All,
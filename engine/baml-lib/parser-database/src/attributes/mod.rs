@@ -27,6 +27,16 @@ pub struct Attributes {
 
     /// @check and @assert attributes attached to the node.
     pub constraints: Vec<Constraint>,
+
+    /// Whether the node is only streamed once it is fully parsed.
+    pub stream_done: Option<bool>,
+
+    /// Whether the parent object is held back while streaming until this
+    /// field has a value.
+    pub stream_not_null: Option<bool>,
+
+    /// Whether the streamed node is wrapped with its completion state.
+    pub stream_with_state: Option<bool>,
 }
 
 impl Attributes {
@@ -69,6 +79,36 @@ impl Attributes {
     pub fn set_skip(&mut self) {
         self.skip.replace(true);
     }
+
+    /// Get stream.done.
+    pub fn stream_done(&self) -> &Option<bool> {
+        &self.stream_done
+    }
+
+    /// Set stream.done.
+    pub fn set_stream_done(&mut self) {
+        self.stream_done.replace(true);
+    }
+
+    /// Get stream.not_null.
+    pub fn stream_not_null(&self) -> &Option<bool> {
+        &self.stream_not_null
+    }
+
+    /// Set stream.not_null.
+    pub fn set_stream_not_null(&mut self) {
+        self.stream_not_null.replace(true);
+    }
+
+    /// Get stream.with_state.
+    pub fn stream_with_state(&self) -> &Option<bool> {
+        &self.stream_with_state
+    }
+
+    /// Set stream.with_state.
+    pub fn set_stream_with_state(&mut self) {
+        self.stream_with_state.replace(true);
+    }
 }
 pub(super) fn resolve_attributes(ctx: &mut Context<'_>) {
     for top in ctx.ast.iter_tops() {
@@ -97,7 +137,7 @@ fn resolve_type_exp_block_attributes<'db>(
 
             for (value_idx, _value) in ast_typexpr.iter_fields() {
                 ctx.assert_all_attributes_processed((type_id, value_idx).into());
-                if let Some(attrs) = to_string_attribute::visit(ctx, &span, false) {
+                if let Some(attrs) = to_string_attribute::visit(ctx, &span, false, SubType::Enum) {
                     enum_attributes.value_serilizers.insert(value_idx, attrs);
                 }
                 ctx.validate_visited_attributes();
@@ -105,7 +145,7 @@ fn resolve_type_exp_block_attributes<'db>(
 
             // Now validate the enum attributes.
             ctx.assert_all_attributes_processed(type_id.into());
-            enum_attributes.serilizer = to_string_attribute::visit(ctx, &span, true);
+            enum_attributes.serilizer = to_string_attribute::visit(ctx, &span, true, SubType::Enum);
             ctx.validate_visited_attributes();
 
             ctx.types.enum_attributes.insert(type_id, enum_attributes);
@@ -115,7 +155,9 @@ fn resolve_type_exp_block_attributes<'db>(
 
             for (field_idx, field) in ast_typexpr.iter_fields() {
                 ctx.assert_all_attributes_processed((type_id, field_idx).into());
-                if let Some(attrs) = to_string_attribute::visit(ctx, &field.span, false) {
+                if let Some(attrs) =
+                    to_string_attribute::visit(ctx, &field.span, false, SubType::Class)
+                {
                     class_attributes.field_serilizers.insert(field_idx, attrs);
                 }
                 ctx.validate_visited_attributes();
@@ -123,7 +165,8 @@ fn resolve_type_exp_block_attributes<'db>(
 
             // Now validate the class attributes.
             ctx.assert_all_attributes_processed(type_id.into());
-            class_attributes.serilizer = to_string_attribute::visit(ctx, &span, true);
+            class_attributes.serilizer =
+                to_string_attribute::visit(ctx, &span, true, SubType::Class);
             ctx.validate_visited_attributes();
 
             ctx.types.class_attributes.insert(type_id, class_attributes);
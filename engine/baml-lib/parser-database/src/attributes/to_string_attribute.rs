@@ -1,6 +1,7 @@
 use baml_types::Constraint;
 use internal_baml_diagnostics::{DatamodelError, Span};
 
+use internal_baml_schema_ast::ast::SubType;
 use itertools::Itertools;
 
 use crate::{context::Context, types::Attributes};
@@ -8,7 +9,12 @@ use crate::{context::Context, types::Attributes};
 use super::alias::visit_alias_attribute;
 use super::constraint::visit_constraint_attributes;
 use super::description::visit_description_attribute;
-pub(super) fn visit(ctx: &mut Context<'_>, span: &Span, as_block: bool) -> Option<Attributes> {
+pub(super) fn visit(
+    ctx: &mut Context<'_>,
+    span: &Span,
+    as_block: bool,
+    sub_type: SubType,
+) -> Option<Attributes> {
     let mut modified = false;
 
     let mut attributes = Attributes::default();
@@ -43,6 +49,27 @@ pub(super) fn visit(ctx: &mut Context<'_>, span: &Span, as_block: bool) -> Optio
         ctx.validate_visited_arguments();
     }
 
+    // @stream.* only apply to classes and their fields.
+    if matches!(sub_type, SubType::Class) {
+        if ctx.visit_optional_single_attr("stream.done") {
+            attributes.set_stream_done();
+            modified = true;
+            ctx.validate_visited_arguments();
+        }
+
+        if !as_block && ctx.visit_optional_single_attr("stream.not_null") {
+            attributes.set_stream_not_null();
+            modified = true;
+            ctx.validate_visited_arguments();
+        }
+
+        if !as_block && ctx.visit_optional_single_attr("stream.with_state") {
+            attributes.set_stream_with_state();
+            modified = true;
+            ctx.validate_visited_arguments();
+        }
+    }
+
     if modified {
         Some(attributes)
     } else {
@@ -1,4 +1,4 @@
-use internal_baml_diagnostics::{DatamodelWarning, Span};
+use internal_baml_diagnostics::{DatamodelWarning, SourceFile, Span};
 use internal_baml_schema_ast::ast::{Argument, ArgumentId, Attribute};
 
 use crate::{
@@ -24,6 +24,7 @@ mod attributes;
 /// See `visit_attributes()`.
 pub(crate) struct Context<'db> {
     pub(crate) ast: &'db ast::SchemaAst,
+    pub(crate) data_files: &'db [SourceFile],
     pub(crate) interner: &'db mut StringInterner,
     pub(crate) names: &'db mut Names,
     pub(crate) types: &'db mut Types,
@@ -34,6 +35,7 @@ pub(crate) struct Context<'db> {
 impl<'db> Context<'db> {
     pub(super) fn new(
         ast: &'db ast::SchemaAst,
+        data_files: &'db [SourceFile],
         interner: &'db mut StringInterner,
         names: &'db mut Names,
         types: &'db mut Types,
@@ -41,6 +43,7 @@ impl<'db> Context<'db> {
     ) -> Self {
         Context {
             ast,
+            data_files,
             interner,
             names,
             types,
@@ -44,12 +44,16 @@ pub use internal_baml_schema_ast::ast;
 use internal_baml_schema_ast::ast::SchemaAst;
 pub use tarjan::Tarjan;
 pub use types::{
-    Attributes, ClientProperties, ContantDelayStrategy, ExponentialBackoffStrategy, PrinterType,
-    PromptAst, PromptVariable, RetryPolicy, RetryPolicyStrategy, StaticType,
+    Attributes, ClientProperties, ContantDelayStrategy, ExponentialBackoffStrategy, PipelineStep,
+    PrinterType, PromptAst, PromptVariable, PromptVariant, RetryPolicy, RetryPolicyStrategy,
+    StaticType, TestDataset, TestDatasetRow,
 };
 
+/// Extensions of the data files that test cases can read their arguments from.
+pub const DATA_FILE_EXTENSIONS: [&str; 2] = ["jsonl", "csv"];
+
 use self::{context::Context, interner::StringId, types::Types};
-use internal_baml_diagnostics::{DatamodelError, Diagnostics};
+use internal_baml_diagnostics::{DatamodelError, Diagnostics, SourceFile};
 use names::Names;
 
 /// ParserDatabase is a container for a Schema AST, together with information
@@ -73,6 +77,7 @@ use names::Names;
 ///   Currently only index name collisions.
 pub struct ParserDatabase {
     ast: ast::SchemaAst,
+    data_files: Vec<SourceFile>,
     interner: interner::StringInterner,
     names: Names,
     types: Types,
@@ -89,6 +94,7 @@ impl ParserDatabase {
     pub fn new() -> Self {
         ParserDatabase {
             ast: ast::SchemaAst { tops: vec![] },
+            data_files: vec![],
             interner: Default::default(),
             names: Default::default(),
             types: Default::default(),
@@ -100,10 +106,17 @@ impl ParserDatabase {
         self.ast.tops.extend(ast.tops);
     }
 
+    /// Adds a data file, e.g. the rows of a test dataset. Data files are not
+    /// parsed as BAML, only read by the blocks that refer to them.
+    pub fn add_data_file(&mut self, file: SourceFile) {
+        self.data_files.push(file);
+    }
+
     /// See the docs on [ParserDatabase](/struct.ParserDatabase.html).
     pub fn validate(&mut self, diag: &mut Diagnostics) -> Result<(), Diagnostics> {
         let mut ctx = Context::new(
             &self.ast,
+            &self.data_files,
             &mut self.interner,
             &mut self.names,
             &mut self.types,
@@ -6,6 +6,12 @@ use crate::{
 };
 
 pub(crate) fn validate_attribute_name(ast_attr: &ast::Attribute, diagnostics: &mut Diagnostics) {
+    // The streaming attributes (`@stream.done`, ...) are the only namespaced ones.
+    if let ast::Identifier::Ref(r, _) = ast_attr.identifier() {
+        if r.path == ["stream"] {
+            return;
+        }
+    }
     validate_name("attribute", ast_attr.identifier(), diagnostics, false);
 }
 
@@ -1,8 +1,9 @@
 use baml_types::Constraint;
+use baml_types::ParserConfig;
 use baml_types::UnresolvedValue;
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span};
 use internal_baml_schema_ast::ast::{
-    Attribute, ValExpId, ValueExprBlock, WithIdentifier, WithName, WithSpan,
+    Attribute, Expression, ValExpId, ValueExprBlock, WithIdentifier, WithName, WithSpan,
 };
 use regex::Regex;
 use std::{collections::HashSet, ops::Deref};
@@ -11,7 +12,8 @@ use crate::attributes::constraint::attribute_as_constraint;
 use crate::{coerce, coerce_array, coerce_expression::coerce_map, context::Context};
 
 use super::{
-    Attributes, ContantDelayStrategy, ExponentialBackoffStrategy, RetryPolicy, RetryPolicyStrategy,
+    dataset::visit_dataset, Attributes, ContantDelayStrategy, ExponentialBackoffStrategy,
+    PromptVariant, RetryPolicy, RetryPolicyStrategy,
 };
 
 fn dedent(s: &str) -> String {
@@ -205,6 +207,8 @@ pub(crate) fn visit_test_case<'db>(
 ) {
     let mut functions = None;
     let mut args = None;
+    let mut dataset = None;
+    let mut has_dataset = false;
 
     config
         .iter_fields()
@@ -253,10 +257,16 @@ pub(crate) fn visit_test_case<'db>(
                 }
                 None => {}
             },
+            ("dataset", Some(val)) => {
+                has_dataset = true;
+                if let Some(val) = val.to_unresolved_value(ctx.diagnostics) {
+                    dataset = visit_dataset(val, ctx);
+                }
+            }
             (name, Some(_)) => ctx.push_error(DatamodelError::new_property_not_known_error(
                 name,
                 f.identifier().span().clone(),
-                ["functions", "args"].to_vec(),
+                ["functions", "args", "dataset"].to_vec(),
             )),
         });
 
@@ -277,20 +287,209 @@ pub(crate) fn visit_test_case<'db>(
             "Missing `functions` property",
             config.identifier().span().clone(),
         )),
-        (Some(_function_name), None) => ctx.push_error(DatamodelError::new_validation_error(
-            "Missing `args` property",
-            config.identifier().span().clone(),
-        )),
-        (Some(functions), Some((args_field_span, args))) => {
+        // Tests that read their arguments from a data file may leave out `args`.
+        (Some(_function_name), None) if !has_dataset => {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "Missing `args` property",
+                config.identifier().span().clone(),
+            ))
+        }
+        (Some(functions), args) => {
+            let (args_field_span, args) =
+                args.unwrap_or_else(|| (config.identifier().span().clone(), Default::default()));
+            if let Some(dataset) = &dataset {
+                for (arg, (_, span)) in &dataset.columns {
+                    if args.contains_key(arg) {
+                        ctx.push_error(DatamodelError::new_validation_error(
+                            &format!("Argument `{arg}` is set in both `args` and `dataset`"),
+                            span.clone(),
+                        ));
+                    }
+                }
+            }
             ctx.types.test_cases.insert(
                 idx,
                 super::TestCase {
                     functions,
                     args,
-                    args_field_span: args_field_span.clone(),
+                    args_field_span,
                     constraints,
+                    dataset,
                 },
             );
         }
     }
 }
+
+/// Reads the `@@parser({ ... })` attribute of a function.
+pub(crate) fn visit_parser_config(
+    function: &ValueExprBlock,
+    ctx: &mut Context<'_>,
+) -> ParserConfig {
+    let mut config = ParserConfig::default();
+    let mut seen = false;
+
+    for attribute in function
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.name.name() == "parser")
+    {
+        if seen {
+            ctx.push_error(DatamodelError::new_duplicate_attribute_error(
+                "@parser",
+                attribute.span.clone(),
+            ));
+            continue;
+        }
+        seen = true;
+
+        let [argument] = attribute.arguments.arguments.as_slice() else {
+            ctx.push_error(DatamodelError::new_attribute_validation_error(
+                "Expected a single map of options.",
+                "parser",
+                attribute.span.clone(),
+            ));
+            continue;
+        };
+        let Some(options) = coerce_map(&argument.value, &coerce::string_with_span, ctx.diagnostics)
+        else {
+            continue;
+        };
+
+        for ((name, span), value) in options {
+            match name {
+                "allow_markdown_json" => config.allow_markdown_json = boolean(value, ctx),
+                "allow_fixes" => config.allow_fixes = boolean(value, ctx),
+                "allow_as_string" => config.allow_as_string = boolean(value, ctx),
+                "find_all_json_objects" => config.find_all_json_objects = boolean(value, ctx),
+                "allow_alternate_formats" => config.allow_alternate_formats = boolean(value, ctx),
+                "max_score" => {
+                    config.max_score = coerce::integer(value, ctx.diagnostics).map(|v| v as i32)
+                }
+                "deny" => {
+                    let Some(flags) =
+                        coerce_array(value, &coerce::string_with_span, ctx.diagnostics)
+                    else {
+                        continue;
+                    };
+                    for (flag, span) in flags {
+                        if ParserConfig::FLAGS.contains(&flag) {
+                            config.deny.push(flag.to_string());
+                        } else {
+                            ctx.push_error(DatamodelError::new_validation_error(
+                                &format!(
+                                    "Unknown parser flag `{}`. Expected one of: {}",
+                                    flag,
+                                    ParserConfig::FLAGS.join(", ")
+                                ),
+                                span.clone(),
+                            ));
+                        }
+                    }
+                }
+                _ => ctx.push_error(DatamodelError::new_property_not_known_error(
+                    name,
+                    span.clone(),
+                    ParserConfig::OPTIONS.to_vec(),
+                )),
+            }
+        }
+    }
+
+    config
+}
+
+pub(crate) fn visit_prompt_variants(
+    function: &ValueExprBlock,
+    ctx: &mut Context<'_>,
+) -> Vec<PromptVariant> {
+    let mut variants: Vec<PromptVariant> = vec![];
+
+    for attribute in function
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.name.name() == "variant")
+    {
+        let [name, options] = attribute.arguments.arguments.as_slice() else {
+            ctx.push_error(DatamodelError::new_attribute_validation_error(
+                "Expected a name and a map of options.",
+                "variant",
+                attribute.span.clone(),
+            ));
+            continue;
+        };
+        let Some((name, name_span)) = coerce::string_with_span(&name.value, ctx.diagnostics) else {
+            continue;
+        };
+        if name == "default_config" {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "The variant name `default_config` is reserved for the prompt of the function.",
+                name_span.clone(),
+            ));
+            continue;
+        }
+        if variants.iter().any(|v| v.name.0 == name) {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Variant `{name}` is already defined."),
+                name_span.clone(),
+            ));
+            continue;
+        }
+        let Some(options) = coerce_map(&options.value, &coerce::string_with_span, ctx.diagnostics)
+        else {
+            continue;
+        };
+
+        let mut variant = PromptVariant {
+            name: (name.to_string(), name_span.clone()),
+            prompt: None,
+            client: None,
+            weight: 0,
+            span: attribute.span.clone(),
+        };
+        for ((option, span), value) in options {
+            match option {
+                "prompt" => {
+                    variant.prompt = coerce::template_string(value, ctx.diagnostics).cloned()
+                }
+                "client" => {
+                    variant.client = coerce::string_with_span(value, ctx.diagnostics)
+                        .map(|(v, span)| (v.to_string(), span.clone()))
+                }
+                "weight" => match coerce::integer(value, ctx.diagnostics) {
+                    Some(weight @ 0..=100) => variant.weight = weight as u32,
+                    Some(_) => ctx.push_error(DatamodelError::new_validation_error(
+                        "The weight of a variant must be between 0 and 100.",
+                        value.span().clone(),
+                    )),
+                    None => {}
+                },
+                _ => ctx.push_error(DatamodelError::new_property_not_known_error(
+                    option,
+                    span.clone(),
+                    vec!["prompt", "client", "weight"],
+                )),
+            }
+        }
+        variants.push(variant);
+    }
+
+    let total_weight = variants.iter().map(|v| v.weight).sum::<u32>();
+    if total_weight > 100 {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!(
+                "The weights of the variants add up to {total_weight}, but must not exceed 100."
+            ),
+            function.identifier().span().clone(),
+        ));
+    }
+
+    variants
+}
+
+fn boolean(value: &Expression, ctx: &mut Context<'_>) -> Option<bool> {
+    match value {
+        Expression::BoolValue(value, _) => Some(*value),
+        _ => coerce::boolean(value, ctx.diagnostics),
+    }
+}
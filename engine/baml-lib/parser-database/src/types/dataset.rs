@@ -0,0 +1,280 @@
+use std::path::Path;
+
+use baml_types::{StringOr, UnresolvedValue};
+use indexmap::IndexMap;
+use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, SourceFile, Span};
+
+use crate::context::Context;
+
+use super::{TestDataset, TestDatasetRow};
+
+/// Reads the `dataset { path ..., args { ... } }` property of a test case and
+/// the rows of the data file it points to.
+pub(crate) fn visit_dataset(
+    value: UnresolvedValue<Span>,
+    ctx: &mut Context<'_>,
+) -> Option<TestDataset> {
+    let (options, span) = match value {
+        UnresolvedValue::<Span>::Map(options, span) => (options, span),
+        other => {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "`dataset` must be a map",
+                other.meta().clone(),
+            ));
+            return None;
+        }
+    };
+
+    let mut path = None;
+    let mut columns = None;
+    for (name, (name_span, value)) in options {
+        match name.as_str() {
+            "path" => match value {
+                UnresolvedValue::<Span>::String(StringOr::Value(p), span) => path = Some((p, span)),
+                other => ctx.push_error(DatamodelError::new_validation_error(
+                    "`path` must be a string",
+                    other.meta().clone(),
+                )),
+            },
+            "args" => match value {
+                UnresolvedValue::<Span>::Map(kv, _) => {
+                    let mut mapping = IndexMap::new();
+                    for (arg, (_, column)) in kv {
+                        match column {
+                            UnresolvedValue::<Span>::String(StringOr::Value(c), span) => {
+                                mapping.insert(arg, (c, span));
+                            }
+                            other => ctx.push_error(DatamodelError::new_validation_error(
+                                "Each argument must be mapped to the name of a column",
+                                other.meta().clone(),
+                            )),
+                        }
+                    }
+                    columns = Some(mapping);
+                }
+                other => ctx.push_error(DatamodelError::new_validation_error(
+                    "`args` must be a map",
+                    other.meta().clone(),
+                )),
+            },
+            _ => ctx.push_error(DatamodelError::new_property_not_known_error(
+                &name,
+                name_span,
+                ["path", "args"].to_vec(),
+            )),
+        }
+    }
+
+    let (Some(path), Some(columns)) = (path, columns) else {
+        ctx.push_error(DatamodelError::new_validation_error(
+            "`dataset` must have a `path` and an `args` mapping",
+            span,
+        ));
+        return None;
+    };
+
+    // Generated clients don't include data files, so a missing one only
+    // leaves the test without cases.
+    let Some(file) = find_data_file(ctx, &path.0) else {
+        ctx.push_warning(DatamodelWarning::new(
+            format!("Data file `{}` not found in baml_src", path.0),
+            path.1.clone(),
+        ));
+        return Some(TestDataset {
+            path,
+            columns,
+            rows: vec![],
+        });
+    };
+    let rows = match file.path_buf().extension().and_then(|ext| ext.to_str()) {
+        Some("jsonl") => jsonl_rows(&file, ctx),
+        Some("csv") => csv_rows(&file, ctx),
+        _ => {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "Data files must be `.jsonl` or `.csv` files",
+                path.1.clone(),
+            ));
+            return None;
+        }
+    };
+
+    // Every row must have the columns the arguments are read from. Only the
+    // first row without a column is reported, datasets can be long.
+    for (column, column_span) in columns.values() {
+        if let Some(row) = rows.iter().find(|row| !row.values.contains_key(column)) {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Row {} of `{}` has no column `{column}`", row.index, path.0),
+                column_span.clone(),
+            ));
+        }
+    }
+
+    Some(TestDataset {
+        path,
+        columns,
+        rows,
+    })
+}
+
+/// Data file paths are relative to `baml_src`.
+fn find_data_file(ctx: &Context<'_>, path: &str) -> Option<SourceFile> {
+    let root = &ctx.diagnostics.root_path;
+    let path = Path::new(path);
+    ctx.data_files
+        .iter()
+        .find(|file| {
+            let file_path = file.path_buf();
+            file_path == &root.join(path) || file_path.strip_prefix(root).ok() == Some(path)
+        })
+        .cloned()
+}
+
+/// Every non-empty line of a JSONL file is a JSON object.
+fn jsonl_rows(file: &SourceFile, ctx: &mut Context<'_>) -> Vec<TestDatasetRow> {
+    let mut rows = vec![];
+    let mut start = 0;
+    for line in file.as_str().split_inclusive('\n') {
+        let end = start + line.trim_end().len();
+        let span = Span::new(file.clone(), start, end);
+        start += line.len();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(serde_json::Value::Object(object)) => rows.push(TestDatasetRow {
+                index: rows.len(),
+                values: object
+                    .into_iter()
+                    .map(|(k, v)| (k, json_to_value(v, &span)))
+                    .collect(),
+                span,
+            }),
+            Ok(_) => ctx.push_error(DatamodelError::new_validation_error(
+                "Each line of a JSONL data file must be a JSON object",
+                span,
+            )),
+            Err(e) => ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Invalid JSON: {e}"),
+                span,
+            )),
+        }
+    }
+    rows
+}
+
+fn json_to_value(value: serde_json::Value, span: &Span) -> UnresolvedValue<Span> {
+    match value {
+        serde_json::Value::Null => UnresolvedValue::Null(span.clone()),
+        serde_json::Value::Bool(b) => UnresolvedValue::Bool(b, span.clone()),
+        serde_json::Value::Number(n) => UnresolvedValue::Numeric(n.to_string(), span.clone()),
+        serde_json::Value::String(s) => UnresolvedValue::String(StringOr::Value(s), span.clone()),
+        serde_json::Value::Array(items) => UnresolvedValue::Array(
+            items.into_iter().map(|v| json_to_value(v, span)).collect(),
+            span.clone(),
+        ),
+        serde_json::Value::Object(object) => UnresolvedValue::Map(
+            object
+                .into_iter()
+                .map(|(k, v)| (k, (span.clone(), json_to_value(v, span))))
+                .collect(),
+            span.clone(),
+        ),
+    }
+}
+
+/// The first record of a CSV file names the columns. Every value is a string.
+fn csv_rows(file: &SourceFile, ctx: &mut Context<'_>) -> Vec<TestDatasetRow> {
+    let mut records = csv_records(file.as_str()).into_iter();
+    let Some((header, _)) = records.next() else {
+        return vec![];
+    };
+
+    let mut rows = vec![];
+    for (record, (start, end)) in records {
+        let span = Span::new(file.clone(), start, end);
+        if record.len() != header.len() {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Expected {} values as in the header, found {}",
+                    header.len(),
+                    record.len()
+                ),
+                span,
+            ));
+            continue;
+        }
+        rows.push(TestDatasetRow {
+            index: rows.len(),
+            values: header
+                .iter()
+                .cloned()
+                .zip(
+                    record
+                        .into_iter()
+                        .map(|v| UnresolvedValue::String(StringOr::Value(v), span.clone())),
+                )
+                .collect(),
+            span,
+        });
+    }
+    rows
+}
+
+/// Splits CSV text into records and their byte ranges. Values may be quoted
+/// with `"`, in which case they can contain commas, newlines and `""`.
+fn csv_records(text: &str) -> Vec<(Vec<String>, (usize, usize))> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut start = 0;
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek().map(|(_, c)| *c) == Some('"') => {
+                chars.next();
+                value.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => value.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut value)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut value));
+                // Blank lines are not records.
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push((std::mem::take(&mut record), (start, idx)));
+                }
+                record.clear();
+                start = idx + 1;
+            }
+            (false, c) => value.push(c),
+        }
+    }
+    if !value.is_empty() || !record.is_empty() {
+        record.push(value);
+        records.push((record, (start, text.len())));
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_records() {
+        let records = csv_records("text,expected\n\"a, \"\"b\"\"\",c\n\nd,\"e\nf\"");
+        assert_eq!(
+            records.into_iter().map(|(r, _)| r).collect::<Vec<_>>(),
+            vec![
+                vec!["text".to_string(), "expected".to_string()],
+                vec!["a, \"b\"".to_string(), "c".to_string()],
+                vec!["d".to_string(), "e\nf".to_string()],
+            ]
+        );
+    }
+}
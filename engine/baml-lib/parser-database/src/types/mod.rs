@@ -8,7 +8,7 @@ use crate::types::configurations::visit_test_case;
 use crate::{context::Context, DatamodelError};
 
 use baml_types::Constraint;
-use baml_types::{StringOr, UnresolvedValue};
+use baml_types::{JinjaExpression, ParserConfig, StringOr, UnresolvedValue};
 use indexmap::IndexMap;
 use internal_baml_diagnostics::{Diagnostics, Span};
 use internal_baml_prompt_parser::ast::{ChatBlock, PrinterBlock, Variable};
@@ -18,6 +18,8 @@ use internal_baml_schema_ast::ast::{
 use internal_llm_client::{ClientProvider, PropertyHandler, UnresolvedClientProperty};
 
 mod configurations;
+mod dataset;
+mod pipeline;
 mod prompt;
 mod types;
 
@@ -25,7 +27,8 @@ pub use crate::attributes::Attributes;
 pub(crate) use types::EnumAttributes;
 pub(crate) use types::*;
 
-use self::configurations::visit_retry_policy;
+use self::configurations::{visit_parser_config, visit_prompt_variants, visit_retry_policy};
+use self::pipeline::visit_steps;
 
 pub(super) fn resolve_types(ctx: &mut Context<'_>) {
     for (top_id, top) in ctx.ast.iter_tops() {
@@ -148,6 +151,30 @@ pub struct TestCase {
     pub args: IndexMap<String, (Span, UnresolvedValue<Span>)>,
     pub args_field_span: Span,
     pub constraints: Vec<(Constraint, Span, Span)>,
+    /// Declared with `dataset { ... }`. The test case runs once per row.
+    pub dataset: Option<TestDataset>,
+}
+
+/// A data file that a test case reads the arguments of each run from.
+#[derive(Debug)]
+pub struct TestDataset {
+    /// Relative to `baml_src`.
+    pub path: (String, Span),
+    /// Maps each argument to the column it is read from.
+    pub columns: IndexMap<String, (String, Span)>,
+    /// The rows of the data file, read when the schema is loaded.
+    pub rows: Vec<TestDatasetRow>,
+}
+
+/// One row of a [`TestDataset`], i.e. the arguments of one run of the test.
+#[derive(Debug)]
+pub struct TestDatasetRow {
+    /// Position of the row in the data file, starting at 0.
+    pub index: usize,
+    /// The values of the row by column. Their spans point into the data file.
+    pub values: IndexMap<String, UnresolvedValue<Span>>,
+    /// The span of the row in the data file.
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -212,11 +239,51 @@ pub struct ExponentialBackoffStrategy {
     pub max_delay_ms: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct FunctionType {
     pub dependencies: (HashSet<String>, HashSet<String>),
     pub prompt: Option<RawString>,
     pub client: Option<(String, Span)>,
+    /// Set with `@@parser({ ... })`.
+    pub parser_config: ParserConfig,
+    /// Declared with `@@variant(name, { ... })`.
+    pub variants: Vec<PromptVariant>,
+    /// Set for functions that call other functions with `steps { ... }`
+    /// instead of having a prompt and client.
+    pub steps: Option<Vec<PipelineStep>>,
+}
+
+/// One step of a function declared with `steps { ... }`.
+#[derive(Debug)]
+pub struct PipelineStep {
+    /// The name other steps and the function's output refer to the step by.
+    pub name: (String, Span),
+    /// The function the step calls.
+    pub function: (String, Span),
+    /// When set, the function is called once per element of this list, with
+    /// the element bound to `item`.
+    pub for_each: Option<(JinjaExpression, Span)>,
+    /// Arguments of the call. Expressions can read the parameters of the
+    /// function, the outputs of earlier steps and `item`.
+    pub args: IndexMap<String, (Span, UnresolvedValue<Span>)>,
+    /// The span of the whole step.
+    pub span: Span,
+}
+
+/// A named alternative to the prompt and client of a function.
+#[derive(Debug, Clone)]
+pub struct PromptVariant {
+    /// The name callers pick the variant by.
+    pub name: (String, Span),
+    /// Falls back to the prompt of the function.
+    pub prompt: Option<RawString>,
+    /// Falls back to the client of the function.
+    pub client: Option<(String, Span)>,
+    /// The percentage of calls that use this variant. Calls not taken by any
+    /// variant use the function's own prompt and client.
+    pub weight: u32,
+    /// The span of the `@@variant(...)` attribute.
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -373,6 +440,7 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
 
     let mut prompt = None;
     let mut client = None;
+    let mut steps = None;
     function
         .iter_fields()
         .for_each(|(_idx, field)| match field.name() {
@@ -389,12 +457,48 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
                     None => None,
                 }
             }
+            "steps" => {
+                steps = match &field.expr {
+                    Some(val) => visit_steps(val, ctx),
+                    None => None,
+                }
+            }
             config => ctx.push_error(DatamodelError::new_validation_error(
                 &format!("Unknown field `{}` in function", config),
                 field.span().clone(),
             )),
         });
 
+    let parser_config = visit_parser_config(function, ctx);
+    let variants = visit_prompt_variants(function, ctx);
+
+    if let Some(steps) = steps {
+        if prompt.is_some() || client.is_some() {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "A function with `steps` cannot have a `prompt` or `client`, its steps call other functions",
+                function.identifier().span().clone(),
+            ));
+        }
+        if let Some(variant) = variants.first() {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "Functions with `steps` cannot have variants",
+                variant.name.1.clone(),
+            ));
+        }
+        ctx.types.function.insert(
+            idx,
+            FunctionType {
+                dependencies: (input_deps, output_deps),
+                prompt: None,
+                client: None,
+                parser_config,
+                variants: vec![],
+                steps: Some(steps),
+            },
+        );
+        return;
+    }
+
     match (prompt, client) {
         (Some(prompt), Some(client)) => {
             ctx.types.function.insert(
@@ -403,9 +507,11 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
                     dependencies: (input_deps.clone(), output_deps),
                     prompt: Some(prompt.clone()),
                     client: Some(client),
+                    parser_config,
+                    variants,
+                    steps: None,
                 },
             );
-
             ctx.types.template_strings.insert(
                 either::Right(idx),
                 TemplateStringProperties {
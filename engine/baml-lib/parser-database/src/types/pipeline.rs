@@ -0,0 +1,190 @@
+use baml_types::{JinjaExpression, StringOr, UnresolvedValue};
+use indexmap::IndexMap;
+use internal_baml_diagnostics::{DatamodelError, Span};
+use internal_baml_schema_ast::ast::{Expression, Identifier, WithName, WithSpan};
+
+use crate::context::Context;
+
+use super::PipelineStep;
+
+/// Reads the `steps { name { call ..., for_each ..., args { ... } } }` field
+/// of a function.
+pub(crate) fn visit_steps(expr: &Expression, ctx: &mut Context<'_>) -> Option<Vec<PipelineStep>> {
+    let Some((entries, span)) = expr.as_map() else {
+        ctx.push_error(DatamodelError::new_validation_error(
+            "`steps` must be a map of step names to calls, e.g. `steps { summary { call Summarize, args { text text } } }`",
+            expr.span().clone(),
+        ));
+        return None;
+    };
+    if entries.is_empty() {
+        ctx.push_error(DatamodelError::new_validation_error(
+            "`steps` must have at least one step",
+            span.clone(),
+        ));
+        return None;
+    }
+
+    let mut steps: Vec<PipelineStep> = vec![];
+    for (key, value) in entries {
+        let Some((name, name_span)) = key.as_string_value() else {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "The name of a step must be an identifier",
+                key.span().clone(),
+            ));
+            continue;
+        };
+        if steps.iter().any(|s| s.name.0 == name) {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Duplicate step `{name}`"),
+                name_span.clone(),
+            ));
+            continue;
+        }
+        if let Some(step) = visit_step((name, name_span), value, ctx) {
+            steps.push(step);
+        }
+    }
+
+    Some(steps)
+}
+
+fn visit_step(
+    (name, name_span): (&str, &Span),
+    value: &Expression,
+    ctx: &mut Context<'_>,
+) -> Option<PipelineStep> {
+    let Some((fields, span)) = value.as_map() else {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!(
+                "Step `{name}` must be a map, e.g. `{name} {{ call MyFunction, args {{ ... }} }}`"
+            ),
+            value.span().clone(),
+        ));
+        return None;
+    };
+
+    let mut function = None;
+    let mut for_each = None;
+    let mut args = IndexMap::new();
+    for (key, value) in fields {
+        let Some((field, field_span)) = key.as_string_value() else {
+            ctx.push_error(DatamodelError::new_validation_error(
+                "Expected a field name",
+                key.span().clone(),
+            ));
+            continue;
+        };
+        match field {
+            "call" => match value.as_identifer() {
+                Some(idn) => function = Some((idn.name().to_string(), idn.span().clone())),
+                None => ctx.push_error(DatamodelError::new_validation_error(
+                    "`call` must be the name of a function",
+                    value.span().clone(),
+                )),
+            },
+            "for_each" => match reference(value) {
+                Some(expr) => for_each = Some((expr, value.span().clone())),
+                None => ctx.push_error(DatamodelError::new_validation_error(
+                    "`for_each` must be a parameter, an earlier step or an expression like `{{ tickets[:10] }}`",
+                    value.span().clone(),
+                )),
+            },
+            "args" => match value.as_map() {
+                Some((kv, _)) => {
+                    for (arg, arg_value) in kv {
+                        let Some((arg, arg_span)) = arg.as_string_value() else {
+                            ctx.push_error(DatamodelError::new_validation_error(
+                                "Expected the name of a parameter",
+                                arg.span().clone(),
+                            ));
+                            continue;
+                        };
+                        if let Some(v) = to_arg_value(arg_value, ctx) {
+                            args.insert(arg.to_string(), (arg_span.clone(), v));
+                        }
+                    }
+                }
+                None => ctx.push_error(DatamodelError::new_validation_error(
+                    "`args` must be a map of parameter names to values",
+                    value.span().clone(),
+                )),
+            },
+            _ => ctx.push_error(DatamodelError::new_property_not_known_error(
+                field,
+                field_span.clone(),
+                ["call", "for_each", "args"].to_vec(),
+            )),
+        }
+    }
+
+    let Some(function) = function else {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!("Step `{name}` must `call` a function"),
+            span.clone(),
+        ));
+        return None;
+    };
+
+    Some(PipelineStep {
+        name: (name.to_string(), name_span.clone()),
+        function,
+        for_each,
+        args,
+        span: span.clone(),
+    })
+}
+
+/// A bare name like `tickets` or `ticket.subject`, or a `{{ ... }}`
+/// expression.
+fn reference(value: &Expression) -> Option<JinjaExpression> {
+    match value {
+        Expression::Identifier(Identifier::Local(name, _)) => {
+            Some(JinjaExpression(name.to_string()))
+        }
+        Expression::Identifier(Identifier::Ref(idn, _)) => {
+            Some(JinjaExpression(idn.full_name.to_string()))
+        }
+        Expression::JinjaExpressionValue(expr, _) => Some(expr.clone()),
+        _ => None,
+    }
+}
+
+/// Arguments are constants, except for names and `{{ ... }}` expressions,
+/// which are evaluated when the step runs.
+fn to_arg_value(value: &Expression, ctx: &mut Context<'_>) -> Option<UnresolvedValue<Span>> {
+    match value {
+        Expression::Identifier(Identifier::Local(name, _))
+            if matches!(name.as_str(), "true" | "false" | "null") =>
+        {
+            value.to_unresolved_value(ctx.diagnostics)
+        }
+        Expression::Array(values, span) => Some(UnresolvedValue::Array(
+            values.iter().filter_map(|v| to_arg_value(v, ctx)).collect(),
+            span.clone(),
+        )),
+        Expression::Map(kv, span) => {
+            let mut map = IndexMap::new();
+            for (k, v) in kv {
+                let Some((key, key_span)) = k.as_string_value() else {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        "Expected a key",
+                        k.span().clone(),
+                    ));
+                    continue;
+                };
+                if let Some(v) = to_arg_value(v, ctx) {
+                    map.insert(key.to_string(), (key_span.clone(), v));
+                }
+            }
+            Some(UnresolvedValue::Map(map, span.clone()))
+        }
+        _ => match reference(value) {
+            Some(expr) => Some(UnresolvedValue::String(
+                StringOr::JinjaExpression(expr),
+                value.span().clone(),
+            )),
+            None => value.to_unresolved_value(ctx.diagnostics),
+        },
+    }
+}
@@ -1,11 +1,11 @@
 use either::Either;
-use internal_baml_diagnostics::DatamodelError;
+use internal_baml_diagnostics::{DatamodelError, Span};
 use internal_baml_schema_ast::ast::{ArgumentId, Identifier, WithIdentifier, WithSpan};
 use internal_llm_client::ClientSpec;
 
 use crate::{
     ast::{self, WithName},
-    types::FunctionType,
+    types::{FunctionType, PipelineStep, PromptVariant},
 };
 
 use super::{ClassWalker, ConfigurationWalker, EnumWalker, Walker};
@@ -119,6 +119,33 @@ impl<'db> FunctionWalker<'db> {
             .template
             .as_str()
     }
+
+    /// The steps of a function that calls other functions instead of having
+    /// a prompt.
+    pub fn steps(self) -> Option<&'db [PipelineStep]> {
+        self.metadata().steps.as_deref()
+    }
+
+    /// Declares the function to Jinja, so that test constraints can call it
+    /// as a judge.
+    pub fn add_to_types(self, types: &mut internal_baml_jinja_types::PredefinedTypes) {
+        let ret_type = match self.ast_function().output() {
+            Some(output) => self.db.to_jinja_type(&output.field_type),
+            None => internal_baml_jinja_types::Type::Unknown,
+        };
+        let mut params = vec![];
+
+        if let Some(p) = self.ast_function().input() {
+            p.args.iter().for_each(|(name, t)| {
+                params.push((
+                    name.name().to_string(),
+                    self.db.to_jinja_type(&t.field_type),
+                ))
+            });
+        }
+
+        types.add_function(self.name(), ret_type, params);
+    }
 }
 
 // impl AstClientSpec {
@@ -140,6 +167,31 @@ impl<'db> FunctionWalker<'db> {
                 self.span().clone(),
             ));
         };
+        self.resolve_client_spec(client)
+    }
+
+    /// Returns the client spec for a variant of the function, which is the
+    /// function's own client unless the variant sets one.
+    pub fn variant_client_spec(
+        self,
+        variant: &PromptVariant,
+    ) -> Result<ClientSpec, DatamodelError> {
+        match variant.client.as_ref() {
+            Some(client) => self.resolve_client_spec(client),
+            None => self.client_spec(),
+        }
+    }
+
+    /// The prompt for a variant of the function, which is the function's own
+    /// prompt unless the variant sets one.
+    pub fn variant_jinja_prompt(self, variant: &'db PromptVariant) -> &'db str {
+        match variant.prompt.as_ref() {
+            Some(prompt) => prompt.value(),
+            None => self.jinja_prompt(),
+        }
+    }
+
+    fn resolve_client_spec(self, client: &(String, Span)) -> Result<ClientSpec, DatamodelError> {
         match ClientSpec::new_from_id(client.0.as_str()) {
             Ok(ClientSpec::Named(name)) => {
                 if let Some(client) = self.db.find_client(&name) {
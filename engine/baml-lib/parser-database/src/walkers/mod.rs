@@ -304,7 +304,8 @@ impl<'db> crate::ParserDatabase {
                     TypeValue::Float => Type::Float,
                     TypeValue::Bool => Type::Bool,
                     TypeValue::Null => Type::None,
-                    TypeValue::Media(_) => Type::Unknown,
+                    TypeValue::Media(_) | TypeValue::Message => Type::Unknown,
+                    TypeValue::Temporal(_) => Type::String,
                 };
                 if arity.is_optional() || matches!(t, Type::None) {
                     t = Type::None | t;
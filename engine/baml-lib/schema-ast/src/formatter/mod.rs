@@ -1,10 +1,3 @@
-use std::{
-    borrow::BorrowMut,
-    cell::{RefCell, RefMut},
-    rc::Rc,
-    sync::Arc,
-};
-
 use crate::parser::{BAMLParser, Rule};
 use anyhow::{anyhow, Result};
 use pest::{
@@ -18,6 +11,10 @@ pub struct FormatOptions {
     pub fail_on_unhandled_rule: bool,
 }
 
+/// Lines longer than this are broken up where the formatter is allowed to
+/// choose (argument lists and arrays).
+const LINE_WIDTH: usize = 100;
+
 pub fn format_schema(source: &str, format_options: FormatOptions) -> Result<String> {
     let mut schema = BAMLParser::parse(Rule::schema, source)?;
     let schema_pair = schema.next().ok_or(anyhow!("Expected a schema"))?;
@@ -32,9 +29,21 @@ pub fn format_schema(source: &str, format_options: FormatOptions) -> Result<Stri
 
     let doc = formatter.schema_to_doc(schema_pair.into_inner())?;
     let mut w = Vec::new();
-    doc.render(10, &mut w)
+    doc.render(LINE_WIDTH, &mut w)
         .map_err(|_| anyhow!("Failed to render doc"))?;
-    Ok(String::from_utf8(w).map_err(|_| anyhow!("Failed to convert to string"))?)
+    let rendered = String::from_utf8(w).map_err(|_| anyhow!("Failed to convert to string"))?;
+
+    // Blank lines inside nested blocks are rendered with the indentation of
+    // the block, strip it so that we never emit whitespace-only lines.
+    let mut formatted = rendered
+        .lines()
+        .map(|line| if line.trim().is_empty() { "" } else { line })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !formatted.is_empty() {
+        formatted.push('\n');
+    }
+    Ok(formatted)
 }
 
 macro_rules! next_pair {
@@ -72,6 +81,30 @@ macro_rules! next_pair {
     }};
 }
 
+/// A formatted element of a block, along with the source lines it spans.
+///
+/// The line numbers are used to preserve (at most one) blank line between
+/// consecutive elements, and to keep attributes and comments on the line the
+/// user put them on.
+struct Item<'a> {
+    first_line: usize,
+    last_line: usize,
+    is_comment: bool,
+    doc: RcDoc<'a, ()>,
+}
+
+impl<'a> Item<'a> {
+    fn new(pair: &Pair<'a, Rule>, doc: RcDoc<'a, ()>) -> Self {
+        let (first_line, last_line) = line_range(pair);
+        Self {
+            first_line,
+            last_line,
+            is_comment: matches!(pair.as_rule(), Rule::comment_block | Rule::trailing_comment),
+            doc,
+        }
+    }
+}
+
 struct Formatter {
     indent_width: isize,
     fail_on_unhandled_rule: bool,
@@ -79,22 +112,33 @@ struct Formatter {
 
 impl Formatter {
     fn schema_to_doc<'a>(&self, mut pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
-        let mut doc = RcDoc::nil();
+        let mut items = vec![];
 
         for pair in &mut pairs {
-            match pair.as_rule() {
-                Rule::type_expression_block => {
-                    doc = doc.append(self.type_expression_block_to_doc(pair.into_inner())?);
+            let doc = match pair.as_rule() {
+                Rule::type_expression_block => self.or_verbatim(pair.clone(), |pair| {
+                    self.type_expression_block_to_doc(pair.into_inner())
+                })?,
+                Rule::value_expression_block => self.or_verbatim(pair.clone(), |pair| {
+                    self.value_expression_block_to_doc(pair.into_inner())
+                })?,
+                Rule::template_declaration => self.or_verbatim(pair.clone(), |pair| {
+                    self.template_declaration_to_doc(pair.into_inner())
+                })?,
+                Rule::comment_block => self.comment_block_to_doc(pair.clone()),
+                Rule::type_alias | Rule::raw_string_literal | Rule::CATCH_ALL => {
+                    pair_to_doc_text(pair.clone())
                 }
-                Rule::EOI => {
+                Rule::empty_lines | Rule::EOI => {
                     // skip
+                    continue;
                 }
-                _ => {
-                    doc = doc.append(self.unhandled_rule_to_doc(pair)?);
-                }
-            }
+                _ => self.unhandled_rule_to_doc(pair.clone())?,
+            };
+            items.push(Item::new(&pair, doc));
         }
-        Ok(doc)
+
+        Ok(join_items(items))
     }
 
     fn type_expression_block_to_doc<'a>(
@@ -103,91 +147,103 @@ impl Formatter {
     ) -> Result<RcDoc<'a, ()>> {
         let class_or_enum = next_pair!(pairs, Rule::identifier)?;
         let ident = next_pair!(pairs, Rule::identifier)?;
-        next_pair!(pairs, Rule::named_argument_list, optional);
+        let args = next_pair!(pairs, Rule::named_argument_list, optional);
         next_pair!(pairs, Rule::BLOCK_OPEN)?;
         let contents = next_pair!(pairs, Rule::type_expression_contents)?;
         next_pair!(pairs, Rule::BLOCK_CLOSE)?;
 
-        Ok(RcDoc::nil()
+        let mut header = RcDoc::nil()
             .append(pair_to_doc_text(class_or_enum))
             .append(RcDoc::space())
-            .append(pair_to_doc_text(ident))
-            .append(RcDoc::space())
-            .append(RcDoc::text("{"))
-            .append(
-                self.type_expression_contents_to_doc(contents.into_inner())?
-                    .nest(self.indent_width)
-                    .group(),
-            )
-            .append(RcDoc::text("}")))
+            .append(pair_to_doc_text(ident));
+        if let Some(args) = args {
+            header = header.append(self.named_argument_list_to_doc(args.into_inner())?);
+        }
+
+        Ok(self.block_to_doc(
+            header,
+            self.type_expression_contents_to_items(contents.into_inner())?,
+        ))
     }
 
-    fn type_expression_contents_to_doc<'a>(
+    fn type_expression_contents_to_items<'a>(
         &self,
         mut pairs: Pairs<'a, Rule>,
-    ) -> Result<RcDoc<'a, ()>> {
-        let mut content_docs = vec![];
+    ) -> Result<Vec<Item<'a>>> {
+        let mut items = vec![];
 
         for pair in &mut pairs {
-            match pair.as_rule() {
-                Rule::type_expression => {
-                    content_docs.push(self.type_expression_to_doc(pair.into_inner())?);
-                }
-                Rule::block_attribute => {
-                    content_docs.push(pair_to_doc_text(pair));
-                }
-                Rule::comment_block => {
-                    content_docs.push(pair_to_doc_text(pair));
-                }
+            let doc = match pair.as_rule() {
+                Rule::type_expression => self.type_expression_to_doc(pair.clone().into_inner())?,
+                Rule::block_attribute => self.attribute_to_doc(pair.clone())?,
+                Rule::BLOCK_LEVEL_CATCH_ALL => pair_to_doc_text(pair.clone()),
+                Rule::comment_block => self.comment_block_to_doc(pair.clone()),
                 Rule::empty_lines => {
                     // skip
+                    continue;
                 }
-                _ => {
-                    content_docs.push(self.unhandled_rule_to_doc(pair)?);
-                }
-            }
+                _ => self.unhandled_rule_to_doc(pair.clone())?,
+            };
+            items.push(Item::new(&pair, doc));
         }
 
-        let doc = if content_docs.len() > 0 {
-            content_docs
-                .into_iter()
-                .fold(RcDoc::hardline(), |acc, doc| {
-                    acc.append(doc).append(RcDoc::hardline())
-                })
-        } else {
-            RcDoc::nil()
-        };
-
-        Ok(doc)
+        Ok(items)
     }
 
     fn type_expression_to_doc<'a>(&self, mut pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
         let ident = next_pair!(pairs, Rule::identifier)?;
-        let field_type_chain = next_pair!(pairs, Rule::field_type_chain)?;
-
-        let mut doc = RcDoc::nil()
-            .append(pair_to_doc_text(ident))
-            .append(RcDoc::space())
-            .append(self.field_type_chain_to_doc(field_type_chain.into_inner())?);
 
+        let mut parts = vec![Item::new(&ident, pair_to_doc_text(ident.clone()))];
         for pair in pairs {
             match pair.as_rule() {
-                Rule::NEWLINE => {
-                    // skip
-                }
+                Rule::field_type_chain => self.field_type_chain_to_items(pair, &mut parts)?,
                 Rule::field_attribute => {
-                    doc = doc.append(pair_to_doc_text(pair).nest(self.indent_width).group());
+                    parts.push(Item::new(&pair, self.attribute_to_doc(pair.clone())?));
                 }
                 Rule::trailing_comment => {
-                    doc = doc.append(pair_to_doc_text(pair).nest(self.indent_width).group());
+                    parts.push(Item::new(&pair, pair_to_doc_text(pair.clone())));
                 }
                 _ => {
-                    doc = doc.append(self.unhandled_rule_to_doc(pair)?);
+                    parts.push(Item::new(&pair, self.unhandled_rule_to_doc(pair.clone())?));
                 }
             }
         }
 
-        Ok(doc)
+        Ok(self.join_parts(parts))
+    }
+
+    /// Flattens a field type chain into its types, operators, attributes and
+    /// trailing comments, so that they can be laid out relative to each other.
+    fn field_type_chain_to_items<'a>(
+        &self,
+        pair: Pair<'a, Rule>,
+        parts: &mut Vec<Item<'a>>,
+    ) -> Result<()> {
+        for pair in pair.into_inner() {
+            match pair.as_rule() {
+                Rule::field_type_with_attr => {
+                    for pair in pair.into_inner() {
+                        let doc = match pair.as_rule() {
+                            Rule::field_type => {
+                                self.field_type_to_doc(pair.clone().into_inner())?
+                            }
+                            Rule::field_attribute => self.attribute_to_doc(pair.clone())?,
+                            Rule::trailing_comment => pair_to_doc_text(pair.clone()),
+                            _ => self.unhandled_rule_to_doc(pair.clone())?,
+                        };
+                        parts.push(Item::new(&pair, doc));
+                    }
+                }
+                Rule::field_operator => {
+                    parts.push(Item::new(&pair, RcDoc::text("|")));
+                }
+                _ => {
+                    parts.push(Item::new(&pair, self.unhandled_rule_to_doc(pair.clone())?));
+                }
+            }
+        }
+
+        Ok(())
     }
 
     fn field_type_chain_to_doc<'a>(&self, pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
@@ -220,7 +276,7 @@ impl Formatter {
                 Rule::field_type => {
                     docs.push(self.field_type_to_doc(pair.into_inner())?);
                 }
-                Rule::field_attribute | Rule::trailing_comment => {
+                Rule::field_attribute => {
                     docs.push(pair_to_doc_text(pair));
                 }
                 _ => {
@@ -257,7 +313,7 @@ impl Formatter {
                     docs.push(RcDoc::text("|"));
                     docs.push(RcDoc::space());
                 }
-                Rule::base_type_with_attr | Rule::non_union => {
+                Rule::base_type_with_attr | Rule::non_union | Rule::optional_token => {
                     docs.push(pair_to_doc_text(pair));
                 }
                 _ => {
@@ -269,6 +325,507 @@ impl Formatter {
         Ok(())
     }
 
+    fn value_expression_block_to_doc<'a>(
+        &self,
+        mut pairs: Pairs<'a, Rule>,
+    ) -> Result<RcDoc<'a, ()>> {
+        let keyword = next_pair!(pairs, Rule::value_expression_keyword)?;
+        let ident = next_pair!(pairs, Rule::identifier)?;
+        let args = next_pair!(pairs, Rule::named_argument_list, optional);
+        let arrow = next_pair!(pairs, Rule::ARROW, optional);
+        let return_type = next_pair!(pairs, Rule::field_type_chain, optional);
+        let spacer = next_pair!(pairs, Rule::SPACER_TEXT)?;
+        next_pair!(pairs, Rule::BLOCK_OPEN)?;
+        let contents = next_pair!(pairs, Rule::value_expression_contents)?;
+        next_pair!(pairs, Rule::BLOCK_CLOSE)?;
+
+        // Comments between the signature and the opening brace have no
+        // obvious home, leave such blocks untouched.
+        if !spacer.as_str().trim().is_empty()
+            || arrow.as_ref().is_some_and(|a| a.as_str().trim() != "->")
+        {
+            return Err(anyhow!("Comments in block signatures are not supported"));
+        }
+
+        let mut header = RcDoc::nil()
+            .append(pair_to_doc_text(keyword))
+            .append(RcDoc::space())
+            .append(pair_to_doc_text(ident));
+        if let Some(args) = args {
+            header = header.append(self.named_argument_list_to_doc(args.into_inner())?);
+        }
+        if arrow.is_some() {
+            header = header.append(RcDoc::text(" ->"));
+        }
+        if let Some(return_type) = return_type {
+            header = header
+                .append(RcDoc::space())
+                .append(self.field_type_chain_to_doc(return_type.into_inner())?);
+        }
+
+        Ok(self.block_to_doc(
+            header,
+            self.value_expression_contents_to_items(contents.into_inner())?,
+        ))
+    }
+
+    fn value_expression_contents_to_items<'a>(
+        &self,
+        mut pairs: Pairs<'a, Rule>,
+    ) -> Result<Vec<Item<'a>>> {
+        let mut items = vec![];
+
+        for pair in &mut pairs {
+            let doc = match pair.as_rule() {
+                Rule::value_expression => {
+                    self.value_expression_to_doc(pair.clone().into_inner())?
+                }
+                Rule::block_attribute => self.attribute_to_doc(pair.clone())?,
+                Rule::BLOCK_LEVEL_CATCH_ALL => pair_to_doc_text(pair.clone()),
+                Rule::comment_block => self.comment_block_to_doc(pair.clone()),
+                Rule::empty_lines => {
+                    // skip
+                    continue;
+                }
+                _ => self.unhandled_rule_to_doc(pair.clone())?,
+            };
+            items.push(Item::new(&pair, doc));
+        }
+
+        Ok(items)
+    }
+
+    fn value_expression_to_doc<'a>(&self, mut pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let ident = next_pair!(pairs, Rule::identifier)?;
+
+        let mut parts = vec![Item::new(&ident, pair_to_doc_text(ident.clone()))];
+        for pair in pairs {
+            let doc = match pair.as_rule() {
+                Rule::expression => self.expression_to_doc(pair.clone())?,
+                Rule::field_attribute => self.attribute_to_doc(pair.clone())?,
+                Rule::trailing_comment => pair_to_doc_text(pair.clone()),
+                _ => self.unhandled_rule_to_doc(pair.clone())?,
+            };
+            parts.push(Item::new(&pair, doc));
+        }
+
+        Ok(self.join_parts(parts))
+    }
+
+    fn named_argument_list_to_doc<'a>(&self, pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let mut args = vec![];
+
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::named_argument => {
+                    args.push(self.named_argument_to_doc(pair.into_inner())?);
+                }
+                Rule::SPACER_TEXT => {
+                    if !pair.as_str().trim().is_empty() {
+                        return Err(anyhow!("Comments in argument lists are not supported"));
+                    }
+                }
+                Rule::openParan | Rule::closeParan => {
+                    // skip
+                }
+                _ => {
+                    args.push(self.unhandled_rule_to_doc(pair)?);
+                }
+            }
+        }
+
+        if args.is_empty() {
+            return Ok(RcDoc::text("()"));
+        }
+
+        Ok(RcDoc::text("(")
+            .append(
+                RcDoc::line_()
+                    .append(RcDoc::intersperse(
+                        args,
+                        RcDoc::text(",").append(RcDoc::line()),
+                    ))
+                    .append(RcDoc::text(",").flat_alt(RcDoc::nil()))
+                    .nest(self.indent_width),
+            )
+            .append(RcDoc::line_())
+            .append(RcDoc::text(")"))
+            .group())
+    }
+
+    fn named_argument_to_doc<'a>(&self, mut pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let ident = next_pair!(pairs, Rule::identifier)?;
+        let mut doc = pair_to_doc_text(ident);
+
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::field_type_chain => {
+                    doc = doc
+                        .append(RcDoc::text(": "))
+                        .append(self.field_type_chain_to_doc(pair.into_inner())?);
+                }
+                Rule::colon => {
+                    doc = doc.append(RcDoc::text(":"));
+                }
+                _ => {
+                    doc = doc.append(self.unhandled_rule_to_doc(pair)?);
+                }
+            }
+        }
+
+        Ok(doc)
+    }
+
+    fn template_declaration_to_doc<'a>(&self, mut pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let keyword = next_pair!(pairs, Rule::TEMPLATE_KEYWORD)?;
+        let ident = next_pair!(pairs, Rule::identifier)?;
+        if next_pair!(pairs, Rule::assignment, optional).is_some() {
+            return Err(anyhow!("Template string assignments are not supported"));
+        }
+        let args = next_pair!(pairs, Rule::named_argument_list, optional);
+        let template = next_pair!(pairs, Rule::raw_string_literal)?;
+
+        let mut doc = RcDoc::nil()
+            .append(pair_to_doc_text(keyword))
+            .append(RcDoc::space())
+            .append(pair_to_doc_text(ident));
+        if let Some(args) = args {
+            doc = doc.append(self.named_argument_list_to_doc(args.into_inner())?);
+        }
+
+        Ok(doc
+            .append(RcDoc::space())
+            .append(self.raw_string_literal_to_doc(template)?))
+    }
+
+    fn expression_to_doc<'a>(&self, pair: Pair<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let Some(inner) = pair.clone().into_inner().next() else {
+            return Ok(pair_to_doc_text(pair));
+        };
+
+        match inner.as_rule() {
+            Rule::map_expression => Ok(self.map_expression_to_doc(inner.into_inner())?),
+            Rule::array_expression => Ok(self.array_expression_to_doc(inner.into_inner())?),
+            Rule::string_literal => match inner.clone().into_inner().next() {
+                Some(raw) if raw.as_rule() == Rule::raw_string_literal => {
+                    self.raw_string_literal_to_doc(raw)
+                }
+                _ => Ok(pair_to_doc_text(inner)),
+            },
+            Rule::jinja_expression | Rule::numeric_literal | Rule::identifier => {
+                Ok(pair_to_doc_text(inner))
+            }
+            _ => self.unhandled_rule_to_doc(inner),
+        }
+    }
+
+    fn map_expression_to_doc<'a>(&self, pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let mut items = vec![];
+
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::map_entry => {
+                    let mut parts = vec![];
+                    for pair in pair.into_inner() {
+                        match pair.as_rule() {
+                            Rule::comment_block => {
+                                items.push(Item::new(
+                                    &pair,
+                                    self.comment_block_to_doc(pair.clone()),
+                                ));
+                            }
+                            Rule::empty_lines => {
+                                // skip
+                            }
+                            Rule::map_key | Rule::ENTRY_CATCH_ALL | Rule::trailing_comment => {
+                                parts.push(Item::new(&pair, pair_to_doc_text(pair.clone())));
+                            }
+                            Rule::expression => {
+                                parts.push(Item::new(&pair, self.expression_to_doc(pair.clone())?));
+                            }
+                            _ => {
+                                parts.push(Item::new(
+                                    &pair,
+                                    self.unhandled_rule_to_doc(pair.clone())?,
+                                ));
+                            }
+                        }
+                    }
+                    if let (Some(first), Some(last)) = (parts.first(), parts.last()) {
+                        let (first_line, last_line) = (first.first_line, last.last_line);
+                        items.push(Item {
+                            first_line,
+                            last_line,
+                            is_comment: false,
+                            doc: self.join_parts(parts),
+                        });
+                    }
+                }
+                Rule::comment_block => {
+                    items.push(Item::new(&pair, self.comment_block_to_doc(pair.clone())));
+                }
+                Rule::empty_lines => {
+                    // skip
+                }
+                _ => {
+                    items.push(Item::new(&pair, self.unhandled_rule_to_doc(pair.clone())?));
+                }
+            }
+        }
+
+        if items.is_empty() {
+            return Ok(RcDoc::text("{}"));
+        }
+
+        Ok(RcDoc::text("{")
+            .append(
+                RcDoc::hardline()
+                    .append(join_items(items))
+                    .nest(self.indent_width),
+            )
+            .append(RcDoc::hardline())
+            .append(RcDoc::text("}")))
+    }
+
+    fn array_expression_to_doc<'a>(&self, pairs: Pairs<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        // Each value is paired with whether it still needs a separating comma
+        // when laid out one per line.
+        let mut values: Vec<(Item<'a>, bool)> = vec![];
+        let mut has_comments = false;
+
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::expression => {
+                    values.push((
+                        Item::new(&pair, self.expression_to_doc(pair.clone())?),
+                        true,
+                    ));
+                }
+                Rule::ARRAY_CATCH_ALL => {
+                    values.push((Item::new(&pair, pair_to_doc_text(pair.clone())), true));
+                }
+                Rule::trailing_comment | Rule::comment_block => {
+                    has_comments = true;
+                    let comment = Item::new(&pair, self.comment_block_to_doc(pair.clone()));
+                    match values.pop() {
+                        // A comment on the same line as a value stays after it,
+                        // past the separating comma.
+                        Some((value, true)) if value.last_line == comment.first_line => values
+                            .push((
+                                Item {
+                                    last_line: comment.last_line,
+                                    doc: value.doc.append(RcDoc::text(", ")).append(comment.doc),
+                                    ..value
+                                },
+                                false,
+                            )),
+                        previous => {
+                            values.extend(previous);
+                            values.push((comment, false));
+                        }
+                    }
+                }
+                Rule::empty_lines => {
+                    // skip
+                }
+                _ => {
+                    values.push((
+                        Item::new(&pair, self.unhandled_rule_to_doc(pair.clone())?),
+                        true,
+                    ));
+                }
+            }
+        }
+
+        if values.is_empty() {
+            return Ok(RcDoc::text("[]"));
+        }
+
+        if has_comments {
+            // Comments force one value per line.
+            let items = values
+                .into_iter()
+                .map(|(item, needs_comma)| {
+                    if needs_comma {
+                        Item {
+                            doc: item.doc.append(RcDoc::text(",")),
+                            ..item
+                        }
+                    } else {
+                        item
+                    }
+                })
+                .collect();
+            return Ok(RcDoc::text("[")
+                .append(
+                    RcDoc::hardline()
+                        .append(join_items(items))
+                        .nest(self.indent_width),
+                )
+                .append(RcDoc::hardline())
+                .append(RcDoc::text("]")));
+        }
+
+        Ok(RcDoc::text("[")
+            .append(
+                RcDoc::line_()
+                    .append(RcDoc::intersperse(
+                        values.into_iter().map(|(item, _)| item.doc),
+                        RcDoc::text(",").append(RcDoc::line()),
+                    ))
+                    .append(RcDoc::text(",").flat_alt(RcDoc::nil()))
+                    .nest(self.indent_width),
+            )
+            .append(RcDoc::line_())
+            .append(RcDoc::text("]"))
+            .group())
+    }
+
+    /// Formats `@attribute(...)` and `@@attribute(...)`. Raw string arguments
+    /// are re-indented, other arguments are kept as written.
+    fn attribute_to_doc<'a>(&self, pair: Pair<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let prefix = if pair.as_rule() == Rule::block_attribute {
+            "@@"
+        } else {
+            "@"
+        };
+        let mut pairs = pair.into_inner();
+        let ident = next_pair!(pairs, Rule::identifier)?;
+        let mut doc = RcDoc::text(prefix).append(pair_to_doc_text(ident));
+
+        if let Some(args) = next_pair!(pairs, Rule::arguments_list, optional) {
+            let mut arg_docs = vec![];
+            for arg in args.into_inner() {
+                let raw_string = arg
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .filter(|inner| inner.as_rule() == Rule::string_literal)
+                    .and_then(|inner| inner.into_inner().next())
+                    .filter(|inner| inner.as_rule() == Rule::raw_string_literal);
+                arg_docs.push(match (arg.as_rule(), raw_string) {
+                    (Rule::expression, Some(raw)) => self.raw_string_literal_to_doc(raw)?,
+                    (Rule::expression, None) => pair_to_doc_text(arg),
+                    _ => self.unhandled_rule_to_doc(arg)?,
+                });
+            }
+            doc = doc
+                .append(RcDoc::text("("))
+                .append(RcDoc::intersperse(arg_docs, RcDoc::text(", ")))
+                .append(RcDoc::text(")"));
+        }
+
+        for pair in pairs {
+            doc = doc.append(self.unhandled_rule_to_doc(pair)?);
+        }
+
+        Ok(doc)
+    }
+
+    /// Re-indents multi-line raw strings to one level deeper than the
+    /// enclosing block. Raw strings are dedented when parsed, so this does not
+    /// change their value.
+    fn raw_string_literal_to_doc<'a>(&self, pair: Pair<'a, Rule>) -> Result<RcDoc<'a, ()>> {
+        let text = pair.as_str();
+        let Some(content) = pair.clone().into_inner().find(|p| {
+            matches!(
+                p.as_rule(),
+                Rule::raw_string_literal_content_1
+                    | Rule::raw_string_literal_content_2
+                    | Rule::raw_string_literal_content_3
+                    | Rule::raw_string_literal_content_4
+                    | Rule::raw_string_literal_content_5
+            )
+        }) else {
+            return self.unhandled_rule_to_doc(pair);
+        };
+
+        let value = content.as_str();
+        if !value.contains('\n') {
+            return Ok(RcDoc::text(text));
+        }
+
+        let start = pair.as_span().start();
+        let open = &text[..content.as_span().start() - start];
+        let close = &text[content.as_span().end() - start..];
+
+        let dedented = bstd::dedent(value.trim_start_matches(['\n', '\r']).trim_end());
+        let lines = dedented
+            .content
+            .lines()
+            .map(|line| RcDoc::text(line.to_string()))
+            .collect::<Vec<_>>();
+
+        Ok(RcDoc::text(open)
+            .append(
+                RcDoc::hardline()
+                    .append(RcDoc::intersperse(lines, RcDoc::hardline()))
+                    .nest(self.indent_width),
+            )
+            .append(RcDoc::hardline())
+            .append(RcDoc::text(close)))
+    }
+
+    fn comment_block_to_doc<'a>(&self, pair: Pair<'a, Rule>) -> RcDoc<'a, ()> {
+        RcDoc::intersperse(pair.into_inner().map(pair_to_doc_text), RcDoc::hardline())
+    }
+
+    /// Lays out `header { ... }` with one item per line.
+    fn block_to_doc<'a>(&self, header: RcDoc<'a, ()>, items: Vec<Item<'a>>) -> RcDoc<'a, ()> {
+        if items.is_empty() {
+            return header.append(RcDoc::text(" {}"));
+        }
+
+        header
+            .append(RcDoc::text(" {"))
+            .append(
+                RcDoc::hardline()
+                    .append(join_items(items))
+                    .nest(self.indent_width),
+            )
+            .append(RcDoc::hardline())
+            .append(RcDoc::text("}"))
+    }
+
+    /// Joins the parts of a single field or entry with spaces, except where
+    /// the user started a new line: attributes that were on their own line
+    /// stay there (indented), and so do comments.
+    fn join_parts<'a>(&self, parts: Vec<Item<'a>>) -> RcDoc<'a, ()> {
+        let mut doc = RcDoc::nil();
+        let mut prev_line = None;
+
+        for part in parts {
+            doc = match prev_line {
+                None => doc.append(part.doc),
+                Some(prev_line) if part.first_line > prev_line => {
+                    if part.is_comment {
+                        doc.append(RcDoc::hardline()).append(part.doc)
+                    } else {
+                        doc.append(RcDoc::hardline().append(part.doc).nest(self.indent_width))
+                    }
+                }
+                Some(_) => doc.append(RcDoc::space()).append(part.doc),
+            };
+            prev_line = Some(part.last_line);
+        }
+
+        doc
+    }
+
+    /// Formats `pair` with `f`, falling back to the original source when the
+    /// formatter does not know how to handle some part of it.
+    fn or_verbatim<'a>(
+        &self,
+        pair: Pair<'a, Rule>,
+        f: impl FnOnce(Pair<'a, Rule>) -> Result<RcDoc<'a, ()>>,
+    ) -> Result<RcDoc<'a, ()>> {
+        match f(pair.clone()) {
+            Ok(doc) => Ok(doc),
+            Err(e) if self.fail_on_unhandled_rule => Err(e),
+            Err(_) => Ok(pair_to_doc_text(pair)),
+        }
+    }
+
     fn unhandled_rule_to_doc<'a>(&self, pair: Pair<'a, Rule>) -> Result<RcDoc<'a, ()>> {
         if self.fail_on_unhandled_rule {
             Err(anyhow!("Unhandled rule: {:?}", pair.as_rule()))
@@ -280,6 +837,37 @@ impl Formatter {
     }
 }
 
+/// Joins items with newlines, keeping at most one blank line wherever the
+/// source had one or more.
+fn join_items(items: Vec<Item<'_>>) -> RcDoc<'_, ()> {
+    let mut doc = RcDoc::nil();
+    let mut prev_line = None;
+
+    for item in items {
+        if let Some(prev_line) = prev_line {
+            doc = doc.append(RcDoc::hardline());
+            if item.first_line > prev_line + 1 {
+                doc = doc.append(RcDoc::hardline());
+            }
+        }
+        prev_line = Some(item.last_line);
+        doc = doc.append(item.doc);
+    }
+
+    doc
+}
+
+/// The first and last source lines holding non-whitespace text of `pair`.
+fn line_range(pair: &Pair<'_, Rule>) -> (usize, usize) {
+    let text = pair.as_str();
+    let trimmed = text.trim_start();
+    let first_line = pair.line_col().0 + text[..text.len() - trimmed.len()].matches('\n').count();
+    (
+        first_line,
+        first_line + trimmed.trim_end().matches('\n').count(),
+    )
+}
+
 fn pair_to_doc_text<'a>(pair: Pair<'a, Rule>) -> RcDoc<'a, ()> {
     RcDoc::text(pair.as_str().trim())
 }
@@ -298,7 +886,7 @@ mod tests {
                 fail_on_unhandled_rule: true,
             },
         )?;
-        assert_eq!(expected.unindent().trim_end(), formatted);
+        assert_eq!(format!("{}\n", expected.unindent().trim_end()), formatted);
         Ok(())
     }
 
@@ -354,4 +942,236 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_format_type_attributes_and_comments() -> anyhow::Result<()> {
+        assert_format_eq(
+            r##"
+                // A comment
+                enum Color {
+                  RED   @alias("red")
+                  GREEN
+                  @description("grass")
+
+                  BLUE // the sky
+                  @@alias("Colour")
+                }
+                class Foo {
+                  /// docs
+                  bar string?   @description(#"
+                    Some bar
+                  "#)
+                }
+            "##,
+            r##"
+                // A comment
+                enum Color {
+                    RED @alias("red")
+                    GREEN
+                        @description("grass")
+
+                    BLUE // the sky
+                    @@alias("Colour")
+                }
+                class Foo {
+                    /// docs
+                    bar string? @description(#"
+                        Some bar
+                    "#)
+                }
+            "##,
+        )
+    }
+
+    #[test]
+    fn test_format_raw_strings_in_attributes() -> anyhow::Result<()> {
+        assert_format_eq(
+            r##"
+                class Foo {
+                  bar string
+                    @description(#"
+                          Multi
+                            line
+                    "#)
+                  baz int @check(positive, {{ this > 0 }})
+                  @@description(#"
+                  The Foo
+                  "#)
+                }
+            "##,
+            r##"
+                class Foo {
+                    bar string
+                        @description(#"
+                            Multi
+                              line
+                        "#)
+                    baz int @check(positive, {{ this > 0 }})
+                    @@description(#"
+                        The Foo
+                    "#)
+                }
+            "##,
+        )
+    }
+
+    #[test]
+    fn test_format_function_and_prompt() -> anyhow::Result<()> {
+        assert_format_eq(
+            r##"
+                function   ExtractResume(resume:string,  images: image[] ) ->Resume|null {
+                  client "openai/gpt-4o"
+                  prompt #"
+                        Extract from:
+                          {{ resume }}
+
+                        {{ ctx.output_format }}
+                  "#
+                }
+
+
+
+                template_string Greet(name: string) #"
+                Hello {{ name }}
+                "#
+            "##,
+            r##"
+                function ExtractResume(resume: string, images: image[]) -> Resume | null {
+                    client "openai/gpt-4o"
+                    prompt #"
+                        Extract from:
+                          {{ resume }}
+
+                        {{ ctx.output_format }}
+                    "#
+                }
+
+                template_string Greet(name: string) #"
+                    Hello {{ name }}
+                "#
+            "##,
+        )
+    }
+
+    #[test]
+    fn test_format_long_argument_list() -> anyhow::Result<()> {
+        assert_format_eq(
+            r##"
+                function Summarize(document_text: string, maximum_summary_length: int, target_audience: string) -> string {
+                  client GPT4
+                  prompt #"Summarize {{ document_text }}"#
+                }
+            "##,
+            r##"
+                function Summarize(
+                    document_text: string,
+                    maximum_summary_length: int,
+                    target_audience: string,
+                ) -> string {
+                    client GPT4
+                    prompt #"Summarize {{ document_text }}"#
+                }
+            "##,
+        )
+    }
+
+    #[test]
+    fn test_format_config_blocks() -> anyhow::Result<()> {
+        assert_format_eq(
+            r#"
+                client<llm> GPT4 {
+                  provider   openai
+                  retry_policy Exponential
+                  options { model "gpt-4o"
+                    api_key env.OPENAI_API_KEY, headers {
+                      "x-custom" "value" // why
+                    }
+                  }
+                }
+                retry_policy Exponential {
+                    max_retries 3
+                    strategy {type exponential_backoff}
+                }
+                generator lang_python {
+                  output_type "python/pydantic"
+                  output_dir "../"
+                }
+                test ExtractResumeTest {
+                  functions [ExtractResume ,  Other]
+                  args {
+                    resume "John Doe"
+                    tags [
+                      "a", // first
+                      "b"
+                    ]
+                    empty {}
+                  }
+                  @@assert({{ this.name == "John Doe" }})
+                }
+            "#,
+            r#"
+                client<llm> GPT4 {
+                    provider openai
+                    retry_policy Exponential
+                    options {
+                        model "gpt-4o"
+                        api_key env.OPENAI_API_KEY
+                        headers {
+                            "x-custom" "value" // why
+                        }
+                    }
+                }
+                retry_policy Exponential {
+                    max_retries 3
+                    strategy {
+                        type exponential_backoff
+                    }
+                }
+                generator lang_python {
+                    output_type "python/pydantic"
+                    output_dir "../"
+                }
+                test ExtractResumeTest {
+                    functions [ExtractResume, Other]
+                    args {
+                        resume "John Doe"
+                        tags [
+                            "a", // first
+                            "b",
+                        ]
+                        empty {}
+                    }
+                    @@assert({{ this.name == "John Doe" }})
+                }
+            "#,
+        )
+    }
+
+    #[test]
+    fn test_format_is_idempotent() -> anyhow::Result<()> {
+        let schema = r##"
+            class Foo {
+              bar string @description(#"
+                multi
+                  line
+              "#)
+            }
+            function Foo(a: int) -> Foo {
+              client GPT4
+              prompt #"
+                {{ _.role("user") }}
+                  {{ a }}
+              "#
+            }
+        "##
+        .unindent();
+        let options = || FormatOptions {
+            indent_width: 2,
+            fail_on_unhandled_rule: true,
+        };
+        let once = format_schema(&schema, options())?;
+        let twice = format_schema(&once, options())?;
+        assert_eq!(once, twice);
+        Ok(())
+    }
 }
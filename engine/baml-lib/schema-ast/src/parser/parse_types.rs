@@ -129,14 +129,13 @@ fn parse_base_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<Fiel
             Rule::identifier => {
                 let identifier = parse_identifier(current.clone(), diagnostics);
                 let field_type = match current.as_str() {
-                    "string" | "int" | "float" | "bool" | "image" | "audio" => {
-                        FieldType::Primitive(
-                            FieldArity::Required,
-                            TypeValue::from_str(identifier.name()).expect("Invalid type value"),
-                            diagnostics.span(current.as_span()),
-                            None,
-                        )
-                    }
+                    "string" | "int" | "float" | "bool" | "image" | "audio" | "pdf" | "video"
+                    | "date" | "datetime" | "duration" | "message" => FieldType::Primitive(
+                        FieldArity::Required,
+                        TypeValue::from_str(identifier.name()).expect("Invalid type value"),
+                        diagnostics.span(current.as_span()),
+                        None,
+                    ),
                     "null" => FieldType::Primitive(
                         FieldArity::Optional,
                         TypeValue::Null,
@@ -92,17 +92,26 @@ pub(crate) fn parse_value_expression_block(
                             let span = item.as_span();
                             let attribute = parse_attribute(item, false, diagnostics);
                             let value_is_test = sub_type == Some(ValueExprBlockType::Test);
+                            let value_is_function = sub_type == Some(ValueExprBlockType::Function);
                             let attribute_name = attribute.name.to_string();
                             let attribute_is_constraint = &attribute_name == "check" || &attribute_name == "assert";
+                            let attribute_is_function_config = &attribute_name == "parser" || &attribute_name == "variant";
 
-                            // Only tests may have block attributes, and the only valid block attributes
-                            // are checks/asserts.
-                            if value_is_test && attribute_is_constraint {
+                            // Only tests and functions may have block attributes. Tests may only
+                            // have checks/asserts, and functions may only have @@parser and @@variant.
+                            if (value_is_test && attribute_is_constraint)
+                                || (value_is_function && attribute_is_function_config)
+                            {
                                 // value_expression_block is compatible with the attribute
                                 attributes.push(attribute);
+                            } else if value_is_function {
+                                diagnostics.push_error(DatamodelError::new_validation_error(
+                                    "Functions may only contain 'parser' and 'variant' attributes",
+                                    diagnostics.span(span),
+                                ))
                             } else if !value_is_test {
                                 diagnostics.push_error(DatamodelError::new_validation_error(
-                                    "Only Tests may contain block-level attributes",
+                                    "Only Tests and Functions may contain block-level attributes",
                                     diagnostics.span(span),
                                 ))
                             } else {
@@ -0,0 +1,159 @@
+use crate::eval::{EvalArm, EvalOptions, EvalReport};
+use crate::BamlRuntime;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct EvalArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(long, help = "the function to evaluate")]
+    function: String,
+    #[arg(
+        long = "test",
+        help = "only run these tests (the name of a test with a dataset runs all of its rows)"
+    )]
+    tests: Vec<String>,
+    #[arg(
+        long = "variant",
+        help = "a prompt variant to run; pass twice to compare two variants"
+    )]
+    variants: Vec<String>,
+    #[arg(
+        long = "client",
+        help = "a client to run the function with; pass twice to compare two clients"
+    )]
+    clients: Vec<String>,
+    #[arg(
+        long,
+        help = "the dataset column with the expected output",
+        default_value = "expected"
+    )]
+    expected_column: String,
+    #[arg(
+        long,
+        help = "how many tests to run at the same time",
+        default_value = "4"
+    )]
+    concurrency: usize,
+    #[arg(long, help = "save the results as JSON to this file")]
+    output: Option<PathBuf>,
+    #[arg(long, help = "compare against results saved with --output")]
+    baseline: Option<PathBuf>,
+}
+
+impl EvalArgs {
+    pub fn run(&self) -> Result<()> {
+        let arms = self.arms()?;
+        let runtime = BamlRuntime::from_directory(&self.from, std::env::vars().collect())
+            .context("Failed to build BAML runtime")?;
+        let options = EvalOptions {
+            expected_column: self.expected_column.clone(),
+            concurrency: self.concurrency,
+        };
+
+        let t = tokio::runtime::Runtime::new()?;
+        let reports = arms
+            .iter()
+            .map(|arm| {
+                t.block_on(runtime.run_eval(&self.function, &self.tests, arm, &options))
+                    .with_context(|| format!("Failed to evaluate {}", arm.label()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match &reports[..] {
+            [a, b] => {
+                println!("{}", a.comparison_table(b));
+                print_changed_cases(a, b);
+            }
+            _ => {
+                for report in &reports {
+                    println!("{}", report.table());
+                }
+            }
+        }
+
+        if let Some(baseline) = &self.baseline {
+            let content = std::fs::read_to_string(baseline)
+                .with_context(|| format!("Failed to read {}", baseline.display()))?;
+            let baseline_reports: Vec<EvalReport> = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", baseline.display()))?;
+            for report in &reports {
+                // Compare against the same arm if the baseline has it.
+                let Some(before) = baseline_reports
+                    .iter()
+                    .find(|b| b.arm == report.arm)
+                    .or(baseline_reports.first())
+                else {
+                    continue;
+                };
+                println!(
+                    "\nCompared to {} ({}):",
+                    baseline.display(),
+                    before.arm.label()
+                );
+                println!("{}", before.comparison_table(report));
+                print_changed_cases(before, report);
+            }
+        }
+
+        if let Some(output) = &self.output {
+            std::fs::write(output, serde_json::to_string_pretty(&reports)?)
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            println!("\nSaved results to {}", output.display());
+        }
+
+        Ok(())
+    }
+
+    /// One arm, or two when comparing variants or clients.
+    fn arms(&self) -> Result<Vec<EvalArm>> {
+        let one = |values: &[String]| match values {
+            [] => Ok(None),
+            [value] => Ok(Some(value.clone())),
+            _ => anyhow::bail!("Only one of --variant and --client can be passed twice"),
+        };
+        Ok(match (&self.variants[..], &self.clients[..]) {
+            ([a, b], clients) => {
+                let client = one(clients)?;
+                [a, b]
+                    .map(|variant| EvalArm {
+                        variant: Some(variant.clone()),
+                        client: client.clone(),
+                    })
+                    .to_vec()
+            }
+            (variants, [a, b]) => {
+                let variant = one(variants)?;
+                [a, b]
+                    .map(|client| EvalArm {
+                        variant: variant.clone(),
+                        client: Some(client.clone()),
+                    })
+                    .to_vec()
+            }
+            (variants, clients) if variants.len() <= 1 && clients.len() <= 1 => vec![EvalArm {
+                variant: one(variants)?,
+                client: one(clients)?,
+            }],
+            _ => anyhow::bail!("At most two variants or two clients can be compared"),
+        })
+    }
+}
+
+fn print_changed_cases(a: &EvalReport, b: &EvalReport) {
+    let changed = a.changed_cases(b);
+    if changed.is_empty() {
+        return;
+    }
+    let outcome = |case: Option<&crate::eval::EvalCase>| match case {
+        None => "missing",
+        Some(c) if c.error.is_some() => "error",
+        Some(c) if c.passed && c.exact_match != Some(false) => "pass",
+        Some(_) => "fail",
+    };
+    println!("\nChanged tests:");
+    for (name, before, after) in changed {
+        println!("  {name}: {} -> {}", outcome(before), outcome(after));
+    }
+}
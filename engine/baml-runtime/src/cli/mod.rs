@@ -1,4 +1,5 @@
 pub mod dev;
+pub mod eval;
 pub mod generate;
 pub mod init;
 pub mod serve;
@@ -1,4 +1,4 @@
-use baml_types::{BamlMediaContent, BamlValue};
+use baml_types::{BamlMediaContent, BamlMessagePart, BamlValue};
 
 use super::error::BamlError;
 use core::result::Result;
@@ -19,6 +19,14 @@ impl BamlServeValidate for BamlValue {
               BamlMediaContent::Base64(_) => Ok(()),
             }
           }
+          BamlValue::Message(m) => {
+            for part in &m.content {
+              if let BamlMessagePart::Media(media) = part {
+                BamlValue::Media(media.clone()).validate_for_baml_serve()?;
+              }
+            }
+            Ok(())
+          }
           BamlValue::List(l) => {
             for v in l {
               v.validate_for_baml_serve()?;
@@ -42,7 +50,8 @@ impl BamlServeValidate for BamlValue {
           BamlValue::Float(_) |
           BamlValue::Int(_) |
           BamlValue::Null |
-          BamlValue::String(_) => Ok(()),
+          BamlValue::String(_) |
+          BamlValue::Temporal(_) => Ok(()),
         }
     }
 }
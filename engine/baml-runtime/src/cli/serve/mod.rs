@@ -356,10 +356,11 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
             Err(e) => return e.into_response(),
         };
 
-        let ctx_mgr = RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None);
         let client_registry = b_options.and_then(|options| options.client_registry);
 
         let locked = self.b.read().await;
+        let ctx_mgr = RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None)
+            .with_baml_src_dir(locked.baml_src_dir().map(Into::into));
         let (result, _trace_id) = locked
             .call_function(b_fn, &args, &ctx_mgr, None, client_registry.as_ref())
             .await;
@@ -446,8 +447,10 @@ Tip: test that the server is up using `curl http://localhost:{}/_debug/ping`
         let client_registry = b_options.and_then(|options| options.client_registry);
 
         tokio::spawn(async move {
+            let baml_src_dir = self.b.read().await.baml_src_dir().map(Into::into);
             let ctx_mgr =
-                RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None);
+                RuntimeContextManager::new_from_env_vars(std::env::vars().collect(), None)
+                    .with_baml_src_dir(baml_src_dir);
 
             let result_stream = self.b.read().await.stream_function(
                 b_fn,
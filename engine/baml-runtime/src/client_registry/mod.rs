@@ -5,7 +5,7 @@ use internal_llm_client::{ClientSpec, PropertyHandler, UnresolvedClientProperty}
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use baml_types::{BamlMap, BamlValue};
+use baml_types::{BamlMap, BamlValue, StringOr, UnresolvedValue};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{internal::llm_client::llm_provider::LLMProvider, RuntimeContext};
@@ -24,6 +24,12 @@ pub struct ClientProperty {
     pub provider: ClientProvider,
     pub retry_policy: Option<String>,
     options: BamlMap<String, BamlValue>,
+    /// Whether `"env.X"` strings in `options` refer to environment variables.
+    /// Only clients built by the application do: a registry deserialized
+    /// from a request body, e.g. by `baml serve`, could otherwise send any
+    /// secret of the server to a `base_url` of the caller's choosing.
+    #[serde(skip)]
+    resolve_env_vars: bool,
 }
 
 impl ClientProperty {
@@ -38,6 +44,7 @@ impl ClientProperty {
             provider,
             retry_policy,
             options,
+            resolve_env_vars: true,
         }
     }
 
@@ -49,6 +56,7 @@ impl ClientProperty {
             options: vec![("model".to_string(), BamlValue::String(model.to_string()))]
                 .into_iter()
                 .collect(),
+            resolve_env_vars: true,
         }
     }
 
@@ -56,7 +64,20 @@ impl ClientProperty {
         let property = PropertyHandler::new(
             self.options
                 .iter()
-                .map(|(k, v)| Ok((k.clone(), ((), v.to_resolvable()?))))
+                .map(|(k, v)| {
+                    let v = v.to_resolvable()?;
+                    Ok((
+                        k.clone(),
+                        (
+                            (),
+                            if self.resolve_env_vars {
+                                env_var_references(v)
+                            } else {
+                                v
+                            },
+                        ),
+                    ))
+                })
                 .collect::<Result<_>>()?,
             (),
         );
@@ -78,6 +99,8 @@ pub struct ClientRegistry {
     #[serde(deserialize_with = "deserialize_clients")]
     clients: HashMap<String, ClientProperty>,
     primary: Option<String>,
+    #[serde(default)]
+    variant: Option<String>,
 }
 
 impl Default for ClientRegistry {
@@ -91,6 +114,7 @@ impl ClientRegistry {
         Self {
             clients: Default::default(),
             primary: None,
+            variant: None,
         }
     }
 
@@ -102,6 +126,16 @@ impl ClientRegistry {
         self.primary = Some(primary);
     }
 
+    /// Makes calls use the prompt variant with this name instead of picking
+    /// one by weight.
+    pub fn set_variant(&mut self, variant: String) {
+        self.variant = Some(variant);
+    }
+
+    pub fn variant(&self) -> Option<&String> {
+        self.variant.as_ref()
+    }
+
     pub fn to_clients(
         &self,
         ctx: &RuntimeContext,
@@ -117,6 +151,31 @@ impl ClientRegistry {
     }
 }
 
+/// Treats strings like `"env.OPENAI_API_KEY"` as references to environment
+/// variables, the same as `env.OPENAI_API_KEY` in a BAML file. They are
+/// resolved when the client is used.
+fn env_var_references(value: UnresolvedValue<()>) -> UnresolvedValue<()> {
+    match value {
+        UnresolvedValue::String(StringOr::Value(s), meta) => match s.strip_prefix("env.") {
+            Some(name) if !name.is_empty() => {
+                UnresolvedValue::String(StringOr::EnvVar(name.to_string()), meta)
+            }
+            _ => UnresolvedValue::String(StringOr::Value(s), meta),
+        },
+        UnresolvedValue::Array(items, meta) => {
+            UnresolvedValue::Array(items.into_iter().map(env_var_references).collect(), meta)
+        }
+        UnresolvedValue::Map(entries, meta) => UnresolvedValue::Map(
+            entries
+                .into_iter()
+                .map(|(k, (m, v))| (k, (m, env_var_references(v))))
+                .collect(),
+            meta,
+        ),
+        other => other,
+    }
+}
+
 fn deserialize_clients<'de, D>(deserializer: D) -> Result<HashMap<String, ClientProperty>, D::Error>
 where
     D: Deserializer<'de>,
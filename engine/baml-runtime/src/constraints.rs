@@ -1,11 +1,18 @@
-use baml_types::{BamlValue, BamlValueWithMeta, Constraint, ConstraintLevel, ResponseCheck};
-use internal_baml_core::ir::jinja_helpers::{evaluate_predicate, render_expression};
+use baml_types::{
+    BamlMap, BamlValue, BamlValueWithMeta, Constraint, ConstraintLevel, ResponseCheck,
+};
+use internal_baml_core::ir::jinja_helpers::{evaluate_predicate, render_expression_with_globals};
 use jsonish::BamlValueWithFlags;
 
 use anyhow::Result;
 use indexmap::IndexMap;
 use minijinja;
-use std::{collections::HashMap, fmt};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 use crate::internal::llm_client::LLMCompleteResponse;
 
@@ -13,22 +20,191 @@ use crate::internal::llm_client::LLMCompleteResponse;
 /// the order that the constraints were specified by the user.
 ///
 /// When a check in a test is evaluated, its results are added to the context
-/// so that future constraints can refer to it.
+/// so that future constraints can refer to it. Tests with a dataset can refer
+/// to the columns of their row as `_.row`.
+///
+/// Constraints can call the functions in `judges`. The result is only final
+/// once `judges` has no pending calls, see [`Judges`].
 pub fn evaluate_test_constraints(
     args: &IndexMap<String, BamlValue>,
+    row: &IndexMap<String, BamlValue>,
     value: &BamlValueWithMeta<Vec<ResponseCheck>>,
     response: &LLMCompleteResponse,
     constraints: Vec<Constraint>,
+    judges: &Judges,
 ) -> TestConstraintsResult {
     // Fold over all the constraints, updating both our success state, and
     // our jinja context full of Check results.
     // Finally, return the success state.
-    constraints
+    let result = constraints
         .into_iter()
         .fold(Accumulator::new(), |acc, constraint| {
-            step_constraints(args, value, response, acc, constraint)
+            step_constraints(args, row, value, response, judges, acc, constraint)
         })
-        .result
+        .result;
+    match result {
+        TestConstraintsResult::Completed {
+            checks,
+            failed_assert,
+            ..
+        } => TestConstraintsResult::Completed {
+            checks,
+            failed_assert,
+            judge_calls: judges.calls(),
+        },
+        _ => result,
+    }
+}
+
+/// A call to a BAML function from a test constraint, which judges the output
+/// of the test, e.g. `{{ MentionsRefundPolicy(summary=this) }}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JudgeCall {
+    pub function_name: String,
+    pub args: BamlMap<String, BamlValue>,
+    /// The output of the judge, or why it failed.
+    pub result: Result<BamlValue, String>,
+}
+
+/// The functions that test constraints can call as judges.
+///
+/// Constraints are evaluated synchronously, so the first time a constraint
+/// calls a judge with some arguments, the call is only recorded as pending
+/// and returns `undefined`. The caller runs the pending calls, records their
+/// results, and evaluates the constraints again, until no calls are pending.
+#[derive(Clone, Debug, Default)]
+pub struct Judges {
+    /// The parameter names of each function, in order.
+    functions: IndexMap<String, Vec<String>>,
+    state: Arc<Mutex<JudgesState>>,
+}
+
+#[derive(Debug, Default)]
+struct JudgesState {
+    calls: Vec<JudgeCall>,
+    pending: Vec<(String, BamlMap<String, BamlValue>)>,
+}
+
+impl Judges {
+    pub fn new(functions: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+        Judges {
+            functions: functions.into_iter().collect(),
+            state: Default::default(),
+        }
+    }
+
+    /// The calls that constraints made to judges that have not run yet.
+    pub fn take_pending(&self) -> Vec<(String, BamlMap<String, BamlValue>)> {
+        std::mem::take(&mut self.state.lock().unwrap().pending)
+    }
+
+    pub fn record(&self, call: JudgeCall) {
+        self.state.lock().unwrap().calls.push(call);
+    }
+
+    /// The judge calls that have run, in order.
+    pub fn calls(&self) -> Vec<JudgeCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    fn globals(&self) -> Vec<(String, minijinja::Value)> {
+        self.functions
+            .keys()
+            .map(|name| {
+                (
+                    name.clone(),
+                    minijinja::Value::from_object(JudgeFunction {
+                        name: name.clone(),
+                        judges: self.clone(),
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    fn call(
+        &self,
+        function_name: &str,
+        args: &[minijinja::Value],
+    ) -> Result<minijinja::Value, minijinja::Error> {
+        use minijinja::{value::Kwargs, Error, ErrorKind};
+
+        let (positional, kwargs): (&[minijinja::Value], Kwargs) =
+            minijinja::value::from_args(args)?;
+        let params = &self.functions[function_name];
+        if positional.len() > params.len() {
+            return Err(Error::new(
+                ErrorKind::TooManyArguments,
+                format!(
+                    "{function_name} takes {} arguments, got {}",
+                    params.len(),
+                    positional.len()
+                ),
+            ));
+        }
+        let to_baml = |value: minijinja::Value| {
+            BamlValue::deserialize(value)
+                .map_err(|e| Error::new(ErrorKind::InvalidOperation, e.to_string()))
+        };
+        let mut call_args = BamlMap::new();
+        for (param, value) in params.iter().zip(positional) {
+            call_args.insert(param.clone(), to_baml(value.clone())?);
+        }
+        for name in kwargs.args() {
+            if !params.iter().any(|p| p == name) {
+                return Err(Error::new(
+                    ErrorKind::TooManyArguments,
+                    format!("{function_name} has no parameter `{name}`"),
+                ));
+            }
+            call_args.insert(name.to_string(), to_baml(kwargs.get(name)?)?);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let done = state
+            .calls
+            .iter()
+            .find(|c| c.function_name == function_name && c.args == call_args);
+        match done {
+            Some(JudgeCall {
+                result: Ok(value), ..
+            }) => Ok(minijinja::Value::from_serialize(value)),
+            Some(JudgeCall { result: Err(e), .. }) => Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!("{function_name} failed: {e}"),
+            )),
+            None => {
+                let call = (function_name.to_string(), call_args);
+                if !state.pending.contains(&call) {
+                    state.pending.push(call);
+                }
+                Ok(minijinja::Value::UNDEFINED)
+            }
+        }
+    }
+}
+
+/// A judge, as a function that Jinja can call.
+#[derive(Debug)]
+struct JudgeFunction {
+    name: String,
+    judges: Judges,
+}
+
+impl fmt::Display for JudgeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl minijinja::value::Object for JudgeFunction {
+    fn call(
+        &self,
+        _state: &minijinja::State<'_, '_>,
+        args: &[minijinja::Value],
+    ) -> Result<minijinja::Value, minijinja::Error> {
+        self.judges.call(&self.name, args)
+    }
 }
 
 /// The result of running a series of block-level constraints within a test.
@@ -39,6 +215,8 @@ pub enum TestConstraintsResult {
     Completed {
         checks: Vec<(String, bool)>,
         failed_assert: Option<String>,
+        /// The judges that the constraints called.
+        judge_calls: Vec<JudgeCall>,
     },
 
     /// There was a problem evaluating a constraint.
@@ -51,6 +229,7 @@ impl TestConstraintsResult {
         TestConstraintsResult::Completed {
             checks: Vec::new(),
             failed_assert: None,
+            judge_calls: Vec::new(),
         }
     }
     fn checks(self) -> Vec<(String, bool)> {
@@ -61,11 +240,16 @@ impl TestConstraintsResult {
     }
     fn add_check_result(self, name: String, result: bool) -> Self {
         match self {
-            TestConstraintsResult::Completed { mut checks, .. } => {
+            TestConstraintsResult::Completed {
+                mut checks,
+                judge_calls,
+                ..
+            } => {
                 checks.push((name, result));
                 TestConstraintsResult::Completed {
                     checks,
                     failed_assert: None,
+                    judge_calls,
                 }
             }
             _ => self,
@@ -73,9 +257,14 @@ impl TestConstraintsResult {
     }
     fn fail_assert(self, name: Option<String>) -> Self {
         match self {
-            TestConstraintsResult::Completed { checks, .. } => TestConstraintsResult::Completed {
+            TestConstraintsResult::Completed {
+                checks,
+                judge_calls,
+                ..
+            } => TestConstraintsResult::Completed {
                 checks,
                 failed_assert: Some(name.unwrap_or("".to_string())),
+                judge_calls,
             },
             _ => self,
         }
@@ -91,10 +280,7 @@ struct Accumulator {
 impl Accumulator {
     pub fn new() -> Self {
         Accumulator {
-            result: TestConstraintsResult::Completed {
-                checks: Vec::new(),
-                failed_assert: None,
-            },
+            result: TestConstraintsResult::empty(),
             check_results: Vec::new(),
         }
     }
@@ -104,8 +290,10 @@ impl Accumulator {
 /// and updating the success state and the jinja context.
 fn step_constraints(
     args: &IndexMap<String, BamlValue>,
+    row: &IndexMap<String, BamlValue>,
     value: &BamlValueWithMeta<Vec<ResponseCheck>>,
     response: &LLMCompleteResponse,
+    judges: &Judges,
     acc: Accumulator,
     constraint: Constraint,
 ) -> Accumulator {
@@ -136,6 +324,7 @@ fn step_constraints(
                 "checks",
                 minijinja::Value::from_serialize(check_results_for_jinja),
             ),
+            ("row", minijinja::Value::from_serialize(row)),
         ]
         .into_iter()
         .collect::<HashMap<_, _>>(),
@@ -152,7 +341,8 @@ fn step_constraints(
     )
     .collect();
 
-    let constraint_result_str = render_expression(&constraint.expression, &ctx);
+    let constraint_result_str =
+        render_expression_with_globals(&constraint.expression, &ctx, judges.globals());
     let bool_result_or_internal_error: Result<bool, String> =
         match constraint_result_str.as_ref().map(|s| s.as_str()) {
             Ok("true") => Ok(true),
@@ -189,6 +379,7 @@ fn step_constraints(
             let result = TestConstraintsResult::Completed {
                 checks: new_checks,
                 failed_assert: None,
+                judge_calls: Vec::new(),
             };
             Accumulator {
                 result,
@@ -335,6 +526,9 @@ mod tests {
                 prompt_tokens: None,
                 output_tokens: None,
                 total_tokens: None,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+                cost: None,
             },
         }
     }
@@ -356,11 +550,19 @@ mod tests {
     }
 
     fn run_pipeline(constraints: &[Constraint]) -> TestConstraintsResult {
+        run_pipeline_with_judges(constraints, &Judges::default())
+    }
+
+    fn run_pipeline_with_judges(
+        constraints: &[Constraint],
+        judges: &Judges,
+    ) -> TestConstraintsResult {
         let args = IndexMap::new();
+        let row = IndexMap::from([("min_kids".to_string(), BamlValue::Int(1))]);
         let value = mk_value();
         let constraints = constraints.into();
         let response = mk_response();
-        evaluate_test_constraints(&args, &value, &response, constraints)
+        evaluate_test_constraints(&args, &row, &value, &response, constraints, judges)
     }
 
     #[test]
@@ -371,6 +573,7 @@ mod tests {
             TestConstraintsResult::Completed {
                 checks: vec![],
                 failed_assert: None,
+                judge_calls: vec![],
             }
         );
     }
@@ -389,7 +592,8 @@ mod tests {
                     ("has_kids".to_string(), true),
                     ("not_too_many".to_string(), true),
                 ],
-                failed_assert: None
+                failed_assert: None,
+                judge_calls: vec![],
             }
         );
     }
@@ -407,7 +611,8 @@ mod tests {
             res,
             TestConstraintsResult::Completed {
                 checks: vec![("not_too_many".to_string(), true),],
-                failed_assert: Some("both_pass".to_string())
+                failed_assert: Some("both_pass".to_string()),
+                judge_calls: vec![],
             }
         );
     }
@@ -428,7 +633,8 @@ mod tests {
                     ("not_too_many".to_string(), true),
                     ("both_pass".to_string(), true),
                 ],
-                failed_assert: None
+                failed_assert: None,
+                judge_calls: vec![],
             }
         );
     }
@@ -451,11 +657,90 @@ mod tests {
                     ("no_kids".to_string(), false),
                     ("way_too_many".to_string(), false)
                 ],
-                failed_assert: None
+                failed_assert: None,
+                judge_calls: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_row() {
+        let res = run_pipeline(&[
+            mk_check("enough_kids", "this.kids|length >= _.row.min_kids"),
+            mk_check("too_many_kids", "this.kids|length > _.row.min_kids * 100"),
+        ]);
+        assert_eq!(
+            res,
+            TestConstraintsResult::Completed {
+                checks: vec![
+                    ("enough_kids".to_string(), true),
+                    ("too_many_kids".to_string(), false)
+                ],
+                failed_assert: None,
+                judge_calls: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_judges() {
+        let judges = Judges::new([(
+            "RateAge".to_string(),
+            vec!["age".to_string(), "strict".to_string()],
+        )]);
+        let constraints = [
+            mk_check("good_age", "RateAge(this.kids[0].age, strict=true) > 3"),
+            mk_check(
+                "same_rating",
+                "RateAge(age=this.kids[0].age, strict=true) > 3",
+            ),
+        ];
+
+        // The first pass only finds out which judges are called.
+        run_pipeline_with_judges(&constraints, &judges);
+        let pending = judges.take_pending();
+        let args = BamlMap::from([
+            ("age".to_string(), BamlValue::Int(6)),
+            ("strict".to_string(), BamlValue::Bool(true)),
+        ]);
+        assert_eq!(pending, vec![("RateAge".to_string(), args.clone())]);
+
+        let call = JudgeCall {
+            function_name: "RateAge".to_string(),
+            args,
+            result: Ok(BamlValue::Int(4)),
+        };
+        judges.record(call.clone());
+        let res = run_pipeline_with_judges(&constraints, &judges);
+        assert!(judges.take_pending().is_empty());
+        assert_eq!(
+            res,
+            TestConstraintsResult::Completed {
+                checks: vec![
+                    ("good_age".to_string(), true),
+                    ("same_rating".to_string(), true)
+                ],
+                failed_assert: None,
+                judge_calls: vec![call],
             }
         );
     }
 
+    #[test]
+    fn test_failed_judge() {
+        let judges = Judges::new([("RateAge".to_string(), vec!["age".to_string()])]);
+        judges.record(JudgeCall {
+            function_name: "RateAge".to_string(),
+            args: BamlMap::from([("age".to_string(), BamlValue::Int(6))]),
+            result: Err("LLM call failed".to_string()),
+        });
+        let res = run_pipeline_with_judges(
+            &[mk_check("good_age", "RateAge(this.kids[0].age) > 3")],
+            &judges,
+        );
+        assert!(matches!(res, TestConstraintsResult::InternalError { .. }));
+    }
+
     #[test]
     fn test_internal_error() {
         let res = run_pipeline(&[mk_check("faulty", "__.result.kids|length > 0")]);
@@ -0,0 +1,622 @@
+//! Evaluations: runs the tests of a function and aggregates their results
+//! into metrics, to measure the quality of a prompt rather than pass or fail.
+
+use anyhow::Result;
+use baml_types::BamlValue;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client_registry::ClientRegistry, constraints::TestConstraintsResult,
+    internal::llm_client::LLMResponse, BamlRuntime, FunctionResult, IRHelper,
+    InternalRuntimeInterface, TestStatus,
+};
+
+/// The name of the prompt a function is declared with, as opposed to the
+/// variants added with `@@variant`.
+const DEFAULT_VARIANT: &str = "default_config";
+
+/// What an evaluation runs the function with. Leaving both unset runs the
+/// function as declared.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EvalArm {
+    /// A prompt variant declared with `@@variant`.
+    pub variant: Option<String>,
+    /// A client to call instead of the function's.
+    pub client: Option<String>,
+}
+
+impl EvalArm {
+    pub fn label(&self) -> String {
+        match (&self.variant, &self.client) {
+            (None, None) => "default".to_string(),
+            (Some(variant), None) => variant.clone(),
+            (None, Some(client)) => client.clone(),
+            (Some(variant), Some(client)) => format!("{variant} ({client})"),
+        }
+    }
+
+    /// Without a variant, the function's own prompt is pinned, so that a
+    /// weighted pick among its variants doesn't mix them into the results.
+    fn client_registry(&self) -> ClientRegistry {
+        let mut cb = ClientRegistry::new();
+        cb.set_variant(
+            self.variant
+                .clone()
+                .unwrap_or_else(|| DEFAULT_VARIANT.to_string()),
+        );
+        if let Some(client) = &self.client {
+            cb.set_primary(client.clone());
+        }
+        cb
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EvalOptions {
+    /// The column of a test's dataset row that holds the expected output.
+    pub expected_column: String,
+    /// How many cases run at the same time.
+    pub concurrency: usize,
+}
+
+impl Default for EvalOptions {
+    fn default() -> Self {
+        Self {
+            expected_column: "expected".to_string(),
+            concurrency: 4,
+        }
+    }
+}
+
+/// The results of one test case.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EvalCase {
+    pub test_name: String,
+    pub passed: bool,
+    /// Set when the function or the checks could not run.
+    pub error: Option<String>,
+    pub checks: IndexMap<String, bool>,
+    /// Whether the output equals the expected output, if the row has one.
+    pub exact_match: Option<bool>,
+    /// The share of the fields of the expected output that the output got
+    /// right. Outputs that are not objects count as a single field.
+    pub field_accuracy: Option<f64>,
+    pub latency_ms: Option<u64>,
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    pub cost: Option<f64>,
+}
+
+/// Aggregate metrics over all cases of an evaluation.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EvalSummary {
+    pub cases: usize,
+    pub pass_rate: f64,
+    pub error_rate: f64,
+    /// The pass rate of each check, over the cases that ran it.
+    pub check_pass_rates: IndexMap<String, f64>,
+    /// Over the cases that have an expected output.
+    pub exact_match_rate: Option<f64>,
+    pub field_accuracy: Option<f64>,
+    pub latency_p50_ms: Option<u64>,
+    pub latency_p90_ms: Option<u64>,
+    pub latency_p99_ms: Option<u64>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: Option<f64>,
+}
+
+/// The results of running the tests of a function with one arm.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EvalReport {
+    pub function_name: String,
+    pub arm: EvalArm,
+    pub summary: EvalSummary,
+    pub cases: Vec<EvalCase>,
+}
+
+impl BamlRuntime {
+    /// Runs `test_names` of `function_name`, or all of its tests if empty,
+    /// and aggregates the results. The name of a test with a dataset runs
+    /// all of its rows.
+    pub async fn run_eval(
+        &self,
+        function_name: &str,
+        test_names: &[String],
+        arm: &EvalArm,
+        options: &EvalOptions,
+    ) -> Result<EvalReport> {
+        let function = self.inner.ir().find_function(function_name)?;
+        let test_names = function
+            .walk_tests()
+            .map(|t| t.test_case().name.clone())
+            .filter(|name| {
+                test_names.is_empty()
+                    || test_names.iter().any(|t| {
+                        name == t
+                            || name
+                                .strip_prefix(t.as_str())
+                                .is_some_and(|rest| rest.starts_with('['))
+                    })
+            })
+            .collect::<Vec<_>>();
+        if test_names.is_empty() {
+            anyhow::bail!("Function {function_name} has no tests to evaluate");
+        }
+
+        let cb = arm.client_registry();
+        let cases = futures::stream::iter(test_names.iter())
+            .map(|test_name| self.eval_case(function_name, test_name, &cb, options))
+            .buffered(options.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(EvalReport {
+            function_name: function_name.to_string(),
+            arm: arm.clone(),
+            summary: EvalSummary::new(&cases),
+            cases,
+        })
+    }
+
+    async fn eval_case(
+        &self,
+        function_name: &str,
+        test_name: &str,
+        cb: &ClientRegistry,
+        options: &EvalOptions,
+    ) -> EvalCase {
+        // Each case gets its own context, so that concurrent cases do not
+        // share a span stack.
+        let ctx = self.create_ctx_manager(BamlValue::String("eval".to_string()), None);
+        let (response, _) = self
+            .run_test_with_client_registry(
+                function_name,
+                test_name,
+                &ctx,
+                None::<fn(FunctionResult)>,
+                Some(cb),
+            )
+            .await;
+
+        let mut case = EvalCase {
+            test_name: test_name.to_string(),
+            passed: false,
+            error: None,
+            checks: IndexMap::new(),
+            exact_match: None,
+            field_accuracy: None,
+            latency_ms: None,
+            input_tokens: None,
+            output_tokens: None,
+            cost: None,
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                case.error = Some(e.to_string());
+                return case;
+            }
+        };
+
+        let result = &response.function_response;
+        // Like the cost, latency and tokens count every attempt, including
+        // retries and fallbacks.
+        case.cost = result.cost();
+        case.latency_ms = result
+            .event_chain()
+            .iter()
+            .filter_map(|(_, response, ..)| match response {
+                LLMResponse::Success(s) => Some(s.latency),
+                LLMResponse::LLMFailure(e) => Some(e.latency),
+                _ => None,
+            })
+            .reduce(|a, b| a + b)
+            .map(|latency| latency.as_millis() as u64);
+        let usage = result.usage();
+        case.input_tokens = usage.input_tokens;
+        case.output_tokens = usage.output_tokens;
+        match &response.constraints_result {
+            TestConstraintsResult::Completed { checks, .. } => {
+                case.checks = checks.iter().cloned().collect();
+            }
+            TestConstraintsResult::InternalError { details } => {
+                case.error = Some(details.clone());
+            }
+        }
+        match response.status() {
+            TestStatus::Pass => case.passed = true,
+            TestStatus::Fail(reason) if case.error.is_none() => {
+                use crate::TestFailReason::*;
+                match reason {
+                    TestConstraintsFailure { .. } => {}
+                    TestLLMFailure(r) => case.error = Some(r.to_string()),
+                    TestParseFailure(e) | TestFinishReasonFailed(e) => {
+                        case.error = Some(e.to_string())
+                    }
+                    TestUnspecified(e) => case.error = Some(e.to_string()),
+                }
+            }
+            _ => {}
+        }
+
+        let rctx = match ctx.create_ctx(None, Some(cb)) {
+            Ok(rctx) => rctx,
+            Err(_) => return case,
+        };
+        let expected = self
+            .inner
+            .get_test_row(function_name, test_name, &rctx)
+            .ok()
+            .and_then(|mut row| row.shift_remove(&options.expected_column));
+        if let (Some(expected), Ok(output)) = (expected, result.result_with_constraints_content()) {
+            let output =
+                serde_json::to_value(BamlValue::from(output)).unwrap_or(serde_json::Value::Null);
+            let expected = expected_json(&expected, &output);
+            case.exact_match = Some(output == expected);
+            case.field_accuracy = Some(field_accuracy(&output, &expected));
+        }
+        case
+    }
+}
+
+/// Expected outputs from CSV files are strings, so they are read as JSON
+/// when the output is not a string.
+fn expected_json(expected: &BamlValue, output: &serde_json::Value) -> serde_json::Value {
+    match (expected, output) {
+        (BamlValue::String(s), serde_json::Value::String(_)) => {
+            serde_json::Value::String(s.clone())
+        }
+        (BamlValue::String(s), _) => {
+            serde_json::from_str(s).unwrap_or_else(|_| serde_json::Value::String(s.clone()))
+        }
+        _ => serde_json::to_value(expected).unwrap_or(serde_json::Value::Null),
+    }
+}
+
+fn field_accuracy(output: &serde_json::Value, expected: &serde_json::Value) -> f64 {
+    match expected {
+        serde_json::Value::Object(fields) if !fields.is_empty() => {
+            let correct = fields
+                .iter()
+                .filter(|(k, v)| output.get(k.as_str()) == Some(*v))
+                .count();
+            correct as f64 / fields.len() as f64
+        }
+        _ => (output == expected) as u8 as f64,
+    }
+}
+
+impl EvalSummary {
+    pub fn new(cases: &[EvalCase]) -> Self {
+        let rate = |n: usize, of: usize| if of == 0 { 0.0 } else { n as f64 / of as f64 };
+        let mean = |values: Vec<f64>| {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+
+        let mut check_counts: IndexMap<String, (usize, usize)> = IndexMap::new();
+        for case in cases {
+            for (name, passed) in &case.checks {
+                let (pass, total) = check_counts.entry(name.clone()).or_default();
+                *pass += *passed as usize;
+                *total += 1;
+            }
+        }
+
+        let mut latencies = cases
+            .iter()
+            .filter_map(|c| c.latency_ms)
+            .collect::<Vec<_>>();
+        latencies.sort_unstable();
+
+        Self {
+            cases: cases.len(),
+            pass_rate: rate(cases.iter().filter(|c| c.passed).count(), cases.len()),
+            error_rate: rate(
+                cases.iter().filter(|c| c.error.is_some()).count(),
+                cases.len(),
+            ),
+            check_pass_rates: check_counts
+                .into_iter()
+                .map(|(name, (pass, total))| (name, rate(pass, total)))
+                .collect(),
+            exact_match_rate: mean(
+                cases
+                    .iter()
+                    .filter_map(|c| c.exact_match.map(|m| m as u8 as f64))
+                    .collect(),
+            ),
+            field_accuracy: mean(cases.iter().filter_map(|c| c.field_accuracy).collect()),
+            latency_p50_ms: percentile(&latencies, 50),
+            latency_p90_ms: percentile(&latencies, 90),
+            latency_p99_ms: percentile(&latencies, 99),
+            input_tokens: cases.iter().filter_map(|c| c.input_tokens).sum(),
+            output_tokens: cases.iter().filter_map(|c| c.output_tokens).sum(),
+            cost: cases.iter().filter_map(|c| c.cost).reduce(|a, b| a + b),
+        }
+    }
+
+    /// The metrics as rows of a table, in a fixed order.
+    fn rows(&self) -> Vec<(String, Option<f64>, Unit)> {
+        let mut rows = vec![
+            ("cases".to_string(), Some(self.cases as f64), Unit::Count),
+            ("pass rate".to_string(), Some(self.pass_rate), Unit::Rate),
+            ("error rate".to_string(), Some(self.error_rate), Unit::Rate),
+        ];
+        rows.extend(
+            self.check_pass_rates
+                .iter()
+                .map(|(name, rate)| (format!("check {name}"), Some(*rate), Unit::Rate)),
+        );
+        rows.extend([
+            ("exact match".to_string(), self.exact_match_rate, Unit::Rate),
+            (
+                "field accuracy".to_string(),
+                self.field_accuracy,
+                Unit::Rate,
+            ),
+            (
+                "latency p50".to_string(),
+                self.latency_p50_ms.map(|v| v as f64),
+                Unit::Millis,
+            ),
+            (
+                "latency p90".to_string(),
+                self.latency_p90_ms.map(|v| v as f64),
+                Unit::Millis,
+            ),
+            (
+                "latency p99".to_string(),
+                self.latency_p99_ms.map(|v| v as f64),
+                Unit::Millis,
+            ),
+            (
+                "input tokens".to_string(),
+                Some(self.input_tokens as f64),
+                Unit::Count,
+            ),
+            (
+                "output tokens".to_string(),
+                Some(self.output_tokens as f64),
+                Unit::Count,
+            ),
+            ("cost".to_string(), self.cost, Unit::Usd),
+        ]);
+        rows
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[u64], p: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Count,
+    Rate,
+    Millis,
+    Usd,
+}
+
+impl Unit {
+    fn format(self, value: Option<f64>) -> String {
+        match (self, value) {
+            (_, None) => "-".to_string(),
+            (Unit::Count, Some(v)) => format!("{v:.0}"),
+            (Unit::Rate, Some(v)) => format!("{:.1}%", v * 100.0),
+            (Unit::Millis, Some(v)) => format!("{v:.0}ms"),
+            (Unit::Usd, Some(v)) => format!("${v:.4}"),
+        }
+    }
+
+    fn format_delta(self, delta: f64) -> String {
+        match self {
+            Unit::Count => format!("{delta:+.0}"),
+            Unit::Rate => format!("{:+.1}pp", delta * 100.0),
+            Unit::Millis => format!("{delta:+.0}ms"),
+            Unit::Usd => format!("{delta:+.4}"),
+        }
+    }
+}
+
+impl EvalReport {
+    /// A markdown table of the metrics of this report.
+    pub fn table(&self) -> String {
+        let mut table = vec![
+            vec!["metric".to_string(), self.arm.label()],
+            vec!["---".to_string(), "---".to_string()],
+        ];
+        for (name, value, unit) in self.summary.rows() {
+            table.push(vec![name, unit.format(value)]);
+        }
+        render_table(table)
+    }
+
+    /// A markdown table of the metrics of both reports side by side, e.g. of
+    /// two prompt variants, or of this run and a saved one.
+    pub fn comparison_table(&self, other: &EvalReport) -> String {
+        let mut table = vec![
+            vec![
+                "metric".to_string(),
+                self.arm.label(),
+                other.arm.label(),
+                "delta".to_string(),
+            ],
+            vec!["---".to_string(); 4],
+        ];
+        let other_rows = other.summary.rows();
+        // Checks that only one of the reports ran still get a row.
+        let mut names = self
+            .summary
+            .rows()
+            .into_iter()
+            .map(|(name, _, unit)| (name, unit))
+            .collect::<Vec<_>>();
+        for (name, _, unit) in &other_rows {
+            if !names.iter().any(|(n, _)| n == name) {
+                names.push((name.clone(), *unit));
+            }
+        }
+        let value_of = |rows: &[(String, Option<f64>, Unit)], name: &str| {
+            rows.iter()
+                .find(|(n, ..)| n == name)
+                .and_then(|(_, v, _)| *v)
+        };
+        let rows = self.summary.rows();
+        for (name, unit) in names {
+            let (a, b) = (value_of(&rows, &name), value_of(&other_rows, &name));
+            let delta = match (a, b) {
+                (Some(a), Some(b)) => unit.format_delta(b - a),
+                _ => "-".to_string(),
+            };
+            table.push(vec![name, unit.format(a), unit.format(b), delta]);
+        }
+        render_table(table)
+    }
+
+    /// The cases whose outcome differs between this report and `other`, by
+    /// test name.
+    pub fn changed_cases<'a>(
+        &'a self,
+        other: &'a EvalReport,
+    ) -> Vec<(&'a str, Option<&'a EvalCase>, Option<&'a EvalCase>)> {
+        let mut names = self
+            .cases
+            .iter()
+            .map(|c| c.test_name.as_str())
+            .collect::<Vec<_>>();
+        for case in &other.cases {
+            if !names.contains(&case.test_name.as_str()) {
+                names.push(&case.test_name);
+            }
+        }
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let a = self.cases.iter().find(|c| c.test_name == name);
+                let b = other.cases.iter().find(|c| c.test_name == name);
+                let outcome = |c: Option<&EvalCase>| c.map(|c| (c.passed, c.exact_match));
+                (outcome(a) != outcome(b)).then_some((name, a, b))
+            })
+            .collect()
+    }
+}
+
+fn render_table(rows: Vec<Vec<String>>) -> String {
+    let widths = (0..rows[0].len())
+        .map(|i| rows.iter().map(|r| r[i].len()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+    rows.iter()
+        .map(|row| {
+            let cells = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    if cell == "---" {
+                        "-".repeat(*width)
+                    } else {
+                        format!("{cell:width$}")
+                    }
+                })
+                .collect::<Vec<_>>();
+            format!("| {} |", cells.join(" | "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(name: &str, passed: bool, latency_ms: u64) -> EvalCase {
+        EvalCase {
+            test_name: name.to_string(),
+            passed,
+            error: None,
+            checks: IndexMap::from([("matches".to_string(), passed)]),
+            exact_match: Some(passed),
+            field_accuracy: Some(if passed { 1.0 } else { 0.5 }),
+            latency_ms: Some(latency_ms),
+            input_tokens: Some(10),
+            output_tokens: Some(5),
+            cost: None,
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let cases = (1..=10)
+            .map(|i| case(&format!("T[{i}]"), i % 2 == 0, i * 100))
+            .collect::<Vec<_>>();
+        let summary = EvalSummary::new(&cases);
+
+        assert_eq!(summary.cases, 10);
+        assert_eq!(summary.pass_rate, 0.5);
+        assert_eq!(summary.check_pass_rates["matches"], 0.5);
+        assert_eq!(summary.exact_match_rate, Some(0.5));
+        assert_eq!(summary.field_accuracy, Some(0.75));
+        assert_eq!(summary.latency_p50_ms, Some(500));
+        assert_eq!(summary.latency_p90_ms, Some(900));
+        assert_eq!(summary.latency_p99_ms, Some(1000));
+        assert_eq!(summary.input_tokens, 100);
+        assert_eq!(summary.cost, None);
+    }
+
+    #[test]
+    fn test_field_accuracy() {
+        let expected = serde_json::json!({"name": "Ada", "age": 36});
+        assert_eq!(
+            field_accuracy(&serde_json::json!({"name": "Ada", "age": 37}), &expected),
+            0.5
+        );
+        assert_eq!(
+            expected_json(
+                &BamlValue::String(r#"{"name": "Ada", "age": 36}"#.to_string()),
+                &serde_json::json!({}),
+            ),
+            expected
+        );
+        assert_eq!(
+            field_accuracy(&serde_json::json!("yes"), &serde_json::json!("yes")),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_comparison() {
+        let a = EvalReport {
+            function_name: "F".to_string(),
+            arm: EvalArm::default(),
+            summary: EvalSummary::new(&[case("T[0]", true, 100), case("T[1]", false, 100)]),
+            cases: vec![case("T[0]", true, 100), case("T[1]", false, 100)],
+        };
+        let b = EvalReport {
+            arm: EvalArm {
+                variant: Some("concise".to_string()),
+                client: None,
+            },
+            summary: EvalSummary::new(&[case("T[0]", true, 200), case("T[1]", true, 200)]),
+            cases: vec![case("T[0]", true, 200), case("T[1]", true, 200)],
+            ..a.clone()
+        };
+
+        let table = a.comparison_table(&b);
+        assert!(
+            table.contains("| pass rate      | 50.0%   | 100.0%  | +50.0pp |"),
+            "{table}"
+        );
+        assert!(table.contains("+100ms"), "{table}");
+
+        let changed = a.changed_cases(&b);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0, "T[1]");
+    }
+}
@@ -0,0 +1,90 @@
+use internal_baml_jinja::{tokenizer::Tokenizer, RenderContext_Client, RenderedPrompt};
+
+use super::{pricing::is_version_of, traits::WithClientProperties};
+
+/// Context windows in tokens. Models match their releases in order, see
+/// `pricing::is_version_of`, so more specific names must come first. Only
+/// models with an exact tokenizer are listed, see `check`.
+const BUILTIN_CONTEXT_WINDOWS: &[(&str, u32)] = &[
+    ("gpt-4o-mini", 128_000),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4-0125", 128_000),
+    ("gpt-4-1106", 128_000),
+    ("gpt-4-32k", 32_768),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("o1-mini", 128_000),
+    ("o1-preview", 128_000),
+    ("o1", 200_000),
+    ("o3-mini", 200_000),
+];
+
+fn builtin_context_window(model: &str) -> Option<u32> {
+    // Proxies often prefix the model, e.g. `openai/gpt-4o`.
+    let model = model.rsplit('/').next().unwrap_or(model);
+    BUILTIN_CONTEXT_WINDOWS
+        .iter()
+        .find(|(name, _)| is_version_of(model, name))
+        .map(|(_, window)| *window)
+}
+
+/// Checks, before sending it, that `prompt` leaves room in the model's context
+/// window for the output the request asks for.
+///
+/// The built-in windows are only checked for models whose tokens are counted
+/// exactly, since an estimate could reject prompts that fit. Setting
+/// `context_window` on the client checks any model, with estimated counts
+/// where there is no exact tokenizer.
+pub fn check(
+    client: &impl WithClientProperties,
+    context: &RenderContext_Client,
+    prompt: &RenderedPrompt,
+) -> Result<(), String> {
+    let model = context.model.as_deref();
+    let tokenizer = Tokenizer::for_model(model);
+    let Some(window) = client.context_window().or_else(|| {
+        tokenizer
+            .is_exact()
+            .then(|| model.and_then(builtin_context_window))
+            .flatten()
+    }) else {
+        return Ok(());
+    };
+
+    let prompt_tokens = tokenizer.count_prompt(prompt) as u64;
+    let output_tokens = client.max_output_tokens().unwrap_or(0);
+    if prompt_tokens + output_tokens <= window as u64 {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Prompt does not fit in the context window of {}: {} prompt tokens + {} max output tokens > {} tokens",
+        model.unwrap_or(&context.name),
+        prompt_tokens,
+        output_tokens,
+        window
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_context_window() {
+        assert_eq!(builtin_context_window("gpt-4o-mini"), Some(128_000));
+        assert_eq!(builtin_context_window("gpt-4-32k-0613"), Some(32_768));
+        assert_eq!(builtin_context_window("gpt-4-0613"), Some(8_192));
+        assert_eq!(builtin_context_window("openai/o1-mini"), Some(128_000));
+        // Counted approximately, so only checked with `context_window` set.
+        assert_eq!(builtin_context_window("claude-3-haiku-20240307"), None);
+        assert_eq!(builtin_context_window("llama3.1"), None);
+        assert_eq!(builtin_context_window("gpt-4.1"), None);
+        assert_eq!(builtin_context_window("gpt-4.5-preview"), None);
+
+        for (model, _) in BUILTIN_CONTEXT_WINDOWS {
+            assert!(Tokenizer::for_model(Some(model)).is_exact(), "{model}");
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
 use colored::*;
+pub mod context_window;
 pub mod llm_provider;
 pub mod orchestrator;
+pub mod pricing;
 pub mod primitive;
+pub mod rate_limit;
 
 pub mod retry_policy;
 mod strategy;
@@ -249,6 +252,14 @@ pub struct LLMCompleteResponseMetadata {
     pub prompt_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
     pub total_tokens: Option<u64>,
+    /// The part of `prompt_tokens` that was written to the provider's prompt
+    /// cache. Only reported by providers with explicit cache breakpoints.
+    pub cache_creation_input_tokens: Option<u64>,
+    /// The part of `prompt_tokens` that was read from the provider's prompt
+    /// cache.
+    pub cache_read_input_tokens: Option<u64>,
+    /// In USD, filled in by the orchestrator from the client's pricing.
+    pub cost: Option<f64>,
 }
 
 // This is how the response gets logged if you print the result to the console.
@@ -280,7 +291,7 @@ impl crate::tracing::Visualize for LLMCompleteResponse {
             format!(
                 "{}",
                 format!(
-                    "Client: {} ({}) - {}ms. StopReason: {}. Tokens(in/out): {}/{}",
+                    "Client: {} ({}) - {}ms. StopReason: {}. Tokens(in/out): {}/{}{}. Cost: {}",
                     self.client,
                     self.model,
                     self.latency.as_millis(),
@@ -293,6 +304,21 @@ impl crate::tracing::Visualize for LLMCompleteResponse {
                         .output_tokens
                         .map(|t| t.to_string())
                         .unwrap_or_else(|| "unknown".to_string()),
+                    match (
+                        self.metadata.cache_read_input_tokens,
+                        self.metadata.cache_creation_input_tokens,
+                    ) {
+                        (None, None) => String::new(),
+                        (read, write) => format!(
+                            ". Cache(read/write): {}/{}",
+                            read.unwrap_or(0),
+                            write.unwrap_or(0)
+                        ),
+                    },
+                    self.metadata
+                        .cost
+                        .map(|c| format!("${c:.6}"))
+                        .unwrap_or_else(|| "unknown".to_string()),
                 )
                 .yellow()
             ),
@@ -7,7 +7,8 @@ use web_time::Duration;
 use crate::{
     internal::{
         llm_client::{
-            parsed_value_to_response,
+            parsed_value_to_response, pricing,
+            rate_limit::RateLimiters,
             traits::{WithClientProperties, WithPrompt, WithSingleCallable},
             LLMResponse, ResponseBamlValue,
         },
@@ -22,6 +23,7 @@ pub async fn orchestrate(
     iter: OrchestratorNodeIterator,
     ir: &IntermediateRepr,
     ctx: &RuntimeContext,
+    rate_limiters: &RateLimiters,
     prompt: &PromptRenderer,
     params: &BamlValue,
     parse_fn: impl Fn(&str) -> Result<BamlValueWithFlags>,
@@ -36,8 +38,20 @@ pub async fn orchestrate(
 ) {
     let mut results = Vec::new();
     let mut total_sleep_duration = std::time::Duration::from_secs(0);
+    let mut call_cost = 0.0;
+    // Clients whose prompt doesn't fit in their context window. Retrying them
+    // can't help, so their remaining retries are skipped.
+    let mut too_long = Vec::new();
 
     for node in iter {
+        if let Some(Err(message)) = ctx.cost_budget.as_ref().map(|b| b.check(call_cost)) {
+            results.push((node.scope, LLMResponse::UserFailure(message), None, None));
+            break;
+        }
+        if too_long.contains(&node.scope.name()) {
+            continue;
+        }
+
         let prompt = match node.render_prompt(ir, prompt, ctx, params).await {
             Ok(p) => p,
             Err(e) => {
@@ -50,7 +64,22 @@ pub async fn orchestrate(
                 continue;
             }
         };
-        let response = node.single_call(ctx, &prompt).await;
+        if let Err(message) = node.provider.check_context_window(&prompt) {
+            too_long.push(node.scope.name());
+            results.push((node.scope, LLMResponse::UserFailure(message), None, None));
+            continue;
+        }
+        let (scope, permit) = rate_limiters.acquire(&node, &prompt).await;
+        let mut response = node.single_call(ctx, &prompt).await;
+        if let Some(permit) = permit {
+            permit.finish(&response);
+        }
+        if let Some(cost) = pricing::add_cost(&node, &mut response) {
+            call_cost += cost;
+            if let Some(budget) = &ctx.cost_budget {
+                budget.record(cost);
+            }
+        }
         let parsed_response = match &response {
             LLMResponse::Success(s) => {
                 if !node
@@ -76,12 +105,7 @@ pub async fn orchestrate(
             Some(Err(e)) => (None, Some(Err(e))),
             None => (None, None),
         };
-        results.push((
-            node.scope,
-            response,
-            parsed_response,
-            response_with_constraints,
-        ));
+        results.push((scope, response, parsed_response, response_with_constraints));
 
         // Currently, we break out of the loop if an LLM responded, even if we couldn't parse the result.
         if results
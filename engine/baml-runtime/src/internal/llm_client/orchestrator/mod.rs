@@ -3,13 +3,13 @@ mod stream;
 
 use web_time::Duration; // Add this line
 
-use crate::RenderCurlSettings;
 use crate::{
     internal::prompt_renderer::PromptRenderer, runtime_interface::InternalClientLookup,
     RuntimeContext,
 };
+use crate::{HttpRequest, RenderCurlSettings};
 
-use super::traits::{WithClientProperties, WithRenderRawCurl};
+use super::traits::{WithClientProperties, WithRenderHttpRequest, WithRenderRawCurl};
 use super::LLMCompleteResponse;
 use super::{
     strategy::roundrobin::RoundRobinStrategy,
@@ -46,6 +46,9 @@ impl std::fmt::Display for ExecutionScope {
             ExecutionScope::Fallback(strategy, index) => {
                 write!(f, "Fallback({}, {})", strategy, index)
             }
+            ExecutionScope::RateLimited(client, waited) => {
+                write!(f, "RateLimited({}, {}ms)", client, waited.as_millis())
+            }
         }
     }
 }
@@ -105,7 +108,12 @@ impl OrchestrationScope {
     pub fn name(&self) -> String {
         self.scope
             .iter()
-            .filter(|scope| !matches!(scope, ExecutionScope::Retry(..)))
+            .filter(|scope| {
+                !matches!(
+                    scope,
+                    ExecutionScope::Retry(..) | ExecutionScope::RateLimited(..)
+                )
+            })
             .map(|scope| format!("{}", scope))
             .collect::<Vec<_>>()
             .join(" + ")
@@ -145,6 +153,8 @@ pub enum ExecutionScope {
     RoundRobin(Arc<RoundRobinStrategy>, usize),
     // StrategyName, ClientIndex
     Fallback(String, usize),
+    // ClientName, TimeSpentWaitingForRateLimits
+    RateLimited(String, Duration),
 }
 
 pub type OrchestratorNodeIterator = Vec<OrchestratorNode>;
@@ -190,6 +200,17 @@ impl WithRenderRawCurl for OrchestratorNode {
     }
 }
 
+impl WithRenderHttpRequest for OrchestratorNode {
+    async fn render_http_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+        stream: bool,
+    ) -> Result<HttpRequest> {
+        self.provider.render_http_request(ctx, prompt, stream).await
+    }
+}
+
 impl WithSingleCallable for OrchestratorNode {
     async fn single_call(&self, ctx: &RuntimeContext, prompt: &RenderedPrompt) -> LLMResponse {
         self.scope
@@ -236,6 +257,18 @@ impl WithClientProperties for OrchestratorNode {
     fn finish_reason_filter(&self) -> &internal_llm_client::FinishReasonFilter {
         self.provider.finish_reason_filter()
     }
+    fn rate_limits(&self) -> &internal_llm_client::RateLimits {
+        self.provider.rate_limits()
+    }
+    fn pricing(&self) -> &internal_llm_client::ModelPricing {
+        self.provider.pricing()
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.provider.context_window()
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.provider.max_output_tokens()
+    }
 
     fn allowed_roles(&self) -> Vec<String> {
         self.provider.allowed_roles()
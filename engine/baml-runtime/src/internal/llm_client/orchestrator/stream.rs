@@ -8,7 +8,8 @@ use web_time::Duration;
 use crate::{
     internal::{
         llm_client::{
-            parsed_value_to_response,
+            parsed_value_to_response, pricing,
+            rate_limit::RateLimiters,
             traits::{WithClientProperties, WithPrompt, WithStreamable},
             LLMErrorResponse, LLMResponse, ResponseBamlValue,
         },
@@ -23,6 +24,7 @@ pub async fn orchestrate_stream<F>(
     iter: OrchestratorNodeIterator,
     ir: &IntermediateRepr,
     ctx: &RuntimeContext,
+    rate_limiters: &RateLimiters,
     prompt: &PromptRenderer,
     params: &BamlValue,
     partial_parse_fn: impl Fn(&str) -> Result<BamlValueWithFlags>,
@@ -42,9 +44,22 @@ where
 {
     let mut results = Vec::new();
     let mut total_sleep_duration = std::time::Duration::from_secs(0);
+    let mut call_cost = 0.0;
+    // Clients whose prompt doesn't fit in their context window. Retrying them
+    // can't help, so their remaining retries are skipped.
+    let mut too_long = Vec::new();
+    let variant = prompt.variant();
 
     //advanced curl viewing, use render_raw_curl on each node. TODO
     for node in iter {
+        if let Some(Err(message)) = ctx.cost_budget.as_ref().map(|b| b.check(call_cost)) {
+            results.push((node.scope, LLMResponse::UserFailure(message), None, None));
+            break;
+        }
+        if too_long.contains(&node.scope.name()) {
+            continue;
+        }
+
         let prompt = match node.render_prompt(ir, prompt, ctx, params).await {
             Ok(p) => p,
             Err(e) => {
@@ -57,10 +72,16 @@ where
                 continue;
             }
         };
+        if let Err(message) = node.provider.check_context_window(&prompt) {
+            too_long.push(node.scope.name());
+            results.push((node.scope, LLMResponse::UserFailure(message), None, None));
+            continue;
+        }
 
+        let (scope, permit) = rate_limiters.acquire(&node, &prompt).await;
         let (system_start, instant_start) = (web_time::SystemTime::now(), web_time::Instant::now());
         let stream_res = node.stream(ctx, &prompt).await;
-        let final_response = match stream_res {
+        let mut final_response = match stream_res {
             Ok(response) => response
                 .map(|stream_part| {
                     if let Some(on_event) = on_event.as_ref() {
@@ -72,12 +93,15 @@ where
                                 }
                                 Err(e) => (None, Some(Err(e))),
                             };
-                            on_event(FunctionResult::new(
-                                node.scope.clone(),
-                                LLMResponse::Success(s.clone()),
-                                parsed,
-                                response_value,
-                            ));
+                            on_event(
+                                FunctionResult::new(
+                                    scope.clone(),
+                                    LLMResponse::Success(s.clone()),
+                                    parsed,
+                                    response_value,
+                                )
+                                .with_variant(variant),
+                            );
                         }
                     }
                     stream_part
@@ -98,6 +122,15 @@ where
                 }),
             Err(response) => response,
         };
+        if let Some(permit) = permit {
+            permit.finish(&final_response);
+        }
+        if let Some(cost) = pricing::add_cost(&node, &mut final_response) {
+            call_cost += cost;
+            if let Some(budget) = &ctx.cost_budget {
+                budget.record(cost);
+            }
+        }
 
         let parsed_response = match &final_response {
             LLMResponse::Success(s) => {
@@ -124,7 +157,7 @@ where
         };
         // parsed_response.map(|r| r.and_then(|v| parsed_value_to_response(v)));
         let sleep_duration = node.error_sleep_duration().cloned();
-        results.push((node.scope, final_response, parsed_response, response_value));
+        results.push((scope, final_response, parsed_response, response_value));
 
         // Currently, we break out of the loop if an LLM responded, even if we couldn't parse the result.
         if results
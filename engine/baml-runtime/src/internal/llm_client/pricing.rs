@@ -0,0 +1,246 @@
+use internal_llm_client::ModelPricing;
+
+use super::{traits::WithClientProperties, LLMCompleteResponseMetadata, LLMResponse};
+
+/// Prices in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Prices {
+    input: f64,
+    output: f64,
+    cache_read: f64,
+    cache_write: f64,
+}
+
+const fn prices(input: f64, output: f64, cache_read: f64, cache_write: f64) -> Prices {
+    Prices {
+        input,
+        output,
+        cache_read,
+        cache_write,
+    }
+}
+
+/// Default prices of models. Models match their releases in order, see
+/// `is_version_of`, so more specific names must come first. Models without a
+/// cache discount or surcharge price cached tokens like other prompt tokens.
+const BUILTIN_PRICING: &[(&str, Prices)] = &[
+    // OpenAI
+    ("gpt-4o-mini", prices(0.15, 0.60, 0.075, 0.15)),
+    ("gpt-4o", prices(2.50, 10.00, 1.25, 2.50)),
+    ("gpt-4-turbo", prices(10.00, 30.00, 10.00, 10.00)),
+    ("gpt-4-0125", prices(10.00, 30.00, 10.00, 10.00)),
+    ("gpt-4-1106", prices(10.00, 30.00, 10.00, 10.00)),
+    ("gpt-4-32k", prices(60.00, 120.00, 60.00, 60.00)),
+    ("gpt-4", prices(30.00, 60.00, 30.00, 30.00)),
+    ("gpt-3.5-turbo", prices(0.50, 1.50, 0.50, 0.50)),
+    ("o1-mini", prices(1.10, 4.40, 0.55, 1.10)),
+    ("o1", prices(15.00, 60.00, 7.50, 15.00)),
+    ("o3-mini", prices(1.10, 4.40, 0.55, 1.10)),
+    // Anthropic
+    ("claude-3-5-sonnet", prices(3.00, 15.00, 0.30, 3.75)),
+    ("claude-3-5-haiku", prices(0.80, 4.00, 0.08, 1.00)),
+    ("claude-3-opus", prices(15.00, 75.00, 1.50, 18.75)),
+    ("claude-3-sonnet", prices(3.00, 15.00, 0.30, 3.75)),
+    ("claude-3-haiku", prices(0.25, 1.25, 0.03, 0.30)),
+    // Google
+    ("gemini-2.0-flash", prices(0.10, 0.40, 0.025, 0.10)),
+    ("gemini-1.5-pro", prices(1.25, 5.00, 0.3125, 1.25)),
+    ("gemini-1.5-flash-8b", prices(0.0375, 0.15, 0.01, 0.0375)),
+    ("gemini-1.5-flash", prices(0.075, 0.30, 0.01875, 0.075)),
+];
+
+/// Whether `model` is `name` or a dated, `-latest` or `-preview` release of
+/// it, e.g. `gpt-4-0613` or `claude-3-5-sonnet@20240620` for `gpt-4` and
+/// `claude-3-5-sonnet`, but not `gpt-4.1`, `gpt-4o` or `o1-pro` for `gpt-4`
+/// and `o1`. Other models are priced differently.
+pub(super) fn is_version_of(model: &str, name: &str) -> bool {
+    let Some(rest) = model.strip_prefix(name) else {
+        return false;
+    };
+    let rest = ["-latest", "-preview"]
+        .iter()
+        .find_map(|tag| rest.strip_prefix(tag))
+        .unwrap_or(rest);
+    rest.is_empty()
+        || rest.starts_with(['@', ':'])
+        || rest
+            .strip_prefix('-')
+            .is_some_and(|date| date.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn builtin_pricing(model: &str) -> Option<Prices> {
+    // Providers prefix model names, e.g. `models/gemini-1.5-pro` on Google AI
+    // or `anthropic.claude-3-5-sonnet-20240620-v1:0` on Bedrock.
+    let model = model.rsplit('/').next().unwrap_or(model);
+    BUILTIN_PRICING
+        .iter()
+        .find(|(name, _)| {
+            is_version_of(model, name)
+                || model
+                    .split_once('.')
+                    .is_some_and(|(_, rest)| is_version_of(rest, name))
+        })
+        .map(|(_, prices)| *prices)
+}
+
+/// The cost of a call in USD, if both the token usage and the prices of the
+/// model are known. Prices set on the client take precedence over the
+/// built-in ones. Cached prompt tokens are priced at the cache read and write
+/// prices, or like other prompt tokens if those are unknown.
+pub fn cost(
+    pricing: &ModelPricing,
+    model: &str,
+    metadata: &LLMCompleteResponseMetadata,
+) -> Option<f64> {
+    let builtin = builtin_pricing(model);
+    let input_price = pricing
+        .input_per_million_tokens
+        .or(builtin.map(|p| p.input))?;
+    let output_price = pricing
+        .output_per_million_tokens
+        .or(builtin.map(|p| p.output))?;
+    let cache_read_price = pricing
+        .cache_read_per_million_tokens
+        .or(builtin.map(|p| p.cache_read))
+        .unwrap_or(input_price);
+    let cache_write_price = pricing
+        .cache_write_per_million_tokens
+        .or(builtin.map(|p| p.cache_write))
+        .unwrap_or(input_price);
+
+    // Providers count cached tokens as part of the prompt.
+    let input_tokens = metadata.prompt_tokens?;
+    let cache_read_tokens = metadata.cache_read_input_tokens.unwrap_or(0);
+    let cache_write_tokens = metadata.cache_creation_input_tokens.unwrap_or(0);
+    let uncached_tokens = input_tokens.saturating_sub(cache_read_tokens + cache_write_tokens);
+    let output_tokens = metadata.output_tokens.or_else(|| {
+        metadata
+            .total_tokens
+            .map(|total| total.saturating_sub(input_tokens))
+    })?;
+
+    Some(
+        (uncached_tokens as f64 * input_price
+            + cache_read_tokens as f64 * cache_read_price
+            + cache_write_tokens as f64 * cache_write_price
+            + output_tokens as f64 * output_price)
+            / 1_000_000.0,
+    )
+}
+
+/// Fills in the cost of a successful response from `client`'s pricing.
+pub fn add_cost(client: &impl WithClientProperties, response: &mut LLMResponse) -> Option<f64> {
+    let LLMResponse::Success(response) = response else {
+        return None;
+    };
+    response.metadata.cost = cost(client.pricing(), &response.model, &response.metadata);
+    response.metadata.cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(prompt_tokens: u64, output_tokens: u64) -> LLMCompleteResponseMetadata {
+        LLMCompleteResponseMetadata {
+            baml_is_complete: true,
+            finish_reason: None,
+            prompt_tokens: Some(prompt_tokens),
+            output_tokens: Some(output_tokens),
+            total_tokens: Some(prompt_tokens + output_tokens),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+            cost: None,
+        }
+    }
+
+    #[test]
+    fn test_builtin_pricing() {
+        let cost = cost(
+            &ModelPricing::default(),
+            "gpt-4o-mini-2024-07-18",
+            &usage(1_000_000, 1_000),
+        );
+        assert!((cost.unwrap() - 0.1506).abs() < 1e-12);
+
+        assert_eq!(
+            builtin_pricing("anthropic.claude-3-5-sonnet-20240620-v1:0"),
+            Some(prices(3.00, 15.00, 0.30, 3.75))
+        );
+        assert_eq!(
+            builtin_pricing("models/gemini-1.5-flash-8b"),
+            Some(prices(0.0375, 0.15, 0.01, 0.0375))
+        );
+        assert_eq!(builtin_pricing("llama3.1"), None);
+
+        // Newer models aren't priced as the older ones they start with.
+        assert_eq!(
+            builtin_pricing("gpt-4-0613"),
+            Some(prices(30.00, 60.00, 30.00, 30.00))
+        );
+        assert_eq!(builtin_pricing("gpt-4.1-mini"), None);
+        assert_eq!(builtin_pricing("gpt-4.5-preview"), None);
+        assert_eq!(builtin_pricing("o1-pro"), None);
+        assert_eq!(
+            builtin_pricing("o1-preview-2024-09-12"),
+            Some(prices(15.00, 60.00, 7.50, 15.00))
+        );
+        assert_eq!(
+            builtin_pricing("claude-3-5-sonnet@20240620"),
+            Some(prices(3.00, 15.00, 0.30, 3.75))
+        );
+    }
+
+    #[test]
+    fn test_client_pricing_overrides_builtin() {
+        let pricing = ModelPricing {
+            input_per_million_tokens: Some(1.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            cost(&pricing, "gpt-4o", &usage(2_000_000, 100_000)),
+            Some(2.0 + 1.0)
+        );
+
+        // Unknown model without both prices set.
+        assert_eq!(cost(&pricing, "my-finetune", &usage(10, 10)), None);
+    }
+
+    #[test]
+    fn test_cached_tokens() {
+        // 1M prompt tokens, of which 600k were read from the cache and 300k
+        // written to it.
+        let metadata = LLMCompleteResponseMetadata {
+            cache_read_input_tokens: Some(600_000),
+            cache_creation_input_tokens: Some(300_000),
+            ..usage(1_000_000, 0)
+        };
+        let cost_anthropic = cost(
+            &ModelPricing::default(),
+            "claude-3-5-sonnet-latest",
+            &metadata,
+        );
+        assert!((cost_anthropic.unwrap() - (0.1 * 3.00 + 0.6 * 0.30 + 0.3 * 3.75)).abs() < 1e-12);
+
+        let metadata_openai = LLMCompleteResponseMetadata {
+            cache_read_input_tokens: Some(500_000),
+            ..usage(1_000_000, 0)
+        };
+        let cost_openai = cost(&ModelPricing::default(), "gpt-4o", &metadata_openai);
+        assert!((cost_openai.unwrap() - (0.5 * 2.50 + 0.5 * 1.25)).abs() < 1e-12);
+
+        // Without cache prices, cached tokens cost as much as other ones.
+        let pricing = ModelPricing {
+            input_per_million_tokens: Some(1.0),
+            output_per_million_tokens: Some(1.0),
+            ..Default::default()
+        };
+        assert_eq!(cost(&pricing, "my-finetune", &metadata), Some(1.0));
+
+        let pricing = ModelPricing {
+            cache_read_per_million_tokens: Some(0.5),
+            ..pricing
+        };
+        assert_eq!(cost(&pricing, "my-finetune", &metadata), Some(0.4 + 0.3));
+    }
+}
@@ -5,7 +5,7 @@ use crate::internal::llm_client::{
 use std::collections::HashMap;
 
 use anyhow::{Context, Result};
-use baml_types::{BamlMap, BamlMedia, BamlMediaContent};
+use baml_types::{BamlMap, BamlMedia, BamlMediaContent, BamlMediaType};
 use eventsource_stream::Eventsource;
 use futures::StreamExt;
 use internal_baml_core::ir::ClientWalker;
@@ -89,6 +89,21 @@ impl WithClientProperties for AnthropicClient {
     fn finish_reason_filter(&self) -> &internal_llm_client::FinishReasonFilter {
         &self.properties.finish_reason_filter
     }
+    fn rate_limits(&self) -> &internal_llm_client::RateLimits {
+        &self.properties.rate_limits
+    }
+    fn pricing(&self) -> &internal_llm_client::ModelPricing {
+        &self.properties.pricing
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.properties.context_window
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.properties
+            .properties
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+    }
     fn default_role(&self) -> String {
         self.properties.default_role()
     }
@@ -143,6 +158,9 @@ impl SseResponseTrait for AnthropicClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -183,10 +201,13 @@ impl SseResponseTrait for AnthropicClient {
                                 );
                                 inner.finish_reason =
                                     body.stop_reason.as_ref().map(ToString::to_string);
-                                inner.prompt_tokens = Some(body.usage.input_tokens);
+                                inner.prompt_tokens = Some(body.usage.prompt_tokens());
                                 inner.output_tokens = Some(body.usage.output_tokens);
                                 inner.total_tokens =
-                                    Some(body.usage.input_tokens + body.usage.output_tokens);
+                                    Some(body.usage.prompt_tokens() + body.usage.output_tokens);
+                                inner.cache_creation_input_tokens =
+                                    body.usage.cache_creation_input_tokens;
+                                inner.cache_read_input_tokens = body.usage.cache_read_input_tokens;
                             }
                             MessageChunk::ContentBlockDelta(event) => {
                                 inner.content += &event.delta.text;
@@ -246,11 +267,11 @@ impl SseResponseTrait for AnthropicClient {
 impl WithStreamChat for AnthropicClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &[RenderedChatMessage],
     ) -> StreamResponse {
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -269,6 +290,11 @@ impl AnthropicClient {
                 provider: client.provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: properties
+                    .properties
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .map(String::from),
             },
             features: ModelFeatures {
                 chat: true,
@@ -292,6 +318,11 @@ impl AnthropicClient {
                 provider: client.elem().provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: properties
+                    .properties
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .map(String::from),
             },
             features: ModelFeatures {
                 chat: true,
@@ -354,6 +385,14 @@ impl RequestBuilder for AnthropicClient {
             }
             either::Either::Right(messages) => {
                 body_obj.extend(self.chat_to_message(messages)?);
+                let breakpoints = count_cache_breakpoints(body_obj)?;
+                if breakpoints > MAX_CACHE_BREAKPOINTS {
+                    anyhow::bail!(
+                        "Anthropic allows at most {} cache breakpoints per request, got {}",
+                        MAX_CACHE_BREAKPOINTS,
+                        breakpoints
+                    );
+                }
             }
         }
 
@@ -370,11 +409,11 @@ impl RequestBuilder for AnthropicClient {
 }
 
 impl WithChat for AnthropicClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         let (response, system_now, instant_now) = match make_parsed_request::<
             AnthropicMessageResponse,
         >(
-            self, either::Either::Right(prompt), false
+            self, ctx, either::Either::Right(prompt), false
         )
         .await
         {
@@ -415,9 +454,12 @@ impl WithChat for AnthropicClient {
                     .stop_reason
                     .as_ref()
                     .map(|r| serde_json::to_string(r).unwrap_or("".into())),
-                prompt_tokens: Some(response.usage.input_tokens),
+                prompt_tokens: Some(response.usage.prompt_tokens()),
                 output_tokens: Some(response.usage.output_tokens),
-                total_tokens: Some(response.usage.input_tokens + response.usage.output_tokens),
+                total_tokens: Some(response.usage.prompt_tokens() + response.usage.output_tokens),
+                cache_creation_input_tokens: response.usage.cache_creation_input_tokens,
+                cache_read_input_tokens: response.usage.cache_read_input_tokens,
+                cost: None,
             },
         })
     }
@@ -439,9 +481,18 @@ impl ToProviderMessage for AnthropicClient {
         mut content: serde_json::Map<String, serde_json::Value>,
         media: &baml_types::BamlMedia,
     ) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let block_type = match media.media_type {
+            BamlMediaType::Image => "image",
+            // Anthropic accepts PDFs as "document" content blocks.
+            BamlMediaType::Pdf => "document",
+            BamlMediaType::Audio | BamlMediaType::Video => anyhow::bail!(
+                "Anthropic does not support {} inputs; only image and pdf media can be sent to this client",
+                media.media_type
+            ),
+        };
         match &media.content {
             BamlMediaContent::Base64(data) => {
-                content.insert("type".into(), media.media_type.to_string().into());
+                content.insert("type".into(), block_type.into());
                 let mut source = serde_json::Map::new();
                 source.insert("type".into(), "base64".into());
                 source.insert("media_type".into(), media.mime_type_as_ok()?.into());
@@ -474,6 +525,79 @@ impl ToProviderMessage for AnthropicClient {
         );
         Ok(map)
     }
+
+    // Anthropic only caches prompts up to explicit breakpoints, so they are
+    // always sent, whatever `allowed_role_metadata` says.
+    fn cache_control_to_message(
+        &self,
+        cache_control: &serde_json::Value,
+    ) -> Result<Option<serde_json::Value>> {
+        validate_cache_control(cache_control)?;
+        Ok(Some(cache_control.clone()))
+    }
+}
+
+/// Anthropic allows at most this many `cache_control` blocks per request.
+const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+// https://docs.anthropic.com/en/docs/build-with-claude/prompt-caching
+fn validate_cache_control(cache_control: &serde_json::Value) -> Result<()> {
+    let Some(obj) = cache_control.as_object() else {
+        anyhow::bail!(
+            "Anthropic cache_control must be an object like {{\"type\": \"ephemeral\"}}, got {}",
+            cache_control
+        );
+    };
+    for (key, value) in obj {
+        match (key.as_str(), value.as_str()) {
+            ("type", Some("ephemeral")) => (),
+            ("type", _) => anyhow::bail!(
+                "Anthropic cache_control type must be \"ephemeral\", got {}",
+                value
+            ),
+            ("ttl", Some("5m" | "1h")) => (),
+            ("ttl", _) => anyhow::bail!(
+                "Anthropic cache_control ttl must be \"5m\" or \"1h\", got {}",
+                value
+            ),
+            (key, _) => anyhow::bail!("Unknown key in Anthropic cache_control: {}", key),
+        }
+    }
+    if !obj.contains_key("type") {
+        anyhow::bail!("Anthropic cache_control is missing \"type\": \"ephemeral\"");
+    }
+    Ok(())
+}
+
+/// Validates the cache breakpoints of a request body, including any set on
+/// `tools` in the client options, and returns how many there are.
+///
+/// Only the blocks Anthropic reads `cache_control` from are inspected: the
+/// `system` blocks, the content blocks of `messages` and the `tools`. A
+/// `cache_control` key anywhere else, e.g. in a tool's `input_schema`, is
+/// just data.
+fn count_cache_breakpoints(body: &serde_json::Map<String, serde_json::Value>) -> Result<usize> {
+    fn as_array(value: Option<&serde_json::Value>) -> &[serde_json::Value] {
+        value
+            .and_then(|v| v.as_array())
+            .map(|items| items.as_slice())
+            .unwrap_or_default()
+    }
+
+    let system = as_array(body.get("system"));
+    let content = as_array(body.get("messages"))
+        .iter()
+        .flat_map(|message| as_array(message.get("content")));
+    let tools = as_array(body.get("tools"));
+
+    let mut count = 0;
+    for block in system.iter().chain(content).chain(tools) {
+        if let Some(cache_control) = block.get("cache_control") {
+            validate_cache_control(cache_control)?;
+            count += 1;
+        }
+    }
+    Ok(count)
 }
 
 impl ToProviderMessageExt for AnthropicClient {
@@ -520,3 +644,45 @@ fn convert_completion_prompt_to_body(prompt: &String) -> HashMap<String, serde_j
     map.insert("prompt".into(), json!(prompt));
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_breakpoints() -> Result<()> {
+        validate_cache_control(&json!({ "type": "ephemeral" }))?;
+        validate_cache_control(&json!({ "type": "ephemeral", "ttl": "1h" }))?;
+        assert!(validate_cache_control(&json!("ephemeral")).is_err());
+        assert!(validate_cache_control(&json!({ "ttl": "5m" })).is_err());
+        assert!(validate_cache_control(&json!({ "type": "persistent" })).is_err());
+        assert!(validate_cache_control(&json!({ "type": "ephemeral", "ttl": "1d" })).is_err());
+        assert!(validate_cache_control(&json!({ "type": "ephemeral", "scope": "org" })).is_err());
+
+        let body = json!({
+            "tools": [{
+                "name": "search",
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "cache_control": { "type": "string" } },
+                },
+                "cache_control": { "type": "ephemeral" },
+            }],
+            "system": [{ "type": "text", "text": "Hi", "cache_control": { "type": "ephemeral" } }],
+            "messages": [{
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": "Doc", "cache_control": { "type": "ephemeral" } },
+                    { "type": "text", "text": "Question" },
+                ],
+            }],
+        });
+        assert_eq!(count_cache_breakpoints(body.as_object().unwrap())?, 3);
+
+        let body =
+            json!({ "tools": [{ "name": "search", "cache_control": { "type": "always" } }] });
+        assert!(count_cache_breakpoints(body.as_object().unwrap()).is_err());
+
+        Ok(())
+    }
+}
@@ -9,8 +9,23 @@ pub struct AnthropicMessageContent {
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct AnthropicUsage {
+    /// The input tokens after the last cache breakpoint, which were neither
+    /// read from nor written to the cache.
     pub input_tokens: u64,
     pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    pub cache_read_input_tokens: Option<u64>,
+}
+
+impl AnthropicUsage {
+    /// All input tokens, cached or not, like `prompt_tokens` on OpenAI.
+    pub fn prompt_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.cache_creation_input_tokens.unwrap_or(0)
+            + self.cache_read_input_tokens.unwrap_or(0)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -190,4 +205,21 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn deserialize_cache_usage() -> Result<()> {
+        let usage: AnthropicUsage = serde_json::from_str(
+            r#"{"input_tokens":21,"cache_creation_input_tokens":188086,"cache_read_input_tokens":0,"output_tokens":393}"#,
+        )?;
+        assert_eq!(usage.cache_creation_input_tokens, Some(188086));
+        assert_eq!(usage.cache_read_input_tokens, Some(0));
+        assert_eq!(usage.prompt_tokens(), 188107);
+
+        let usage: AnthropicUsage =
+            serde_json::from_str(r#"{"input_tokens":21,"output_tokens":393}"#)?;
+        assert_eq!(usage.cache_creation_input_tokens, None);
+        assert_eq!(usage.prompt_tokens(), 21);
+
+        Ok(())
+    }
 }
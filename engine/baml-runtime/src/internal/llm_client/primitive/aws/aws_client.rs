@@ -24,18 +24,20 @@ use web_time::Instant;
 use web_time::SystemTime;
 
 use crate::client_registry::ClientProperty;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::internal::llm_client::primitive::cassette::Cassette;
 use crate::internal::llm_client::traits::{ToProviderMessageExt, WithClientProperties};
 use crate::internal::llm_client::{
     primitive::request::RequestBuilder,
     traits::{
-        StreamResponse, WithChat, WithClient, WithNoCompletion, WithRenderRawCurl, WithRetryPolicy,
-        WithStreamChat,
+        StreamResponse, WithChat, WithClient, WithNoCompletion, WithRenderHttpRequest,
+        WithRenderRawCurl, WithRetryPolicy, WithStreamChat,
     },
     ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata, LLMErrorResponse, LLMResponse,
     ModelFeatures, ResolveMediaUrls,
 };
 
-use crate::{RenderCurlSettings, RuntimeContext};
+use crate::{HttpRequest, RenderCurlSettings, RuntimeContext};
 
 // represents client that interacts with the Anthropic API
 pub struct AwsClient {
@@ -73,6 +75,7 @@ impl AwsClient {
                 provider: client.provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: Some(properties.model.clone()),
             },
             features: ModelFeatures {
                 chat: true,
@@ -96,6 +99,7 @@ impl AwsClient {
                 provider: client.elem().provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: Some(properties.model.clone()),
             },
             features: ModelFeatures {
                 chat: true,
@@ -265,6 +269,21 @@ impl WithRenderRawCurl for AwsClient {
     }
 }
 
+impl WithRenderHttpRequest for AwsClient {
+    async fn render_http_request(
+        &self,
+        _ctx: &RuntimeContext,
+        _prompt: &internal_baml_jinja::RenderedPrompt,
+        _stream: bool,
+    ) -> Result<HttpRequest> {
+        // The AWS SDK builds, signs and sends Bedrock requests itself, and
+        // doesn't expose the request it would send.
+        anyhow::bail!(
+            "Building requests without sending them is not supported for the aws-bedrock provider"
+        )
+    }
+}
+
 // getters for client info
 impl WithRetryPolicy for AwsClient {
     fn retry_policy_name(&self) -> Option<&str> {
@@ -285,6 +304,22 @@ impl WithClientProperties for AwsClient {
     fn finish_reason_filter(&self) -> &internal_llm_client::FinishReasonFilter {
         &self.properties.finish_reason_filter
     }
+    fn rate_limits(&self) -> &internal_llm_client::RateLimits {
+        &self.properties.rate_limits
+    }
+    fn pricing(&self) -> &internal_llm_client::ModelPricing {
+        &self.properties.pricing
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.properties.context_window
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.properties
+            .inference_config
+            .as_ref()
+            .and_then(|config| config.max_tokens)
+            .and_then(|t| u64::try_from(t).ok())
+    }
     fn default_role(&self) -> String {
         self.properties.default_role()
     }
@@ -317,6 +352,20 @@ impl WithStreamChat for AwsClient {
         let request_options = Default::default();
         let prompt = internal_baml_jinja::RenderedPrompt::Chat(chat_messages.to_vec());
 
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = Cassette::ensure_off(ctx.env_vars(), "aws-bedrock") {
+            return Err(LLMResponse::LLMFailure(LLMErrorResponse {
+                client,
+                model,
+                prompt,
+                start_time: SystemTime::now(),
+                request_options,
+                latency: web_time::Duration::ZERO,
+                message: format!("{:#?}", e),
+                code: ErrorCode::Other(2),
+            }));
+        }
+
         let aws_client = match self.client_anyhow().await {
             Ok(c) => c,
             Err(e) => {
@@ -416,6 +465,9 @@ impl WithStreamChat for AwsClient {
                         prompt_tokens: None,
                         output_tokens: None,
                         total_tokens: None,
+                        cache_creation_input_tokens: None,
+                        cache_read_input_tokens: None,
+                        cost: None,
                     },
                 }),
                 response,
@@ -508,10 +560,10 @@ impl AwsClient {
         &self,
         media: &baml_types::BamlMedia,
     ) -> Result<bedrock::types::ContentBlock> {
-        if media.media_type != BamlMediaType::Image {
+        if !matches!(media.media_type, BamlMediaType::Image | BamlMediaType::Pdf) {
             anyhow::bail!(
-                "AWS supports images, but does not support this media type: {:#?}",
-                media
+                "AWS Bedrock does not support {} inputs; only image and pdf media can be sent to this client",
+                media.media_type
             )
         }
         match &media.content {
@@ -525,6 +577,19 @@ impl AwsClient {
                     "BAML internal error (AWSBedrock): media URL should have been resolved to base64"
                 )
             }
+            BamlMediaContent::Base64(b64_media) if media.media_type == BamlMediaType::Pdf => {
+                Ok(bedrock::types::ContentBlock::Document(
+                    bedrock::types::DocumentBlock::builder()
+                        .set_format(Some(bedrock::types::DocumentFormat::Pdf))
+                        // Bedrock requires a name for every document block.
+                        .set_name(Some("document".to_string()))
+                        .set_source(Some(bedrock::types::DocumentSource::Bytes(Blob::new(
+                            aws_smithy_types::base64::decode(b64_media.base64.clone())?,
+                        ))))
+                        .build()
+                        .context("Failed to build document block")?,
+                ))
+            }
             BamlMediaContent::Base64(b64_media) => Ok(bedrock::types::ContentBlock::Image(
                 bedrock::types::ImageBlock::builder()
                     .set_format(Some(bedrock::types::ImageFormat::from(
@@ -609,6 +674,20 @@ impl WithChat for AwsClient {
         let request_options = Default::default();
         let prompt = internal_baml_jinja::RenderedPrompt::Chat(chat_messages.to_vec());
 
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = Cassette::ensure_off(_ctx.env_vars(), "aws-bedrock") {
+            return LLMResponse::LLMFailure(LLMErrorResponse {
+                client,
+                model,
+                prompt,
+                start_time: SystemTime::now(),
+                request_options,
+                latency: web_time::Duration::ZERO,
+                message: format!("{:#?}", e),
+                code: ErrorCode::Other(2),
+            });
+        }
+
         let aws_client = match self.client_anyhow().await {
             Ok(c) => c,
             Err(e) => {
@@ -695,6 +774,9 @@ impl WithChat for AwsClient {
                         .usage
                         .as_ref()
                         .and_then(|i| i.total_tokens.try_into().ok()),
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
+                    cost: None,
                 },
             }),
             Err(e) => LLMResponse::LLMFailure(LLMErrorResponse {
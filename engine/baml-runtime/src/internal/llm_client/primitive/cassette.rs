@@ -0,0 +1,289 @@
+//! Record/replay of provider HTTP traffic, so that tests can run against real
+//! prompts without network access.
+//!
+//! Set `BAML_CASSETTE_MODE` to `record` to save every provider response to the
+//! cassette at `BAML_CASSETTE`, or to `replay` to serve requests from it.
+//! Requests are matched on method, URL (without its query string) and
+//! normalized JSON body. Headers are never recorded, so API keys stay out of
+//! cassettes.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+
+const MODE_ENV_VAR: &str = "BAML_CASSETTE_MODE";
+const PATH_ENV_VAR: &str = "BAML_CASSETTE";
+const DEFAULT_PATH: &str = "baml_cassette.json";
+
+/// Serializes writes to cassettes, so that concurrent requests don't drop
+/// each other's interactions.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CassetteMode {
+    Record,
+    Replay,
+}
+
+pub(super) struct Cassette {
+    mode: CassetteMode,
+    path: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct RecordedRequest {
+    method: String,
+    url: String,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    /// The raw body. For streams, this is the whole event stream.
+    body: String,
+}
+
+impl Cassette {
+    /// The cassette configured by the environment, if recording or replaying
+    /// is turned on.
+    pub(super) fn from_env(env: &HashMap<String, String>) -> Result<Option<Self>> {
+        let mode = match env.get(MODE_ENV_VAR).map(|m| m.trim()) {
+            None | Some("") | Some("off") => return Ok(None),
+            Some("record") => CassetteMode::Record,
+            Some("replay") => CassetteMode::Replay,
+            Some(other) => anyhow::bail!(
+                "Invalid {MODE_ENV_VAR}: {other}. Expected one of: record, replay, off"
+            ),
+        };
+        let path = env
+            .get(PATH_ENV_VAR)
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_PATH));
+        Ok(Some(Self { mode, path }))
+    }
+
+    pub(super) fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Fails if a cassette is configured. For clients that don't send their
+    /// requests through reqwest, so that they are not silently sent for real
+    /// while recording or replaying.
+    pub(super) fn ensure_off(env: &HashMap<String, String>, provider: &str) -> Result<()> {
+        match Self::from_env(env)? {
+            None => Ok(()),
+            Some(_) => anyhow::bail!(
+                "{provider} clients can't be recorded or replayed. Unset {MODE_ENV_VAR} or set it to off"
+            ),
+        }
+    }
+
+    /// Serves a request from the cassette. It is an error for the request to
+    /// not have been recorded.
+    pub(super) fn replay(&self, request: &RecordedRequest) -> Result<reqwest::Response> {
+        let file = self.load()?.with_context(|| {
+            format!(
+                "Cassette {} does not exist. Record it first with {MODE_ENV_VAR}=record",
+                self.path.display()
+            )
+        })?;
+        let interaction = file
+            .interactions
+            .into_iter()
+            .find(|i| &i.request == request)
+            .with_context(|| {
+                format!(
+                    "No response recorded in cassette {} for {} {}",
+                    self.path.display(),
+                    request.method,
+                    request.url
+                )
+            })?;
+        interaction.response.into_response()
+    }
+
+    /// Saves the response to the cassette, replacing any earlier recording of
+    /// the same request, and returns an equivalent response to read from.
+    pub(super) async fn record(
+        &self,
+        request: RecordedRequest,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response> {
+        let recorded = RecordedResponse {
+            status: response.status().as_u16(),
+            content_type: response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from),
+            body: response
+                .text()
+                .await
+                .context("Failed to read the response to record")?,
+        };
+
+        {
+            let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut file = self.load()?.unwrap_or_default();
+            file.interactions.retain(|i| i.request != request);
+            file.interactions.push(Interaction {
+                request,
+                response: recorded.clone(),
+            });
+            if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&self.path, serde_json::to_string_pretty(&file)?)
+                .with_context(|| format!("Failed to write cassette {}", self.path.display()))?;
+        }
+
+        recorded.into_response()
+    }
+
+    fn load(&self) -> Result<Option<CassetteFile>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read cassette {}", self.path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse cassette {}", self.path.display()))
+            .map(Some)
+    }
+}
+
+impl RecordedRequest {
+    pub(super) fn new(req: &reqwest::Request) -> Self {
+        let mut url = req.url().clone();
+        url.set_query(None);
+        url.set_fragment(None);
+
+        let body = match req.body().and_then(|b| b.as_bytes()) {
+            Some(bytes) => match serde_json::from_slice(bytes) {
+                Ok(json) => normalize(json),
+                Err(_) => serde_json::Value::String(String::from_utf8_lossy(bytes).into_owned()),
+            },
+            None => serde_json::Value::Null,
+        };
+
+        Self {
+            method: req.method().to_string(),
+            url: url.to_string(),
+            body,
+        }
+    }
+}
+
+impl RecordedResponse {
+    fn into_response(self) -> Result<reqwest::Response> {
+        let mut builder = http::Response::builder().status(self.status);
+        if let Some(content_type) = self.content_type {
+            builder = builder.header(CONTENT_TYPE, content_type);
+        }
+        Ok(builder.body(self.body)?.into())
+    }
+}
+
+/// Sorts object keys, so that requests match regardless of the order in which
+/// the provider clients wrote their fields.
+fn normalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, normalize(v)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(normalize).collect())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(body: &str) -> reqwest::Request {
+        reqwest::Client::new()
+            .post("https://api.openai.com/v1/chat/completions?api-version=1")
+            .body(body.to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_requests_match_regardless_of_key_order() {
+        let a = RecordedRequest::new(&request(r#"{"model": "gpt-4o", "stream": true}"#));
+        let b = RecordedRequest::new(&request(r#"{"stream": true, "model": "gpt-4o"}"#));
+        assert_eq!(a, b);
+        assert_eq!(a.url, "https://api.openai.com/v1/chat/completions");
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("baml-cassette-{}.json", uuid::Uuid::new_v4()));
+        let env = HashMap::from([
+            (MODE_ENV_VAR.to_string(), "record".to_string()),
+            (PATH_ENV_VAR.to_string(), path.display().to_string()),
+        ]);
+        let recorder = Cassette::from_env(&env)?.unwrap();
+        let req = RecordedRequest::new(&request(r#"{"model": "gpt-4o"}"#));
+
+        let live: reqwest::Response = http::Response::builder()
+            .status(200)
+            .header(CONTENT_TYPE, "text/event-stream")
+            .body("data: {\"a\": 1}\n\n")?
+            .into();
+        let recorded = recorder.record(req.clone(), live).await?;
+        assert_eq!(recorded.text().await?, "data: {\"a\": 1}\n\n");
+
+        let mut env = env;
+        env.insert(MODE_ENV_VAR.to_string(), "replay".to_string());
+        let player = Cassette::from_env(&env)?.unwrap();
+        let replayed = player.replay(&req)?;
+        assert_eq!(replayed.status(), 200);
+        assert_eq!(replayed.headers()[CONTENT_TYPE], "text/event-stream");
+        assert_eq!(replayed.text().await?, "data: {\"a\": 1}\n\n");
+
+        let miss = RecordedRequest::new(&request(r#"{"model": "gpt-4o-mini"}"#));
+        assert!(player.replay(&miss).is_err());
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_ensure_off() {
+        assert!(Cassette::ensure_off(&HashMap::new(), "aws-bedrock").is_ok());
+
+        let env = HashMap::from([(MODE_ENV_VAR.to_string(), "replay".to_string())]);
+        let err = Cassette::ensure_off(&env, "aws-bedrock").unwrap_err();
+        assert!(err.to_string().contains("aws-bedrock"));
+    }
+}
@@ -78,6 +78,22 @@ impl WithClientProperties for GoogleAIClient {
     fn finish_reason_filter(&self) -> &internal_llm_client::FinishReasonFilter {
         &self.properties.finish_reason_filter
     }
+    fn rate_limits(&self) -> &internal_llm_client::RateLimits {
+        &self.properties.rate_limits
+    }
+    fn pricing(&self) -> &internal_llm_client::ModelPricing {
+        &self.properties.pricing
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.properties.context_window
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.properties
+            .properties
+            .get("generationConfig")
+            .and_then(|config| config.get("maxOutputTokens"))
+            .and_then(|v| v.as_u64())
+    }
     fn default_role(&self) -> String {
         self.properties.default_role()
     }
@@ -135,6 +151,9 @@ impl SseResponseTrait for GoogleAIClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -187,12 +206,12 @@ impl SseResponseTrait for GoogleAIClient {
 impl WithStreamChat for GoogleAIClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &[RenderedChatMessage],
     ) -> StreamResponse {
         //incomplete, streaming response object is returned
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -210,6 +229,7 @@ impl GoogleAIClient {
                 provider: client.elem().provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: Some(properties.model.clone()),
             },
             features: ModelFeatures {
                 chat: true,
@@ -238,6 +258,7 @@ impl GoogleAIClient {
                 provider: client.provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: Some(properties.model.clone()),
             },
             features: ModelFeatures {
                 chat: true,
@@ -310,15 +331,19 @@ impl RequestBuilder for GoogleAIClient {
 }
 
 impl WithChat for GoogleAIClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         //non-streaming, complete response is returned
-        let (response, system_now, instant_now) =
-            match make_parsed_request::<GoogleResponse>(self, either::Either::Right(prompt), false)
-                .await
-            {
-                Ok(v) => v,
-                Err(e) => return e,
-            };
+        let (response, system_now, instant_now) = match make_parsed_request::<GoogleResponse>(
+            self,
+            ctx,
+            either::Either::Right(prompt),
+            false,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
 
         if response.candidates.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -369,6 +394,9 @@ impl WithChat for GoogleAIClient {
                 prompt_tokens: response.usage_metadata.prompt_token_count,
                 output_tokens: response.usage_metadata.candidates_token_count,
                 total_tokens: response.usage_metadata.total_token_count,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+                cost: None,
             },
         })
     }
@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use baml_types::BamlMap;
+use futures::StreamExt;
+use internal_baml_core::ir::ClientWalker;
+use internal_baml_jinja::{RenderContext_Client, RenderedChatMessage, RenderedPrompt};
+use internal_llm_client::mock::{MockError, MockErrorKind, MockResponse, ResolvedMock};
+use internal_llm_client::{
+    AllowedRoleMetadata, ClientProvider, FinishReasonFilter, ModelPricing, RateLimits,
+    ResolvedClientProperty, UnresolvedClientProperty,
+};
+
+use crate::client_registry::ClientProperty;
+use crate::internal::llm_client::traits::{
+    StreamResponse, WithChat, WithClient, WithClientProperties, WithCompletion,
+    WithRenderHttpRequest, WithRenderRawCurl, WithRetryPolicy, WithStreamChat,
+    WithStreamCompletion,
+};
+use crate::internal::llm_client::{
+    ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata, LLMErrorResponse, LLMResponse,
+    ModelFeatures, ResolveMediaUrls,
+};
+use crate::{HttpRequest, RenderCurlSettings, RuntimeContext};
+
+/// A client that answers from canned responses instead of calling a model,
+/// so that tests can exercise retries, fallbacks and parsing offline.
+pub struct MockClient {
+    pub name: String,
+    pub retry_policy: Option<String>,
+    pub context: RenderContext_Client,
+    pub features: ModelFeatures,
+    properties: ResolvedMock,
+    request_options: BamlMap<String, serde_json::Value>,
+    /// How many times each set of matching responses has been used, so that
+    /// successive requests step through the set in order.
+    cursors: Mutex<HashMap<Vec<usize>, usize>>,
+}
+
+fn resolve_properties(
+    provider: &ClientProvider,
+    properties: &UnresolvedClientProperty<()>,
+    ctx: &RuntimeContext,
+) -> Result<ResolvedMock> {
+    let properties = properties.resolve(provider, &ctx.eval_ctx(false))?;
+
+    let ResolvedClientProperty::Mock(props) = properties else {
+        anyhow::bail!(
+            "Invalid client property. Should have been a mock property but got: {}",
+            properties.name()
+        );
+    };
+
+    Ok(props)
+}
+
+impl WithRetryPolicy for MockClient {
+    fn retry_policy_name(&self) -> Option<&str> {
+        self.retry_policy.as_deref()
+    }
+}
+
+impl WithClientProperties for MockClient {
+    fn allowed_metadata(&self) -> &AllowedRoleMetadata {
+        &self.properties.allowed_metadata
+    }
+    fn supports_streaming(&self) -> bool {
+        self.properties
+            .supported_request_modes
+            .stream
+            .unwrap_or(true)
+    }
+    fn finish_reason_filter(&self) -> &FinishReasonFilter {
+        &self.properties.finish_reason_filter
+    }
+    fn rate_limits(&self) -> &RateLimits {
+        &self.properties.rate_limits
+    }
+    fn pricing(&self) -> &ModelPricing {
+        &self.properties.pricing
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.properties.context_window
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        None
+    }
+    fn default_role(&self) -> String {
+        self.properties.default_role()
+    }
+    fn allowed_roles(&self) -> Vec<String> {
+        self.properties.allowed_roles()
+    }
+}
+
+impl WithClient for MockClient {
+    fn context(&self) -> &RenderContext_Client {
+        &self.context
+    }
+
+    fn model_features(&self) -> &ModelFeatures {
+        &self.features
+    }
+}
+
+impl WithChat for MockClient {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+        self.respond(ctx, RenderedPrompt::Chat(prompt.to_vec()))
+            .await
+    }
+}
+
+impl WithCompletion for MockClient {
+    fn completion_options(
+        &self,
+        _ctx: &RuntimeContext,
+    ) -> Result<internal_baml_jinja::CompletionOptions> {
+        Ok(internal_baml_jinja::CompletionOptions::new("\n".into()))
+    }
+
+    async fn completion(&self, ctx: &RuntimeContext, prompt: &str) -> LLMResponse {
+        self.respond(ctx, RenderedPrompt::Completion(prompt.to_string()))
+            .await
+    }
+}
+
+impl WithStreamChat for MockClient {
+    async fn stream_chat(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &[RenderedChatMessage],
+    ) -> StreamResponse {
+        self.stream_respond(ctx, RenderedPrompt::Chat(prompt.to_vec()))
+            .await
+    }
+}
+
+impl WithStreamCompletion for MockClient {
+    async fn stream_completion(&self, ctx: &RuntimeContext, prompt: &str) -> StreamResponse {
+        self.stream_respond(ctx, RenderedPrompt::Completion(prompt.to_string()))
+            .await
+    }
+}
+
+impl WithRenderRawCurl for MockClient {
+    async fn render_raw_curl(
+        &self,
+        _ctx: &RuntimeContext,
+        _prompt: &[RenderedChatMessage],
+        _render_settings: RenderCurlSettings,
+    ) -> Result<String> {
+        Ok(format!(
+            "# {} is a mock client: it answers from its configured responses and sends no request.",
+            self.name
+        ))
+    }
+}
+
+impl WithRenderHttpRequest for MockClient {
+    async fn render_http_request(
+        &self,
+        _ctx: &RuntimeContext,
+        _prompt: &RenderedPrompt,
+        _stream: bool,
+    ) -> Result<HttpRequest> {
+        anyhow::bail!("The mock provider does not send HTTP requests")
+    }
+}
+
+impl MockClient {
+    pub fn new(client: &ClientWalker, ctx: &RuntimeContext) -> Result<Self> {
+        let properties = resolve_properties(&client.elem().provider, &client.options(), ctx)?;
+        Ok(Self {
+            name: client.name().into(),
+            context: RenderContext_Client {
+                name: client.name().into(),
+                provider: client.elem().provider.to_string(),
+                default_role: properties.default_role(),
+                allowed_roles: properties.allowed_roles(),
+                model: None,
+            },
+            features: ModelFeatures {
+                chat: true,
+                completion: true,
+                anthropic_system_constraints: false,
+                resolve_media_urls: ResolveMediaUrls::Never,
+                allowed_metadata: properties.allowed_metadata.clone(),
+            },
+            retry_policy: client
+                .elem()
+                .retry_policy_id
+                .as_ref()
+                .map(|s| s.to_string()),
+            properties,
+            request_options: Default::default(),
+            cursors: Default::default(),
+        })
+    }
+
+    pub fn dynamic_new(client: &ClientProperty, ctx: &RuntimeContext) -> Result<Self> {
+        let properties = resolve_properties(&client.provider, &client.unresolved_options()?, ctx)?;
+        Ok(Self {
+            name: client.name.clone(),
+            context: RenderContext_Client {
+                name: client.name.clone(),
+                provider: client.provider.to_string(),
+                default_role: properties.default_role(),
+                allowed_roles: properties.allowed_roles(),
+                model: None,
+            },
+            features: ModelFeatures {
+                chat: true,
+                completion: true,
+                anthropic_system_constraints: false,
+                resolve_media_urls: ResolveMediaUrls::Never,
+                allowed_metadata: properties.allowed_metadata.clone(),
+            },
+            retry_policy: client.retry_policy.clone(),
+            properties,
+            request_options: Default::default(),
+            cursors: Default::default(),
+        })
+    }
+
+    pub fn request_options(&self) -> &BamlMap<String, serde_json::Value> {
+        &self.request_options
+    }
+
+    /// The client's inline responses followed by those in its fixtures file.
+    async fn responses(&self, ctx: &RuntimeContext) -> Result<Vec<MockResponse>> {
+        let mut responses = self.properties.responses.clone();
+        if let Some(path) = &self.properties.fixtures {
+            let Some(ref baml_src_reader) = *ctx.baml_src else {
+                anyhow::bail!("Internal error: no baml src reader provided");
+            };
+            let bytes = baml_src_reader(path.as_str())
+                .await
+                .context(format!("Failed to read mock fixtures {path}"))?;
+            let fixtures: Vec<MockResponse> = serde_json::from_slice(&bytes).context(format!(
+                "Mock fixtures {path} must be a JSON array of responses"
+            ))?;
+            responses.extend(fixtures);
+        }
+        Ok(responses)
+    }
+
+    /// Picks the response for a request.
+    ///
+    /// Responses that match by function name or prompt take precedence over
+    /// ones that match every request. Successive requests that match the same
+    /// responses cycle through them in order.
+    async fn select(&self, ctx: &RuntimeContext, prompt: &RenderedPrompt) -> Result<MockResponse> {
+        let responses = self.responses(ctx).await?;
+        let prompt_text = prompt_text(prompt);
+
+        let mut specific = vec![];
+        let mut catch_all = vec![];
+        for (i, response) in responses.iter().enumerate() {
+            if response.function.is_none() && response.prompt_regex.is_none() {
+                catch_all.push(i);
+                continue;
+            }
+            if let Some(function) = &response.function {
+                if ctx.function_name.as_deref() != Some(function.as_str()) {
+                    continue;
+                }
+            }
+            if let Some(pattern) = &response.prompt_regex {
+                let regex = regex::Regex::new(pattern)
+                    .context(format!("Invalid prompt_regex in mock response {i}"))?;
+                if !regex.is_match(&prompt_text) {
+                    continue;
+                }
+            }
+            specific.push(i);
+        }
+
+        let candidates = if specific.is_empty() {
+            catch_all
+        } else {
+            specific
+        };
+        if candidates.is_empty() {
+            anyhow::bail!(
+                "No mock response of client {} matches function {}",
+                self.name,
+                ctx.function_name.as_deref().unwrap_or("<unknown>")
+            );
+        }
+
+        let index = {
+            let mut cursors = self.cursors.lock().unwrap();
+            let cursor = cursors.entry(candidates.clone()).or_default();
+            let index = candidates[*cursor % candidates.len()];
+            *cursor += 1;
+            index
+        };
+
+        Ok(responses[index].clone())
+    }
+
+    fn failure(
+        &self,
+        prompt: RenderedPrompt,
+        error: &MockError,
+        message: Option<String>,
+        system_start: web_time::SystemTime,
+        instant_start: web_time::Instant,
+    ) -> LLMResponse {
+        let (code, default_message) = match error {
+            MockError::Status(status) => (
+                ErrorCode::from_u16(*status),
+                format!("Mock error with status {status}"),
+            ),
+            MockError::Kind(MockErrorKind::MalformedResponse) => (
+                ErrorCode::Other(2),
+                "Failed to parse into a response accepted by the mock provider".to_string(),
+            ),
+        };
+        LLMResponse::LLMFailure(LLMErrorResponse {
+            client: self.context.name.clone(),
+            model: Some(self.properties.model.clone()),
+            prompt,
+            start_time: system_start,
+            latency: instant_start.elapsed(),
+            request_options: self.request_options.clone(),
+            message: message.unwrap_or(default_message),
+            code,
+        })
+    }
+
+    async fn respond(&self, ctx: &RuntimeContext, prompt: RenderedPrompt) -> LLMResponse {
+        let (system_start, instant_start) = (web_time::SystemTime::now(), web_time::Instant::now());
+        let response = match self.select(ctx, &prompt).await {
+            Ok(response) => response,
+            Err(e) => return LLMResponse::UserFailure(format!("{:#}", e)),
+        };
+
+        sleep_ms(response.latency_ms.unwrap_or(self.properties.latency_ms)).await;
+
+        if let Some(error) = &response.error {
+            return self.failure(prompt, error, response.message, system_start, instant_start);
+        }
+
+        let finish_reason = response.finish_reason.unwrap_or_else(|| "stop".to_string());
+        LLMResponse::Success(LLMCompleteResponse {
+            client: self.context.name.clone(),
+            model: self.properties.model.clone(),
+            prompt,
+            request_options: self.request_options.clone(),
+            content: response.content.unwrap_or_default(),
+            start_time: system_start,
+            latency: instant_start.elapsed(),
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: finish_reason == "stop",
+                finish_reason: Some(finish_reason),
+                prompt_tokens: None,
+                output_tokens: None,
+                total_tokens: None,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+                cost: None,
+            },
+        })
+    }
+
+    async fn stream_respond(&self, ctx: &RuntimeContext, prompt: RenderedPrompt) -> StreamResponse {
+        let (system_start, instant_start) = (web_time::SystemTime::now(), web_time::Instant::now());
+        let response = match self.select(ctx, &prompt).await {
+            Ok(response) => response,
+            Err(e) => return Err(LLMResponse::UserFailure(format!("{:#}", e))),
+        };
+
+        sleep_ms(response.latency_ms.unwrap_or(self.properties.latency_ms)).await;
+
+        if let Some(error) = &response.error {
+            return Err(self.failure(prompt, error, response.message, system_start, instant_start));
+        }
+
+        let finish_reason = response.finish_reason.unwrap_or_else(|| "stop".to_string());
+        let content = response.content.unwrap_or_default();
+        let chunks = accumulated_chunks(&content, self.properties.chunk_size);
+        let last = chunks.len() - 1;
+        let chunk_latency_ms = self.properties.chunk_latency_ms;
+
+        let template = LLMCompleteResponse {
+            client: self.context.name.clone(),
+            model: self.properties.model.clone(),
+            prompt,
+            request_options: self.request_options.clone(),
+            content: String::new(),
+            start_time: system_start,
+            latency: instant_start.elapsed(),
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: false,
+                finish_reason: None,
+                prompt_tokens: None,
+                output_tokens: None,
+                total_tokens: None,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+                cost: None,
+            },
+        };
+
+        Ok(Box::pin(
+            futures::stream::iter(chunks.into_iter().enumerate()).then(move |(i, content)| {
+                let mut response = template.clone();
+                let finish_reason = finish_reason.clone();
+                async move {
+                    if i > 0 {
+                        sleep_ms(chunk_latency_ms).await;
+                    }
+                    response.content = content;
+                    response.latency = instant_start.elapsed();
+                    if i == last {
+                        response.metadata.baml_is_complete = finish_reason == "stop";
+                        response.metadata.finish_reason = Some(finish_reason);
+                    }
+                    LLMResponse::Success(response)
+                }
+            }),
+        ))
+    }
+}
+
+/// The prompt as plain text, for matching `prompt_regex` against.
+fn prompt_text(prompt: &RenderedPrompt) -> String {
+    match prompt {
+        RenderedPrompt::Completion(text) => text.clone(),
+        RenderedPrompt::Chat(messages) => messages
+            .iter()
+            .map(|m| {
+                format!(
+                    "{}: {}",
+                    m.role,
+                    m.parts.iter().map(|p| p.to_string()).collect::<String>()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// The content seen after each streamed chunk of `chunk_size` characters.
+fn accumulated_chunks(content: &str, chunk_size: Option<usize>) -> Vec<String> {
+    let Some(chunk_size) = chunk_size else {
+        return vec![content.to_string()];
+    };
+    let chars = content.chars().collect::<Vec<_>>();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    (1..=chars.len().div_ceil(chunk_size))
+        .map(|n| chars[..(n * chunk_size).min(chars.len())].iter().collect())
+        .collect()
+}
+
+async fn sleep_ms(ms: u64) {
+    if ms > 0 {
+        async_std::task::sleep(std::time::Duration::from_millis(ms)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::accumulated_chunks;
+
+    #[test]
+    fn test_accumulated_chunks() {
+        assert_eq!(accumulated_chunks("hello", None), vec!["hello"]);
+        assert_eq!(
+            accumulated_chunks("hello", Some(2)),
+            vec!["he", "hell", "hello"]
+        );
+        assert_eq!(accumulated_chunks("", Some(2)), vec![""]);
+    }
+}
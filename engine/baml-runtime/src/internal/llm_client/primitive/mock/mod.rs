@@ -0,0 +1,3 @@
+mod mock_client;
+
+pub use mock_client::MockClient;
@@ -7,29 +7,33 @@ use internal_llm_client::{AllowedRoleMetadata, ClientProvider, OpenAIClientProvi
 
 use crate::{
     client_registry::ClientProperty, internal::prompt_renderer::PromptRenderer,
-    runtime_interface::InternalClientLookup, RenderCurlSettings, RuntimeContext,
+    runtime_interface::InternalClientLookup, HttpRequest, RenderCurlSettings, RuntimeContext,
 };
 
 use self::{
-    anthropic::AnthropicClient, aws::AwsClient, google::GoogleAIClient, openai::OpenAIClient,
-    request::RequestBuilder, vertex::VertexClient,
+    anthropic::AnthropicClient, aws::AwsClient, google::GoogleAIClient, mock::MockClient,
+    openai::OpenAIClient, request::RequestBuilder, vertex::VertexClient,
 };
 
 use super::{
+    context_window,
     orchestrator::{
         ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState, OrchestratorNode,
         OrchestratorNodeIterator,
     },
     traits::{
-        WithClient, WithClientProperties, WithPrompt, WithRenderRawCurl, WithRetryPolicy,
-        WithSingleCallable, WithStreamable,
+        WithClient, WithClientProperties, WithPrompt, WithRenderHttpRequest, WithRenderRawCurl,
+        WithRetryPolicy, WithSingleCallable, WithStreamable,
     },
     LLMResponse,
 };
 
 mod anthropic;
 mod aws;
+#[cfg(not(target_arch = "wasm32"))]
+mod cassette;
 mod google;
+mod mock;
 mod openai;
 pub(super) mod request;
 mod vertex;
@@ -45,6 +49,7 @@ pub enum LLMPrimitive2 {
     GoogleAIClient,
     VertexClient,
     AwsClient,
+    MockClient,
 }
 
 // #[derive(Delegate)]
@@ -56,6 +61,7 @@ pub enum LLMPrimitiveProvider {
     Google(GoogleAIClient),
     Vertex(VertexClient),
     Aws(aws::AwsClient),
+    Mock(MockClient),
 }
 
 macro_rules! match_llm_provider {
@@ -67,6 +73,7 @@ macro_rules! match_llm_provider {
             LLMPrimitiveProvider::Google(client) => client.$method($($args),*).await,
             LLMPrimitiveProvider::Aws(client) => client.$method($($args),*).await,
             LLMPrimitiveProvider::Vertex(client) => client.$method($($args),*).await,
+            LLMPrimitiveProvider::Mock(client) => client.$method($($args),*).await,
         }
     };
 
@@ -77,6 +84,7 @@ macro_rules! match_llm_provider {
             LLMPrimitiveProvider::Google(client) => client.$method($($args),*),
             LLMPrimitiveProvider::Aws(client) => client.$method($($args),*),
             LLMPrimitiveProvider::Vertex(client) => client.$method($($args),*),
+            LLMPrimitiveProvider::Mock(client) => client.$method($($args),*),
         }
     };
 }
@@ -97,6 +105,18 @@ impl WithClientProperties for LLMPrimitiveProvider {
     fn finish_reason_filter(&self) -> &internal_llm_client::FinishReasonFilter {
         match_llm_provider!(self, finish_reason_filter)
     }
+    fn rate_limits(&self) -> &internal_llm_client::RateLimits {
+        match_llm_provider!(self, rate_limits)
+    }
+    fn pricing(&self) -> &internal_llm_client::ModelPricing {
+        match_llm_provider!(self, pricing)
+    }
+    fn context_window(&self) -> Option<u32> {
+        match_llm_provider!(self, context_window)
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        match_llm_provider!(self, max_output_tokens)
+    }
     fn default_role(&self) -> String {
         match_llm_provider!(self, default_role)
     }
@@ -130,6 +150,7 @@ impl TryFrom<(&ClientProperty, &RuntimeContext)> for LLMPrimitiveProvider {
             ClientProvider::AwsBedrock => AwsClient::dynamic_new(value, ctx).map(Into::into),
             ClientProvider::GoogleAi => GoogleAIClient::dynamic_new(value, ctx).map(Into::into),
             ClientProvider::Vertex => VertexClient::dynamic_new(value, ctx).map(Into::into),
+            ClientProvider::Mock => MockClient::dynamic_new(value, ctx).map(Into::into),
             ClientProvider::Strategy(strategy_client_provider) => {
                 unimplemented!(
                     "Strategy client providers are not supported yet in LLMPrimitiveProvider"
@@ -188,6 +209,7 @@ impl TryFrom<(&ClientWalker<'_>, &RuntimeContext)> for LLMPrimitiveProvider {
             ClientProvider::AwsBedrock => AwsClient::new(client, ctx).map(Into::into),
             ClientProvider::GoogleAi => GoogleAIClient::new(client, ctx).map(Into::into),
             ClientProvider::Vertex => VertexClient::new(client, ctx).map(Into::into),
+            ClientProvider::Mock => MockClient::new(client, ctx).map(Into::into),
             ClientProvider::Strategy(strategy_client_provider) => {
                 unimplemented!(
                     "Strategy client providers are not supported yet in LLMPrimitiveProvider"
@@ -220,6 +242,17 @@ impl WithRenderRawCurl for LLMPrimitiveProvider {
     }
 }
 
+impl WithRenderHttpRequest for LLMPrimitiveProvider {
+    async fn render_http_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+        stream: bool,
+    ) -> Result<HttpRequest> {
+        match_llm_provider!(self, render_http_request, async, ctx, prompt, stream)
+    }
+}
+
 impl WithSingleCallable for LLMPrimitiveProvider {
     async fn single_call(
         &self,
@@ -263,6 +296,7 @@ impl std::fmt::Display for LLMPrimitiveProvider {
             LLMPrimitiveProvider::Google(_) => write!(f, "Google"),
             LLMPrimitiveProvider::Aws(_) => write!(f, "AWS"),
             LLMPrimitiveProvider::Vertex(_) => write!(f, "Vertex"),
+            LLMPrimitiveProvider::Mock(_) => write!(f, "Mock"),
         }
     }
 }
@@ -275,4 +309,12 @@ impl LLMPrimitiveProvider {
     pub fn request_options(&self) -> &BamlMap<String, serde_json::Value> {
         match_llm_provider!(self, request_options)
     }
+
+    /// See `context_window::check`.
+    pub fn check_context_window(
+        &self,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> Result<(), String> {
+        context_window::check(self, match_llm_provider!(self, context), prompt)
+    }
 }
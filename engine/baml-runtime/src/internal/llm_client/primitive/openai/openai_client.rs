@@ -6,7 +6,8 @@ use baml_types::{BamlMap, BamlMedia, BamlMediaContent, BamlMediaType};
 use internal_baml_core::ir::ClientWalker;
 use internal_baml_jinja::{ChatMessagePart, RenderContext_Client, RenderedChatMessage};
 use internal_llm_client::openai::ResolvedOpenAI;
-use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter};
+use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter, ModelPricing, RateLimits};
+use serde::de::DeserializeOwned;
 use serde_json::json;
 
 use crate::internal::llm_client::{
@@ -14,7 +15,10 @@ use crate::internal::llm_client::{
 };
 
 use super::properties;
-use super::types::{ChatCompletionResponse, ChatCompletionResponseDelta};
+use super::types::{
+    ChatCompletionChoiceDelta, ChatCompletionGeneric, ChatCompletionResponse, CompletionChoice,
+    CompletionResponse, StreamChoice,
+};
 
 use crate::client_registry::ClientProperty;
 use crate::internal::llm_client::primitive::request::{
@@ -22,10 +26,10 @@ use crate::internal::llm_client::primitive::request::{
 };
 use crate::internal::llm_client::traits::{
     SseResponseTrait, StreamResponse, ToProviderMessage, ToProviderMessageExt,
-    WithClientProperties, WithStreamChat,
+    WithClientProperties, WithStreamChat, WithStreamCompletion,
 };
 use crate::internal::llm_client::{
-    traits::{WithChat, WithClient, WithNoCompletion, WithRetryPolicy},
+    traits::{WithChat, WithClient, WithCompletion, WithRetryPolicy},
     LLMResponse, ModelFeatures,
 };
 
@@ -60,6 +64,21 @@ impl WithClientProperties for OpenAIClient {
     fn finish_reason_filter(&self) -> &FinishReasonFilter {
         &self.properties.finish_reason_filter
     }
+    fn rate_limits(&self) -> &RateLimits {
+        &self.properties.rate_limits
+    }
+    fn pricing(&self) -> &ModelPricing {
+        &self.properties.pricing
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.properties.context_window
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        ["max_completion_tokens", "max_tokens"]
+            .iter()
+            .find_map(|key| self.properties.properties.get(*key))
+            .and_then(|v| v.as_u64())
+    }
 
     fn allowed_roles(&self) -> Vec<String> {
         self.properties.allowed_roles()
@@ -84,83 +103,98 @@ impl WithClient for OpenAIClient {
     }
 }
 
-impl WithNoCompletion for OpenAIClient {}
-// TODO: Enable completion with support for completion streams
-// impl WithCompletion for OpenAIClient {
-//     fn completion_options(
-//         &self,
-//         ctx: &RuntimeContext,
-//     ) -> Result<internal_baml_jinja::CompletionOptions> {
-//         return Ok(internal_baml_jinja::CompletionOptions::new("\n".into()));
-//     }
-
-//     async fn completion(&self, ctx: &RuntimeContext, prompt: &String) -> LLMResponse {
-//         let (response, system_start, instant_start) =
-//             match make_parsed_request::<CompletionResponse>(
-//                 self,
-//                 either::Either::Left(prompt),
-//                 false,
-//             )
-//             .await
-//             {
-//                 Ok(v) => v,
-//                 Err(e) => return e,
-//             };
-
-//         if response.choices.len() != 1 {
-//             return LLMResponse::LLMFailure(LLMErrorResponse {
-//                 client: self.context.name.to_string(),
-//                 model: None,
-//                 prompt: internal_baml_jinja::RenderedPrompt::Completion(prompt.clone()),
-//                 start_time: system_start,
-//                 latency: instant_start.elapsed(),
-//                 request_options: self.properties.properties.clone(),
-//                 message: format!(
-//                     "Expected exactly one choices block, got {}",
-//                     response.choices.len()
-//                 ),
-//                 code: ErrorCode::Other(200),
-//             });
-//         }
-
-//         let usage = response.usage.as_ref();
-
-//         LLMResponse::Success(LLMCompleteResponse {
-//             client: self.context.name.to_string(),
-//             prompt: internal_baml_jinja::RenderedPrompt::Completion(prompt.clone()),
-//             content: response.choices[0].text.clone(),
-//             start_time: system_start,
-//             latency: instant_start.elapsed(),
-//             model: response.model,
-//             request_options: self.properties.properties.clone(),
-//             metadata: LLMCompleteResponseMetadata {
-//                 baml_is_complete: match response.choices.get(0) {
-//                     Some(c) => match c.finish_reason {
-//                         Some(FinishReason::Stop) => true,
-//                         _ => false,
-//                     },
-//                     None => false,
-//                 },
-//                 finish_reason: match response.choices.get(0) {
-//                     Some(c) => match c.finish_reason {
-//                         Some(FinishReason::Stop) => Some(FinishReason::Stop.to_string()),
-//                         _ => None,
-//                     },
-//                     None => None,
-//                 },
-//                 prompt_tokens: usage.map(|u| u.prompt_tokens),
-//                 output_tokens: usage.map(|u| u.completion_tokens),
-//                 total_tokens: usage.map(|u| u.total_tokens),
-//             },
-//         })
-//     }
-// }
+impl WithCompletion for OpenAIClient {
+    fn completion_options(
+        &self,
+        _ctx: &RuntimeContext,
+    ) -> Result<internal_baml_jinja::CompletionOptions> {
+        Ok(internal_baml_jinja::CompletionOptions::new("\n".into()))
+    }
+
+    async fn completion(&self, ctx: &RuntimeContext, prompt: &str) -> LLMResponse {
+        let prompt = prompt.to_string();
+        let (response, system_start, instant_start) =
+            match make_parsed_request::<CompletionResponse>(
+                self,
+                ctx,
+                either::Either::Left(&prompt),
+                false,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => return e,
+            };
+
+        if response.choices.len() != 1 {
+            return LLMResponse::LLMFailure(LLMErrorResponse {
+                client: self.context.name.to_string(),
+                model: None,
+                prompt: internal_baml_jinja::RenderedPrompt::Completion(prompt),
+                start_time: system_start,
+                latency: instant_start.elapsed(),
+                request_options: self.properties.properties.clone(),
+                message: format!(
+                    "Expected exactly one choices block, got {}",
+                    response.choices.len()
+                ),
+                code: ErrorCode::Other(200),
+            });
+        }
+
+        let usage = response.usage.as_ref();
+
+        LLMResponse::Success(LLMCompleteResponse {
+            client: self.context.name.to_string(),
+            prompt: internal_baml_jinja::RenderedPrompt::Completion(prompt),
+            content: response.choices[0].text.clone(),
+            start_time: system_start,
+            latency: instant_start.elapsed(),
+            model: response.model,
+            request_options: self.properties.properties.clone(),
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: match response.choices.get(0) {
+                    Some(c) => c.finish_reason.as_ref().is_some_and(|f| f == "stop"),
+                    None => false,
+                },
+                finish_reason: match response.choices.get(0) {
+                    Some(c) => c.finish_reason.clone(),
+                    None => None,
+                },
+                prompt_tokens: usage.map(|u| u.prompt_tokens),
+                output_tokens: usage.map(|u| u.completion_tokens),
+                total_tokens: usage.map(|u| u.total_tokens),
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: usage.and_then(|u| u.cached_tokens()),
+                cost: None,
+            },
+        })
+    }
+}
+
+impl WithStreamCompletion for OpenAIClient {
+    async fn stream_completion(&self, ctx: &RuntimeContext, prompt: &str) -> StreamResponse {
+        let prompt = prompt.to_string();
+        let (resp, system_start, instant_start) =
+            match make_request(self, ctx, either::Either::Left(&prompt), true).await {
+                Ok(v) => v,
+                Err(e) => return Err(e),
+            };
+        self.event_stream::<CompletionChoice>(
+            resp,
+            internal_baml_jinja::RenderedPrompt::Completion(prompt),
+            system_start,
+            instant_start,
+        )
+    }
+}
 
 impl WithChat for OpenAIClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         let (response, system_start, instant_start) =
             match make_parsed_request::<ChatCompletionResponse>(
                 self,
+                ctx,
                 either::Either::Right(prompt),
                 false,
             )
@@ -213,6 +247,9 @@ impl WithChat for OpenAIClient {
                 prompt_tokens: usage.map(|u| u.prompt_tokens),
                 output_tokens: usage.map(|u| u.completion_tokens),
                 total_tokens: usage.map(|u| u.total_tokens),
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: usage.and_then(|u| u.cached_tokens()),
+                cost: None,
             },
         })
     }
@@ -300,7 +337,29 @@ impl SseResponseTrait for OpenAIClient {
         system_start: web_time::SystemTime,
         instant_start: web_time::Instant,
     ) -> StreamResponse {
-        let prompt = prompt.to_vec();
+        self.event_stream::<ChatCompletionChoiceDelta>(
+            resp,
+            internal_baml_jinja::RenderedPrompt::Chat(prompt.to_vec()),
+            system_start,
+            instant_start,
+        )
+    }
+}
+
+impl OpenAIClient {
+    /// Accumulates the server-sent events of a streaming `/chat/completions`
+    /// or `/completions` response, which differ only in where each chunk
+    /// carries its text.
+    fn event_stream<C>(
+        &self,
+        resp: reqwest::Response,
+        prompt: internal_baml_jinja::RenderedPrompt,
+        system_start: web_time::SystemTime,
+        instant_start: web_time::Instant,
+    ) -> StreamResponse
+    where
+        C: StreamChoice + DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+    {
         let client_name = self.context.name.clone();
         let params = self.properties.properties.clone();
         Ok(Box::pin(
@@ -309,8 +368,8 @@ impl SseResponseTrait for OpenAIClient {
                 .take_while(|event| {
                     std::future::ready(event.as_ref().is_ok_and(|e| e.data != "[DONE]"))
                 })
-                .map(|event| -> Result<ChatCompletionResponseDelta> {
-                    Ok(serde_json::from_str::<ChatCompletionResponseDelta>(
+                .map(|event| -> Result<ChatCompletionGeneric<C>> {
+                    Ok(serde_json::from_str::<ChatCompletionGeneric<C>>(
                         &event?.data,
                     )?)
                 })
@@ -318,7 +377,7 @@ impl SseResponseTrait for OpenAIClient {
                 .scan(
                     Ok(LLMCompleteResponse {
                         client: client_name.clone(),
-                        prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.clone()),
+                        prompt: prompt.clone(),
                         content: "".to_string(),
                         start_time: system_start,
                         latency: instant_start.elapsed(),
@@ -330,6 +389,9 @@ impl SseResponseTrait for OpenAIClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -348,9 +410,7 @@ impl SseResponseTrait for OpenAIClient {
                                         } else {
                                             Some(inner.model.clone())
                                         },
-                                        prompt: internal_baml_jinja::RenderedPrompt::Chat(
-                                            prompt.clone(),
-                                        ),
+                                        prompt: prompt.clone(),
                                         start_time: system_start,
                                         request_options: params.clone(),
                                         latency: instant_start.elapsed(),
@@ -361,18 +421,19 @@ impl SseResponseTrait for OpenAIClient {
                             }
                         };
                         if let Some(choice) = event.choices.first() {
-                            if let Some(content) = choice.delta.content.as_ref() {
-                                inner.content += content.as_str();
+                            if let Some(content) = choice.text() {
+                                inner.content += content;
                             }
                             inner.model = event.model;
-                            inner.metadata.finish_reason = choice.finish_reason.clone();
-                            inner.metadata.baml_is_complete = choice.finish_reason.as_ref().is_some_and(|s| s == "stop");
+                            inner.metadata.finish_reason = choice.finish_reason().cloned();
+                            inner.metadata.baml_is_complete = choice.finish_reason().is_some_and(|s| s == "stop");
                         }
                         inner.latency = instant_start.elapsed();
                         if let Some(usage) = event.usage.as_ref() {
                             inner.metadata.prompt_tokens = Some(usage.prompt_tokens);
                             inner.metadata.output_tokens = Some(usage.completion_tokens);
                             inner.metadata.total_tokens = Some(usage.total_tokens);
+                            inner.metadata.cache_read_input_tokens = usage.cached_tokens();
                         }
 
                         std::future::ready(Some(LLMResponse::Success(inner.clone())))
@@ -385,11 +446,11 @@ impl SseResponseTrait for OpenAIClient {
 impl WithStreamChat for OpenAIClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &[RenderedChatMessage],
     ) -> StreamResponse {
         let (resp, system_start, instant_start) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -407,10 +468,15 @@ macro_rules! make_openai_client {
                 provider: $client.provider.to_string(),
                 default_role: $properties.default_role(),
                 allowed_roles: $properties.allowed_roles(),
+                model: $properties
+                    .properties
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .map(String::from),
             },
             features: ModelFeatures {
-                chat: true,
-                completion: false,
+                chat: $properties.supports_chat(),
+                completion: $properties.supports_completion(),
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::Never,
                 allowed_metadata: $properties.allowed_metadata.clone(),
@@ -429,10 +495,15 @@ macro_rules! make_openai_client {
                 provider: $client.elem().provider.to_string(),
                 default_role: $properties.default_role(),
                 allowed_roles: $properties.allowed_roles(),
+                model: $properties
+                    .properties
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .map(String::from),
             },
             features: ModelFeatures {
-                chat: true,
-                completion: false,
+                chat: $properties.supports_chat(),
+                completion: $properties.supports_completion(),
                 anthropic_system_constraints: false,
                 resolve_media_urls: ResolveMediaUrls::Never,
                 allowed_metadata: $properties.allowed_metadata.clone(),
@@ -526,6 +597,11 @@ impl ToProviderMessage for OpenAIClient {
         let media_type = match media.media_type {
             BamlMediaType::Image => "image",
             BamlMediaType::Audio => "audio",
+            BamlMediaType::Pdf | BamlMediaType::Video => anyhow::bail!(
+                "{} does not support {} inputs; only image and audio media can be sent to this client",
+                self.provider,
+                media.media_type
+            ),
         };
         let media_type = format!("{}_url", media_type);
         match &media.content {
@@ -622,3 +698,115 @@ impl ToProviderMessageExt for OpenAIClient {
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use baml_types::BamlValue;
+    use internal_baml_jinja::RenderedPrompt;
+    use internal_llm_client::{ClientProvider, OpenAIClientProviderVariant};
+    use reqwest::header::CONTENT_TYPE;
+
+    fn generic_client() -> OpenAIClient {
+        let ctx = RuntimeContext::new(
+            Default::default(),
+            None,
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            Default::default(),
+            Default::default(),
+        );
+        let options = [
+            ("base_url", "http://localhost:8000/v1"),
+            ("model", "my-model"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), BamlValue::String(v.to_string())))
+        .collect();
+        let client = ClientProperty::new(
+            "MyClient".to_string(),
+            ClientProvider::OpenAI(OpenAIClientProviderVariant::Generic),
+            None,
+            options,
+        );
+        OpenAIClient::dynamic_new_generic(&client, &ctx).unwrap()
+    }
+
+    #[test]
+    fn test_parse_completion_response() -> Result<()> {
+        let response: CompletionResponse = serde_json::from_str(
+            r#"{
+                "id": "cmpl-1",
+                "object": "text_completion",
+                "created": 1700000000,
+                "model": "my-model",
+                "choices": [
+                    {"text": "Hello world", "index": 0, "logprobs": null, "finish_reason": "stop"}
+                ],
+                "usage": {
+                    "prompt_tokens": 5,
+                    "completion_tokens": 2,
+                    "total_tokens": 7,
+                    "prompt_tokens_details": {"cached_tokens": 3}
+                }
+            }"#,
+        )?;
+
+        assert_eq!(response.choices.len(), 1);
+        assert_eq!(response.choices[0].text, "Hello world");
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.total_tokens, 7);
+        assert_eq!(usage.cached_tokens(), Some(3));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_completion_event_stream() -> Result<()> {
+        let body = [
+            r#"{"id":"cmpl-1","object":"text_completion","created":1700000000,"model":"my-model","choices":[{"text":"Hello","index":0,"logprobs":null,"finish_reason":null}]}"#,
+            r#"{"id":"cmpl-1","object":"text_completion","created":1700000000,"model":"my-model","choices":[{"text":" world","index":0,"logprobs":null,"finish_reason":"stop"}]}"#,
+            r#"{"id":"cmpl-1","object":"text_completion","created":1700000000,"model":"my-model","choices":[],"usage":{"prompt_tokens":5,"completion_tokens":2,"total_tokens":7}}"#,
+            "[DONE]",
+        ]
+        .map(|data| format!("data: {data}\n\n"))
+        .concat();
+        let resp: reqwest::Response = http::Response::builder()
+            .status(200)
+            .header(CONTENT_TYPE, "text/event-stream")
+            .body(body)?
+            .into();
+
+        let Ok(stream) = generic_client().event_stream::<CompletionChoice>(
+            resp,
+            RenderedPrompt::Completion("Say hello".to_string()),
+            web_time::SystemTime::now(),
+            web_time::Instant::now(),
+        ) else {
+            panic!("Failed to start the stream");
+        };
+        let responses = stream.collect::<Vec<_>>().await;
+
+        let contents = responses
+            .iter()
+            .map(|r| match r {
+                LLMResponse::Success(r) => r.content.as_str(),
+                _ => panic!("Expected only successful responses"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(contents, ["Hello", "Hello world", "Hello world"]);
+
+        let Some(LLMResponse::Success(last)) = responses.last() else {
+            panic!("Expected a successful response");
+        };
+        assert_eq!(last.model, "my-model");
+        assert_eq!(last.metadata.finish_reason.as_deref(), Some("stop"));
+        assert!(last.metadata.baml_is_complete);
+        assert_eq!(last.metadata.prompt_tokens, Some(5));
+        assert_eq!(last.metadata.output_tokens, Some(2));
+
+        Ok(())
+    }
+}
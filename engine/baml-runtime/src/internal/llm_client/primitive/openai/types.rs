@@ -26,6 +26,13 @@ pub struct ChatCompletionGeneric<C> {
     pub usage: Option<CompletionUsage>,
 }
 
+/// A choice in a streamed response, whose text arrives in a different place
+/// for chat and legacy completion requests.
+pub trait StreamChoice {
+    fn text(&self) -> Option<&str>;
+    fn finish_reason(&self) -> Option<&String>;
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct CompletionChoice {
     pub finish_reason: Option<String>,
@@ -56,6 +63,26 @@ pub struct CompletionUsage {
     pub completion_tokens: u64,
     /// Total number of tokens used in the request (prompt + completion).
     pub total_tokens: u64,
+    /// Breakdown of the tokens in the prompt.
+    #[serde(default)]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+/// Breakdown of the tokens in the prompt.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PromptTokensDetails {
+    /// Number of tokens in the prompt that were read from the prompt cache.
+    /// OpenAI caches long prompts automatically.
+    #[serde(default)]
+    pub cached_tokens: Option<u64>,
+}
+
+impl CompletionUsage {
+    pub fn cached_tokens(&self) -> Option<u64> {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|d| d.cached_tokens)
+    }
 }
 
 /// A chat completion message generated by the model.
@@ -82,6 +109,26 @@ pub struct ChatCompletionChoiceDelta {
     pub delta: ChatCompletionMessageDelta,
 }
 
+impl StreamChoice for CompletionChoice {
+    fn text(&self) -> Option<&str> {
+        Some(&self.text)
+    }
+
+    fn finish_reason(&self) -> Option<&String> {
+        self.finish_reason.as_ref()
+    }
+}
+
+impl StreamChoice for ChatCompletionChoiceDelta {
+    fn text(&self) -> Option<&str> {
+        self.delta.content.as_deref()
+    }
+
+    fn finish_reason(&self) -> Option<&String> {
+        self.finish_reason.as_ref()
+    }
+}
+
 /// Same as ChatCompletionMessage, but received during a response stream.
 #[derive(Deserialize, Clone, Debug)]
 pub struct ChatCompletionMessageDelta {
@@ -7,6 +7,7 @@ use reqwest::Response;
 use serde::de::DeserializeOwned;
 
 use crate::internal::llm_client::{traits::WithClient, ErrorCode, LLMErrorResponse, LLMResponse};
+use crate::RuntimeContext;
 
 pub trait RequestBuilder {
     #[allow(async_fn_in_trait)]
@@ -31,8 +32,42 @@ fn to_prompt(
     }
 }
 
+/// Sends the request, unless `ctx` configures a cassette to record it to or
+/// replay it from.
+#[cfg(not(target_arch = "wasm32"))]
+async fn execute(
+    client: &impl RequestBuilder,
+    ctx: &RuntimeContext,
+    req: reqwest::Request,
+) -> Result<Response> {
+    use super::cassette::{Cassette, CassetteMode, RecordedRequest};
+
+    let Some(cassette) = Cassette::from_env(ctx.env_vars())? else {
+        return Ok(client.http_client().execute(req).await?);
+    };
+
+    let recorded = RecordedRequest::new(&req);
+    match cassette.mode() {
+        CassetteMode::Replay => cassette.replay(&recorded),
+        CassetteMode::Record => {
+            let response = client.http_client().execute(req).await?;
+            cassette.record(recorded, response).await
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn execute(
+    client: &impl RequestBuilder,
+    _ctx: &RuntimeContext,
+    req: reqwest::Request,
+) -> Result<Response> {
+    Ok(client.http_client().execute(req).await?)
+}
+
 pub async fn make_request(
     client: &(impl WithClient + RequestBuilder),
+    ctx: &RuntimeContext,
     prompt: either::Either<&String, &[RenderedChatMessage]>,
     stream: bool,
 ) -> Result<(Response, web_time::SystemTime, web_time::Instant), LLMResponse> {
@@ -74,7 +109,8 @@ pub async fn make_request(
         }
     };
 
-    let response = match client.http_client().execute(req).await {
+    let url = req.url().to_string();
+    let response = match execute(client, ctx, req).await {
         Ok(response) => response,
         Err(e) => {
             return Err(LLMResponse::LLMFailure(LLMErrorResponse {
@@ -92,7 +128,6 @@ pub async fn make_request(
 
     let status = response.status();
     if !status.is_success() {
-        let url = response.url().to_string();
         let text = response.text().await.map_or_else(
             |_| "<no response>".to_string(),
             |text| {
@@ -120,10 +155,11 @@ pub async fn make_request(
 
 pub async fn make_parsed_request<T: DeserializeOwned>(
     client: &(impl WithClient + RequestBuilder),
+    ctx: &RuntimeContext,
     prompt: either::Either<&String, &[RenderedChatMessage]>,
     stream: bool,
 ) -> Result<(T, web_time::SystemTime, web_time::Instant), LLMResponse> {
-    let (response, system_now, instant_now) = make_request(client, prompt, stream).await?;
+    let (response, system_now, instant_now) = make_request(client, ctx, prompt, stream).await?;
     let j = match response.json::<serde_json::Value>().await {
         Ok(response) => response,
         Err(e) => {
@@ -116,6 +116,22 @@ impl WithClientProperties for VertexClient {
     fn finish_reason_filter(&self) -> &internal_llm_client::FinishReasonFilter {
         &self.properties.finish_reason_filter
     }
+    fn rate_limits(&self) -> &internal_llm_client::RateLimits {
+        &self.properties.rate_limits
+    }
+    fn pricing(&self) -> &internal_llm_client::ModelPricing {
+        &self.properties.pricing
+    }
+    fn context_window(&self) -> Option<u32> {
+        self.properties.context_window
+    }
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.properties
+            .properties
+            .get("generationConfig")
+            .and_then(|config| config.get("maxOutputTokens"))
+            .and_then(|v| v.as_u64())
+    }
     fn default_role(&self) -> String {
         self.properties.default_role()
     }
@@ -173,6 +189,9 @@ impl SseResponseTrait for VertexClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -234,7 +253,7 @@ impl WithStreamChat for VertexClient {
     ) -> StreamResponse {
         //incomplete, streaming response object is returned
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -252,6 +271,7 @@ impl VertexClient {
                 provider: client.elem().provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: Some(properties.model.clone()),
             },
             features: ModelFeatures {
                 chat: true,
@@ -280,6 +300,7 @@ impl VertexClient {
                 provider: client.provider.to_string(),
                 default_role: properties.default_role(),
                 allowed_roles: properties.allowed_roles(),
+                model: Some(properties.model.clone()),
             },
             features: ModelFeatures {
                 chat: true,
@@ -399,15 +420,19 @@ impl RequestBuilder for VertexClient {
 }
 
 impl WithChat for VertexClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         //non-streaming, complete response is returned
-        let (response, system_now, instant_now) =
-            match make_parsed_request::<VertexResponse>(self, either::Either::Right(prompt), false)
-                .await
-            {
-                Ok(v) => v,
-                Err(e) => return e,
-            };
+        let (response, system_now, instant_now) = match make_parsed_request::<VertexResponse>(
+            self,
+            ctx,
+            either::Either::Right(prompt),
+            false,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
 
         if response.candidates.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -471,6 +496,9 @@ impl WithChat for VertexClient {
                 prompt_tokens: usage_metadata.prompt_token_count,
                 output_tokens: usage_metadata.candidates_token_count,
                 total_tokens: usage_metadata.total_token_count,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+                cost: None,
             },
         })
     }
@@ -0,0 +1,308 @@
+use std::sync::{Arc, Mutex};
+
+use dashmap::DashMap;
+use internal_baml_jinja::RenderedPrompt;
+use internal_llm_client::RateLimits;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use web_time::{Duration, Instant};
+
+use super::{
+    orchestrator::{ExecutionScope, OrchestrationScope, OrchestratorNode},
+    traits::WithClientProperties,
+    LLMResponse,
+};
+
+/// The limiters for every client of a runtime, keyed by client name, so that
+/// all the calls made through a `BamlRuntime` share the same limits.
+#[derive(Default)]
+pub struct RateLimiters {
+    limiters: DashMap<String, Arc<RateLimiter>>,
+}
+
+impl RateLimiters {
+    fn get(&self, client: &str, limits: &RateLimits) -> Option<Arc<RateLimiter>> {
+        if limits.is_unlimited() {
+            return None;
+        }
+
+        let mut limiter = self
+            .limiters
+            .entry(client.to_string())
+            .or_insert_with(|| Arc::new(RateLimiter::new(limits.clone())));
+        // The client was redefined with different limits (e.g. through a
+        // ClientRegistry), so start over with the new ones.
+        if limiter.limits != *limits {
+            *limiter = Arc::new(RateLimiter::new(limits.clone()));
+        }
+        Some(limiter.clone())
+    }
+
+    /// Waits until `node` is allowed to send `prompt`.
+    ///
+    /// Returns the scope to report the call under, which records how long the
+    /// call was queued if the client has limits, and the permit to hold for
+    /// the duration of the call.
+    pub async fn acquire(
+        &self,
+        node: &OrchestratorNode,
+        prompt: &RenderedPrompt,
+    ) -> (OrchestrationScope, Option<RateLimitPermit>) {
+        let client = node.provider.name();
+        let Some(limiter) = self.get(client, node.rate_limits()) else {
+            return (node.scope.clone(), None);
+        };
+
+        let permit = limiter.acquire(estimate_tokens(prompt)).await;
+
+        // The direct client stays last, see `OrchestrationScope::direct_client_name`.
+        let mut scope = node.scope.clone();
+        let position = scope.scope.len().saturating_sub(1);
+        scope.scope.insert(
+            position,
+            ExecutionScope::RateLimited(client.to_string(), permit.waited),
+        );
+
+        (scope, Some(permit))
+    }
+}
+
+pub struct RateLimiter {
+    limits: RateLimits,
+    concurrency: Option<Arc<Semaphore>>,
+    // Callers take turns in the order they arrived (tokio's mutex is fair), so
+    // only the caller at the head of the queue waits for the buckets to refill.
+    queue: tokio::sync::Mutex<()>,
+    buckets: Mutex<Buckets>,
+}
+
+struct Buckets {
+    requests: Option<TokenBucket>,
+    tokens: Option<TokenBucket>,
+}
+
+impl RateLimiter {
+    fn new(limits: RateLimits) -> Self {
+        Self {
+            concurrency: limits
+                .max_concurrent_requests
+                .map(|n| Arc::new(Semaphore::new(n as usize))),
+            queue: tokio::sync::Mutex::new(()),
+            buckets: Mutex::new(Buckets {
+                requests: limits.requests_per_minute.map(TokenBucket::per_minute),
+                tokens: limits.tokens_per_minute.map(TokenBucket::per_minute),
+            }),
+            limits,
+        }
+    }
+
+    async fn acquire(self: Arc<Self>, tokens: u64) -> RateLimitPermit {
+        let start = Instant::now();
+
+        // Semaphore permits are also handed out in FIFO order.
+        let concurrency = match &self.concurrency {
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+
+        {
+            let _turn = self.queue.lock().await;
+            loop {
+                let wait = self.buckets.lock().unwrap().take(tokens);
+                match wait {
+                    Some(wait) => async_std::task::sleep(wait).await,
+                    None => break,
+                }
+            }
+        }
+
+        RateLimitPermit {
+            limiter: self,
+            reserved_tokens: tokens,
+            waited: start.elapsed(),
+            _concurrency: concurrency,
+        }
+    }
+}
+
+impl Buckets {
+    /// Takes a request and `tokens` from the buckets, or returns how long to
+    /// wait before they are available.
+    fn take(&mut self, tokens: u64) -> Option<Duration> {
+        let wait = [(&mut self.requests, 1), (&mut self.tokens, tokens)]
+            .into_iter()
+            .filter_map(|(bucket, amount)| bucket.as_mut().map(|b| b.time_until(amount as f64)))
+            .max()
+            .unwrap_or_default();
+
+        if !wait.is_zero() {
+            return Some(wait);
+        }
+
+        if let Some(bucket) = &mut self.requests {
+            bucket.available -= 1.0;
+        }
+        if let Some(bucket) = &mut self.tokens {
+            bucket.available -= tokens as f64;
+        }
+        None
+    }
+}
+
+/// Refills continuously up to a minute's worth of capacity.
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn per_minute(limit: u32) -> Self {
+        Self {
+            capacity: limit as f64,
+            available: limit as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    fn time_until(&mut self, amount: f64) -> Duration {
+        let now = Instant::now();
+        let refilled = now.duration_since(self.updated).as_secs_f64() * self.capacity / 60.0;
+        self.available = (self.available + refilled).min(self.capacity);
+        self.updated = now;
+
+        // A request bigger than the whole bucket only waits for a full bucket.
+        let missing = amount.min(self.capacity) - self.available;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing * 60.0 / self.capacity)
+        }
+    }
+}
+
+/// Held for the duration of a call. Dropping it frees up the concurrency slot.
+pub struct RateLimitPermit {
+    limiter: Arc<RateLimiter>,
+    reserved_tokens: u64,
+    waited: Duration,
+    _concurrency: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimitPermit {
+    /// Charges the tokens the provider reports the call actually used, in place
+    /// of the estimate that was reserved up front.
+    pub fn finish(self, response: &LLMResponse) {
+        let LLMResponse::Success(response) = response else {
+            return;
+        };
+        let metadata = &response.metadata;
+        let used = metadata.total_tokens.or_else(|| {
+            metadata
+                .prompt_tokens
+                .zip(metadata.output_tokens)
+                .map(|(prompt, output)| prompt + output)
+        });
+        let Some(used) = used else {
+            return;
+        };
+
+        if let Some(bucket) = &mut self.limiter.buckets.lock().unwrap().tokens {
+            bucket.available =
+                (bucket.available + self.reserved_tokens as f64 - used as f64).min(bucket.capacity);
+        }
+    }
+}
+
+/// A rough count (~4 characters per token) used to reserve tokens before a
+/// call; it is corrected with the provider's usage once the call finishes.
+fn estimate_tokens(prompt: &RenderedPrompt) -> u64 {
+    let chars = match prompt {
+        RenderedPrompt::Completion(text) => text.len(),
+        RenderedPrompt::Chat(messages) => messages
+            .iter()
+            .flat_map(|m| m.parts.iter())
+            .filter_map(|p| p.as_text())
+            .map(|t| t.len())
+            .sum(),
+    };
+    (chars as u64).div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(limits: RateLimits) -> Arc<RateLimiter> {
+        Arc::new(RateLimiter::new(limits))
+    }
+
+    #[test]
+    fn test_bucket_waits_for_refill() {
+        let mut buckets = Buckets {
+            requests: Some(TokenBucket::per_minute(2)),
+            tokens: Some(TokenBucket::per_minute(600)),
+        };
+
+        assert_eq!(buckets.take(100), None);
+        assert_eq!(buckets.take(100), None);
+
+        // Out of requests: one refills every 30s.
+        let wait = buckets.take(100).unwrap();
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_oversized_request_waits_for_full_bucket() {
+        let mut bucket = TokenBucket::per_minute(60);
+        bucket.available = 0.0;
+
+        let wait = bucket.time_until(1000.0);
+        assert!(wait > Duration::from_secs(59) && wait <= Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_shared() {
+        let limiter = limiter(RateLimits {
+            max_concurrent_requests: Some(1),
+            ..Default::default()
+        });
+
+        let first = limiter.clone().acquire(0).await;
+        let second = limiter.clone().acquire(0);
+        tokio::pin!(second);
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(50), &mut second)
+                .await
+                .is_err()
+        );
+
+        drop(first);
+        let second = second.await;
+        assert!(second.waited >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_limiter_is_replaced_when_limits_change() {
+        let limiters = RateLimiters::default();
+        let limits = RateLimits {
+            requests_per_minute: Some(10),
+            ..Default::default()
+        };
+
+        let a = limiters.get("client", &limits).unwrap();
+        let b = limiters.get("client", &limits).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let c = limiters
+            .get(
+                "client",
+                &RateLimits {
+                    requests_per_minute: Some(20),
+                    ..limits
+                },
+            )
+            .unwrap();
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert!(limiters.get("client", &RateLimits::default()).is_none());
+    }
+}
@@ -2,7 +2,7 @@ use std::{collections::HashMap, path::PathBuf, pin::Pin};
 
 use anyhow::{Context, Result};
 use aws_smithy_types::byte_stream::error::Error;
-use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter};
+use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter, ModelPricing, RateLimits};
 use serde_json::{json, Map};
 
 mod chat;
@@ -12,7 +12,7 @@ pub use self::{
     completion::{WithCompletion, WithNoCompletion, WithStreamCompletion},
 };
 use super::{primitive::request::RequestBuilder, LLMResponse, ModelFeatures};
-use crate::{internal::llm_client::ResolveMediaUrls, RenderCurlSettings};
+use crate::{internal::llm_client::ResolveMediaUrls, HttpRequest, RenderCurlSettings};
 use crate::{internal::prompt_renderer::PromptRenderer, RuntimeContext};
 use baml_types::{BamlMedia, BamlMediaContent, BamlMediaType, BamlValue, MediaBase64, MediaUrl};
 use base64::{prelude::BASE64_STANDARD, Engine};
@@ -37,6 +37,12 @@ pub trait WithClientProperties {
     fn allowed_metadata(&self) -> &AllowedRoleMetadata;
     fn supports_streaming(&self) -> bool;
     fn finish_reason_filter(&self) -> &FinishReasonFilter;
+    fn rate_limits(&self) -> &RateLimits;
+    fn pricing(&self) -> &ModelPricing;
+    /// Overrides the context window of the model, see `context_window::check`.
+    fn context_window(&self) -> Option<u32>;
+    /// The most tokens the request lets the model generate, if set.
+    fn max_output_tokens(&self) -> Option<u64>;
     fn default_role(&self) -> String;
     fn allowed_roles(&self) -> Vec<String>;
 }
@@ -67,6 +73,22 @@ pub trait ToProviderMessage: WithClient {
         &self,
         content: &RenderedChatMessage,
     ) -> Result<Map<String, serde_json::Value>>;
+
+    /// The `cache_control` to send for a part marked by
+    /// `_.cache_breakpoint()` or `_.role(cache_control=...)`, or `None` to
+    /// drop it. Providers without cache breakpoints cache prompts on their
+    /// own, if at all, so by default it is only sent if the client allows it
+    /// in `allowed_role_metadata`.
+    fn cache_control_to_message(
+        &self,
+        cache_control: &serde_json::Value,
+    ) -> Result<Option<serde_json::Value>> {
+        Ok(self
+            .model_features()
+            .allowed_metadata
+            .is_allowed("cache_control")
+            .then(|| cache_control.clone()))
+    }
 }
 
 fn merge_messages(chat: &[RenderedChatMessage]) -> Vec<RenderedChatMessage> {
@@ -101,7 +123,11 @@ pub trait ToProviderMessageExt: ToProviderMessage {
             ChatMessagePart::WithMeta(p, meta) => {
                 let mut content = self.part_to_message(content, p)?;
                 for (k, v) in meta {
-                    if self.model_features().allowed_metadata.is_allowed(k) {
+                    if k == "cache_control" {
+                        if let Some(v) = self.cache_control_to_message(v)? {
+                            content.insert(k.clone(), v);
+                        }
+                    } else if self.model_features().allowed_metadata.is_allowed(k) {
                         content.insert(k.clone(), v.clone());
                     }
                 }
@@ -144,6 +170,18 @@ pub trait WithRenderRawCurl {
     ) -> Result<String>;
 }
 
+pub trait WithRenderHttpRequest {
+    /// Builds the request that `single_call` (or `stream`, if `stream` is set)
+    /// would send for `prompt`, without sending it.
+    #[allow(async_fn_in_trait)]
+    async fn render_http_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+        stream: bool,
+    ) -> Result<HttpRequest>;
+}
+
 impl<T> WithSingleCallable for T
 where
     T: WithClient + WithChat + WithCompletion + WithClientProperties,
@@ -312,6 +350,65 @@ where
     }
 }
 
+impl<T> WithRenderHttpRequest for T
+where
+    T: WithClient + WithChat + WithCompletion + RequestBuilder + WithClientProperties,
+{
+    async fn render_http_request(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+        stream: bool,
+    ) -> Result<HttpRequest> {
+        let stream = stream && self.supports_streaming();
+        let request_builder = match prompt {
+            RenderedPrompt::Chat(chat) => {
+                let chat = process_media_urls(
+                    self.model_features().resolve_media_urls,
+                    true,
+                    None,
+                    ctx,
+                    chat,
+                )
+                .await?;
+                self.build_request(either::Right(&chat), false, stream)
+                    .await?
+            }
+            RenderedPrompt::Completion(completion) => {
+                self.build_request(either::Left(completion), false, stream)
+                    .await?
+            }
+        };
+        let request = request_builder.build()?;
+
+        let headers = request
+            .headers()
+            .iter()
+            .filter(|(name, _)| name.as_str() != "baml-original-url")
+            .map(|(name, value)| {
+                Ok((
+                    name.to_string(),
+                    value
+                        .to_str()
+                        .context(format!("Header {name} is not valid UTF-8"))?
+                        .to_string(),
+                ))
+            })
+            .collect::<Result<indexmap::IndexMap<_, _>>>()?;
+        let body = match request.body().and_then(|b| b.as_bytes()) {
+            Some(bytes) => serde_json::from_slice(bytes).context("Request body is not JSON")?,
+            None => serde_json::Value::Null,
+        };
+
+        Ok(HttpRequest {
+            url: request.url().to_string(),
+            method: request.method().to_string(),
+            headers,
+            body,
+        })
+    }
+}
+
 // Stream related
 pub trait SseResponseTrait {
     fn response_stream(
@@ -480,7 +577,7 @@ async fn process_media(
                                 .strip_prefix("file://")
                                 .unwrap_or(media_path.as_str())
                         ),
-                        Some(format!("{}/{}", part.media_type, ext)),
+                        Some(part.media_type.mime_type_for_extension(&ext)),
                     ));
                 }
             }
@@ -497,7 +594,7 @@ async fn process_media(
 
             if mime_type.is_none() {
                 if let Some(ext) = media_file.extension() {
-                    mime_type = Some(format!("{}/{}", part.media_type, ext));
+                    mime_type = Some(part.media_type.mime_type_for_extension(&ext));
                 }
             }
 
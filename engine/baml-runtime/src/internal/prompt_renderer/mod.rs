@@ -4,14 +4,17 @@ use jsonish::BamlValueWithFlags;
 use render_output_format::render_output_format;
 
 use anyhow::Result;
-use baml_types::{BamlValue, FieldType};
+use baml_types::{BamlValue, FieldType, ParserConfig};
 use internal_baml_core::{
     error_unsupported,
-    ir::{repr::IntermediateRepr, FunctionWalker, IRHelper},
+    ir::{
+        repr::{Function, FunctionConfig, IntermediateRepr},
+        FunctionWalker, IRHelper,
+    },
 };
 use internal_baml_jinja::{
-    types::OutputFormatContent, RenderContext, RenderContext_Client, RenderedPrompt,
-    TemplateStringMacro,
+    types::{requests_alternate_style, OutputFormatContent},
+    RenderContext, RenderContext_Client, RenderedPrompt, TemplateStringMacro,
 };
 
 use crate::RuntimeContext;
@@ -21,6 +24,9 @@ pub struct PromptRenderer {
     client_spec: ClientSpec,
     output_defs: OutputFormatContent,
     output_type: FieldType,
+    parser_config: ParserConfig,
+    variant: String,
+    has_variants: bool,
 }
 
 impl PromptRenderer {
@@ -30,9 +36,19 @@ impl PromptRenderer {
         ctx: &RuntimeContext,
     ) -> Result<PromptRenderer> {
         let func_v2 = function.elem();
-        let Some(config) = func_v2.configs.first() else {
-            error_unsupported!("function", function.name(), "no valid prompt found")
-        };
+        let config = select_variant(func_v2, ctx)?;
+
+        // Outputs are only parsed as YAML, XML or TOML when the function opts
+        // in, or when its prompt asks for one of them.
+        let mut parser_config = func_v2.parser_config.clone();
+        if parser_config.allow_alternate_formats.is_none() {
+            let uses_alternate_style = requests_alternate_style(&config.prompt_template)
+                || ir.walk_template_strings().any(|t| {
+                    config.prompt_template.contains(t.name())
+                        && requests_alternate_style(t.template())
+                });
+            parser_config.allow_alternate_formats = uses_alternate_style.then_some(true);
+        }
 
         Ok(PromptRenderer {
             function_name: function.name().into(),
@@ -42,6 +58,9 @@ impl PromptRenderer {
             },
             output_defs: render_output_format(ir, ctx, &func_v2.output)?,
             output_type: func_v2.output.clone(),
+            parser_config,
+            variant: config.name.clone(),
+            has_variants: func_v2.configs.len() > 1,
         })
     }
 
@@ -49,13 +68,30 @@ impl PromptRenderer {
         &self.client_spec
     }
 
-    pub fn parse(&self, raw_string: &str, allow_partials: bool) -> Result<BamlValueWithFlags> {
-        jsonish::from_str(
+    /// The name of the prompt variant used, if the function has variants.
+    pub fn variant(&self) -> Option<&str> {
+        self.has_variants.then_some(self.variant.as_str())
+    }
+
+    pub fn parse(
+        &self,
+        ir: &IntermediateRepr,
+        raw_string: &str,
+        allow_partials: bool,
+    ) -> Result<BamlValueWithFlags> {
+        let parsed = jsonish::from_str_with_config(
             &self.output_defs,
             &self.output_type,
             raw_string,
             allow_partials,
-        )
+            &self.parser_config,
+        )?;
+        // Partial values follow the @stream.* attributes of the output classes.
+        Ok(if allow_partials {
+            jsonish::deserializer::streaming::process_partial(ir, parsed)
+        } else {
+            parsed
+        })
     }
 
     pub fn render_prompt(
@@ -69,7 +105,7 @@ impl PromptRenderer {
 
         let func_v2 = func.elem();
 
-        let Some(config) = func_v2.configs.first() else {
+        let Some(config) = func_v2.configs.iter().find(|c| c.name == self.variant) else {
             error_unsupported!("function", self.function_name, "no valid prompt found")
         };
 
@@ -97,3 +133,166 @@ impl PromptRenderer {
         )
     }
 }
+
+/// Picks the prompt variant of a function: the one the caller asked for, else
+/// the one named by the `variant` tag, else one at random by weight. Outside of
+/// function calls, e.g. when previewing a prompt, the random pick is skipped in
+/// favor of the function's own prompt.
+fn select_variant<'f>(function: &'f Function, ctx: &RuntimeContext) -> Result<&'f FunctionConfig> {
+    let find = |name: &str| function.configs.iter().find(|c| c.name == name);
+
+    if let Some(name) = &ctx.variant {
+        return find(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Function {} has no variant named `{}`. Expected one of: {}",
+                function.name,
+                name,
+                function
+                    .configs
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        });
+    }
+    // The tag applies to every function in the context, so functions without
+    // a variant of that name ignore it.
+    if let Some(config) = ctx
+        .tags
+        .get("variant")
+        .and_then(|v| v.as_str())
+        .and_then(find)
+    {
+        return Ok(config);
+    }
+
+    let Some(default) = function.configs.first() else {
+        error_unsupported!("function", function.name, "no valid prompt found")
+    };
+    if ctx.function_name.is_none() {
+        return Ok(default);
+    }
+    let mut roll = fastrand::u32(..100);
+    for config in &function.configs {
+        if roll < config.weight {
+            return Ok(config);
+        }
+        roll -= config.weight;
+    }
+    Ok(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{client_registry::ClientRegistry, BamlRuntime, RuntimeContextManager};
+
+    fn runtime() -> BamlRuntime {
+        let files = vec![(
+            "test-file.baml",
+            r##"
+          function Summarize(text: string) -> string {
+            client "openai/gpt-4o"
+            prompt #"Summarize: {{ text }}"#
+            @@variant(concise, {
+              client "openai/gpt-4o-mini"
+              prompt #"Summarize in one sentence: {{ text }}"#
+              weight 100
+            })
+            @@variant(long, {
+              prompt #"Summarize at length: {{ text }}"#
+            })
+            @@variant(as_yaml, {
+              prompt #"Summarize: {{ text }} {{ ctx.output_format(style="yaml") }}"#
+            })
+          }"##,
+        )]
+        .into_iter()
+        .collect();
+        let env_vars: HashMap<&str, &str> = HashMap::new();
+        BamlRuntime::from_file_content(".", &files, env_vars).unwrap()
+    }
+
+    fn renderer(runtime: &BamlRuntime, ctx: &RuntimeContext) -> Result<PromptRenderer> {
+        let ir = runtime.inner.ir.as_ref();
+        let function = ir.find_function("Summarize").unwrap();
+        PromptRenderer::from_function(&function, ir, ctx)
+    }
+
+    fn ctx_manager(runtime: &BamlRuntime) -> RuntimeContextManager {
+        runtime.create_ctx_manager(BamlValue::Null, None)
+    }
+
+    #[test]
+    fn calls_pick_variants_by_weight() {
+        let runtime = runtime();
+        let mut ctx = ctx_manager(&runtime).create_ctx(None, None).unwrap();
+
+        // Previews use the function's own prompt.
+        let preview = renderer(&runtime, &ctx).unwrap();
+        assert_eq!(preview.variant(), Some("default_config"));
+
+        ctx.function_name = Some("Summarize".to_string());
+        let call = renderer(&runtime, &ctx).unwrap();
+        assert_eq!(call.variant(), Some("concise"));
+        assert_eq!(call.client_spec().as_str(), "openai/gpt-4o-mini");
+    }
+
+    #[test]
+    fn tag_picks_variant() {
+        let runtime = runtime();
+        let ctx_manager = ctx_manager(&runtime);
+        ctx_manager.enter("test");
+        ctx_manager.upsert_tags(HashMap::from([(
+            "variant".to_string(),
+            BamlValue::String("long".to_string()),
+        )]));
+        let mut ctx = ctx_manager.create_ctx(None, None).unwrap();
+        ctx.function_name = Some("Summarize".to_string());
+
+        let renderer = renderer(&runtime, &ctx).unwrap();
+        assert_eq!(renderer.variant(), Some("long"));
+        // The variant keeps the client of the function.
+        assert_eq!(renderer.client_spec().as_str(), "openai/gpt-4o");
+    }
+
+    #[test]
+    fn caller_picks_variant() {
+        let runtime = runtime();
+        let mut registry = ClientRegistry::new();
+        registry.set_variant("long".to_string());
+        let mut ctx = ctx_manager(&runtime)
+            .create_ctx(None, Some(&registry))
+            .unwrap();
+        ctx.function_name = Some("Summarize".to_string());
+        assert_eq!(renderer(&runtime, &ctx).unwrap().variant(), Some("long"));
+
+        ctx.variant = Some("missing".to_string());
+        let err = renderer(&runtime, &ctx).err().unwrap();
+        assert!(
+            err.to_string().contains("has no variant named `missing`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn yaml_style_allows_alternate_formats() {
+        let runtime = runtime();
+        let mut ctx = ctx_manager(&runtime).create_ctx(None, None).unwrap();
+        ctx.function_name = Some("Summarize".to_string());
+
+        ctx.variant = Some("long".to_string());
+        let renderer_json = renderer(&runtime, &ctx).unwrap();
+        assert_eq!(renderer_json.parser_config.allow_alternate_formats, None);
+
+        ctx.variant = Some("as_yaml".to_string());
+        let renderer_yaml = renderer(&runtime, &ctx).unwrap();
+        assert_eq!(
+            renderer_yaml.parser_config.allow_alternate_formats,
+            Some(true)
+        );
+    }
+}
@@ -10,6 +10,7 @@ pub mod cli;
 pub mod client_registry;
 pub mod constraints;
 pub mod errors;
+pub mod eval;
 pub mod request;
 mod runtime;
 pub mod runtime_interface;
@@ -60,20 +61,22 @@ pub(crate) use internal_baml_jinja::{ChatMessagePart, RenderedPrompt};
 #[cfg(not(feature = "internal"))]
 pub(crate) use runtime_interface::InternalRuntimeInterface;
 
+pub use internal::llm_client::ResponseBamlValue;
 pub use internal_baml_core::internal_baml_diagnostics;
 pub use internal_baml_core::internal_baml_diagnostics::Diagnostics as DiagnosticsError;
 pub use internal_baml_core::ir::{scope_diagnostics, FieldType, IRHelper, TypeValue};
 
-use crate::constraints::{evaluate_test_constraints, TestConstraintsResult};
-use crate::internal::llm_client::LLMResponse;
+use crate::constraints::{evaluate_test_constraints, JudgeCall, Judges, TestConstraintsResult};
+use crate::internal::llm_client::{LLMCompleteResponse, LLMResponse};
 
 #[cfg(not(target_arch = "wasm32"))]
 static TOKIO_SINGLETON: OnceLock<std::io::Result<Arc<tokio::runtime::Runtime>>> = OnceLock::new();
 
 pub struct BamlRuntime {
-    pub(crate) inner: InternalBamlRuntime,
+    pub(crate) inner: Arc<InternalBamlRuntime>,
     tracer: Arc<BamlTracer>,
     env_vars: HashMap<String, String>,
+    baml_src_dir: Option<PathBuf>,
     #[cfg(not(target_arch = "wasm32"))]
     pub async_runtime: Arc<tokio::runtime::Runtime>,
 }
@@ -83,6 +86,11 @@ impl BamlRuntime {
         &self.env_vars
     }
 
+    /// The `baml_src` directory the runtime was loaded from, if it is on disk.
+    pub fn baml_src_dir(&self) -> Option<&std::path::Path> {
+        self.baml_src_dir.as_deref()
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn get_tokio_singleton() -> Result<Arc<tokio::runtime::Runtime>> {
         match TOKIO_SINGLETON.get_or_init(|| tokio::runtime::Runtime::new().map(Arc::new)) {
@@ -137,9 +145,10 @@ impl BamlRuntime {
             .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
             .collect();
         Ok(BamlRuntime {
-            inner: InternalBamlRuntime::from_directory(&path)?,
+            inner: Arc::new(InternalBamlRuntime::from_directory(&path)?),
             tracer: BamlTracer::new(None, env_vars.into_iter())?.into(),
             env_vars: copy,
+            baml_src_dir: Some(path),
             #[cfg(not(target_arch = "wasm32"))]
             async_runtime: Self::get_tokio_singleton()?,
         })
@@ -155,9 +164,10 @@ impl BamlRuntime {
             .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
             .collect();
         Ok(BamlRuntime {
-            inner: InternalBamlRuntime::from_file_content(root_path, files)?,
+            inner: Arc::new(InternalBamlRuntime::from_file_content(root_path, files)?),
             tracer: BamlTracer::new(None, env_vars.into_iter())?.into(),
             env_vars: copy,
+            baml_src_dir: Some(PathBuf::from(root_path)).filter(|p| p.is_dir()),
             #[cfg(not(target_arch = "wasm32"))]
             async_runtime: Self::get_tokio_singleton()?,
         })
@@ -165,7 +175,7 @@ impl BamlRuntime {
 
     #[cfg(feature = "internal")]
     pub fn internal(&self) -> &impl InternalRuntimeInterface {
-        &self.inner
+        &*self.inner
     }
 
     pub fn create_ctx_manager(
@@ -173,7 +183,8 @@ impl BamlRuntime {
         language: BamlValue,
         baml_src_reader: BamlSrcReader,
     ) -> RuntimeContextManager {
-        let ctx = RuntimeContextManager::new_from_env_vars(self.env_vars.clone(), baml_src_reader);
+        let ctx = RuntimeContextManager::new_from_env_vars(self.env_vars.clone(), baml_src_reader)
+            .with_baml_src_dir(self.baml_src_dir.clone());
         let tags: HashMap<String, BamlValue> = [("baml.language", language)]
             .into_iter()
             .map(|(k, v)| (k.to_string(), v))
@@ -219,26 +230,35 @@ impl BamlRuntime {
         ctx: &RuntimeContextManager,
         on_event: Option<F>,
     ) -> (Result<TestResponse>, Option<uuid::Uuid>)
+    where
+        F: Fn(FunctionResult),
+    {
+        self.run_test_with_client_registry(function_name, test_name, ctx, on_event, None)
+            .await
+    }
+
+    /// Like `run_test`, but with the clients and prompt variant of `cb`.
+    pub async fn run_test_with_client_registry<F>(
+        &self,
+        function_name: &str,
+        test_name: &str,
+        ctx: &RuntimeContextManager,
+        on_event: Option<F>,
+        cb: Option<&ClientRegistry>,
+    ) -> (Result<TestResponse>, Option<uuid::Uuid>)
     where
         F: Fn(FunctionResult),
     {
         let span = self.tracer.start_span(test_name, ctx, &Default::default());
 
         let run_to_response = || async {
-            let rctx = ctx.create_ctx(None, None)?;
+            let rctx = ctx.create_ctx(None, cb)?;
             let (params, constraints) =
                 self.get_test_params_and_constraints(function_name, test_name, &rctx, true)?;
+            let row = self.inner.get_test_row(function_name, test_name, &rctx)?;
             log::info!("params: {:#?}", params);
-            let rctx_stream = ctx.create_ctx(None, None)?;
-            let mut stream = self.inner.stream_function_impl(
-                function_name.into(),
-                &params,
-                self.tracer.clone(),
-                rctx_stream,
-                #[cfg(not(target_arch = "wasm32"))]
-                self.async_runtime.clone(),
-            )?;
-            let (response_res, span_uuid) = stream.run(on_event, ctx, None, None).await;
+            let mut stream = self.stream_function(function_name.into(), &params, ctx, None, cb)?;
+            let (response_res, span_uuid) = stream.run(on_event, ctx, None, cb).await;
             log::info!("response_res: {:#?}", response_res);
             let res = response_res?;
             let (_, llm_resp, _, val) = res
@@ -263,7 +283,15 @@ impl BamlRuntime {
             } else {
                 match val {
                     Some(Ok(value)) => {
-                        evaluate_test_constraints(&params, value, complete_resp, constraints)
+                        self.run_test_constraints(
+                            &params,
+                            &row,
+                            value,
+                            complete_resp,
+                            constraints,
+                            ctx,
+                        )
+                        .await
                     }
                     _ => TestConstraintsResult::empty(),
                 }
@@ -295,6 +323,57 @@ impl BamlRuntime {
         (response, target_id)
     }
 
+    /// Evaluates the constraints of a test, calling the judges they use. The
+    /// judges are traced as children of the test's span.
+    async fn run_test_constraints(
+        &self,
+        params: &BamlMap<String, BamlValue>,
+        row: &IndexMap<String, BamlValue>,
+        value: &ResponseBamlValue,
+        response: &LLMCompleteResponse,
+        constraints: Vec<Constraint>,
+        ctx: &RuntimeContextManager,
+    ) -> TestConstraintsResult {
+        let judges = Judges::new(self.inner.ir().walk_functions().map(|f| {
+            (
+                f.name().to_string(),
+                f.inputs().iter().map(|(name, _)| name.clone()).collect(),
+            )
+        }));
+        loop {
+            let result = evaluate_test_constraints(
+                params,
+                row,
+                value,
+                response,
+                constraints.clone(),
+                &judges,
+            );
+            let pending = judges.take_pending();
+            if pending.is_empty() {
+                return result;
+            }
+            // Judges run one at a time, so that their spans nest correctly.
+            for (function_name, args) in pending {
+                let (response, _) = self
+                    .call_function(function_name.clone(), &args, ctx, None, None)
+                    .await;
+                let result = match response {
+                    Ok(response) => response
+                        .result_with_constraints_content()
+                        .map(BamlValue::from)
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                judges.record(JudgeCall {
+                    function_name,
+                    args,
+                    result,
+                });
+            }
+        }
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn call_function_sync(
         &self,
@@ -318,14 +397,10 @@ impl BamlRuntime {
     ) -> (Result<FunctionResult>, Option<uuid::Uuid>) {
         log::trace!("Calling function: {}", function_name);
         let span = self.tracer.start_span(&function_name, ctx, params);
-        let response = match ctx.create_ctx(tb, cb) {
-            Ok(rctx) => {
-                self.inner
-                    .call_function_impl(function_name, params, rctx)
-                    .await
-            }
-            Err(e) => Err(e),
-        };
+        let response = self
+            .inner
+            .call_function_with_steps(function_name, params, ctx, tb, cb)
+            .await;
 
         let mut target_id = None;
         if let Some(span) = span {
@@ -351,14 +426,78 @@ impl BamlRuntime {
         tb: Option<&TypeBuilder>,
         cb: Option<&ClientRegistry>,
     ) -> Result<FunctionResultStream> {
-        self.inner.stream_function_impl(
+        let mut stream = self.inner.stream_function_impl(
             function_name,
             params,
             self.tracer.clone(),
             ctx.create_ctx(tb, cb)?,
             #[cfg(not(target_arch = "wasm32"))]
             self.async_runtime.clone(),
-        )
+        )?;
+        stream.steps_runtime = Some(self.inner.clone());
+        Ok(stream)
+    }
+
+    /// Builds the HTTP request that calling `function_name` would send to its
+    /// client, without sending it. For fallback and round-robin clients, this
+    /// is the request to the first client that would be tried.
+    pub async fn build_request(
+        &self,
+        function_name: &str,
+        params: &BamlMap<String, BamlValue>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> Result<HttpRequest> {
+        let rctx = ctx.create_ctx(tb, cb)?;
+        self.inner
+            .render_http_request(function_name, &rctx, params, stream, None)
+            .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn build_request_sync(
+        &self,
+        function_name: &str,
+        params: &BamlMap<String, BamlValue>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> Result<HttpRequest> {
+        let fut = self.build_request(function_name, params, ctx, tb, cb, stream);
+        self.async_runtime.block_on(fut)
+    }
+
+    /// Parses a complete LLM response into the output type of
+    /// `function_name`, evaluating its checks and asserts.
+    pub fn parse_llm_response(
+        &self,
+        function_name: &str,
+        llm_response: &str,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+    ) -> Result<ResponseBamlValue> {
+        let rctx = ctx.create_ctx(tb, cb)?;
+        self.inner
+            .parse_llm_response(function_name, &rctx, llm_response, false)
+    }
+
+    /// Like [`Self::parse_llm_response`], but for the text an LLM has streamed
+    /// so far, which may end partway through the output.
+    pub fn parse_llm_response_partial(
+        &self,
+        function_name: &str,
+        llm_response: &str,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+    ) -> Result<ResponseBamlValue> {
+        let rctx = ctx.create_ctx(tb, cb)?;
+        self.inner
+            .parse_llm_response(function_name, &rctx, llm_response, true)
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -575,10 +714,23 @@ impl ExperimentalTracingInterface for BamlRuntime {
     }
 }
 
+/// The source files of a project, which clients are generated and deployed
+/// from.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn baml_src_files(dir: &std::path::PathBuf) -> Result<Vec<PathBuf>> {
-    static VALID_EXTENSIONS: [&str; 2] = ["baml", "json"];
+    find_src_files(dir, &["baml", "json"])
+}
 
+/// The source files of a project and the data files its tests read their
+/// rows from. Data files are not part of generated clients.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn baml_src_and_data_files(dir: &std::path::PathBuf) -> Result<Vec<PathBuf>> {
+    let [jsonl, csv] = internal_baml_core::internal_baml_parser_database::DATA_FILE_EXTENSIONS;
+    find_src_files(dir, &["baml", "json", jsonl, csv])
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn find_src_files(dir: &std::path::PathBuf, extensions: &[&str]) -> Result<Vec<PathBuf>> {
     log::trace!("Reading files from {:#}", dir.to_string_lossy());
 
     if !dir.exists() {
@@ -612,7 +764,7 @@ pub fn baml_src_files(dir: &std::path::PathBuf) -> Result<Vec<PathBuf>> {
             let Some(ext) = ext.to_str() else {
                 return false;
             };
-            VALID_EXTENSIONS.contains(&ext)
+            extensions.contains(&ext)
         })
         .map(|e| e.path().to_path_buf())
         .collect::<Vec<_>>();
@@ -1,4 +1,5 @@
 mod ir_features;
+mod pipeline;
 pub(crate) mod runtime_interface;
 
 use anyhow::Result;
@@ -22,13 +23,16 @@ use internal_baml_core::{
 };
 use std::sync::Arc;
 
-use crate::internal::llm_client::{llm_provider::LLMProvider, retry_policy::CallablePolicy};
+use crate::internal::llm_client::{
+    llm_provider::LLMProvider, rate_limit::RateLimiters, retry_policy::CallablePolicy,
+};
 
 pub struct InternalBamlRuntime {
     pub(crate) ir: Arc<IntermediateRepr>,
     diagnostics: Diagnostics,
     clients: DashMap<String, Arc<LLMProvider>>,
     retry_policies: DashMap<String, CallablePolicy>,
+    rate_limiters: Arc<RateLimiters>,
 }
 
 impl InternalBamlRuntime {
@@ -54,6 +58,7 @@ impl InternalBamlRuntime {
             diagnostics: schema.diagnostics,
             clients: Default::default(),
             retry_policies: Default::default(),
+            rate_limiters: Default::default(),
         })
     }
 
@@ -76,6 +81,7 @@ impl InternalBamlRuntime {
             diagnostics: schema.diagnostics,
             clients: Default::default(),
             retry_policies: Default::default(),
+            rate_limiters: Default::default(),
         })
     }
 }
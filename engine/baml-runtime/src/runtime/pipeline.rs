@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use baml_types::{BamlMap, BamlValue, EvaluationContext, StringOr, UnresolvedValue};
+use internal_baml_core::ir::{jinja_helpers::evaluate_expression, repr::PipelineStep, IRHelper};
+
+use super::InternalBamlRuntime;
+use crate::{
+    client_registry::ClientRegistry, runtime_interface::RuntimeInterface,
+    type_builder::TypeBuilder, FunctionResult, RuntimeContextManager,
+};
+
+impl InternalBamlRuntime {
+    /// Calls a function, running its steps first if it has any. Steps have no
+    /// spans of their own: they run inside the span of the call.
+    pub(crate) async fn call_function_with_steps(
+        &self,
+        function_name: String,
+        params: &BamlMap<String, BamlValue>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+    ) -> Result<FunctionResult> {
+        match self
+            .run_steps(function_name, params.clone(), ctx, tb, cb)
+            .await?
+        {
+            Ok((function_name, params)) => {
+                self.call_function_impl(function_name, &params, ctx.create_ctx(tb, cb)?)
+                    .await
+            }
+            Err(failed) => Ok(failed),
+        }
+    }
+
+    /// The function with a prompt that a call to `function_name` ends with:
+    /// the function itself, or the one the last step of its steps calls.
+    pub(crate) fn final_function(&self, function_name: &str) -> Result<String> {
+        let mut function_name = function_name.to_string();
+        loop {
+            let function = self.ir.find_function(&function_name)?;
+            match function.elem().steps().last() {
+                Some(last) => function_name = last.function.clone(),
+                None => return Ok(function_name),
+            }
+        }
+    }
+
+    /// Runs every step of a function but the last, then does the same for
+    /// the function the last step calls, until reaching a function with a
+    /// prompt. Returns that function and its arguments, or the result of the
+    /// step that failed.
+    pub(crate) async fn run_steps(
+        &self,
+        mut function_name: String,
+        mut params: BamlMap<String, BamlValue>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+    ) -> Result<std::result::Result<(String, BamlMap<String, BamlValue>), FunctionResult>> {
+        loop {
+            let function = self.ir.find_function(&function_name)?;
+            let Some((last, steps)) = function.elem().steps().split_last() else {
+                return Ok(Ok((function_name, params)));
+            };
+            let rctx = ctx.create_ctx(tb, cb)?;
+
+            let mut vars = params;
+            for step in steps {
+                let items = match &step.for_each {
+                    Some(expr) => match evaluate_expression(expr, &jinja_vars(&vars, None))? {
+                        BamlValue::List(items) => Some(items),
+                        other => anyhow::bail!(
+                            "`for_each` of step `{}` must be a list, got {}",
+                            step.name,
+                            other.r#type()
+                        ),
+                    },
+                    None => None,
+                };
+                // The evaluation context must not live across an await, or
+                // the future would not be `Send`.
+                let calls = {
+                    let eval_ctx = rctx.eval_ctx(false);
+                    match &items {
+                        Some(items) => items
+                            .iter()
+                            .map(|item| step_args(step, &vars, Some(item), &eval_ctx))
+                            .collect::<Result<Vec<_>>>()?,
+                        None => vec![step_args(step, &vars, None, &eval_ctx)?],
+                    }
+                };
+
+                // The calls of a `for_each` step run concurrently.
+                let results = futures::future::join_all(calls.iter().map(|args| {
+                    Box::pin(self.call_function_with_steps(
+                        step.function.clone(),
+                        args,
+                        ctx,
+                        tb,
+                        cb,
+                    ))
+                }))
+                .await;
+                let mut outputs = vec![];
+                for result in results {
+                    let result = result?;
+                    let output = result
+                        .result_with_constraints_content()
+                        .map(BamlValue::from)
+                        .ok();
+                    match output {
+                        Some(output) => outputs.push(output),
+                        None => return Ok(Err(result)),
+                    }
+                }
+                let output = match items {
+                    Some(_) => BamlValue::List(outputs),
+                    None => outputs.remove(0),
+                };
+                vars.insert(step.name.clone(), output);
+            }
+
+            params = step_args(last, &vars, None, &rctx.eval_ctx(false))?;
+            function_name = last.function.clone();
+        }
+    }
+}
+
+/// Evaluates the arguments of a step against the parameters of the function,
+/// the outputs of earlier steps and `item`.
+fn step_args(
+    step: &PipelineStep,
+    vars: &BamlMap<String, BamlValue>,
+    item: Option<&BamlValue>,
+    eval_ctx: &EvaluationContext<'_>,
+) -> Result<BamlMap<String, BamlValue>> {
+    let jinja_ctx = jinja_vars(vars, item);
+    step.args
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), arg_value(value, &jinja_ctx, eval_ctx)?)))
+        .collect()
+}
+
+/// Expressions are evaluated, everything else is a constant.
+fn arg_value(
+    value: &UnresolvedValue<()>,
+    jinja_ctx: &HashMap<String, minijinja::Value>,
+    eval_ctx: &EvaluationContext<'_>,
+) -> Result<BamlValue> {
+    match value {
+        UnresolvedValue::String(StringOr::JinjaExpression(expr), ..) => {
+            evaluate_expression(expr, jinja_ctx)
+        }
+        UnresolvedValue::Array(values, ..) => Ok(BamlValue::List(
+            values
+                .iter()
+                .map(|v| arg_value(v, jinja_ctx, eval_ctx))
+                .collect::<Result<_>>()?,
+        )),
+        UnresolvedValue::Map(values, ..) => Ok(BamlValue::Map(
+            values
+                .iter()
+                .map(|(k, (_, v))| Ok((k.clone(), arg_value(v, jinja_ctx, eval_ctx)?)))
+                .collect::<Result<_>>()?,
+        )),
+        other => other.resolve_serde::<BamlValue>(eval_ctx),
+    }
+}
+
+fn jinja_vars(
+    vars: &BamlMap<String, BamlValue>,
+    item: Option<&BamlValue>,
+) -> HashMap<String, minijinja::Value> {
+    vars.iter()
+        .map(|(name, value)| (name.clone(), value.clone().into()))
+        .chain(item.map(|item| ("item".to_string(), item.clone().into())))
+        .collect()
+}
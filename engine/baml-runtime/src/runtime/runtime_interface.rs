@@ -2,7 +2,7 @@ use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use super::InternalBamlRuntime;
 use crate::internal::llm_client::traits::WithClientProperties;
-use crate::internal::llm_client::LLMResponse;
+use crate::internal::llm_client::{parsed_value_to_response, LLMResponse, ResponseBamlValue};
 use crate::{
     client_registry::ClientProperty,
     internal::{
@@ -14,14 +14,14 @@ use crate::{
             },
             primitive::LLMPrimitiveProvider,
             retry_policy::CallablePolicy,
-            traits::{WithPrompt, WithRenderRawCurl},
+            traits::{WithPrompt, WithRenderHttpRequest, WithRenderRawCurl},
         },
         prompt_renderer::PromptRenderer,
     },
     runtime_interface::{InternalClientLookup, RuntimeConstructor},
     tracing::BamlTracer,
-    FunctionResult, FunctionResultStream, InternalRuntimeInterface, RenderCurlSettings,
-    RuntimeContext, RuntimeInterface,
+    FunctionResult, FunctionResultStream, HttpRequest, InternalRuntimeInterface,
+    RenderCurlSettings, RuntimeContext, RuntimeInterface,
 };
 use anyhow::{Context, Result};
 use baml_types::{BamlMap, BamlValue, Constraint, EvaluationContext};
@@ -74,7 +74,11 @@ impl<'a> InternalClientLookup<'a> for InternalBamlRuntime {
                         .find_client(client_name)
                         .context(format!("Could not find client with name: {}", client_name))?;
                     let client = LLMProvider::try_from((&walker, ctx)).map(Arc::new)?;
-                    clients.insert(client_name.into(), client.clone());
+                    // Secrets read from files or commands may be rotated, so
+                    // resolve them again on the next call.
+                    if !ctx.dereferenced_secrets() {
+                        clients.insert(client_name.into(), client.clone());
+                    }
                     Ok(client)
                 }
             }
@@ -211,6 +215,61 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
             .await
     }
 
+    async fn render_http_request(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        params: &BamlMap<String, BamlValue>,
+        stream: bool,
+        node_index: Option<usize>,
+    ) -> Result<HttpRequest> {
+        let func = self.get_function(function_name, ctx)?;
+        let baml_args = self.ir().check_function_params(
+            &func,
+            params,
+            ArgCoercer {
+                span_path: None,
+                allow_implicit_cast_to_string: false,
+            },
+        )?;
+
+        let renderer = PromptRenderer::from_function(&func, self.ir(), ctx)?;
+
+        let client_spec = renderer.client_spec();
+        let client = self.get_llm_provider(client_spec, ctx)?;
+        let mut selected =
+            client.iter_orchestrator(&mut Default::default(), Default::default(), ctx, self)?;
+        let node_index = node_index.unwrap_or(0);
+
+        if node_index >= selected.len() {
+            return Err(anyhow::anyhow!(
+                "Execution Node out of bounds (render request): {} >= {} for client {}",
+                node_index,
+                selected.len(),
+                client_spec,
+            ));
+        }
+
+        let node = selected.swap_remove(node_index);
+        let prompt = node
+            .render_prompt(self.ir(), &renderer, ctx, &baml_args)
+            .await?;
+        node.render_http_request(ctx, &prompt, stream).await
+    }
+
+    fn parse_llm_response(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        llm_response: &str,
+        allow_partials: bool,
+    ) -> Result<ResponseBamlValue> {
+        let func = self.get_function(function_name, ctx)?;
+        let renderer = PromptRenderer::from_function(&func, self.ir(), ctx)?;
+        let parsed = renderer.parse(self.ir(), llm_response, allow_partials)?;
+        Ok(parsed_value_to_response(&parsed))
+    }
+
     fn get_function<'ir>(
         &'ir self,
         function_name: &str,
@@ -285,6 +344,17 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
         let walker = self.ir().find_test(&func, test_name)?;
         Ok(walker.item.1.elem.constraints.clone())
     }
+
+    fn get_test_row(
+        &self,
+        function_name: &str,
+        test_name: &str,
+        ctx: &RuntimeContext,
+    ) -> Result<BamlMap<String, BamlValue>> {
+        let func = self.get_function(function_name, ctx)?;
+        let walker = self.ir().find_test(&func, test_name)?;
+        walker.test_case_row(&ctx.eval_ctx(true))
+    }
 }
 
 impl RuntimeConstructor for InternalBamlRuntime {
@@ -314,12 +384,13 @@ impl RuntimeConstructor for InternalBamlRuntime {
             diagnostics: schema.diagnostics,
             clients: Default::default(),
             retry_policies: Default::default(),
+            rate_limiters: Default::default(),
         })
     }
 
     #[cfg(not(target_arch = "wasm32"))]
     fn from_directory(dir: &std::path::Path) -> Result<InternalBamlRuntime> {
-        InternalBamlRuntime::from_files(dir, crate::baml_src_files(&dir.to_path_buf())?)
+        InternalBamlRuntime::from_files(dir, crate::baml_src_and_data_files(&dir.to_path_buf())?)
     }
 }
 
@@ -328,8 +399,9 @@ impl RuntimeInterface for InternalBamlRuntime {
         &self,
         function_name: String,
         params: &BamlMap<String, BamlValue>,
-        ctx: RuntimeContext,
+        mut ctx: RuntimeContext,
     ) -> Result<crate::FunctionResult> {
+        ctx.function_name = Some(function_name.clone());
         let func = match self.get_function(&function_name, &ctx) {
             Ok(func) => func,
             Err(e) => {
@@ -377,13 +449,18 @@ impl RuntimeInterface for InternalBamlRuntime {
         let orchestrator = self.orchestration_graph(renderer.client_spec(), &ctx)?;
 
         // Now actually execute the code.
-        let (history, _) =
-            orchestrate_call(orchestrator, self.ir(), &ctx, &renderer, &baml_args, |s| {
-                renderer.parse(s, false)
-            })
-            .await;
-
-        FunctionResult::new_chain(history)
+        let (history, _) = orchestrate_call(
+            orchestrator,
+            self.ir(),
+            &ctx,
+            &self.rate_limiters,
+            &renderer,
+            &baml_args,
+            |s| renderer.parse(self.ir(), s, false),
+        )
+        .await;
+
+        FunctionResult::new_chain(history).map(|r| r.with_variant(renderer.variant()))
     }
 
     fn stream_function_impl(
@@ -391,11 +468,14 @@ impl RuntimeInterface for InternalBamlRuntime {
         function_name: String,
         params: &BamlMap<String, BamlValue>,
         tracer: Arc<BamlTracer>,
-        ctx: RuntimeContext,
+        mut ctx: RuntimeContext,
         #[cfg(not(target_arch = "wasm32"))] tokio_runtime: Arc<tokio::runtime::Runtime>,
     ) -> Result<FunctionResultStream> {
+        ctx.function_name = Some(function_name.clone());
         let func = self.get_function(&function_name, &ctx)?;
-        let renderer = PromptRenderer::from_function(&func, self.ir(), &ctx)?;
+        // A function with steps streams the call its last step makes.
+        let final_func = self.get_function(&self.final_function(&function_name)?, &ctx)?;
+        let renderer = PromptRenderer::from_function(&final_func, self.ir(), &ctx)?;
         let orchestrator = self.orchestration_graph(renderer.client_spec(), &ctx)?;
         let Some(baml_args) = self
             .ir
@@ -416,10 +496,12 @@ impl RuntimeInterface for InternalBamlRuntime {
             ir: self.ir.clone(),
             params: baml_args,
             orchestrator,
+            rate_limiters: self.rate_limiters.clone(),
             tracer,
             renderer,
             #[cfg(not(target_arch = "wasm32"))]
             tokio_runtime,
+            steps_runtime: None,
         })
     }
 }
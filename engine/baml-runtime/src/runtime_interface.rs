@@ -8,6 +8,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::internal::llm_client::llm_provider::LLMProvider;
 use crate::internal::llm_client::orchestrator::{OrchestrationScope, OrchestratorNode};
+use crate::internal::llm_client::ResponseBamlValue;
 use crate::tracing::{BamlTracer, TracingSpan};
 use crate::types::on_log_event::LogEventCallbackSync;
 use crate::{
@@ -16,7 +17,7 @@ use crate::{
     types::FunctionResultStream,
     FunctionResult, RuntimeContext,
 };
-use crate::{RenderCurlSettings, RuntimeContextManager};
+use crate::{HttpRequest, RenderCurlSettings, RuntimeContextManager};
 
 pub(crate) trait RuntimeConstructor {
     #[cfg(not(target_arch = "wasm32"))]
@@ -150,6 +151,24 @@ pub trait InternalRuntimeInterface {
         node_index: Option<usize>,
     ) -> Result<String>;
 
+    #[allow(async_fn_in_trait)]
+    async fn render_http_request(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        params: &BamlMap<String, BamlValue>,
+        stream: bool,
+        node_index: Option<usize>,
+    ) -> Result<HttpRequest>;
+
+    fn parse_llm_response(
+        &self,
+        function_name: &str,
+        ctx: &RuntimeContext,
+        llm_response: &str,
+        allow_partials: bool,
+    ) -> Result<ResponseBamlValue>;
+
     fn ir(&self) -> &IntermediateRepr;
 
     fn get_test_params(
@@ -166,4 +185,11 @@ pub trait InternalRuntimeInterface {
         test_name: &str,
         ctx: &RuntimeContext,
     ) -> Result<Vec<Constraint>>;
+
+    fn get_test_row(
+        &self,
+        function_name: &str,
+        test_name: &str,
+        ctx: &RuntimeContext,
+    ) -> Result<BamlMap<String, BamlValue>>;
 }
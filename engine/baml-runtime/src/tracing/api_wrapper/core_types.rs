@@ -130,7 +130,10 @@ pub struct LLMOutputModelMetadata {
     pub prompt_tokens: Option<i64>,
     pub output_tokens: Option<i64>,
     pub total_tokens: Option<i64>,
+    pub cache_creation_input_tokens: Option<i64>,
+    pub cache_read_input_tokens: Option<i64>,
     pub finish_reason: Option<String>,
+    pub cost: Option<f64>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -162,6 +165,18 @@ pub enum ContentPart {
     UrlAudio(String),
     #[serde(rename = "b64_audio")]
     B64Audio(String),
+    #[serde(rename = "file_pdf")]
+    FilePdf(String, String),
+    #[serde(rename = "url_pdf")]
+    UrlPdf(String),
+    #[serde(rename = "b64_pdf")]
+    B64Pdf(String),
+    #[serde(rename = "file_video")]
+    FileVideo(String, String),
+    #[serde(rename = "url_video")]
+    UrlVideo(String),
+    #[serde(rename = "b64_video")]
+    B64Video(String),
     #[serde(rename = "with_meta")]
     WithMeta(Box<ContentPart>, HashMap<String, Value>),
 }
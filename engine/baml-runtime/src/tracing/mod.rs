@@ -159,6 +159,7 @@ struct BamlEventJson {
     total_tries: usize,
 
     // LLM Info
+    variant: Option<String>,
     client: String,
     model: String,
     latency_ms: u128,
@@ -172,10 +173,14 @@ struct BamlEventJson {
 
     // Token Usage
     tokens: Option<TokenUsage>,
+    // In USD, summed across all tries
+    cost: Option<f64>,
 
     // Response/Error Info
     parsed_response_type: Option<String>,
     parsed_response: Option<String>,
+    // JSON string
+    parse_report: Option<String>,
     error: Option<String>,
 }
 
@@ -184,6 +189,8 @@ struct TokenUsage {
     prompt_tokens: Option<u64>,
     completion_tokens: Option<u64>,
     total_tokens: Option<u64>,
+    cache_creation_input_tokens: Option<u64>,
+    cache_read_input_tokens: Option<u64>,
 }
 
 impl BamlTracer {
@@ -445,6 +452,7 @@ impl BamlTracer {
                 start_time: to_iso_string(&span.start_time),
                 num_tries: 0,
                 total_tries: 0,
+                variant: None,
                 client: "unknown".to_string(),
                 model: "unknown".to_string(),
                 latency_ms: 0,
@@ -453,8 +461,10 @@ impl BamlTracer {
                 llm_reply: None,
                 request_options_json: None,
                 tokens: None,
+                cost: None,
                 parsed_response_type: None,
                 parsed_response: None,
+                parse_report: None,
                 error: Some(error.to_string()),
             };
             rust_tracing::event!(
@@ -477,12 +487,14 @@ impl BamlTracer {
         let num_tries = response.event_chain().len();
         let total_tries = response.event_chain().len();
         let error = error_from_result(response).map(|e| e.message.clone());
+        let variant = response.variant().map(|v| v.to_string());
 
         match last_ctx {
             LLMResponse::Success(resp) => BamlEventJson {
                 start_time,
                 num_tries,
                 total_tries,
+                variant,
                 client: resp.client.clone(),
                 model: resp.model.clone(),
                 latency_ms: resp.latency.as_millis(),
@@ -496,7 +508,10 @@ impl BamlTracer {
                     prompt_tokens: resp.metadata.prompt_tokens,
                     completion_tokens: resp.metadata.output_tokens,
                     total_tokens: resp.metadata.total_tokens,
+                    cache_creation_input_tokens: resp.metadata.cache_creation_input_tokens,
+                    cache_read_input_tokens: resp.metadata.cache_read_input_tokens,
                 }),
+                cost: response.cost(),
                 parsed_response_type: response
                     .result_with_constraints()
                     .as_ref()
@@ -507,12 +522,16 @@ impl BamlTracer {
                     .as_ref()
                     .and_then(|r| r.as_ref().ok())
                     .map(|v| serde_json::to_string(v).unwrap_or_default()),
+                parse_report: response
+                    .parse_report()
+                    .map(|r| serde_json::to_string(&r).unwrap_or_default()),
                 error,
             },
             LLMResponse::LLMFailure(err) => BamlEventJson {
                 start_time,
                 num_tries,
                 total_tries,
+                variant,
                 client: err.client.clone(),
                 model: err.model.clone().unwrap_or_default(),
                 latency_ms: err.latency.as_millis(),
@@ -523,14 +542,17 @@ impl BamlTracer {
                     serde_json::to_string(&err.request_options).unwrap_or_default(),
                 ),
                 tokens: None,
+                cost: response.cost(),
                 parsed_response_type: None,
                 parsed_response: None,
+                parse_report: None,
                 error,
             },
             LLMResponse::UserFailure(msg) | LLMResponse::InternalFailure(msg) => BamlEventJson {
                 start_time,
                 num_tries,
                 total_tries,
+                variant,
                 client: "unknown".to_string(),
                 model: "unknown".to_string(),
                 latency_ms: 0,
@@ -539,8 +561,10 @@ impl BamlTracer {
                 llm_reply: None,
                 request_options_json: None,
                 tokens: None,
+                cost: response.cost(),
                 parsed_response_type: None,
                 parsed_response: None,
+                parse_report: None,
                 error: Some(msg.clone()),
             },
         }
@@ -822,7 +846,14 @@ impl ToLogSchema for FunctionResult {
             } else {
                 None
             },
-            context: (api, event_chain, tags, &span).into(),
+            context: {
+                let mut context: LogSchemaContext = (api, event_chain, tags, &span).into();
+                // The last span in the chain is the call of this function.
+                if let Some(call) = context.event_chain.last_mut() {
+                    call.variant_name = self.variant().map(|v| v.to_string());
+                }
+                context
+            },
             io: IO {
                 input: Some((&span.params).into()),
                 output: self
@@ -943,18 +974,42 @@ impl From<&internal_baml_jinja::ChatMessagePart> for ContentPart {
                             data.relpath.to_string_lossy().into_owned(),
                         )
                     }
+                    (BamlMediaType::Pdf, baml_types::BamlMediaContent::File(data)) => {
+                        ContentPart::FilePdf(
+                            data.span_path.to_string_lossy().into_owned(),
+                            data.relpath.to_string_lossy().into_owned(),
+                        )
+                    }
+                    (BamlMediaType::Video, baml_types::BamlMediaContent::File(data)) => {
+                        ContentPart::FileVideo(
+                            data.span_path.to_string_lossy().into_owned(),
+                            data.relpath.to_string_lossy().into_owned(),
+                        )
+                    }
                     (BamlMediaType::Image, baml_types::BamlMediaContent::Base64(data)) => {
                         ContentPart::B64Image(data.base64.clone())
                     }
                     (BamlMediaType::Audio, baml_types::BamlMediaContent::Base64(data)) => {
                         ContentPart::B64Audio(data.base64.clone())
                     }
+                    (BamlMediaType::Pdf, baml_types::BamlMediaContent::Base64(data)) => {
+                        ContentPart::B64Pdf(data.base64.clone())
+                    }
+                    (BamlMediaType::Video, baml_types::BamlMediaContent::Base64(data)) => {
+                        ContentPart::B64Video(data.base64.clone())
+                    }
                     (BamlMediaType::Image, baml_types::BamlMediaContent::Url(data)) => {
                         ContentPart::UrlImage(data.url.clone())
                     }
                     (BamlMediaType::Audio, baml_types::BamlMediaContent::Url(data)) => {
                         ContentPart::UrlAudio(data.url.clone())
                     }
+                    (BamlMediaType::Pdf, baml_types::BamlMediaContent::Url(data)) => {
+                        ContentPart::UrlPdf(data.url.clone())
+                    }
+                    (BamlMediaType::Video, baml_types::BamlMediaContent::Url(data)) => {
+                        ContentPart::UrlVideo(data.url.clone())
+                    }
                 }
             }
             internal_baml_jinja::ChatMessagePart::WithMeta(inner, meta) => ContentPart::WithMeta(
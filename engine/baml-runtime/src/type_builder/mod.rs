@@ -1,7 +1,11 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
 
 use baml_types::{BamlValue, FieldType};
 use indexmap::IndexMap;
+use internal_baml_core::ir::json_schema_import::JsonSchemaImport;
 
 use crate::runtime_context::{PropertyAttributes, RuntimeClassOverride, RuntimeEnumOverride};
 
@@ -206,6 +210,60 @@ impl TypeBuilder {
         )
     }
 
+    /// Add the classes and enums described by a JSON Schema (or OpenAPI)
+    /// document. Types named like one in `taken` (e.g. the project's own
+    /// classes and enums) are renamed, see
+    /// [`JsonSchemaImport::from_json_schema_with_taken_names`].
+    ///
+    /// Returns the import, whose `root` is the type of the root schema, if it
+    /// describes one.
+    pub fn add_json_schema(
+        &self,
+        schema: &serde_json::Value,
+        root_name: &str,
+        taken: &HashSet<String>,
+    ) -> anyhow::Result<JsonSchemaImport> {
+        let import = JsonSchemaImport::from_json_schema_with_taken_names(schema, root_name, taken)?;
+
+        for (name, imported) in import.enums.iter() {
+            let enm = self.r#enum(name);
+            let enm = enm.lock().unwrap();
+            if let Some(description) = &imported.description {
+                enm.with_meta("description", BamlValue::String(description.clone()));
+            }
+            for value in imported.values.iter() {
+                let value_builder = enm.value(&value.name);
+                if let Some(alias) = &value.alias {
+                    value_builder
+                        .lock()
+                        .unwrap()
+                        .with_meta("alias", BamlValue::String(alias.clone()));
+                }
+            }
+        }
+
+        for (name, imported) in import.classes.iter() {
+            let cls = self.class(name);
+            let cls = cls.lock().unwrap();
+            if let Some(description) = &imported.description {
+                cls.with_meta("description", BamlValue::String(description.clone()));
+            }
+            for field in imported.fields.iter() {
+                let property = cls.property(&field.name);
+                let property = property.lock().unwrap();
+                property.r#type(field.r#type.clone());
+                if let Some(alias) = &field.alias {
+                    property.with_meta("alias", BamlValue::String(alias.clone()));
+                }
+                if let Some(description) = &field.description {
+                    property.with_meta("description", BamlValue::String(description.clone()));
+                }
+            }
+        }
+
+        Ok(import)
+    }
+
     pub fn to_overrides(
         &self,
     ) -> (
@@ -310,4 +368,44 @@ mod tests {
             .r#type(FieldType::int())
             .with_meta("alias", BamlValue::String("years".to_string()));
     }
+
+    #[test]
+    fn test_add_json_schema() {
+        let builder = TypeBuilder::new();
+        let import = builder
+            .add_json_schema(
+                &serde_json::json!({
+                    "title": "Person",
+                    "type": "object",
+                    "properties": {
+                        "first-name": { "type": "string", "description": "Given name" },
+                        "role": { "$ref": "#/$defs/Role" },
+                    },
+                    "required": ["first-name", "role"],
+                    "$defs": {
+                        "Role": { "enum": ["admin", "member"] }
+                    }
+                }),
+                "Root",
+                &HashSet::new(),
+            )
+            .unwrap();
+        assert_eq!(import.root, Some(FieldType::class("Person")));
+
+        let (classes, enums) = builder.to_overrides();
+        let (field_type, attrs) = &classes["Person"].new_fields["first_name"];
+        assert_eq!(field_type, &FieldType::string());
+        assert_eq!(
+            attrs.alias,
+            Some(BamlValue::String("first-name".to_string()))
+        );
+        assert_eq!(
+            classes["Person"].new_fields["role"].0,
+            FieldType::r#enum("Role")
+        );
+        assert_eq!(
+            enums["Role"].values["ADMIN"].alias,
+            Some(BamlValue::String("admin".to_string()))
+        );
+    }
 }
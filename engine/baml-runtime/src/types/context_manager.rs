@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 
@@ -9,16 +10,18 @@ use std::fmt;
 
 use crate::{client_registry::ClientRegistry, type_builder::TypeBuilder, RuntimeContext, SpanCtx};
 
-use super::runtime_context::BamlSrcReader;
+use super::{runtime_context::BamlSrcReader, CostBudget};
 
 type BamlContext = (uuid::Uuid, String, HashMap<String, BamlValue>);
 
 #[derive(Clone)]
 pub struct RuntimeContextManager {
     baml_src_reader: Arc<BamlSrcReader>,
+    baml_src_dir: Option<PathBuf>,
     context: Arc<Mutex<Vec<BamlContext>>>,
     env_vars: HashMap<String, String>,
     global_tags: Arc<Mutex<HashMap<String, BamlValue>>>,
+    cost_budget: Arc<Mutex<Option<Arc<CostBudget>>>>,
 }
 
 impl fmt::Debug for RuntimeContextManager {
@@ -34,10 +37,13 @@ impl RuntimeContextManager {
     pub fn deep_clone(&self) -> Self {
         Self {
             baml_src_reader: self.baml_src_reader.clone(),
+            baml_src_dir: self.baml_src_dir.clone(),
 
             context: Arc::new(Mutex::new(self.context.lock().unwrap().clone())),
             env_vars: self.env_vars.clone(),
             global_tags: Arc::new(Mutex::new(self.global_tags.lock().unwrap().clone())),
+            // Copies keep spending against the same budget.
+            cost_budget: Arc::new(Mutex::new(self.cost_budget.lock().unwrap().clone())),
         }
     }
 
@@ -47,12 +53,30 @@ impl RuntimeContextManager {
     ) -> Self {
         Self {
             baml_src_reader: Arc::new(baml_src_reader),
+            baml_src_dir: None,
             context: Default::default(),
             env_vars,
             global_tags: Default::default(),
+            cost_budget: Default::default(),
         }
     }
 
+    /// Sets the project's `baml_src` directory, where secrets are read from
+    /// the `.env` file.
+    pub fn with_baml_src_dir(mut self, baml_src_dir: Option<PathBuf>) -> Self {
+        self.baml_src_dir = baml_src_dir;
+        self
+    }
+
+    /// Limits how much the function calls made with this context may spend, in
+    /// USD. Replaces any previous budget, starting over from nothing spent.
+    pub fn set_cost_budget(&self, per_call: Option<f64>, per_context: Option<f64>) {
+        *self.cost_budget.lock().unwrap() = match (per_call, per_context) {
+            (None, None) => None,
+            _ => Some(Arc::new(CostBudget::new(per_call, per_context))),
+        };
+    }
+
     pub fn upsert_tags(&self, tags: HashMap<String, BamlValue>) {
         let mut ctx = self.context.lock().unwrap();
         if let Some((.., last_tags)) = ctx.last_mut() {
@@ -129,6 +153,7 @@ impl RuntimeContextManager {
 
         let mut ctx = RuntimeContext::new(
             self.baml_src_reader.clone(),
+            self.baml_src_dir.clone(),
             self.env_vars.clone(),
             tags,
             Default::default(),
@@ -145,6 +170,8 @@ impl RuntimeContextManager {
         };
 
         ctx.client_overrides = client_overrides;
+        ctx.variant = cb.and_then(|cb| cb.variant().cloned());
+        ctx.cost_budget = self.cost_budget.lock().unwrap().clone();
 
         Ok(ctx)
     }
@@ -152,14 +179,17 @@ impl RuntimeContextManager {
     pub fn create_ctx_with_default(&self) -> RuntimeContext {
         let ctx = self.context.lock().unwrap();
 
-        RuntimeContext::new(
+        let mut runtime_ctx = RuntimeContext::new(
             self.baml_src_reader.clone(),
+            self.baml_src_dir.clone(),
             self.env_vars.clone(),
             ctx.last().map(|(.., x)| x).cloned().unwrap_or_default(),
             Default::default(),
             Default::default(),
             Default::default(),
-        )
+        );
+        runtime_ctx.cost_budget = self.cost_budget.lock().unwrap().clone();
+        runtime_ctx
     }
 
     pub fn context_depth(&self) -> usize {
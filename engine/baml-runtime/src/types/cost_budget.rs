@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+
+/// Spending limits in USD for the function calls made with a context.
+///
+/// The cost of a call is only known when the provider reports token usage and
+/// the model has a price, so calls without either are not counted.
+#[derive(Debug, Default)]
+pub struct CostBudget {
+    per_call: Option<f64>,
+    per_context: Option<f64>,
+    spent: Mutex<f64>,
+}
+
+impl CostBudget {
+    pub fn new(per_call: Option<f64>, per_context: Option<f64>) -> Self {
+        Self {
+            per_call,
+            per_context,
+            spent: Mutex::new(0.0),
+        }
+    }
+
+    /// Total spent across all the calls made with this budget.
+    pub fn spent(&self) -> f64 {
+        *self.spent.lock().unwrap()
+    }
+
+    pub fn record(&self, cost: f64) {
+        *self.spent.lock().unwrap() += cost;
+    }
+
+    /// Checked before every attempt (retries and fallbacks included) of a
+    /// function call that has spent `call_spent` so far.
+    pub fn check(&self, call_spent: f64) -> Result<(), String> {
+        if let Some(limit) = self.per_call {
+            if call_spent >= limit {
+                return Err(format!(
+                    "Cost budget exceeded: this call spent ${call_spent:.6} of its ${limit:.6} limit"
+                ));
+            }
+        }
+        if let Some(limit) = self.per_context {
+            let spent = self.spent();
+            if spent >= limit {
+                return Err(format!(
+                    "Cost budget exceeded: ${spent:.6} spent of the ${limit:.6} limit for this context"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_limits() {
+        let budget = CostBudget::new(Some(0.5), Some(1.0));
+        assert!(budget.check(0.0).is_ok());
+        assert!(budget.check(0.5).is_err());
+
+        budget.record(0.4);
+        budget.record(0.4);
+        assert!(budget.check(0.0).is_ok());
+
+        budget.record(0.2);
+        assert!(budget.check(0.0).is_err());
+        assert!(CostBudget::default().check(f64::MAX).is_ok());
+    }
+}
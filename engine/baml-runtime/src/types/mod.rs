@@ -1,19 +1,35 @@
 mod context_manager;
+mod cost_budget;
 // mod expression_helper;
 pub mod on_log_event;
 mod response;
 pub(crate) mod runtime_context;
+mod secrets;
 mod stream;
 mod trace_stats;
 
 pub use context_manager::RuntimeContextManager;
-pub use response::{FunctionResult, TestFailReason, TestResponse, TestStatus};
+pub use cost_budget::CostBudget;
+pub use response::{FunctionResult, TestFailReason, TestResponse, TestStatus, TokenUsage};
 pub use runtime_context::{RuntimeContext, SpanCtx};
 pub use stream::FunctionResultStream;
 pub use trace_stats::{InnerTraceStats, TraceStats};
 
+use indexmap::IndexMap;
+use serde::Serialize;
+
 #[derive(Debug, Clone, Copy)]
 pub struct RenderCurlSettings {
     pub stream: bool,
     pub as_shell_commands: bool,
 }
+
+/// The HTTP request BAML would send to a provider for a function call, built
+/// but not sent.
+#[derive(Debug, Clone, Serialize)]
+pub struct HttpRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: IndexMap<String, String>,
+    pub body: serde_json::Value,
+}
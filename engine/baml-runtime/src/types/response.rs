@@ -8,7 +8,19 @@ use anyhow::Result;
 use colored::*;
 
 use baml_types::BamlValue;
-use jsonish::BamlValueWithFlags;
+use jsonish::{BamlValueWithFlags, ParseReport};
+
+/// Token usage summed over every attempt made for a call, including retries
+/// and fallbacks. A count is `None` if no attempt reported it.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct TokenUsage {
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    /// Input tokens written to the provider's prompt cache.
+    pub cache_creation_input_tokens: Option<u64>,
+    /// Input tokens read from the provider's prompt cache.
+    pub cache_read_input_tokens: Option<u64>,
+}
 
 #[derive(Debug)]
 pub struct FunctionResult {
@@ -18,6 +30,7 @@ pub struct FunctionResult {
         Option<Result<BamlValueWithFlags>>,
         Option<Result<ResponseBamlValue>>,
     )>,
+    variant: Option<String>,
 }
 
 impl std::fmt::Display for FunctionResult {
@@ -58,6 +71,7 @@ impl FunctionResult {
     ) -> Self {
         Self {
             event_chain: vec![(scope, response, parsed, baml_value)],
+            variant: None,
         }
     }
 
@@ -84,7 +98,21 @@ impl FunctionResult {
             anyhow::bail!("No events in the chain");
         }
 
-        Ok(Self { event_chain: chain })
+        Ok(Self {
+            event_chain: chain,
+            variant: None,
+        })
+    }
+
+    pub(crate) fn with_variant(mut self, variant: Option<&str>) -> Self {
+        self.variant = variant.map(|v| v.to_string());
+        self
+    }
+
+    /// The name of the prompt variant that was used, or `None` if the function
+    /// has no variants.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
     }
 
     pub fn content(&self) -> Result<&str> {
@@ -95,6 +123,45 @@ impl FunctionResult {
         &self.event_chain.last().unwrap().1
     }
 
+    /// The cost in USD of every attempt made for this call, including retries
+    /// and fallbacks, or `None` if none of them has a known cost.
+    pub fn cost(&self) -> Option<f64> {
+        self.event_chain
+            .iter()
+            .filter_map(|(_, response, ..)| match response {
+                LLMResponse::Success(s) => s.metadata.cost,
+                _ => None,
+            })
+            .reduce(|a, b| a + b)
+    }
+
+    /// The token usage of every attempt made for this call, including
+    /// retries and fallbacks.
+    pub fn usage(&self) -> TokenUsage {
+        fn add(total: &mut Option<u64>, tokens: Option<u64>) {
+            if let Some(tokens) = tokens {
+                *total = Some(total.unwrap_or(0) + tokens);
+            }
+        }
+
+        let mut usage = TokenUsage::default();
+        for (_, response, ..) in &self.event_chain {
+            if let LLMResponse::Success(s) = response {
+                add(&mut usage.input_tokens, s.metadata.prompt_tokens);
+                add(&mut usage.output_tokens, s.metadata.output_tokens);
+                add(
+                    &mut usage.cache_creation_input_tokens,
+                    s.metadata.cache_creation_input_tokens,
+                );
+                add(
+                    &mut usage.cache_read_input_tokens,
+                    s.metadata.cache_read_input_tokens,
+                );
+            }
+        }
+        usage
+    }
+
     pub fn scope(&self) -> &OrchestrationScope {
         &self.event_chain.last().unwrap().0
     }
@@ -103,6 +170,15 @@ impl FunctionResult {
         &self.event_chain.last().unwrap().2
     }
 
+    /// The fixes the parser applied to each path of the parsed value, or
+    /// `None` if the response could not be parsed.
+    pub fn parse_report(&self) -> Option<ParseReport> {
+        match self.parsed() {
+            Some(Ok(v)) => Some(ParseReport::new(v)),
+            _ => None,
+        }
+    }
+
     /// Get the parsed result. This logic is strange because parsing errors can
     /// be forwarded to a different field in the orchestrator.
     /// TODO: (Greg) Fix the strange logic.
@@ -255,6 +331,7 @@ impl TestResponse {
                     TestConstraintsResult::Completed {
                         checks,
                         failed_assert,
+                        ..
                     } => {
                         let n_failed_checks: usize =
                             checks.iter().filter(|(_, pass)| !pass).count();
@@ -2,10 +2,12 @@ use anyhow::Result;
 use baml_types::{BamlValue, EvaluationContext, UnresolvedValue};
 use indexmap::IndexMap;
 use internal_baml_core::ir::FieldType;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use crate::internal::llm_client::llm_provider::LLMProvider;
 
+use super::{secrets::SecretResolver, CostBudget};
+
 #[derive(Debug, Clone)]
 pub struct SpanCtx {
     pub span_id: uuid::Uuid,
@@ -52,15 +54,27 @@ pub struct RuntimeContext {
     // path to baml_src in the local filesystem
     pub baml_src: Arc<BamlSrcReader>,
     env: HashMap<String, String>,
+    secrets: SecretResolver,
     pub tags: HashMap<String, BamlValue>,
     pub client_overrides: Option<(Option<String>, HashMap<String, Arc<LLMProvider>>)>,
     pub class_override: IndexMap<String, RuntimeClassOverride>,
     pub enum_overrides: IndexMap<String, RuntimeEnumOverride>,
+    /// The BAML function being called, if this context is for a function call.
+    pub function_name: Option<String>,
+    pub cost_budget: Option<Arc<CostBudget>>,
+    /// The prompt variant the caller asked for, if any.
+    pub variant: Option<String>,
 }
 
 impl RuntimeContext {
     pub fn eval_ctx(&self, strict: bool) -> EvaluationContext<'_> {
-        EvaluationContext::new(&self.env, !strict)
+        EvaluationContext::new(&self.env, !strict).with_resolver(&self.secrets)
+    }
+
+    /// Whether a secret was read from a file or a command while resolving
+    /// values with this context.
+    pub(crate) fn dereferenced_secrets(&self) -> bool {
+        self.secrets.dereferenced_secrets()
     }
 
     pub fn env_vars(&self) -> &HashMap<String, String> {
@@ -73,6 +87,7 @@ impl RuntimeContext {
 
     pub fn new(
         baml_src: Arc<BamlSrcReader>,
+        baml_src_dir: Option<PathBuf>,
         env: HashMap<String, String>,
         tags: HashMap<String, BamlValue>,
         client_overrides: Option<(Option<String>, HashMap<String, Arc<LLMProvider>>)>,
@@ -82,10 +97,14 @@ impl RuntimeContext {
         RuntimeContext {
             baml_src,
             env,
+            secrets: SecretResolver::new(baml_src_dir),
             tags,
             client_overrides,
             class_override,
             enum_overrides,
+            function_name: None,
+            cost_budget: None,
+            variant: None,
         }
     }
 
@@ -96,7 +115,7 @@ impl RuntimeContext {
         // otherwise, will return a value with the missing environment variables replaced with the string "${key}"
         strict: bool,
     ) -> Result<T> {
-        let ctx = EvaluationContext::new(&self.env, strict);
+        let ctx = EvaluationContext::new(&self.env, strict).with_resolver(&self.secrets);
         match expr.resolve_serde::<T>(&ctx) {
             Ok(v) => Ok(v),
             Err(e) => anyhow::bail!(
@@ -0,0 +1,421 @@
+//! Resolution of `env.X` references in client options.
+//!
+//! Values are looked up in order from the `sources` configured in
+//! `BAML_SECRET_RESOLVERS` (default: `env,dotenv`):
+//!
+//! - `env`: the environment variables passed to the runtime.
+//! - `dotenv`: the `.env` file at `BAML_DOTENV` (default: `.env`). Relative
+//!   paths are resolved against the project's `baml_src` directory.
+//!
+//! A value found this way may itself point at the secret, if the resolver is
+//! listed in `BAML_SECRET_RESOLVERS`. Both are off by default, since they read
+//! arbitrary files and run arbitrary commands:
+//!
+//! - `file`: `file:/run/secrets/openai_key` is replaced by the contents of
+//!   the file.
+//! - `exec`: `exec:op read op://vault/openai/key` is replaced by the stdout of
+//!   the command. Outputs are cached for `BAML_SECRET_EXEC_TTL` seconds
+//!   (default: 300).
+//!
+//! The settings themselves are only read from the environment variables, never
+//! from the `.env` file: a checked out project must not be able to turn on
+//! `file` or `exec` by itself. Everything is resolved lazily, when a client is
+//! built for a call.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use baml_types::ResolveEnvVar;
+use web_time::{Duration, Instant};
+
+const RESOLVERS_ENV_VAR: &str = "BAML_SECRET_RESOLVERS";
+const DOTENV_ENV_VAR: &str = "BAML_DOTENV";
+const EXEC_TTL_ENV_VAR: &str = "BAML_SECRET_EXEC_TTL";
+const DEFAULT_DOTENV_PATH: &str = ".env";
+const DEFAULT_RESOLVERS: &str = "env,dotenv";
+const DEFAULT_EXEC_TTL: Duration = Duration::from_secs(300);
+
+const FILE_PREFIX: &str = "file:";
+const EXEC_PREFIX: &str = "exec:";
+
+/// Outputs of `exec:` commands, shared by all runtimes in the process.
+static EXEC_CACHE: OnceLock<Mutex<HashMap<String, (Instant, String)>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Env,
+    DotEnv,
+}
+
+#[derive(Debug)]
+struct Settings {
+    sources: Vec<Source>,
+    file: bool,
+    exec: bool,
+    exec_ttl: Duration,
+}
+
+#[derive(Default)]
+pub struct SecretResolver {
+    // Where relative `.env` paths are looked up. Falls back to the working
+    // directory when the runtime was not loaded from a directory.
+    baml_src_dir: Option<PathBuf>,
+    dotenv: OnceLock<HashMap<String, String>>,
+    // Set once a value was read from a file or a command, since those can
+    // change while the process runs.
+    dereferenced: AtomicBool,
+}
+
+impl ResolveEnvVar for SecretResolver {
+    fn resolve_env_var(
+        &self,
+        key: &str,
+        env_vars: &HashMap<String, String>,
+    ) -> Result<Option<String>> {
+        let settings = self.settings(env_vars)?;
+
+        for source in &settings.sources {
+            let value = match source {
+                Source::Env => env_vars.get(key),
+                Source::DotEnv => self.dotenv(env_vars).get(key),
+            };
+            if let Some(value) = value {
+                return self
+                    .dereference(value, &settings)
+                    .with_context(|| format!("Failed to resolve environment variable {key}"))
+                    .map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl SecretResolver {
+    pub(crate) fn new(baml_src_dir: Option<PathBuf>) -> Self {
+        Self {
+            baml_src_dir,
+            ..Default::default()
+        }
+    }
+
+    /// Whether any value came from a `file:` or `exec:` reference. Clients
+    /// built from such values should not be cached, so that rotated secrets
+    /// are picked up.
+    pub(crate) fn dereferenced_secrets(&self) -> bool {
+        self.dereferenced.load(Ordering::Relaxed)
+    }
+
+    fn setting(&self, key: &str, env_vars: &HashMap<String, String>) -> Option<String> {
+        env_vars
+            .get(key)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn settings(&self, env_vars: &HashMap<String, String>) -> Result<Settings> {
+        let mut settings = Settings {
+            sources: vec![],
+            file: false,
+            exec: false,
+            exec_ttl: DEFAULT_EXEC_TTL,
+        };
+
+        let resolvers = self
+            .setting(RESOLVERS_ENV_VAR, env_vars)
+            .unwrap_or_else(|| DEFAULT_RESOLVERS.to_string());
+        for resolver in resolvers.split(',').map(str::trim) {
+            match resolver {
+                "env" => settings.sources.push(Source::Env),
+                "dotenv" => settings.sources.push(Source::DotEnv),
+                "file" => settings.file = true,
+                "exec" => settings.exec = true,
+                other => anyhow::bail!(
+                    "Invalid {RESOLVERS_ENV_VAR}: {other}. Expected a comma separated list of: env, dotenv, file, exec"
+                ),
+            }
+        }
+
+        if let Some(ttl) = self.setting(EXEC_TTL_ENV_VAR, env_vars) {
+            settings.exec_ttl = ttl.parse::<u64>().map(Duration::from_secs).map_err(|_| {
+                anyhow::anyhow!("Invalid {EXEC_TTL_ENV_VAR}: {ttl}. Expected a number of seconds")
+            })?;
+        }
+
+        Ok(settings)
+    }
+
+    fn dotenv(&self, env_vars: &HashMap<String, String>) -> &HashMap<String, String> {
+        self.dotenv.get_or_init(|| {
+            let path = env_vars
+                .get(DOTENV_ENV_VAR)
+                .filter(|p| !p.is_empty())
+                .map_or(Path::new(DEFAULT_DOTENV_PATH), Path::new);
+            let path = match &self.baml_src_dir {
+                Some(dir) if path.is_relative() => dir.join(path),
+                _ => path.to_path_buf(),
+            };
+            match std::fs::read_to_string(&path) {
+                Ok(content) => parse_dotenv(&content),
+                Err(e) => {
+                    log::debug!("Not loading {}: {}", path.display(), e);
+                    HashMap::new()
+                }
+            }
+        })
+    }
+
+    fn dereference(&self, value: &str, settings: &Settings) -> Result<String> {
+        if let Some(path) = value.strip_prefix(FILE_PREFIX).filter(|_| settings.file) {
+            self.dereferenced.store(true, Ordering::Relaxed);
+            let content = std::fs::read_to_string(path.trim())
+                .with_context(|| format!("Failed to read secret file {}", path.trim()))?;
+            return Ok(content.trim_end_matches(['\r', '\n']).to_string());
+        }
+
+        if let Some(command) = value.strip_prefix(EXEC_PREFIX).filter(|_| settings.exec) {
+            self.dereferenced.store(true, Ordering::Relaxed);
+            return exec_cached(command.trim(), settings.exec_ttl);
+        }
+
+        Ok(value.to_string())
+    }
+}
+
+fn exec_cached(command: &str, ttl: Duration) -> Result<String> {
+    let cache = EXEC_CACHE.get_or_init(Default::default);
+
+    if let Some((fetched_at, output)) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(command)
+    {
+        if fetched_at.elapsed() < ttl {
+            return Ok(output.clone());
+        }
+    }
+
+    let output = exec(command)?;
+    cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(command.to_string(), (Instant::now(), output.clone()));
+    Ok(output)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn exec(command: &str) -> Result<String> {
+    let output = if cfg!(windows) {
+        std::process::Command::new("cmd")
+            .args(["/C", command])
+            .output()
+    } else {
+        std::process::Command::new("sh")
+            .args(["-c", command])
+            .output()
+    }
+    .with_context(|| format!("Failed to run secret command: {command}"))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Secret command `{command}` failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)
+        .context("Secret command did not print valid UTF-8")?
+        .trim_end_matches(['\r', '\n'])
+        .to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn exec(command: &str) -> Result<String> {
+    anyhow::bail!("exec: secrets are not supported in this environment: {command}")
+}
+
+/// Parses `KEY=value` lines. Supports comments, an `export ` prefix and
+/// single or double quoted values.
+fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            quoted
+                .rsplit_once('"')
+                .map_or(quoted, |(v, _)| v)
+                .replace("\\n", "\n")
+                .replace("\\\"", "\"")
+        } else if let Some(quoted) = value.strip_prefix('\'') {
+            quoted
+                .rsplit_once('\'')
+                .map_or(quoted, |(v, _)| v)
+                .to_string()
+        } else {
+            // Unquoted values end at an inline comment.
+            value
+                .split_once(" #")
+                .map_or(value, |(v, _)| v)
+                .trim_end()
+                .to_string()
+        };
+
+        vars.insert(key.trim().to_string(), value);
+    }
+
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use baml_types::{EvaluationContext, GetEnvVar};
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn temp_file(content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("baml-secret-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            r#"
+# comment
+OPENAI_API_KEY=sk-123
+export ANTHROPIC_API_KEY = "sk-ant \"quoted\""
+SINGLE='a # b'
+INLINE=value # comment
+"#,
+        );
+        assert_eq!(vars["OPENAI_API_KEY"], "sk-123");
+        assert_eq!(vars["ANTHROPIC_API_KEY"], "sk-ant \"quoted\"");
+        assert_eq!(vars["SINGLE"], "a # b");
+        assert_eq!(vars["INLINE"], "value");
+    }
+
+    #[test]
+    fn test_env_then_dotenv() -> Result<()> {
+        let dotenv = temp_file("A=from-dotenv\nB=from-dotenv\n");
+        let vars = env(&[("A", "from-env"), ("BAML_DOTENV", dotenv.to_str().unwrap())]);
+        let resolver = SecretResolver::default();
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+
+        assert_eq!(ctx.get_env_var("A")?, "from-env");
+        assert_eq!(ctx.get_env_var("B")?, "from-dotenv");
+        assert!(ctx.get_env_var("C").is_err());
+
+        // The order of the sources is configurable.
+        let mut vars = vars;
+        vars.insert(RESOLVERS_ENV_VAR.into(), "dotenv,env".into());
+        let resolver = SecretResolver::default();
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert_eq!(ctx.get_env_var("A")?, "from-dotenv");
+
+        std::fs::remove_file(dotenv)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_dotenv_in_baml_src() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("baml-src-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("config"))?;
+        std::fs::write(dir.join(".env"), "A=default\n")?;
+        std::fs::write(dir.join("config/.env"), "A=configured\n")?;
+
+        let vars = env(&[]);
+        let resolver = SecretResolver::new(Some(dir.clone()));
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert_eq!(ctx.get_env_var("A")?, "default");
+
+        let vars = env(&[("BAML_DOTENV", "config/.env")]);
+        let resolver = SecretResolver::new(Some(dir.clone()));
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert_eq!(ctx.get_env_var("A")?, "configured");
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_secret() -> Result<()> {
+        let secret = temp_file("sk-from-file\n");
+        let vars = env(&[("KEY", &format!("file:{}", secret.display()))]);
+
+        // Disabled unless listed.
+        let resolver = SecretResolver::default();
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert!(ctx.get_env_var("KEY")?.starts_with("file:"));
+        assert!(!resolver.dereferenced_secrets());
+
+        let mut vars = vars;
+        vars.insert(RESOLVERS_ENV_VAR.into(), "env,file".into());
+        let resolver = SecretResolver::default();
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert_eq!(ctx.get_env_var("KEY")?, "sk-from-file");
+        assert!(resolver.dereferenced_secrets());
+
+        std::fs::remove_file(secret)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_settings_ignore_dotenv() -> Result<()> {
+        let secret = temp_file("sk-from-file\n");
+        let dotenv = temp_file(&format!(
+            "BAML_SECRET_RESOLVERS=env,file\nKEY=file:{}\n",
+            secret.display()
+        ));
+        let vars = env(&[("BAML_DOTENV", dotenv.to_str().unwrap())]);
+        let resolver = SecretResolver::default();
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert!(ctx.get_env_var("KEY")?.starts_with("file:"));
+        assert!(!resolver.dereferenced_secrets());
+
+        std::fs::remove_file(secret)?;
+        std::fs::remove_file(dotenv)?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exec_secret_is_cached() -> Result<()> {
+        let counter = temp_file("");
+        let command = format!("echo x >> {0}; wc -l < {0} | tr -d ' '", counter.display());
+        let vars = env(&[
+            ("KEY", &format!("exec:{command}")),
+            (RESOLVERS_ENV_VAR, "env,exec"),
+        ]);
+        let resolver = SecretResolver::default();
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+
+        assert_eq!(ctx.get_env_var("KEY")?, "1");
+        assert_eq!(ctx.get_env_var("KEY")?, "1");
+
+        // With no TTL, the command runs every time.
+        let mut vars = vars;
+        vars.insert(EXEC_TTL_ENV_VAR.into(), "0".into());
+        let ctx = EvaluationContext::new(&vars, false).with_resolver(&resolver);
+        assert_eq!(ctx.get_env_var("KEY")?, "2");
+
+        std::fs::remove_file(counter)?;
+        Ok(())
+    }
+}
@@ -7,9 +7,13 @@ use std::sync::Arc;
 use crate::{
     client_registry::ClientRegistry,
     internal::{
-        llm_client::orchestrator::{orchestrate_stream, OrchestratorNodeIterator},
+        llm_client::{
+            orchestrator::{orchestrate_stream, OrchestratorNodeIterator},
+            rate_limit::RateLimiters,
+        },
         prompt_renderer::PromptRenderer,
     },
+    runtime::InternalBamlRuntime,
     tracing::BamlTracer,
     type_builder::TypeBuilder,
     FunctionResult, RuntimeContextManager,
@@ -26,9 +30,13 @@ pub struct FunctionResultStream {
     pub(crate) renderer: PromptRenderer,
     pub(crate) ir: Arc<IntermediateRepr>,
     pub(crate) orchestrator: OrchestratorNodeIterator,
+    pub(crate) rate_limiters: Arc<RateLimiters>,
     pub(crate) tracer: Arc<BamlTracer>,
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) tokio_runtime: Arc<tokio::runtime::Runtime>,
+    /// Runs the steps of a function with steps before the stream starts. The
+    /// stream is the call that the last step makes.
+    pub(crate) steps_runtime: Option<Arc<InternalBamlRuntime>>,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -91,24 +99,37 @@ impl FunctionResultStream {
             .tracer
             .start_span(&self.function_name, ctx, &local_params);
 
+        let steps = match &self.steps_runtime {
+            Some(runtime) => {
+                runtime
+                    .run_steps(self.function_name.clone(), local_params, ctx, tb, cb)
+                    .await
+            }
+            None => Ok(Ok((self.function_name.clone(), local_params))),
+        };
+
         let rctx = ctx.create_ctx(tb, cb);
-        let res = match rctx {
-            Ok(rctx) => {
+        let res = match (rctx, steps) {
+            (Err(e), _) | (_, Err(e)) => Err(e),
+            // A step before the last one failed.
+            (_, Ok(Err(failed))) => Ok(failed),
+            (Ok(mut rctx), Ok(Ok((function_name, params)))) => {
+                rctx.function_name = Some(function_name);
                 let (history, _) = orchestrate_stream(
                     local_orchestrator,
                     self.ir.as_ref(),
                     &rctx,
+                    &self.rate_limiters,
                     &self.renderer,
-                    &baml_types::BamlValue::Map(local_params),
-                    |content| self.renderer.parse(content, true),
-                    |content| self.renderer.parse(content, false),
+                    &baml_types::BamlValue::Map(params),
+                    |content| self.renderer.parse(self.ir.as_ref(), content, true),
+                    |content| self.renderer.parse(self.ir.as_ref(), content, false),
                     on_event,
                 )
                 .await;
 
-                FunctionResult::new_chain(history)
+                FunctionResult::new_chain(history).map(|r| r.with_variant(self.renderer.variant()))
             }
-            Err(e) => Err(e),
         };
 
         let mut target_id = None;
@@ -11,7 +11,7 @@ mod internal_tests {
 
     // use baml_runtime::internal::llm_client::orchestrator::OrchestrationScope;
     use baml_runtime::InternalRuntimeInterface;
-    use baml_types::BamlValue;
+    use baml_types::{BamlValue, BamlValueWithMeta};
 
     use baml_runtime::{
         internal::llm_client::LLMResponse, DiagnosticsError, IRHelper, RenderedPrompt,
@@ -498,4 +498,452 @@ test TestTree {
 
         Ok(())
     }
+
+    #[test]
+    fn test_build_request() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(
+            r##"
+client<llm> GPT4o {
+  provider openai
+  options {
+    model gpt-4o
+    api_key env.OPENAI_API_KEY
+  }
+}
+
+function Greet(name: string) -> string {
+  client GPT4o
+  prompt #"
+    Say hello to {{ name }}.
+  "#
+}
+        "##,
+        )?;
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = [("name".to_string(), BamlValue::String("Ada".to_string()))]
+            .into_iter()
+            .collect();
+
+        let request = runtime.build_request_sync("Greet", &params, &ctx, None, None, true)?;
+
+        assert_eq!(request.url, "https://api.openai.com/v1/chat/completions");
+        assert_eq!(request.method, "POST");
+        assert_eq!(
+            request.headers.get("authorization").map(String::as_str),
+            Some("Bearer OPENAI_API_KEY")
+        );
+        assert_eq!(request.body["model"], "gpt-4o");
+        assert_eq!(request.body["stream"], true);
+        assert_eq!(
+            request.body["messages"][0]["content"][0]["text"],
+            "Say hello to Ada."
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_llm_response() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(
+            r##"
+class Person {
+  name string
+  age int @check(adult, {{ this >= 18 }})
+}
+
+function ExtractPerson(text: string) -> Person {
+  client "openai/gpt-4o"
+  prompt #"
+    {{ text }}
+    {{ ctx.output_format }}
+  "#
+}
+        "##,
+        )?;
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+
+        let parsed = runtime.parse_llm_response(
+            "ExtractPerson",
+            r#"Here you go: ```json
+{"name": "Ada", "age": 36}
+```"#,
+            &ctx,
+            None,
+            None,
+        )?;
+        let BamlValueWithMeta::Class(class_name, fields, _) = parsed else {
+            panic!("Expected a class, got {parsed:?}");
+        };
+        assert_eq!(class_name, "Person");
+        assert_eq!(
+            fields["name"].clone().value(),
+            BamlValue::String("Ada".into())
+        );
+        assert_eq!(fields["age"].meta()[0].name, "adult");
+        assert_eq!(fields["age"].meta()[0].status, "succeeded");
+
+        // A partial response parses into whatever fields are complete so far.
+        let partial = runtime.parse_llm_response_partial(
+            "ExtractPerson",
+            r#"{"name": "Ada", "ag"#,
+            &ctx,
+            None,
+            None,
+        )?;
+        let BamlValueWithMeta::Class(_, fields, _) = partial else {
+            panic!("Expected a class, got {partial:?}");
+        };
+        assert_eq!(
+            fields["name"].clone().value(),
+            BamlValue::String("Ada".into())
+        );
+
+        assert!(runtime
+            .parse_llm_response("ExtractPerson", "I don't know", &ctx, None, None)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mock_client() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(
+            r##"
+class Person {
+  name string
+  age int
+}
+
+retry_policy Once {
+  max_retries 1
+}
+
+client<llm> Mock {
+  provider mock
+  retry_policy Once
+  options {
+    responses [
+      { function "Greet", content "Hello, Ada!" },
+      { error 500 },
+      { content #"{"name": "Ada", "age": 36}"# },
+    ]
+  }
+}
+
+function ExtractPerson(text: string) -> Person {
+  client Mock
+  prompt #"
+    {{ text }}
+    {{ ctx.output_format }}
+  "#
+}
+
+function Greet(name: string) -> string {
+  client Mock
+  prompt #"Say hi to {{ name }}"#
+}
+        "##,
+        )?;
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = [("text".to_string(), BamlValue::String("Ada, 36".into()))]
+            .into_iter()
+            .collect();
+
+        // The first response fails with a 500, so the retry gets the second.
+        let (result, _) =
+            runtime.call_function_sync("ExtractPerson".to_string(), &params, &ctx, None, None);
+        let result = result?;
+        assert_eq!(result.content()?, r#"{"name": "Ada", "age": 36}"#);
+        assert!(result.parsed_content().is_ok());
+
+        // Responses for a specific function win over catch-all ones.
+        let params = [("name".to_string(), BamlValue::String("Ada".into()))]
+            .into_iter()
+            .collect();
+        let (result, _) =
+            runtime.call_function_sync("Greet".to_string(), &params, &ctx, None, None);
+        assert_eq!(result?.content()?, "Hello, Ada!");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dataset() -> anyhow::Result<()> {
+        let files = HashMap::from([
+            (
+                "baml_src/main.baml",
+                r##"
+client<llm> Mock {
+  provider mock
+  options {
+    responses [{ content "Hello, Ada!" }]
+  }
+}
+
+function Greet(name: string, greeting: string) -> string {
+  client Mock
+  prompt #"{{ greeting }} {{ name }}"#
+}
+
+test Greetings {
+  functions [Greet]
+  args {
+    greeting "Hello"
+  }
+  dataset {
+    path "data/greetings.jsonl"
+    args {
+      name "person"
+    }
+  }
+  @@check(expected, {{ this == _.row.expected }})
+}
+"##,
+            ),
+            (
+                "baml_src/data/greetings.jsonl",
+                concat!(
+                    r#"{"person": "Ada", "expected": "Hello, Ada!"}"#,
+                    "\n",
+                    r#"{"person": "Bob", "expected": "Hello, Bob!"}"#,
+                    "\n"
+                ),
+            ),
+        ]);
+        let runtime =
+            BamlRuntime::from_file_content("baml_src", &files, HashMap::<String, String>::new())?;
+
+        let tests = runtime
+            .internal()
+            .ir()
+            .walk_tests()
+            .map(|t| t.test_case().name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(tests, vec!["Greetings[0]", "Greetings[1]"]);
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = runtime.get_test_params(
+            "Greet",
+            "Greetings[1]",
+            &ctx.create_ctx_with_default(),
+            true,
+        )?;
+        assert_eq!(params["name"], BamlValue::String("Bob".into()));
+        assert_eq!(params["greeting"], BamlValue::String("Hello".into()));
+
+        for (test_name, passes) in [("Greetings[0]", true), ("Greetings[1]", false)] {
+            let (response, _) = runtime.async_runtime.block_on(runtime.run_test(
+                "Greet",
+                test_name,
+                &ctx,
+                None::<fn(baml_runtime::FunctionResult)>,
+            ));
+            assert_eq!(
+                response?.status() == baml_runtime::TestStatus::Pass,
+                passes,
+                "{test_name}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_dataset_values_match_parameter_types() -> anyhow::Result<()> {
+        let files = HashMap::from([
+            (
+                "baml_src/main.baml",
+                r##"
+function Repeat(text: string, times: int, shout: bool, suffix: string?) -> string {
+  client "openai/gpt-4o-mini"
+  prompt #"{{ text }} {{ times }} {{ shout }} {{ suffix }}"#
+}
+
+test Rows {
+  functions [Repeat]
+  dataset {
+    path "data/rows.csv"
+    args {
+      text "text"
+      times "times"
+      shout "shout"
+      suffix "suffix"
+    }
+  }
+}
+"##,
+            ),
+            (
+                "baml_src/data/rows.csv",
+                "text,times,shout,suffix\n42,3,true,\n",
+            ),
+        ]);
+        let runtime =
+            BamlRuntime::from_file_content("baml_src", &files, HashMap::<String, String>::new())?;
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params =
+            runtime.get_test_params("Repeat", "Rows[0]", &ctx.create_ctx_with_default(), true)?;
+        assert_eq!(params["text"], BamlValue::String("42".into()));
+        assert_eq!(params["times"], BamlValue::Int(3));
+        assert_eq!(params["shout"], BamlValue::Bool(true));
+        assert_eq!(params["suffix"], BamlValue::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_judge() -> anyhow::Result<()> {
+        use baml_runtime::constraints::TestConstraintsResult;
+
+        let files = HashMap::from([(
+            "baml_src/main.baml",
+            r##"
+client<llm> Mock {
+  provider mock
+  options {
+    responses [{ content "Orders can be refunded for 30 days." }]
+  }
+}
+
+client<llm> MockJudge {
+  provider mock
+  options {
+    responses [{ content "true" }]
+  }
+}
+
+function Summarize(text: string) -> string {
+  client Mock
+  prompt #"Summarize {{ text }}"#
+}
+
+function MentionsRefundPolicy(summary: string) -> bool {
+  client MockJudge
+  prompt #"Does this mention the refund policy? {{ summary }}"#
+}
+
+test RefundPolicy {
+  functions [Summarize]
+  args {
+    text "Orders can be returned within 30 days for a full refund."
+  }
+  @@check(mentions_refunds, {{ MentionsRefundPolicy(summary=this) }})
+}
+"##,
+        )]);
+        let runtime =
+            BamlRuntime::from_file_content("baml_src", &files, HashMap::<String, String>::new())?;
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let (response, _) = runtime.async_runtime.block_on(runtime.run_test(
+            "Summarize",
+            "RefundPolicy",
+            &ctx,
+            None::<fn(baml_runtime::FunctionResult)>,
+        ));
+        let response = response?;
+        assert_eq!(response.status(), baml_runtime::TestStatus::Pass);
+
+        let TestConstraintsResult::Completed {
+            checks,
+            judge_calls,
+            ..
+        } = response.constraints_result
+        else {
+            panic!("Expected the constraints to be evaluated");
+        };
+        assert_eq!(checks, vec![("mentions_refunds".to_string(), true)]);
+        assert_eq!(judge_calls.len(), 1);
+        assert_eq!(judge_calls[0].function_name, "MentionsRefundPolicy");
+        assert_eq!(
+            judge_calls[0].args["summary"],
+            BamlValue::String("Orders can be refunded for 30 days.".into())
+        );
+        assert_eq!(judge_calls[0].result, Ok(BamlValue::Bool(true)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline() -> anyhow::Result<()> {
+        let runtime = make_test_runtime(
+            r##"
+client<llm> Mock {
+  provider mock
+  options {
+    responses [
+      { function "ExtractNames", content #"["Ada", "Bob"]"# },
+      { function "Greet", prompt_regex "Ada", content "Hi Ada!" },
+      { function "Greet", prompt_regex "Bob", content "Hi Bob!" },
+      { function "Combine", content "Hi Ada! Hi Bob!" },
+    ]
+  }
+}
+
+function ExtractNames(text: string) -> string[] {
+  client Mock
+  prompt #"Names in {{ text }}"#
+}
+
+function Greet(name: string) -> string {
+  client Mock
+  prompt #"Say hi to {{ name }}"#
+}
+
+function Combine(greetings: string[], count: int) -> string {
+  client Mock
+  prompt #"Combine the {{ count }} greetings {{ greetings }}"#
+}
+
+function GreetEveryone(text: string) -> string {
+  steps {
+    names {
+      call ExtractNames
+      args { text text }
+    }
+    greetings {
+      call Greet
+      for_each names
+      args { name item }
+    }
+    combined {
+      call Combine
+      args {
+        greetings greetings
+        count {{ names|length }}
+      }
+    }
+  }
+}
+        "##,
+        )?;
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = [("text".to_string(), BamlValue::String("Ada and Bob".into()))]
+            .into_iter()
+            .collect();
+
+        let (result, _) =
+            runtime.call_function_sync("GreetEveryone".to_string(), &params, &ctx, None, None);
+        let result = result?;
+        assert_eq!(result.content()?, "Hi Ada! Hi Bob!");
+        assert!(result.parsed_content().is_ok());
+
+        // Streams run the steps first, then stream the call of the last one.
+        let mut stream =
+            runtime.stream_function("GreetEveryone".to_string(), &params, &ctx, None, None)?;
+        let (result, _) = runtime.async_runtime.block_on(stream.run(
+            None::<fn(baml_runtime::FunctionResult)>,
+            &ctx,
+            None,
+            None,
+        ));
+        assert_eq!(result?.content()?, "Hi Ada! Hi Bob!");
+
+        Ok(())
+    }
 }
@@ -11,10 +11,11 @@ use baml_runtime::RenderCurlSettings;
 use baml_runtime::{
     internal::llm_client::LLMResponse, BamlRuntime, DiagnosticsError, IRHelper, RenderedPrompt,
 };
-use baml_types::{BamlMediaType, BamlValue, GeneratorOutputType, TypeValue};
+use baml_types::{BamlMediaType, BamlTemporalType, BamlValue, GeneratorOutputType, TypeValue};
 use indexmap::IndexMap;
 use internal_baml_codegen::version_check::GeneratorType;
 use internal_baml_codegen::version_check::{check_version, VersionCheckMode};
+use internal_baml_core::internal_baml_schema_ast::{format_schema, FormatOptions};
 use internal_llm_client::AllowedRoleMetadata;
 use jsonish::deserializer::deserialize_flags::Flag;
 use jsonish::BamlValueWithFlags;
@@ -220,6 +221,29 @@ impl WasmProject {
         }
     }
 
+    /// Formats the current (possibly unsaved) contents of a file, returning
+    /// `undefined` if the file is not part of the project.
+    #[wasm_bindgen]
+    pub fn format_file(&self, name: &str) -> Result<Option<String>, JsError> {
+        let Some(content) = self
+            .unsaved_files
+            .get(name)
+            .or_else(|| self.files.get(name))
+        else {
+            return Ok(None);
+        };
+
+        format_schema(
+            content,
+            FormatOptions {
+                indent_width: 2,
+                fail_on_unhandled_rule: false,
+            },
+        )
+        .map(Some)
+        .map_err(|e| JsError::new(format!("{e:#}").as_str()))
+    }
+
     #[wasm_bindgen]
     pub fn diagnostics(&self, rt: &WasmRuntime) -> WasmDiagnosticError {
         let mut hm = self.files.iter().collect::<HashMap<_, _>>();
@@ -439,6 +463,8 @@ pub struct WasmLLMResponse {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
     pub total_tokens: Option<u64>,
+    pub cache_creation_input_tokens: Option<u64>,
+    pub cache_read_input_tokens: Option<u64>,
     pub stop_reason: Option<String>,
 }
 
@@ -566,6 +592,7 @@ fn flatten_checks(value: &BamlValueWithFlags) -> (serde_json::Value, usize) {
             (J::Object(values), counts.iter().sum())
         }
         BamlValueWithFlags::Null(_) => (J::Null, 0),
+        BamlValueWithFlags::Temporal(v) => (J::String(v.value.to_iso8601()), 0),
         BamlValueWithFlags::Media(_) => (
             serde_json::Value::String("media type not supported".to_string()),
             0,
@@ -772,6 +799,8 @@ impl IntoWasm
                 input_tokens: s.metadata.prompt_tokens,
                 output_tokens: s.metadata.output_tokens,
                 total_tokens: s.metadata.total_tokens,
+                cache_creation_input_tokens: s.metadata.cache_creation_input_tokens,
+                cache_read_input_tokens: s.metadata.cache_read_input_tokens,
                 stop_reason: s.metadata.finish_reason.clone(),
             }),
             _ => None,
@@ -863,12 +892,24 @@ fn get_dummy_value(
                 TypeValue::Float => "0.5".to_string(),
                 TypeValue::Bool => "true".to_string(),
                 TypeValue::Null => "null".to_string(),
+                TypeValue::Temporal(BamlTemporalType::Date) => "\"2024-03-01\"".to_string(),
+                TypeValue::Temporal(BamlTemporalType::DateTime) => {
+                    "\"2024-03-01T14:30:00Z\"".to_string()
+                }
+                TypeValue::Temporal(BamlTemporalType::Duration) => "\"PT2H30M\"".to_string(),
                 TypeValue::Media(BamlMediaType::Image) => {
                     "{ url \"https://imgs.xkcd.com/comics/standards.png\" }".to_string()
                 }
                 TypeValue::Media(BamlMediaType::Audio) => {
                     "{ url \"https://actions.google.com/sounds/v1/emergency/beeper_emergency_call.ogg\" }".to_string()
                 }
+                TypeValue::Media(BamlMediaType::Pdf) => {
+                    "{ url \"https://www.w3.org/WAI/ER/tests/xhtml/testfiles/resources/pdf/dummy.pdf\" }".to_string()
+                }
+                TypeValue::Media(BamlMediaType::Video) => {
+                    "{ url \"https://www.w3schools.com/html/mov_bbb.mp4\" }".to_string()
+                }
+                TypeValue::Message => "{ role \"user\" content \"Hello!\" }".to_string(),
             };
 
             Some(dummy)
@@ -1734,6 +1775,11 @@ impl ToJsValue for ExecutionScope {
                 set_property(&obj, "name", JsValue::from_str(name));
                 set_property(&obj, "index", JsValue::from_f64(*index as f64));
             }
+            ExecutionScope::RateLimited(name, waited) => {
+                set_property(&obj, "type", JsValue::from_str("RateLimited"));
+                set_property(&obj, "name", JsValue::from_str(name));
+                set_property(&obj, "waited", JsValue::from_f64(waited.as_millis() as f64));
+            }
         }
         obj.into()
     }
@@ -28,6 +28,9 @@ pub(crate) enum Commands {
     #[command(about = "Starts a development server")]
     Dev(baml_runtime::cli::dev::DevArgs),
 
+    #[command(about = "Runs the tests of a function and reports aggregate metrics")]
+    Eval(baml_runtime::cli::eval::EvalArgs),
+
     #[command(subcommand, about = "Authenticate with Boundary Cloud")]
     Auth(crate::auth::AuthCommands),
 
@@ -37,8 +40,14 @@ pub(crate) enum Commands {
     #[command(about = "Deploy a BAML project to Boundary Cloud")]
     Deploy(crate::deploy::DeployArgs),
 
-    #[command(about = "Format BAML source files", name = "fmt", hide = true)]
+    #[command(about = "Format BAML source files", name = "fmt")]
     Format(crate::format::FormatArgs),
+
+    #[command(subcommand, about = "Import types defined outside of BAML")]
+    Import(crate::import::ImportCommands),
+
+    #[command(about = "Starts a language server for BAML (speaks LSP over stdio)")]
+    Lsp(crate::lsp::LspArgs),
 }
 
 impl RuntimeCli {
@@ -62,13 +71,22 @@ impl RuntimeCli {
                 args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
                 args.run(defaults)
             }
+            Commands::Eval(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
             Commands::Auth(args) => t.block_on(async { args.run_async().await }),
             Commands::Login(args) => t.block_on(async { args.run_async().await }),
             Commands::Deploy(args) => {
                 args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
                 t.block_on(async { args.run_async().await })
             }
-            Commands::Format(args) => args.run(),
+            Commands::Format(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
+            Commands::Import(args) => args.run(),
+            Commands::Lsp(args) => args.run(),
         }
     }
 }
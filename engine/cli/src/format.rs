@@ -1,31 +1,66 @@
 use std::{fs, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use baml_runtime::baml_src_files;
 use clap::Args;
 use internal_baml_core::internal_baml_schema_ast::{format_schema, FormatOptions};
 
+use crate::colordiff::print_diff;
+
 #[derive(Args, Debug)]
 pub struct FormatArgs {
     #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
     pub from: PathBuf,
+
+    #[arg(
+        long,
+        help = "Do not write any files; print a diff and exit with an error if any file is not formatted"
+    )]
+    pub check: bool,
 }
 
 impl FormatArgs {
     pub fn run(&self) -> Result<()> {
-        let source = fs::read_to_string(&self.from)?;
-        let formatted = format_schema(
-            &source,
-            FormatOptions {
-                indent_width: 4,
-                fail_on_unhandled_rule: false,
-            },
-        )?;
-
-        let mut to = self.from.clone();
-        to.set_extension("formatted.baml");
-        fs::write(&to, formatted)?;
-
-        log::info!("Formatted {} to {}", self.from.display(), to.display());
+        let paths = baml_src_files(&self.from)
+            .context("Failed while searching for .baml files in baml_src/")?
+            .into_iter()
+            .filter(|p| p.extension().is_some_and(|ext| ext == "baml"));
+
+        let mut unformatted = vec![];
+        for path in paths {
+            let source =
+                fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?;
+            let formatted = format_schema(
+                &source,
+                FormatOptions {
+                    indent_width: 2,
+                    fail_on_unhandled_rule: false,
+                },
+            )
+            .context(format!("Failed to format {}", path.display()))?;
+
+            if formatted == source {
+                continue;
+            }
+
+            if self.check {
+                println!("{}", path.display());
+                print_diff(&source, &formatted);
+            } else {
+                fs::write(&path, &formatted)
+                    .context(format!("Failed to write {}", path.display()))?;
+                log::info!("Formatted {}", path.display());
+            }
+            unformatted.push(path);
+        }
+
+        if self.check && !unformatted.is_empty() {
+            anyhow::bail!(
+                "{} {} not formatted, run `baml-cli fmt` to fix",
+                unformatted.len(),
+                bstd::pluralize(unformatted.len(), "file is", "files are")
+            );
+        }
 
         Ok(())
     }
@@ -0,0 +1,76 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use internal_baml_core::ir::json_schema_import::JsonSchemaImport;
+
+#[derive(Subcommand, Debug)]
+pub enum ImportCommands {
+    #[command(
+        about = "Generate BAML classes and enums from a JSON Schema or OpenAPI document",
+        name = "jsonschema"
+    )]
+    JsonSchema(JsonSchemaArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct JsonSchemaArgs {
+    #[arg(help = "path/to/schema.json (JSON or YAML)")]
+    pub file: PathBuf,
+
+    #[arg(
+        long,
+        help = "Name of the class generated for the root schema, if it has no title",
+        default_value = "Root"
+    )]
+    pub root_name: String,
+
+    #[arg(
+        long,
+        short,
+        help = "Where to write the generated BAML, e.g. baml_src/schema.baml (defaults to stdout)"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+impl ImportCommands {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            ImportCommands::JsonSchema(args) => args.run(),
+        }
+    }
+}
+
+impl JsonSchemaArgs {
+    pub fn run(&self) -> Result<()> {
+        let contents = fs::read_to_string(&self.file)
+            .with_context(|| format!("Failed to read {}", self.file.display()))?;
+        let document: serde_json::Value = match serde_json::from_str(&contents) {
+            Ok(document) => document,
+            // YAML is a superset of JSON, so only try it once JSON has failed.
+            Err(_) => serde_yaml::from_str(&contents)
+                .with_context(|| format!("{} is neither JSON nor YAML", self.file.display()))?,
+        };
+
+        let import = JsonSchemaImport::from_json_schema(&document, &self.root_name)
+            .with_context(|| format!("Failed to import {}", self.file.display()))?;
+        let baml = import.to_baml();
+
+        match &self.output {
+            Some(output) => {
+                fs::write(output, baml)
+                    .with_context(|| format!("Failed to write {}", output.display()))?;
+                log::info!(
+                    "Imported {} classes and {} enums from {} to {}",
+                    import.classes.len(),
+                    import.enums.len(),
+                    self.file.display(),
+                    output.display()
+                );
+            }
+            None => print!("{baml}"),
+        }
+
+        Ok(())
+    }
+}
@@ -4,6 +4,8 @@ pub(crate) mod colordiff;
 pub(crate) mod commands;
 pub(crate) mod deploy;
 pub(crate) mod format;
+pub(crate) mod import;
+pub(crate) mod lsp;
 pub(crate) mod propelauth;
 pub(crate) mod tui;
 
@@ -0,0 +1,799 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use internal_baml_core::{
+    ast::{
+        BlockArgs, Expression, FieldType, Identifier, RawString, Top, TypeExpressionBlock,
+        WithDocumentation, WithIdentifier, WithName, WithSpan,
+    },
+    internal_baml_diagnostics::{SourceFile, Span},
+    ValidatedSchema,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Documentation,
+};
+
+use super::position;
+
+const PRIMITIVE_TYPES: &[&str] = &[
+    "string", "int", "float", "bool", "null", "image", "audio", "pdf", "video", "date", "datetime",
+    "duration", "message", "map",
+];
+
+const KEYWORDS: &[&str] = &[
+    "class",
+    "enum",
+    "function",
+    "client<llm>",
+    "retry_policy",
+    "template_string",
+    "generator",
+    "test",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SymbolKind {
+    Class,
+    Enum,
+    Function,
+    Client,
+    RetryPolicy,
+    TemplateString,
+    Generator,
+    Test,
+}
+
+impl SymbolKind {
+    fn of(top: &Top) -> Self {
+        match top {
+            Top::Class(_) => SymbolKind::Class,
+            Top::Enum(_) => SymbolKind::Enum,
+            Top::Function(_) => SymbolKind::Function,
+            Top::Client(_) => SymbolKind::Client,
+            Top::RetryPolicy(_) => SymbolKind::RetryPolicy,
+            Top::TemplateString(_) => SymbolKind::TemplateString,
+            Top::Generator(_) => SymbolKind::Generator,
+            Top::TestCase(_) => SymbolKind::Test,
+        }
+    }
+
+    fn completion_kind(self) -> CompletionItemKind {
+        match self {
+            SymbolKind::Class => CompletionItemKind::CLASS,
+            SymbolKind::Enum => CompletionItemKind::ENUM,
+            SymbolKind::Function | SymbolKind::TemplateString => CompletionItemKind::FUNCTION,
+            SymbolKind::Client | SymbolKind::RetryPolicy => CompletionItemKind::MODULE,
+            SymbolKind::Generator | SymbolKind::Test => CompletionItemKind::EVENT,
+        }
+    }
+
+    /// Generators and tests are never referenced by name.
+    fn is_referenceable(self) -> bool {
+        !matches!(self, SymbolKind::Generator | SymbolKind::Test)
+    }
+}
+
+/// A top-level declaration.
+struct Symbol {
+    name: String,
+    kind: SymbolKind,
+    /// Span of the declared name.
+    name_span: Span,
+    /// Span of the whole declaration.
+    span: Span,
+    documentation: Option<String>,
+}
+
+/// A use of a top-level declaration by name.
+struct Reference {
+    name: String,
+    span: Span,
+}
+
+/// What a dotted Jinja expression such as `resume.education` resolves to.
+enum JinjaTarget<'a> {
+    Type(&'a FieldType),
+    /// The `ctx` global.
+    Ctx,
+    /// The `_` global.
+    Underscore,
+}
+
+/// Everything the language server knows about one `baml_src` directory.
+pub(super) struct Analysis {
+    schema: ValidatedSchema,
+    files: HashMap<PathBuf, SourceFile>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+}
+
+impl Analysis {
+    pub(super) fn new(root: &Path, files: Vec<SourceFile>) -> Self {
+        let schema = internal_baml_core::validate(root, files.clone());
+
+        let mut symbols = vec![];
+        let mut references = vec![];
+        let mut prompts = vec![];
+
+        for (_, top) in schema.db.ast().iter_tops() {
+            symbols.push(Symbol {
+                name: top.name().to_string(),
+                kind: SymbolKind::of(top),
+                name_span: top.identifier().span().clone(),
+                span: top.span().clone(),
+                documentation: documentation(top).map(ToString::to_string),
+            });
+
+            match top {
+                Top::Class(block) | Top::Enum(block) => {
+                    for field in block.fields.iter() {
+                        if let Some(field_type) = &field.expr {
+                            type_references(field_type, &mut references);
+                        }
+                    }
+                }
+                Top::Function(block)
+                | Top::Client(block)
+                | Top::RetryPolicy(block)
+                | Top::Generator(block)
+                | Top::TestCase(block) => {
+                    if let Some(input) = block.input() {
+                        args_references(input, &mut references);
+                    }
+                    if let Some(output) = block.output() {
+                        type_references(&output.field_type, &mut references);
+                    }
+                    for field in block.fields() {
+                        if let Some(expr) = &field.expr {
+                            expression_references(expr, &mut references);
+                        }
+                    }
+                    prompts.extend(prompt(top));
+                }
+                Top::TemplateString(template) => {
+                    if let Some(input) = template.input() {
+                        args_references(input, &mut references);
+                    }
+                    prompts.extend(prompt(top));
+                }
+            }
+        }
+
+        // Template strings are called like macros from prompts, e.g.
+        // `{{ PrintUser(user) }}`.
+        let template_strings = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::TemplateString)
+            .map(|s| s.name.as_str())
+            .collect::<HashSet<_>>();
+        for prompt in prompts.iter() {
+            let start = prompt.span().start;
+            for (idx, name) in jinja_identifiers(prompt.raw_value()) {
+                if template_strings.contains(name) {
+                    references.push(Reference {
+                        name: name.to_string(),
+                        span: Span::new(
+                            prompt.span().file.clone(),
+                            start + idx,
+                            start + idx + name.len(),
+                        ),
+                    });
+                }
+            }
+        }
+
+        Self {
+            schema,
+            files: files
+                .into_iter()
+                .map(|file| (file.path_buf().clone(), file))
+                .collect(),
+            symbols,
+            references,
+        }
+    }
+
+    pub(super) fn text(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(|file| file.as_str())
+    }
+
+    /// Errors and warnings, grouped by file. Every file in the project has an
+    /// entry, so that publishing these clears stale diagnostics.
+    pub(super) fn diagnostics(&self) -> HashMap<PathBuf, Vec<Diagnostic>> {
+        let mut diagnostics: HashMap<PathBuf, Vec<Diagnostic>> = self
+            .files
+            .keys()
+            .map(|path| (path.clone(), vec![]))
+            .collect();
+
+        let errors = self
+            .schema
+            .diagnostics
+            .errors()
+            .iter()
+            .map(|e| (e.span(), e.message(), DiagnosticSeverity::ERROR));
+        let warnings = self
+            .schema
+            .diagnostics
+            .warnings()
+            .iter()
+            .map(|w| (w.span(), w.message(), DiagnosticSeverity::WARNING));
+
+        for (span, message, severity) in errors.chain(warnings) {
+            diagnostics
+                .entry(span.file.path_buf().clone())
+                .or_default()
+                .push(Diagnostic {
+                    range: span_range(span),
+                    severity: Some(severity),
+                    source: Some("baml".to_string()),
+                    message: message.to_string(),
+                    ..Default::default()
+                });
+        }
+
+        diagnostics
+    }
+
+    /// Span of the declaration of the symbol at `offset`.
+    pub(super) fn definition(&self, path: &Path, offset: usize) -> Option<&Span> {
+        self.symbol_at(path, offset).map(|symbol| &symbol.name_span)
+    }
+
+    /// Spans of all uses of the symbol at `offset`.
+    pub(super) fn references(
+        &self,
+        path: &Path,
+        offset: usize,
+        include_declaration: bool,
+    ) -> Vec<&Span> {
+        let Some(symbol) = self.symbol_at(path, offset) else {
+            return vec![];
+        };
+
+        let declaration = include_declaration.then_some(&symbol.name_span);
+        declaration
+            .into_iter()
+            .chain(
+                self.references
+                    .iter()
+                    .filter(|r| r.name == symbol.name)
+                    .map(|r| &r.span),
+            )
+            .collect()
+    }
+
+    /// Spans to replace to rename the symbol at `offset`, or an error if
+    /// `new_name` can't be used for it.
+    pub(super) fn rename(
+        &self,
+        path: &Path,
+        offset: usize,
+        new_name: &str,
+    ) -> anyhow::Result<Vec<&Span>> {
+        let Some(symbol) = self.symbol_at(path, offset) else {
+            anyhow::bail!("There is no symbol to rename here");
+        };
+
+        let mut chars = new_name.chars();
+        let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            anyhow::bail!("`{new_name}` is not a valid identifier");
+        }
+        if matches!(symbol.kind, SymbolKind::Class | SymbolKind::Enum)
+            && !new_name.starts_with(|c: char| c.is_ascii_uppercase())
+        {
+            anyhow::bail!("Class and enum names must start with an uppercase letter");
+        }
+        if self
+            .symbols
+            .iter()
+            .any(|s| s.name == new_name && s.kind.is_referenceable())
+        {
+            anyhow::bail!("`{new_name}` is already defined");
+        }
+
+        Ok(self.references(path, offset, true))
+    }
+
+    /// Markdown describing what is at `offset`.
+    pub(super) fn hover(&self, path: &Path, offset: usize) -> Option<String> {
+        if let Some(symbol) = self.symbol_at(path, offset) {
+            let source = &symbol.span.file.as_str()[symbol.span.start..symbol.span.end];
+            let code = match symbol.kind {
+                // The signature is more useful than the prompt.
+                SymbolKind::Function => source.split('{').next().unwrap_or(source),
+                _ => source,
+            };
+            let mut hover = format!("```baml\n{}\n```", code.trim());
+            if let Some(documentation) = &symbol.documentation {
+                hover.push_str("\n\n");
+                hover.push_str(documentation);
+            }
+            return Some(hover);
+        }
+
+        // Jinja variables in prompts.
+        let (args, _) = self.prompt_at(path, offset)?;
+        let text = self.text(path)?;
+        let end = offset
+            + text[offset..]
+                .find(|c: char| !is_ident_char(c))
+                .unwrap_or(text.len() - offset);
+        let chain = jinja_chain(&text[..end]);
+        let target = self.resolve_jinja(args, &chain.split('.').collect::<Vec<_>>())?;
+        match target {
+            JinjaTarget::Type(field_type) => Some(format!(
+                "```baml\n{chain}: {}\n```",
+                type_source(field_type)
+            )),
+            JinjaTarget::Ctx | JinjaTarget::Underscore => None,
+        }
+    }
+
+    /// Completions at `offset`: Jinja variables and fields inside prompts,
+    /// and declarations and types elsewhere.
+    pub(super) fn completions(&self, path: &Path, offset: usize) -> Vec<CompletionItem> {
+        let Some(text) = self.text(path) else {
+            return vec![];
+        };
+
+        if let Some((args, prompt)) = self.prompt_at(path, offset) {
+            let before = &text[prompt.span().start..offset];
+            let in_expression = match (before.rfind(['{']), before.rfind(['}'])) {
+                (Some(open), Some(close)) => open > close,
+                (Some(_), None) => true,
+                _ => false,
+            };
+            if !in_expression {
+                return vec![];
+            }
+
+            let chain = jinja_chain(before);
+            return match chain.rsplit_once('.') {
+                Some((parent, _)) => {
+                    let segments = parent.split('.').collect::<Vec<_>>();
+                    match self.resolve_jinja(args, &segments) {
+                        Some(target) => self.jinja_members(target),
+                        None => vec![],
+                    }
+                }
+                None => args
+                    .into_iter()
+                    .flat_map(|args| args.args.iter())
+                    .map(|(name, arg)| CompletionItem {
+                        label: name.name().to_string(),
+                        kind: Some(CompletionItemKind::VARIABLE),
+                        detail: Some(type_source(&arg.field_type).to_string()),
+                        ..Default::default()
+                    })
+                    .chain(["ctx", "_"].into_iter().map(|name| CompletionItem {
+                        label: name.to_string(),
+                        kind: Some(CompletionItemKind::MODULE),
+                        ..Default::default()
+                    }))
+                    .chain(
+                        self.symbols
+                            .iter()
+                            .filter(|s| s.kind == SymbolKind::TemplateString)
+                            .map(|s| self.symbol_completion(s)),
+                    )
+                    .collect(),
+            };
+        }
+
+        self.symbols
+            .iter()
+            .filter(|s| s.kind.is_referenceable())
+            .map(|s| self.symbol_completion(s))
+            .chain(PRIMITIVE_TYPES.iter().map(|name| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::TYPE_PARAMETER),
+                ..Default::default()
+            }))
+            .chain(KEYWORDS.iter().map(|name| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                ..Default::default()
+            }))
+            .collect()
+    }
+
+    fn symbol_completion(&self, symbol: &Symbol) -> CompletionItem {
+        CompletionItem {
+            label: symbol.name.clone(),
+            kind: Some(symbol.kind.completion_kind()),
+            documentation: symbol.documentation.clone().map(Documentation::String),
+            ..Default::default()
+        }
+    }
+
+    fn jinja_members(&self, target: JinjaTarget<'_>) -> Vec<CompletionItem> {
+        let member = |name: &str, kind| CompletionItem {
+            label: name.to_string(),
+            kind: Some(kind),
+            ..Default::default()
+        };
+
+        match target {
+            JinjaTarget::Ctx => vec![
+                member("output_format", CompletionItemKind::PROPERTY),
+                member("client", CompletionItemKind::PROPERTY),
+                member("tags", CompletionItemKind::PROPERTY),
+            ],
+            JinjaTarget::Underscore => vec![
+                member("role", CompletionItemKind::FUNCTION),
+                member("chat", CompletionItemKind::FUNCTION),
+            ],
+            JinjaTarget::Type(field_type) => self
+                .class_of(field_type)
+                .map(|class| {
+                    class
+                        .fields
+                        .iter()
+                        .map(|field| CompletionItem {
+                            label: field.name().to_string(),
+                            kind: Some(CompletionItemKind::FIELD),
+                            detail: field.expr.as_ref().map(|t| type_source(t).to_string()),
+                            documentation: field
+                                .documentation()
+                                .map(|doc| Documentation::String(doc.to_string())),
+                            ..Default::default()
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn symbol_at(&self, path: &Path, offset: usize) -> Option<&Symbol> {
+        let in_file = |span: &Span| span.file.path_buf() == path && span.contains(offset);
+
+        let name = self
+            .symbols
+            .iter()
+            .find(|s| in_file(&s.name_span))
+            .map(|s| s.name.as_str())
+            .or_else(|| {
+                self.references
+                    .iter()
+                    .find(|r| in_file(&r.span))
+                    .map(|r| r.name.as_str())
+            })?;
+
+        self.symbols
+            .iter()
+            .find(|s| s.name == name && s.kind.is_referenceable())
+            .or_else(|| self.symbols.iter().find(|s| s.name == name))
+    }
+
+    /// The arguments and prompt of the function or template string whose
+    /// prompt contains `offset`.
+    fn prompt_at(&self, path: &Path, offset: usize) -> Option<(Option<&BlockArgs>, &RawString)> {
+        self.schema
+            .db
+            .ast()
+            .iter_tops()
+            .filter_map(|(_, top)| {
+                let args = match top {
+                    Top::Function(block) => block.input(),
+                    Top::TemplateString(template) => template.input(),
+                    _ => return None,
+                };
+                prompt(top).map(|prompt| (args, prompt))
+            })
+            .find(|(_, prompt)| {
+                prompt.span().file.path_buf() == path && prompt.span().contains(offset)
+            })
+    }
+
+    fn resolve_jinja<'a>(
+        &'a self,
+        args: Option<&'a BlockArgs>,
+        segments: &[&str],
+    ) -> Option<JinjaTarget<'a>> {
+        let (first, rest) = segments.split_first()?;
+        let mut target = match *first {
+            "ctx" => JinjaTarget::Ctx,
+            "_" => JinjaTarget::Underscore,
+            name => JinjaTarget::Type(
+                &args?
+                    .args
+                    .iter()
+                    .find(|(arg, _)| arg.name() == name)?
+                    .1
+                    .field_type,
+            ),
+        };
+
+        for segment in rest {
+            let JinjaTarget::Type(field_type) = target else {
+                return None;
+            };
+            let field = self
+                .class_of(field_type)?
+                .fields
+                .iter()
+                .find(|field| field.name() == *segment)?;
+            target = JinjaTarget::Type(field.expr.as_ref()?);
+        }
+
+        Some(target)
+    }
+
+    fn class_of(&self, field_type: &FieldType) -> Option<&TypeExpressionBlock> {
+        let FieldType::Symbol(_, name, _) = field_type else {
+            return None;
+        };
+        self.schema
+            .db
+            .ast()
+            .iter_tops()
+            .find_map(|(_, top)| match top {
+                Top::Class(class) if class.name() == name.name() => Some(class),
+                _ => None,
+            })
+    }
+}
+
+fn documentation(top: &Top) -> Option<&str> {
+    match top {
+        Top::Class(block) | Top::Enum(block) => block.documentation(),
+        Top::TemplateString(template) => template.documentation(),
+        Top::Function(block)
+        | Top::Client(block)
+        | Top::RetryPolicy(block)
+        | Top::Generator(block)
+        | Top::TestCase(block) => block.documentation(),
+    }
+}
+
+/// The Jinja prompt of a function or template string.
+fn prompt(top: &Top) -> Option<&RawString> {
+    match top {
+        Top::Function(block) => block
+            .fields()
+            .iter()
+            .find(|field| field.name() == "prompt")?
+            .expr
+            .as_ref()?
+            .as_raw_string_value(),
+        Top::TemplateString(template) => template.value().as_raw_string_value(),
+        _ => None,
+    }
+}
+
+fn type_references(field_type: &FieldType, references: &mut Vec<Reference>) {
+    for identifier in field_type.flat_idns() {
+        if let Identifier::Local(name, span) = identifier {
+            references.push(Reference {
+                name: name.clone(),
+                span: span.clone(),
+            });
+        }
+    }
+}
+
+fn args_references(args: &BlockArgs, references: &mut Vec<Reference>) {
+    for (_, arg) in args.args.iter() {
+        type_references(&arg.field_type, references);
+    }
+}
+
+/// Identifiers in block fields, e.g. `client GPT4` or `functions [Foo, Bar]`.
+fn expression_references(expr: &Expression, references: &mut Vec<Reference>) {
+    match expr {
+        Expression::Identifier(Identifier::Local(name, span)) => references.push(Reference {
+            name: name.clone(),
+            span: span.clone(),
+        }),
+        Expression::Array(values, _) => {
+            for value in values {
+                expression_references(value, references);
+            }
+        }
+        Expression::Map(entries, _) => {
+            for (_, value) in entries {
+                expression_references(value, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn type_source(field_type: &FieldType) -> &str {
+    let span = field_type.span();
+    span.file.as_str()[span.start..span.end].trim()
+}
+
+/// The dotted Jinja expression that `text` ends with, e.g. `resume.` for
+/// `{{ resume.`.
+fn jinja_chain(text: &str) -> &str {
+    let start = text
+        .rfind(|c: char| !is_ident_char(c) && c != '.')
+        .map_or(0, |idx| idx + 1);
+    &text[start..]
+}
+
+/// The identifiers in the `{{ ... }}` and `{% ... %}` blocks of a Jinja
+/// template, with their offsets. String literals and attributes, like the
+/// `name` of `"name"` or `user.name`, are skipped.
+fn jinja_identifiers(text: &str) -> Vec<(usize, &str)> {
+    let mut identifiers = vec![];
+    let mut pos = 0;
+    while let Some(open) = text[pos..].find('{').map(|idx| pos + idx) {
+        let close = match text[open + 1..].chars().next() {
+            Some('{') => "}}",
+            Some('%') => "%}",
+            _ => {
+                pos = open + 1;
+                continue;
+            }
+        };
+        let start = open + 2;
+        let end = text[start..]
+            .find(close)
+            .map_or(text.len(), |idx| start + idx);
+
+        let mut quote = None;
+        let mut prev = ' ';
+        let mut chars = text[start..end].char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match quote {
+                Some(_) if c == '\\' => {
+                    chars.next();
+                }
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if is_ident_char(c) && !is_ident_char(prev) && prev != '.' => {
+                    let ident = &text[start + idx..end];
+                    let len = ident
+                        .find(|c: char| !is_ident_char(c))
+                        .unwrap_or(ident.len());
+                    identifiers.push((start + idx, &ident[..len]));
+                }
+                None => {}
+            }
+            prev = c;
+        }
+
+        pos = (end + close.len()).min(text.len());
+    }
+    identifiers
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+pub(super) fn span_range(span: &Span) -> lsp_types::Range {
+    position::range(span.file.as_str(), span.start, span.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r##"
+class Education {
+  school string
+  /// The year they graduated
+  year int
+}
+
+class Resume {
+  name string
+  education Education[]
+}
+
+template_string PrintEducation(e: Education) #"
+  {{ e.school }}
+"#
+
+/// Pulls a resume out of raw text.
+function ExtractResume(text: string) -> Resume {
+  client GPT4
+  prompt #"
+    {{ PrintEducation(text) }}
+    {{ text.
+  "#
+}
+"##;
+
+    fn analysis() -> (Analysis, PathBuf) {
+        let path = PathBuf::from("/project/baml_src/main.baml");
+        let file = SourceFile::from((path.clone(), SOURCE.to_string()));
+        (
+            Analysis::new(Path::new("/project/baml_src"), vec![file]),
+            path,
+        )
+    }
+
+    fn offset_of(needle: &str) -> usize {
+        SOURCE.find(needle).unwrap()
+    }
+
+    #[test]
+    fn test_definition_and_references() {
+        let (analysis, path) = analysis();
+
+        let definition = analysis
+            .definition(&path, offset_of("Education[]"))
+            .unwrap();
+        assert_eq!(definition.start, offset_of("Education {"));
+
+        let references = analysis.references(&path, offset_of("Education {"), false);
+        assert_eq!(references.len(), 2);
+
+        let references = analysis.references(&path, offset_of("PrintEducation(text)"), true);
+        assert_eq!(references.len(), 2);
+    }
+
+    #[test]
+    fn test_jinja_identifiers() {
+        let text = r#"PrintUser {{ PrintUser(user) }} {% if user.PrintUser %}"PrintUser"{{ "PrintUser" ~ X }}{% endif %}"#;
+        let identifiers = jinja_identifiers(text)
+            .into_iter()
+            .map(|(idx, name)| {
+                assert_eq!(&text[idx..idx + name.len()], name);
+                name
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            identifiers,
+            vec!["PrintUser", "user", "if", "user", "X", "endif"]
+        );
+    }
+
+    #[test]
+    fn test_rename_validation() {
+        let (analysis, path) = analysis();
+        let offset = offset_of("Resume {");
+
+        assert_eq!(analysis.rename(&path, offset, "Cv").unwrap().len(), 2);
+        assert!(analysis.rename(&path, offset, "cv").is_err());
+        assert!(analysis.rename(&path, offset, "Education").is_err());
+        assert!(analysis.rename(&path, offset, "C-v").is_err());
+    }
+
+    #[test]
+    fn test_hover() {
+        let (analysis, path) = analysis();
+
+        let hover = analysis.hover(&path, offset_of("ExtractResume")).unwrap();
+        assert!(hover.contains("function ExtractResume(text: string) -> Resume"));
+        assert!(hover.contains("Pulls a resume out of raw text."));
+        assert!(!hover.contains("prompt"));
+
+        let hover = analysis.hover(&path, offset_of("e.school") + 2).unwrap();
+        assert!(hover.contains("e.school: string"));
+    }
+
+    #[test]
+    fn test_completions() {
+        let (analysis, path) = analysis();
+        let labels = |offset| {
+            analysis
+                .completions(&path, offset)
+                .into_iter()
+                .map(|item| item.label)
+                .collect::<Vec<_>>()
+        };
+
+        let in_prompt = labels(offset_of("PrintEducation(text)"));
+        assert!(in_prompt.contains(&"text".to_string()));
+        assert!(in_prompt.contains(&"ctx".to_string()));
+        assert!(in_prompt.contains(&"PrintEducation".to_string()));
+
+        let fields = labels(offset_of("e.school") + 2);
+        assert_eq!(fields, vec!["school", "year"]);
+
+        let outside = labels(offset_of("client GPT4"));
+        assert!(outside.contains(&"Resume".to_string()));
+        assert!(outside.contains(&"string".to_string()));
+    }
+}
@@ -0,0 +1,25 @@
+//! A language server for BAML, speaking LSP over stdio.
+//!
+//! This gives editors other than VS Code diagnostics, go-to-definition,
+//! find-references, hover, completion, rename and formatting.
+
+mod analysis;
+mod position;
+mod server;
+
+use anyhow::Result;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct LspArgs {
+    /// Accepted for compatibility with editors that always pass `--stdio`;
+    /// stdio is the only supported transport.
+    #[arg(long, hide = true)]
+    pub stdio: bool,
+}
+
+impl LspArgs {
+    pub fn run(&self) -> Result<()> {
+        server::run()
+    }
+}
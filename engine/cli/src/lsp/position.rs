@@ -0,0 +1,73 @@
+//! Conversions between byte offsets into BAML sources and LSP positions,
+//! whose `character` counts UTF-16 code units.
+
+use lsp_types::{Position, Range};
+
+pub(super) fn offset_to_position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+pub(super) fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(idx) => line_start += idx + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |idx| line_start + idx);
+
+    let mut units = 0;
+    for (idx, ch) in text[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + idx;
+        }
+        units += ch.len_utf16();
+    }
+    line_end
+}
+
+pub(super) fn range(text: &str, start: usize, end: usize) -> Range {
+    Range::new(
+        offset_to_position(text, start),
+        offset_to_position(text, end),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let text = "class Foo {\n  bar string // ünïcode 🦀\n}\n";
+        for (offset, _) in text.char_indices() {
+            let position = offset_to_position(text, offset);
+            assert_eq!(position_to_offset(text, position), offset);
+        }
+    }
+
+    #[test]
+    fn test_utf16_columns() {
+        let text = "a🦀b\ncd";
+        assert_eq!(offset_to_position(text, 5), Position::new(0, 3));
+        assert_eq!(offset_to_position(text, text.len()), Position::new(1, 2));
+        assert_eq!(position_to_offset(text, Position::new(0, 3)), 5);
+        // Positions past the end of a line clamp to the end of that line.
+        assert_eq!(position_to_offset(text, Position::new(0, 40)), 6);
+        assert_eq!(position_to_offset(text, Position::new(7, 0)), text.len());
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use baml_runtime::baml_src_and_data_files;
+use internal_baml_core::{
+    internal_baml_diagnostics::SourceFile,
+    internal_baml_parser_database::DATA_FILE_EXTENSIONS,
+    internal_baml_schema_ast::{format_schema, FormatOptions},
+};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+        DidSaveTextDocument, Notification as _, PublishDiagnostics,
+    },
+    request::{
+        Completion, Formatting, GotoDefinition, HoverRequest, References, Rename, Request as _,
+    },
+    CompletionOptions, CompletionResponse, GotoDefinitionResponse, Hover, HoverContents,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams,
+    ServerCapabilities, TextDocumentPositionParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+};
+use serde::de::DeserializeOwned;
+
+use super::{
+    analysis::{span_range, Analysis},
+    position,
+};
+
+pub(super) fn run() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![".".to_string(), "{".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    Server::default().serve(&connection)?;
+
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Server {
+    /// Contents of documents open in the editor, which take precedence over
+    /// what is on disk.
+    open_documents: HashMap<PathBuf, String>,
+    /// Analyses of every project with an open document, keyed by the
+    /// project's `baml_src` directory.
+    projects: HashMap<PathBuf, Analysis>,
+}
+
+impl Server {
+    fn serve(&mut self, connection: &Connection) -> Result<()> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    if let Err(e) = self.handle_notification(connection, notification) {
+                        log::error!("{:#}", e);
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        match request.method.as_str() {
+            HoverRequest::METHOD => respond::<HoverRequest>(request, |params| {
+                let (analysis, path, offset) =
+                    self.locate(&params.text_document_position_params)?;
+                Ok(analysis.hover(&path, offset).map(|value| Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value,
+                    }),
+                    range: None,
+                }))
+            }),
+            GotoDefinition::METHOD => respond::<GotoDefinition>(request, |params| {
+                let (analysis, path, offset) =
+                    self.locate(&params.text_document_position_params)?;
+                Ok(analysis
+                    .definition(&path, offset)
+                    .and_then(location)
+                    .map(GotoDefinitionResponse::Scalar))
+            }),
+            References::METHOD => respond::<References>(request, |params| {
+                let (analysis, path, offset) = self.locate(&params.text_document_position)?;
+                let spans = analysis.references(&path, offset, params.context.include_declaration);
+                Ok(Some(spans.into_iter().filter_map(location).collect()))
+            }),
+            Rename::METHOD => respond::<Rename>(request, |params| {
+                let (analysis, path, offset) = self.locate(&params.text_document_position)?;
+                let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+                for span in analysis.rename(&path, offset, &params.new_name)? {
+                    let Some(location) = location(span) else {
+                        continue;
+                    };
+                    changes.entry(location.uri).or_default().push(TextEdit {
+                        range: location.range,
+                        new_text: params.new_name.clone(),
+                    });
+                }
+                Ok(Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }))
+            }),
+            Completion::METHOD => respond::<Completion>(request, |params| {
+                let (analysis, path, offset) = self.locate(&params.text_document_position)?;
+                Ok(Some(CompletionResponse::Array(
+                    analysis.completions(&path, offset),
+                )))
+            }),
+            Formatting::METHOD => respond::<Formatting>(request, |params| {
+                let path = file_path(&params.text_document.uri)?;
+                let text = self.read(&path)?;
+                let formatted = format_schema(
+                    &text,
+                    FormatOptions {
+                        indent_width: 2,
+                        fail_on_unhandled_rule: false,
+                    },
+                )?;
+                if formatted == text {
+                    return Ok(Some(vec![]));
+                }
+                Ok(Some(vec![TextEdit {
+                    range: position::range(&text, 0, text.len()),
+                    new_text: formatted,
+                }]))
+            }),
+            method => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request: {method}"),
+            ),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        connection: &Connection,
+        notification: Notification,
+    ) -> Result<()> {
+        let path = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)?;
+                let path = file_path(&params.text_document.uri)?;
+                self.open_documents
+                    .insert(path.clone(), params.text_document.text);
+                path
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification.extract::<lsp_types::DidChangeTextDocumentParams>(
+                    DidChangeTextDocument::METHOD,
+                )?;
+                let path = file_path(&params.text_document.uri)?;
+                // With full sync, the last change holds the whole document.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.open_documents.insert(path.clone(), change.text);
+                }
+                path
+            }
+            DidSaveTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidSaveTextDocumentParams>(DidSaveTextDocument::METHOD)?;
+                file_path(&params.text_document.uri)?
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification.extract::<lsp_types::DidCloseTextDocumentParams>(
+                    DidCloseTextDocument::METHOD,
+                )?;
+                let path = file_path(&params.text_document.uri)?;
+                self.open_documents.remove(&path);
+                path
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params = notification.extract::<lsp_types::DidChangeWatchedFilesParams>(
+                    DidChangeWatchedFiles::METHOD,
+                )?;
+                let roots = params
+                    .changes
+                    .iter()
+                    .filter_map(|change| file_path(&change.uri).ok())
+                    .map(|path| project_root(&path))
+                    .collect::<Vec<_>>();
+                for root in roots {
+                    if self.projects.contains_key(&root) {
+                        self.analyze(connection, root)?;
+                    }
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+
+        self.analyze(connection, project_root(&path))
+    }
+
+    /// Re-validates the project rooted at `root` and publishes its
+    /// diagnostics.
+    fn analyze(&mut self, connection: &Connection, root: PathBuf) -> Result<()> {
+        let mut paths = if root.is_dir() {
+            baml_src_and_data_files(&root)?
+        } else {
+            vec![]
+        };
+        for path in self.open_documents.keys() {
+            if path.starts_with(&root) && !paths.contains(path) {
+                paths.push(path.clone());
+            }
+        }
+
+        let files = paths
+            .into_iter()
+            .filter(|path| {
+                path.extension().is_some_and(|ext| {
+                    ext == "baml" || DATA_FILE_EXTENSIONS.iter().any(|data_ext| ext == *data_ext)
+                })
+            })
+            .filter_map(|path| {
+                let text = self.read(&path).ok()?;
+                Some(SourceFile::from((path, text)))
+            })
+            .collect::<Vec<_>>();
+
+        let analysis = Analysis::new(&root, files);
+        for (path, diagnostics) in analysis.diagnostics() {
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+            connection
+                .sender
+                .send(Message::Notification(Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    params,
+                )))?;
+        }
+        self.projects.insert(root, analysis);
+
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> Result<String> {
+        match self.open_documents.get(path) {
+            Some(text) => Ok(text.clone()),
+            None => fs::read_to_string(path).context(format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// The analysis, file and byte offset that an LSP position refers to.
+    fn locate(&self, params: &TextDocumentPositionParams) -> Result<(&Analysis, PathBuf, usize)> {
+        let path = file_path(&params.text_document.uri)?;
+        let analysis = self
+            .projects
+            .get(&project_root(&path))
+            .context(format!("{} has not been opened", path.display()))?;
+        let text = analysis
+            .text(&path)
+            .context(format!("{} is not part of a BAML project", path.display()))?;
+        let offset = position::position_to_offset(text, params.position);
+        Ok((analysis, path, offset))
+    }
+}
+
+/// Deserializes the params of `request`, runs `handler` on them, and wraps
+/// the result in a response.
+fn respond<R: lsp_types::request::Request>(
+    request: Request,
+    handler: impl FnOnce(R::Params) -> Result<R::Result>,
+) -> Response
+where
+    R::Params: DeserializeOwned,
+{
+    let id: RequestId = request.id.clone();
+    let result = serde_json::from_value::<R::Params>(request.params)
+        .map_err(anyhow::Error::from)
+        .and_then(handler);
+    match result {
+        Ok(result) => Response::new_ok(id, result),
+        Err(e) => Response::new_err(id, ErrorCode::RequestFailed as i32, format!("{:#}", e)),
+    }
+}
+
+fn file_path(uri: &Url) -> Result<PathBuf> {
+    uri.to_file_path()
+        .map_err(|_| anyhow::anyhow!("{uri} is not a file"))
+}
+
+fn location(span: &internal_baml_core::internal_baml_diagnostics::Span) -> Option<Location> {
+    Some(Location::new(
+        Url::from_file_path(span.file.path_buf()).ok()?,
+        span_range(span),
+    ))
+}
+
+/// The `baml_src` directory containing `path`, or its parent directory if it
+/// isn't in one.
+fn project_root(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == "baml_src"))
+        .or_else(|| path.parent())
+        .unwrap_or(path)
+        .to_path_buf()
+}
@@ -1,11 +1,11 @@
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use baml_types::{BamlMediaType, FieldType, LiteralValue, TypeValue};
+use baml_types::{BamlMediaType, BamlTemporalType, FieldType, LiteralValue, TypeValue};
 use indexmap::IndexMap;
 use internal_baml_core::ir::{
-    repr::{Function, IntermediateRepr, Node, Walker},
-    ClassWalker, EnumWalker,
+    repr::{Field, Function, IntermediateRepr, Node, Walker},
+    ClassWalker, EnumWalker, IRHelper,
 };
 use serde::Serialize;
 use serde_json::json;
@@ -66,6 +66,8 @@ impl LanguageFeatures for OpenApiLanguageFeatures {
 pub struct OpenApiSchema<'ir> {
     paths: Vec<OpenApiMethodDef<'ir>>,
     schemas: IndexMap<&'ir str, TypeSpecWithMeta>,
+    /// The `Partial*` counterparts of `schemas`, used by the `/stream/` endpoints.
+    partial_schemas: IndexMap<String, TypeSpecWithMeta>,
 }
 
 impl Serialize for OpenApiSchema<'_> {
@@ -76,7 +78,12 @@ impl Serialize for OpenApiSchema<'_> {
         let schemas = match self
             .schemas
             .iter()
-            .map(|(name, schema)| Ok((*name, serde_json::to_value(schema)?)))
+            .map(|(name, schema)| Ok((name.to_string(), serde_json::to_value(schema)?)))
+            .chain(
+                self.partial_schemas
+                    .iter()
+                    .map(|(name, schema)| Ok((name.clone(), serde_json::to_value(schema)?))),
+            )
             .collect::<core::result::Result<Vec<_>, serde_json::Error>>()
         {
             Ok(schemas) => schemas,
@@ -104,8 +111,7 @@ impl Serialize for OpenApiSchema<'_> {
                 .iter()
                 .flat_map(|p| vec![
                     (format!("/call/{}", p.function_name), p.as_json("call", "application/json")),
-                    // TODO: publish a schema for streams
-                    // (format!("/stream/{}", p.function_name), p.as_json("stream", "text/event-stream"))
+                    (format!("/stream/{}", p.function_name), p.as_json("stream", "text/event-stream")),
                 ])
                 .collect::<IndexMap<_, _>>(),
             "components": {
@@ -186,15 +192,135 @@ impl Serialize for OpenApiSchema<'_> {
                             ],
                         }),
                     ),
+                    (
+                        "BamlPdf",
+                        json!({
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "title": "BamlPdfBase64",
+                                    "properties": {
+                                        "base64": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["base64"],
+                                },
+                                {
+                                    "type": "object",
+                                    "title": "BamlPdfUrl",
+                                    "properties": {
+                                        "url": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["url"],
+                                }
+                            ],
+                        }),
+                    ),
+                    (
+                        "BamlVideo",
+                        json!({
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "title": "BamlVideoBase64",
+                                    "properties": {
+                                        "base64": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["base64"],
+                                },
+                                {
+                                    "type": "object",
+                                    "title": "BamlVideoUrl",
+                                    "properties": {
+                                        "url": {
+                                            "type": "string",
+                                        },
+                                        "media_type": {
+                                            "type": "string",
+                                        },
+                                    },
+                                    "required": ["url"],
+                                }
+                            ],
+                        }),
+                    ),
+                    (
+                        "BamlMessage",
+                        json!({
+                            "type": "object",
+                            "properties": {
+                                "role": {
+                                    "type": "string",
+                                },
+                                "content": {
+                                    "oneOf": [
+                                        { "type": "string" },
+                                        {
+                                            "type": "array",
+                                            "items": {
+                                                "oneOf": [
+                                                    { "type": "string" },
+                                                    {
+                                                        "type": "object",
+                                                        "title": "BamlMessageMedia",
+                                                        "properties": {
+                                                            "type": {
+                                                                "type": "string",
+                                                                "enum": ["image", "audio", "pdf", "video"],
+                                                            },
+                                                            "url": {
+                                                                "type": "string",
+                                                            },
+                                                            "base64": {
+                                                                "type": "string",
+                                                            },
+                                                            "media_type": {
+                                                                "type": "string",
+                                                            },
+                                                        },
+                                                        "required": ["type"],
+                                                    }
+                                                ],
+                                            },
+                                        }
+                                    ],
+                                },
+                                "name": {
+                                    "type": "string",
+                                },
+                                "metadata": {
+                                    "type": "object",
+                                    "additionalProperties": true,
+                                },
+                            },
+                            "required": ["role", "content"],
+                        }),
+                    ),
                     (
                         "BamlOptions",
                         json!({
                             "type": "object",
                             "nullable": false,
+                            "description": "Runtime options for a single call, passed as the `__baml_options__` field of the request body.",
                             "properties": {
                                 "client_registry": {
                                     "type": "object",
                                     "nullable": false,
+                                    "description": "Overrides the clients defined in baml_src for this call. `primary` names the client to use.",
                                     "properties": {
                                         "clients": {
                                             "type": "array",
@@ -248,6 +374,7 @@ impl Serialize for OpenApiSchema<'_> {
                     )
                 ]
                 .into_iter()
+                .map(|(name, schema)| (name.to_string(), schema))
                 .chain(schemas.into_iter())
                 .collect::<IndexMap<_, _>>(),
             }
@@ -260,20 +387,31 @@ struct OpenApiMethodDef<'ir> {
     function_name: &'ir str,
     request_body: TypeSpecWithMeta,
     response: TypeSpecWithMeta,
+    /// The type of each server-sent event emitted by `/stream/{function_name}`.
+    stream_response: TypeSpecWithMeta,
 }
 
 impl OpenApiMethodDef<'_> {
     fn as_json(&self, tag: &str, response_type: &str) -> serde_json::Value {
+        let (description, response) = if tag == "call" {
+            ("Successful operation", &self.response)
+        } else {
+            (
+                "Successful operation. The response is a stream of server-sent events, \
+                 where the data of each event is a JSON-encoded partial result.",
+                &self.stream_response,
+            )
+        };
         let mut as_json = json!({
             "requestBody": {
                 "$ref": format!("#/components/requestBodies/{}", self.function_name),
             },
             "responses": {
                 "200": {
-                    "description": "Successful operation",
+                    "description": description,
                     "content": {
                         response_type: {
-                            "schema": self.response
+                            "schema": response
                         }
                     }
                 }
@@ -289,7 +427,8 @@ impl OpenApiMethodDef<'_> {
             } else {
                 json!({
                     "tags": ["stream"],
-                    // "operationId": self.function_name,
+                    // operationIds must be unique across the whole spec
+                    "operationId": format!("{}Stream", self.function_name),
                 })
             }
             .as_object_mut()
@@ -341,6 +480,10 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for OpenApi
                 .chain(ir.walk_enums().map(|e| Ok((e.name(), e.try_into()?))))
                 .chain(ir.walk_classes().map(|c| Ok((c.name(), c.try_into()?))))
                 .collect::<Result<_>>()?,
+            partial_schemas: ir
+                .walk_classes()
+                .map(|c| Ok((partial_class_name(c.name()), partial_class_type_spec(c)?)))
+                .collect::<Result<_>>()?,
         })
     }
 }
@@ -398,6 +541,9 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: Some(
+                        "Reserved for BAML runtime options; see BamlOptions.".to_string(),
+                    ),
                     nullable: true,
                 },
                 type_spec: TypeSpec::Ref {
@@ -421,6 +567,7 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                     title: Some(format!("{}Request", function_name)),
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: TypeSpec::Inline(TypeDef::Class {
@@ -446,6 +593,15 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                 response_type.meta.title = Some(format!("{}Response", function_name));
                 response_type
             },
+            stream_response: {
+                let mut response_type = value
+                    .item
+                    .elem
+                    .output()
+                    .to_partial_type_spec(value.db, false)?;
+                response_type.meta.title = Some(format!("{}PartialResponse", function_name));
+                response_type
+            },
         })
     }
 }
@@ -466,6 +622,7 @@ impl<'ir> TryFrom<EnumWalker<'ir>> for TypeSpecWithMeta {
                         .collect(),
                 ),
                 r#const: None,
+                description: None,
                 nullable: false,
             },
             type_spec: TypeSpec::Inline(TypeDef::String),
@@ -482,6 +639,7 @@ impl<'ir> TryFrom<ClassWalker<'ir>> for TypeSpecWithMeta {
                 title: None,
                 r#enum: None,
                 r#const: None,
+                description: None,
                 nullable: false,
             },
             type_spec: TypeSpec::Inline(TypeDef::Class {
@@ -521,8 +679,103 @@ impl<'ir> TryFrom<ClassWalker<'ir>> for TypeSpecWithMeta {
     }
 }
 
+fn partial_class_name(name: &str) -> String {
+    format!("Partial{name}")
+}
+
+/// The schema for `Partial<Class>`, mirroring the partial types generated for
+/// Python and TypeScript: every field may still be missing while streaming.
+fn partial_class_type_spec(c: ClassWalker<'_>) -> Result<TypeSpecWithMeta> {
+    let properties = c
+        .item
+        .elem
+        .static_fields
+        .iter()
+        .map(|f| {
+            Ok((
+                f.elem.name.to_string(),
+                partial_field_type_spec(c.db, f).context(format!(
+                    "Failed to convert {}.{} to OpenAPI partial type",
+                    c.name(),
+                    f.elem.name
+                ))?,
+            ))
+        })
+        .collect::<Result<IndexMap<_, _>>>()?;
+    Ok(TypeSpecWithMeta {
+        meta: TypeMetadata::default(),
+        type_spec: TypeSpec::Inline(TypeDef::Class {
+            required: properties
+                .iter()
+                .filter_map(|(name, t)| {
+                    if t.meta.nullable {
+                        None
+                    } else {
+                        Some(name.clone())
+                    }
+                })
+                .collect(),
+            properties,
+            additional_properties: false,
+        }),
+    })
+}
+
+/// The partial schema of a field, following its `@stream.*` attributes.
+fn partial_field_type_spec(ir: &IntermediateRepr, field: &Node<Field>) -> Result<TypeSpecWithMeta> {
+    let r#type = &field.elem.r#type.elem;
+    let mut type_spec = if field.attributes.get("stream.done").is_some() {
+        // Only set once fully parsed, so it already has its final type.
+        let mut type_spec = r#type.to_type_spec(ir)?;
+        type_spec.meta.nullable = true;
+        type_spec
+    } else {
+        r#type.to_partial_type_spec(ir, false)?
+    };
+    if field.attributes.get("stream.not_null").is_some() && !r#type.is_optional() {
+        type_spec.meta.nullable = false;
+    }
+    if field.attributes.get("stream.with_state").is_some() {
+        type_spec = TypeSpecWithMeta {
+            meta: TypeMetadata::default(),
+            type_spec: TypeSpec::Inline(TypeDef::Class {
+                properties: vec![
+                    ("value".to_string(), type_spec),
+                    (
+                        "state".to_string(),
+                        TypeSpecWithMeta {
+                            meta: TypeMetadata {
+                                r#enum: Some(vec![
+                                    "Pending".to_string(),
+                                    "Incomplete".to_string(),
+                                    "Complete".to_string(),
+                                ]),
+                                ..Default::default()
+                            },
+                            type_spec: TypeSpec::Inline(TypeDef::String),
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                required: vec!["value".to_string(), "state".to_string()],
+                additional_properties: false,
+            }),
+        };
+    }
+    Ok(type_spec)
+}
+
 trait ToTypeReferenceInTypeDefinition<'ir> {
     fn to_type_spec(&self, ir: &'ir IntermediateRepr) -> Result<TypeSpecWithMeta>;
+
+    /// The type of a value while it is still being streamed. `wrapped` is set
+    /// for list items and union variants, which are never null themselves.
+    fn to_partial_type_spec(
+        &self,
+        ir: &'ir IntermediateRepr,
+        wrapped: bool,
+    ) -> Result<TypeSpecWithMeta>;
 }
 
 impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
@@ -533,6 +786,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: TypeSpec::Ref {
@@ -544,6 +798,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: match v {
@@ -557,6 +812,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: TypeSpec::Inline(TypeDef::Array {
@@ -572,6 +828,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         title: None,
                         r#enum: None,
                         r#const: None,
+                        description: None,
                         nullable: false,
                     },
                     type_spec: TypeSpec::Inline(TypeDef::Map {
@@ -584,6 +841,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     title: None,
                     r#enum: None,
                     r#const: None,
+                    description: None,
                     nullable: false,
                 },
                 type_spec: match inner {
@@ -596,12 +854,28 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         "BAML<->OpenAPI only allows nulls in unions, not as a literal"
                     ),
                     TypeValue::String => TypeSpec::Inline(TypeDef::String),
+                    TypeValue::Temporal(t) => TypeSpec::Inline(TypeDef::FormattedString {
+                        format: match t {
+                            BamlTemporalType::Date => "date",
+                            BamlTemporalType::DateTime => "date-time",
+                            BamlTemporalType::Duration => "duration",
+                        },
+                    }),
                     TypeValue::Media(BamlMediaType::Audio) => TypeSpec::Ref {
                         r#ref: "#/components/schemas/BamlAudio".to_string(),
                     },
                     TypeValue::Media(BamlMediaType::Image) => TypeSpec::Ref {
                         r#ref: "#/components/schemas/BamlImage".to_string(),
                     },
+                    TypeValue::Media(BamlMediaType::Pdf) => TypeSpec::Ref {
+                        r#ref: "#/components/schemas/BamlPdf".to_string(),
+                    },
+                    TypeValue::Media(BamlMediaType::Video) => TypeSpec::Ref {
+                        r#ref: "#/components/schemas/BamlVideo".to_string(),
+                    },
+                    TypeValue::Message => TypeSpec::Ref {
+                        r#ref: "#/components/schemas/BamlMessage".to_string(),
+                    },
                 },
             },
             FieldType::Union(union) => {
@@ -622,6 +896,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         title: None,
                         r#enum: None,
                         r#const: None,
+                        description: None,
                         nullable: false,
                     },
                     type_spec: TypeSpec::Union { one_of },
@@ -657,6 +932,95 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
             },
         })
     }
+
+    fn to_partial_type_spec(
+        &self,
+        ir: &'ir IntermediateRepr,
+        wrapped: bool,
+    ) -> Result<TypeSpecWithMeta> {
+        Ok(match self {
+            FieldType::Class(name) => TypeSpecWithMeta {
+                meta: TypeMetadata {
+                    nullable: !wrapped,
+                    ..Default::default()
+                },
+                type_spec: TypeSpec::Ref {
+                    // Classes marked `@@stream.done` are only streamed once
+                    // complete.
+                    r#ref: if ir
+                        .find_class(name)
+                        .map(|c| c.stream_done())
+                        .unwrap_or(false)
+                    {
+                        format!("#/components/schemas/{}", name)
+                    } else {
+                        format!("#/components/schemas/{}", partial_class_name(name))
+                    },
+                },
+            },
+            FieldType::Enum(_) | FieldType::Primitive(_) | FieldType::Literal(_) => {
+                let mut type_spec = self.to_type_spec(ir)?;
+                type_spec.meta.nullable = true;
+                type_spec
+            }
+            FieldType::List(inner) => TypeSpecWithMeta {
+                meta: TypeMetadata::default(),
+                type_spec: TypeSpec::Inline(TypeDef::Array {
+                    items: inner.to_partial_type_spec(ir, true)?.into(),
+                }),
+            },
+            FieldType::Map(key, value) => {
+                if !matches!(**key, FieldType::Primitive(TypeValue::String)) {
+                    anyhow::bail!("BAML<->OpenAPI only supports string keys in maps")
+                }
+                TypeSpecWithMeta {
+                    meta: TypeMetadata::default(),
+                    type_spec: TypeSpec::Inline(TypeDef::Map {
+                        additional_properties: Box::new(value.to_partial_type_spec(ir, false)?),
+                    }),
+                }
+            }
+            FieldType::Union(union) => {
+                let one_of = union
+                    .iter()
+                    .filter(|t| !t.is_null())
+                    .map(|t| t.to_partial_type_spec(ir, true))
+                    .collect::<Result<Vec<_>>>()?;
+
+                if one_of.is_empty() {
+                    anyhow::bail!("BAML<->OpenAPI unions must have at least one non-null type")
+                }
+
+                TypeSpecWithMeta {
+                    meta: TypeMetadata {
+                        nullable: true,
+                        ..Default::default()
+                    },
+                    type_spec: TypeSpec::Union { one_of },
+                }
+            }
+            FieldType::Tuple(_) => {
+                anyhow::bail!("BAML<->OpenAPI tuple support is not implemented")
+            }
+            FieldType::Optional(inner) => inner.to_partial_type_spec(ir, false)?,
+            FieldType::Constrained { base, .. } => match field_type_attributes(self) {
+                Some(checks) => TypeSpecWithMeta {
+                    meta: TypeMetadata::default(),
+                    type_spec: TypeSpec::Inline(TypeDef::Class {
+                        properties: vec![
+                            ("value".to_string(), base.to_partial_type_spec(ir, false)?),
+                            ("checks".to_string(), type_def_for_checks(checks)),
+                        ]
+                        .into_iter()
+                        .collect(),
+                        required: vec!["value".to_string(), "checks".to_string()],
+                        additional_properties: false,
+                    }),
+                },
+                None => base.to_partial_type_spec(ir, wrapped)?,
+            },
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -683,7 +1047,10 @@ struct TypeMetadata {
     /// We only allow string-shaped const values
     #[serde(skip_serializing_if = "Option::is_none")]
     r#const: Option<String>,
-    // description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+
     /// Nulls in OpenAPI are weird: https://swagger.io/docs/specification/data-models/data-types/
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     nullable: bool,
@@ -712,6 +1079,9 @@ enum TypeDef {
     #[serde(rename = "string")]
     String,
 
+    #[serde(rename = "string")]
+    FormattedString { format: &'static str },
+
     #[serde(rename = "object")]
     #[serde(rename_all = "camelCase")]
     Class {
@@ -740,3 +1110,124 @@ enum TypeDef {
     #[serde(rename = "boolean")]
     Bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use internal_baml_core::{configuration::GeneratorDefaultClientMode, ir::repr::make_test_ir};
+
+    fn openapi_spec(source_code: &str) -> serde_json::Value {
+        let ir = make_test_ir(source_code).unwrap();
+        let generator = crate::GeneratorArgs::new(
+            "openapi",
+            "baml_src",
+            vec![],
+            "0.0.0".to_string(),
+            true,
+            GeneratorDefaultClientMode::Async,
+            vec![],
+        )
+        .unwrap();
+        let schema: OpenApiSchema = (&ir, &generator).try_into().unwrap();
+        serde_json::to_value(&schema).unwrap()
+    }
+
+    #[test]
+    fn test_stream_paths_and_partial_schemas() {
+        let spec = openapi_spec(
+            r##"
+class Person {
+  name string @stream.done
+  id int @stream.not_null
+  kind "person"
+  nickname string?
+  tags string[] @stream.with_state
+  address Address
+}
+
+class Address {
+  street string
+  @@stream.done
+}
+
+function ExtractPerson(text: string) -> Person {
+  client "openai/gpt-4o"
+  prompt #"{{ text }}"#
+}
+"##,
+        );
+
+        assert_eq!(
+            spec["paths"]["/call/ExtractPerson"]["post"]["operationId"],
+            "ExtractPerson"
+        );
+        assert_eq!(
+            spec["paths"]["/stream/ExtractPerson"],
+            json!({
+                "post": {
+                    "requestBody": {
+                        "$ref": "#/components/requestBodies/ExtractPerson"
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Successful operation. The response is a stream of server-sent events, where the data of each event is a JSON-encoded partial result.",
+                            "content": {
+                                "text/event-stream": {
+                                    "schema": {
+                                        "title": "ExtractPersonPartialResponse",
+                                        "nullable": true,
+                                        "$ref": "#/components/schemas/PartialPerson"
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "tags": ["stream"],
+                    "operationId": "ExtractPersonStream"
+                }
+            })
+        );
+        assert_eq!(
+            spec["components"]["schemas"]["PartialPerson"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "nullable": true, "type": "string" },
+                    "id": { "type": "integer" },
+                    "kind": { "nullable": true, "type": "string" },
+                    "nickname": { "nullable": true, "type": "string" },
+                    "tags": {
+                        "type": "object",
+                        "properties": {
+                            "value": {
+                                "type": "array",
+                                "items": { "nullable": true, "type": "string" }
+                            },
+                            "state": {
+                                "enum": ["Pending", "Incomplete", "Complete"],
+                                "type": "string"
+                            }
+                        },
+                        "required": ["value", "state"],
+                        "additionalProperties": false
+                    },
+                    // `@@stream.done` classes keep their complete schema.
+                    "address": { "nullable": true, "$ref": "#/components/schemas/Address" }
+                },
+                "required": ["id", "tags"],
+                "additionalProperties": false
+            })
+        );
+        assert_eq!(
+            spec["components"]["schemas"]["PartialAddress"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "street": { "nullable": true, "type": "string" }
+                },
+                "required": [],
+                "additionalProperties": false
+            })
+        );
+    }
+}
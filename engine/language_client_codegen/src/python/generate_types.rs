@@ -7,7 +7,7 @@ use crate::{field_type_attributes, type_check_attributes, TypeCheckAttributes};
 
 use super::python_language_features::ToPython;
 use internal_baml_core::ir::{
-    repr::{Docstring, IntermediateRepr},
+    repr::{Docstring, Field, IntermediateRepr, Node},
     ClassWalker, EnumWalker, FieldType, IRHelper,
 };
 
@@ -152,10 +152,7 @@ impl<'ir> From<ClassWalker<'ir>> for PartialPythonClass<'ir> {
                 .map(|f| {
                     (
                         f.elem.name.as_str(),
-                        add_default_value(
-                            &f.elem.r#type.elem,
-                            &f.elem.r#type.elem.to_partial_type_ref(c.db, false),
-                        ),
+                        add_default_value(&f.elem.r#type.elem, &partial_field_type_ref(c.db, f)),
                         f.elem.docstring.as_ref().map(render_docstring),
                     )
                 })
@@ -165,6 +162,36 @@ impl<'ir> From<ClassWalker<'ir>> for PartialPythonClass<'ir> {
     }
 }
 
+/// The type of a field in a partial class, following its `@stream.*`
+/// attributes.
+fn partial_field_type_ref(ir: &IntermediateRepr, field: &Node<Field>) -> String {
+    let r#type = &field.elem.r#type.elem;
+    let mut type_ref = if field.attributes.get("stream.done").is_some() {
+        // Only set once fully parsed, so it already has its final type.
+        let final_type_ref =
+            super::ToTypeReferenceInClientDefinition::to_type_ref(r#type, ir, false);
+        if r#type.is_optional() {
+            final_type_ref
+        } else {
+            format!("Optional[{final_type_ref}]")
+        }
+    } else {
+        r#type.to_partial_type_ref(ir, false)
+    };
+    if field.attributes.get("stream.not_null").is_some() && !r#type.is_optional() {
+        if let Some(inner) = type_ref
+            .strip_prefix("Optional[")
+            .and_then(|t| t.strip_suffix(']'))
+        {
+            type_ref = inner.to_string();
+        }
+    }
+    if field.attributes.get("stream.with_state").is_some() {
+        type_ref = format!("StreamState[{type_ref}]");
+    }
+    type_ref
+}
+
 pub fn add_default_value(node: &FieldType, type_str: &String) -> String {
     if type_str.starts_with("Optional[") {
         format!("{} = None", type_str)
@@ -256,6 +283,19 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
 
     fn to_partial_type_ref(&self, ir: &IntermediateRepr, wrapped: bool) -> String {
         match self {
+            // Classes marked `@@stream.done` are only streamed once complete.
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.stream_done())
+                    .unwrap_or(false) =>
+            {
+                if wrapped {
+                    format!("types.{name}")
+                } else {
+                    format!("Optional[types.{name}]")
+                }
+            }
             FieldType::Class(name) => {
                 if wrapped {
                     format!("\"{name}\"")
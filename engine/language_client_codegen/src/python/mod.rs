@@ -149,32 +149,18 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for PythonClient
     fn try_from((ir, _): (&'_ IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
         let functions = ir
             .walk_functions()
-            .map(|f| {
-                let configs = f.walk_impls();
-
-                let funcs = configs
-                    .into_iter()
-                    .map(|c| {
-                        let (_function, _impl_) = c.item;
-                        Ok(PythonFunction {
-                            name: f.name().to_string(),
-                            partial_return_type: f.elem().output().to_partial_type_ref(ir, true),
-                            return_type: f.elem().output().to_type_ref(ir, true),
-                            args: f
-                                .inputs()
-                                .iter()
-                                .map(|(name, r#type)| {
-                                    (name.to_string(), r#type.to_type_ref(ir, false))
-                                })
-                                .collect(),
-                        })
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-                Ok(funcs)
+            // Generated once per function, whether it has variants, steps or
+            // a single prompt.
+            .map(|f| PythonFunction {
+                name: f.name().to_string(),
+                partial_return_type: f.elem().output().to_partial_type_ref(ir, true),
+                return_type: f.elem().output().to_type_ref(ir, true),
+                args: f
+                    .inputs()
+                    .iter()
+                    .map(|(name, r#type)| (name.to_string(), r#type.to_type_ref(ir, false)))
+                    .collect(),
             })
-            .collect::<Result<Vec<Vec<PythonFunction>>>>()?
-            .into_iter()
-            .flatten()
             .collect();
         Ok(PythonClient { funcs: functions })
     }
@@ -254,6 +240,14 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                     format!("Optional[types.{name}]")
                 }
             }
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.stream_done())
+                    .unwrap_or(false) =>
+            {
+                format!("types.{name}")
+            }
             FieldType::Class(name) => format!("partial_types.{name}"),
             FieldType::Literal(value) => to_python_literal(value),
             FieldType::List(inner) => {
@@ -1,5 +1,5 @@
 use crate::dir_writer::LanguageFeatures;
-use baml_types::{BamlMediaType, TypeValue};
+use baml_types::{BamlMediaType, BamlTemporalType, TypeValue};
 
 #[derive(Default)]
 pub(super) struct PythonLanguageFeatures {}
@@ -38,6 +38,12 @@ impl ToPython for TypeValue {
             TypeValue::Null => "None",
             TypeValue::Media(BamlMediaType::Image) => "baml_py.Image",
             TypeValue::Media(BamlMediaType::Audio) => "baml_py.Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "baml_py.Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "baml_py.Video",
+            TypeValue::Message => "baml_py.Message",
+            TypeValue::Temporal(BamlTemporalType::Date) => "datetime.date",
+            TypeValue::Temporal(BamlTemporalType::DateTime) => "datetime.datetime",
+            TypeValue::Temporal(BamlTemporalType::Duration) => "datetime.timedelta",
         }
         .to_string()
     }
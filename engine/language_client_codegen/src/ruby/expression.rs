@@ -1,4 +1,4 @@
-use baml_types::{BamlMediaType, StringOr, TypeValue, UnresolvedValue};
+use baml_types::{BamlMediaType, BamlTemporalType, StringOr, TypeValue, UnresolvedValue};
 
 use super::ruby_language_features::ToRuby;
 
@@ -52,6 +52,12 @@ impl ToRuby for TypeValue {
             TypeValue::Null => "null",
             TypeValue::Media(BamlMediaType::Image) => "Image",
             TypeValue::Media(BamlMediaType::Audio) => "Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "Video",
+            TypeValue::Message => "Message",
+            TypeValue::Temporal(BamlTemporalType::Date) => "Date",
+            TypeValue::Temporal(BamlTemporalType::DateTime) => "Time",
+            TypeValue::Temporal(BamlTemporalType::Duration) => "number",
         }
         .to_string()
     }
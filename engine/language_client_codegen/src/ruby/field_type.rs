@@ -1,4 +1,4 @@
-use baml_types::{BamlMediaType, FieldType, LiteralValue, TypeValue};
+use baml_types::{BamlMediaType, BamlTemporalType, FieldType, LiteralValue, TypeValue};
 
 use crate::field_type_attributes;
 
@@ -34,6 +34,13 @@ impl ToRuby for FieldType {
                 // TODO: Create Baml::Types::Image
                 TypeValue::Media(BamlMediaType::Image) => "Baml::Image",
                 TypeValue::Media(BamlMediaType::Audio) => "Baml::Audio",
+                TypeValue::Media(BamlMediaType::Pdf) => "Baml::Pdf",
+                TypeValue::Media(BamlMediaType::Video) => "Baml::Video",
+                TypeValue::Message => "Baml::Message",
+                TypeValue::Temporal(BamlTemporalType::Date) => "Date",
+                TypeValue::Temporal(BamlTemporalType::DateTime) => "Time",
+                // Durations are passed around as a number of seconds.
+                TypeValue::Temporal(BamlTemporalType::Duration) => "Float",
             }),
             FieldType::Union(inner) => format!(
                 // https://sorbet.org/docs/union-types
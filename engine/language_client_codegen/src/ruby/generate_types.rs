@@ -9,8 +9,8 @@ use crate::{field_type_attributes, type_check_attributes, TypeCheckAttributes};
 
 use super::ruby_language_features::ToRuby;
 use internal_baml_core::ir::{
-    repr::{Docstring, IntermediateRepr},
-    ClassWalker, EnumWalker, FieldType,
+    repr::{Docstring, Field, IntermediateRepr, Node},
+    ClassWalker, EnumWalker, FieldType, IRHelper,
 };
 
 #[derive(askama::Template)]
@@ -138,7 +138,7 @@ impl<'ir> From<ClassWalker<'ir>> for PartialRubyStruct<'ir> {
                 .map(|f| {
                     (
                         f.elem.name.as_str(),
-                        f.elem.r#type.elem.to_partial_type_ref(),
+                        partial_field_type_ref(c.db, f),
                         f.elem.docstring.as_ref().map(|d| render_docstring(d, true)),
                     )
                 })
@@ -153,9 +153,37 @@ impl<'ir> From<ClassWalker<'ir>> for PartialRubyStruct<'ir> {
     }
 }
 
+/// The type of a field in a partial struct, following its `@stream.*`
+/// attributes.
+fn partial_field_type_ref(ir: &IntermediateRepr, field: &Node<Field>) -> String {
+    let r#type = &field.elem.r#type.elem;
+    let mut type_ref = if field.attributes.get("stream.done").is_some() {
+        // Only set once fully parsed, so it already has its final type.
+        if r#type.is_optional() {
+            r#type.to_type_ref()
+        } else {
+            format!("T.nilable({})", r#type.to_type_ref())
+        }
+    } else {
+        r#type.to_partial_type_ref(ir)
+    };
+    if field.attributes.get("stream.not_null").is_some() && !r#type.is_optional() {
+        if let Some(inner) = type_ref
+            .strip_prefix("T.nilable(")
+            .and_then(|t| t.strip_suffix(')'))
+        {
+            type_ref = inner.to_string();
+        }
+    }
+    if field.attributes.get("stream.with_state").is_some() {
+        type_ref = format!("Baml::StreamState[{type_ref}]");
+    }
+    type_ref
+}
+
 pub(super) trait ToTypeReferenceInTypeDefinition {
     fn to_type_ref(&self) -> String;
-    fn to_partial_type_ref(&self) -> String;
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String;
 }
 
 impl ToTypeReferenceInTypeDefinition for FieldType {
@@ -164,14 +192,23 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
         self.to_ruby()
     }
 
-    fn to_partial_type_ref(&self) -> String {
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String {
         match self {
+            // Classes marked `@@stream.done` are only streamed once complete.
+            FieldType::Class(name)
+                if ir
+                    .find_class(name)
+                    .map(|c| c.stream_done())
+                    .unwrap_or(false) =>
+            {
+                format!("T.nilable(Baml::Types::{})", name.clone())
+            }
             FieldType::Class(name) => format!("Baml::PartialTypes::{}", name.clone()),
             FieldType::Enum(name) => format!("T.nilable(Baml::Types::{})", name.clone()),
             // TODO: Temporary solution until we figure out Ruby literals.
-            FieldType::Literal(value) => value.literal_base_type().to_partial_type_ref(),
+            FieldType::Literal(value) => value.literal_base_type().to_partial_type_ref(ir),
             // https://sorbet.org/docs/stdlib-generics
-            FieldType::List(inner) => format!("T::Array[{}]", inner.to_partial_type_ref()),
+            FieldType::List(inner) => format!("T::Array[{}]", inner.to_partial_type_ref(ir)),
             FieldType::Map(key, value) => format!(
                 "T::Hash[{}, {}]",
                 match key.as_ref() {
@@ -181,7 +218,7 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                     | FieldType::Union(_) => FieldType::string().to_type_ref(),
                     _ => key.to_type_ref(),
                 },
-                value.to_partial_type_ref()
+                value.to_partial_type_ref(ir)
             ),
             FieldType::Primitive(_) => format!("T.nilable({})", self.to_type_ref()),
             FieldType::Union(inner) => format!(
@@ -189,7 +226,7 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                 "T.nilable(T.any({}))",
                 inner
                     .iter()
-                    .map(|t| t.to_partial_type_ref())
+                    .map(|t| t.to_partial_type_ref(ir))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
@@ -198,17 +235,17 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
                 "T.nilable([{}])",
                 inner
                     .iter()
-                    .map(|t| t.to_partial_type_ref())
+                    .map(|t| t.to_partial_type_ref(ir))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            FieldType::Optional(inner) => inner.to_partial_type_ref(),
+            FieldType::Optional(inner) => inner.to_partial_type_ref(ir),
             FieldType::Constrained { base, .. } => match field_type_attributes(self) {
                 Some(checks) => {
-                    let base_type_ref = base.to_partial_type_ref();
+                    let base_type_ref = base.to_partial_type_ref(ir);
                     format!("Baml::Checked[{base_type_ref}]")
                 }
-                None => base.to_partial_type_ref(),
+                None => base.to_partial_type_ref(ir),
             },
         }
     }
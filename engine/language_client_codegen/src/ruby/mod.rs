@@ -56,29 +56,18 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir crate::GeneratorArgs)> for RubyCl
     fn try_from((ir, _): (&'ir IntermediateRepr, &'ir crate::GeneratorArgs)) -> Result<Self> {
         let functions = ir
             .walk_functions()
-            .map(|f| {
-                let configs = f.walk_impls();
-
-                let funcs = configs
-                    .map(|c| {
-                        let (_function, _impl_) = c.item;
-                        Ok(RubyFunction {
-                            name: f.name().to_string(),
-                            partial_return_type: f.elem().output().to_partial_type_ref(),
-                            return_type: f.elem().output().to_ruby(),
-                            args: f
-                                .inputs()
-                                .iter()
-                                .map(|(name, r#type)| (name.to_string(), r#type.to_type_ref()))
-                                .collect(),
-                        })
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-                Ok(funcs)
+            // Generated once per function, whether it has variants, steps or
+            // a single prompt.
+            .map(|f| RubyFunction {
+                name: f.name().to_string(),
+                partial_return_type: f.elem().output().to_partial_type_ref(ir),
+                return_type: f.elem().output().to_ruby(),
+                args: f
+                    .inputs()
+                    .iter()
+                    .map(|(name, r#type)| (name.to_string(), r#type.to_type_ref()))
+                    .collect(),
             })
-            .collect::<Result<Vec<Vec<RubyFunction>>>>()?
-            .into_iter()
-            .flatten()
             .collect();
         Ok(RubyClient { funcs: functions })
     }
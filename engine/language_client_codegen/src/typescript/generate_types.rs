@@ -4,7 +4,7 @@ use anyhow::Result;
 use itertools::Itertools;
 
 use internal_baml_core::ir::{
-    repr::{Docstring, IntermediateRepr},
+    repr::{Docstring, Field, IntermediateRepr, Node},
     ClassWalker, EnumWalker,
 };
 
@@ -26,6 +26,21 @@ pub(crate) struct TypescriptTypes<'ir> {
     classes: Vec<TypescriptClass<'ir>>,
 }
 
+#[derive(askama::Template)]
+#[template(path = "partial_types.ts.j2", escape = "none")]
+pub(crate) struct TypescriptStreamTypes<'ir> {
+    partial_classes: Vec<PartialTypescriptClass<'ir>>,
+}
+
+/// The TS interface corresponding to Partial<TypeDefinedInBaml>
+struct PartialTypescriptClass<'ir> {
+    name: &'ir str,
+    // the name, whether it may be missing, and the type of the field.
+    fields: Vec<(&'ir str, bool, String)>,
+    dynamic: bool,
+    docstring: Option<String>,
+}
+
 struct TypescriptEnum<'ir> {
     pub name: &'ir str,
     pub values: Vec<(&'ir str, Option<String>)>,
@@ -59,6 +74,19 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptTyp
     }
 }
 
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptStreamTypes<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'ir GeneratorArgs)) -> Result<Self> {
+        Ok(TypescriptStreamTypes {
+            partial_classes: ir
+                .walk_classes()
+                .map(PartialTypescriptClass::from)
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
 impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypeBuilder<'ir> {
     type Error = anyhow::Error;
 
@@ -132,6 +160,63 @@ impl<'ir> From<&ClassWalker<'ir>> for TypescriptClass<'ir> {
     }
 }
 
+impl<'ir> From<ClassWalker<'ir>> for PartialTypescriptClass<'ir> {
+    fn from(c: ClassWalker<'ir>) -> PartialTypescriptClass<'ir> {
+        PartialTypescriptClass {
+            name: c.name(),
+            dynamic: c.item.attributes.get("dynamic_type").is_some(),
+            fields: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .map(|f| {
+                    let (optional, type_ref) = partial_field_type_ref(c.db, f);
+                    (f.elem.name.as_str(), optional, type_ref)
+                })
+                .collect(),
+            docstring: c
+                .item
+                .elem
+                .docstring
+                .as_ref()
+                .map(|d| render_docstring(d, false)),
+        }
+    }
+}
+
+/// Whether a field of a partial interface may be missing, and its type,
+/// following its `@stream.*` attributes.
+fn partial_field_type_ref(ir: &IntermediateRepr, field: &Node<Field>) -> (bool, String) {
+    let r#type = &field.elem.r#type.elem;
+    let mut optional = true;
+    let mut type_ref = if field.attributes.get("stream.done").is_some() {
+        // Only set once fully parsed, so it already has its final type.
+        let final_type_ref = r#type.to_qualified_type_ref(ir, "types.");
+        if r#type.is_optional() {
+            final_type_ref
+        } else {
+            format!("({final_type_ref} | null)")
+        }
+    } else {
+        r#type.to_partial_type_ref(ir, "types.", "")
+    };
+    if field.attributes.get("stream.not_null").is_some() && !r#type.is_optional() {
+        optional = false;
+        if let Some(inner) = type_ref
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(" | null)"))
+        {
+            type_ref = inner.to_string();
+        }
+    }
+    if field.attributes.get("stream.with_state").is_some() {
+        optional = false;
+        type_ref = format!("types.StreamState<{type_ref}>");
+    }
+    (optional, type_ref)
+}
+
 pub fn type_name_for_checks(checks: &TypeCheckAttributes) -> String {
     checks
         .0
@@ -55,7 +55,7 @@ impl From<TypescriptClient> for SyncTypescriptClient {
 #[derive(Debug)]
 struct TypescriptFunction {
     name: String,
-    // partial_return_type: String,
+    partial_return_type: String,
     return_type: String,
     args: Vec<(String, bool, String)>,
 }
@@ -88,6 +88,10 @@ pub(crate) fn generate(
 ) -> Result<IndexMap<PathBuf, String>> {
     let mut collector = FileCollector::<TypescriptLanguageFeatures>::new();
     collector.add_template::<generate_types::TypescriptTypes>("types.ts", (ir, generator))?;
+    collector.add_template::<generate_types::TypescriptStreamTypes>(
+        "partial_types.ts",
+        (ir, generator),
+    )?;
     collector.add_template::<generate_types::TypeBuilder>("type_builder.ts", (ir, generator))?;
     collector.add_template::<AsyncTypescriptClient>("async_client.ts", (ir, generator))?;
     collector.add_template::<SyncTypescriptClient>("sync_client.ts", (ir, generator))?;
@@ -123,35 +127,28 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for TypescriptCli
     fn try_from((ir, _): (&IntermediateRepr, &crate::GeneratorArgs)) -> Result<Self> {
         let functions = ir
             .walk_functions()
-            .map(|f| {
-                let configs = f.walk_impls();
-
-                let funcs = configs
-                    .map(|c| {
-                        let (_function, _impl_) = c.item;
-                        Ok(TypescriptFunction {
-                            name: f.name().to_string(),
-                            return_type: f.elem().output().to_type_ref(ir),
-                            // partial_return_type: f.elem().output().to_partial_type_ref(ir),
-                            args: f
-                                .inputs()
-                                .iter()
-                                .map(|(name, r#type)| {
-                                    (
-                                        name.to_string(),
-                                        r#type.is_optional(),
-                                        r#type.to_type_ref(ir),
-                                    )
-                                })
-                                .collect(),
-                        })
+            // Generated once per function, whether it has variants, steps or
+            // a single prompt.
+            .map(|f| TypescriptFunction {
+                name: f.name().to_string(),
+                return_type: f.elem().output().to_type_ref(ir),
+                partial_return_type: f.elem().output().to_partial_type_ref(
+                    ir,
+                    "",
+                    "partial_types.",
+                ),
+                args: f
+                    .inputs()
+                    .iter()
+                    .map(|(name, r#type)| {
+                        (
+                            name.to_string(),
+                            r#type.is_optional(),
+                            r#type.to_type_ref(ir),
+                        )
                     })
-                    .collect::<Result<Vec<_>>>()?;
-                Ok(funcs)
+                    .collect(),
             })
-            .collect::<Result<Vec<Vec<TypescriptFunction>>>>()?
-            .into_iter()
-            .flatten()
             .collect();
 
         let types = ir
@@ -203,57 +200,106 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for TypescriptIni
 }
 
 trait ToTypeReferenceInClientDefinition {
-    fn to_type_ref(&self, ir: &IntermediateRepr) -> String;
+    fn to_type_ref(&self, ir: &IntermediateRepr) -> String {
+        self.to_qualified_type_ref(ir, "")
+    }
 
-    // fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String;
+    /// Like `to_type_ref`, but names defined in `types.ts` are prefixed with
+    /// `types_module` (e.g. `types.`).
+    fn to_qualified_type_ref(&self, ir: &IntermediateRepr, types_module: &str) -> String;
+
+    /// The type of a value while it is being streamed. Names defined in
+    /// `types.ts` and `partial_types.ts` are prefixed with `types_module` and
+    /// `partial_module` respectively.
+    fn to_partial_type_ref(
+        &self,
+        ir: &IntermediateRepr,
+        types_module: &str,
+        partial_module: &str,
+    ) -> String;
 }
 
 impl ToTypeReferenceInClientDefinition for FieldType {
-    // fn to_partial_type_ref(&self, ir: &IntermediateRepr) -> String {
-    //     match self {
-    //         FieldType::Enum(name) => {
-    //             if ir
-    //                 .find_enum(name)
-    //                 .map(|e| e.item.attributes.get("dynamic_type").is_some())
-    //                 .unwrap_or(false)
-    //             {
-    //                 format!("(string | {name} | null)")
-    //             } else {
-    //                 format!("({name} | null)")
-    //             }
-    //         }
-    //         FieldType::Class(name) => format!("(RecursivePartialNull<{name}>)"),
-    //         FieldType::List(inner) => format!("{}[]", inner.to_partial_type_ref(ir)),
-    //         FieldType::Map(key, value) => {
-    //             format!(
-    //                 "(Record<{}, {}> | null)",
-    //                 key.to_type_ref(ir),
-    //                 value.to_partial_type_ref(ir)
-    //             )
-    //         }
-    //         FieldType::Literal(value) => value.to_string(),
-    //         FieldType::Primitive(r#type) => format!("({} | null)", r#type.to_typescript()),
-    //         FieldType::Union(inner) => format!(
-    //             "({} | null)",
-    //             inner
-    //                 .iter()
-    //                 .map(|t| t.to_partial_type_ref(ir))
-    //                 .collect::<Vec<_>>()
-    //                 .join(" | ")
-    //         ),
-    //         FieldType::Tuple(inner) => format!(
-    //             "([{}] | null)",
-    //             inner
-    //                 .iter()
-    //                 .map(|t| t.to_partial_type_ref(ir))
-    //                 .collect::<Vec<_>>()
-    //                 .join(", ")
-    //         ),
-    //         FieldType::Optional(inner) => format!("({} | null)", inner.to_partial_type_ref(ir)),
-    //     }
-    // }
+    fn to_partial_type_ref(
+        &self,
+        ir: &IntermediateRepr,
+        types_module: &str,
+        partial_module: &str,
+    ) -> String {
+        match self {
+            FieldType::Enum(name) => {
+                if ir
+                    .find_enum(name)
+                    .map(|e| e.item.attributes.get("dynamic_type").is_some())
+                    .unwrap_or(false)
+                {
+                    format!("(string | {types_module}{name} | null)")
+                } else {
+                    format!("({types_module}{name} | null)")
+                }
+            }
+            FieldType::Class(name) => {
+                // Classes marked `@@stream.done` are only streamed once complete.
+                if ir
+                    .find_class(name)
+                    .map(|c| c.stream_done())
+                    .unwrap_or(false)
+                {
+                    format!("({types_module}{name} | null)")
+                } else {
+                    format!("({partial_module}{name} | null)")
+                }
+            }
+            FieldType::List(inner) => format!(
+                "{}[]",
+                inner.to_partial_type_ref(ir, types_module, partial_module)
+            ),
+            FieldType::Map(key, value) => {
+                let k = key.to_qualified_type_ref(ir, types_module);
+                let v = value.to_partial_type_ref(ir, types_module, partial_module);
 
-    fn to_type_ref(&self, ir: &IntermediateRepr) -> String {
+                match key.as_ref() {
+                    FieldType::Enum(_)
+                    | FieldType::Union(_)
+                    | FieldType::Literal(LiteralValue::String(_)) => {
+                        format!("(Partial<Record<{k}, {v}>> | null)")
+                    }
+                    _ => format!("(Record<{k}, {v}> | null)"),
+                }
+            }
+            FieldType::Literal(value) => format!("({value} | null)"),
+            FieldType::Primitive(r#type) => format!("({} | null)", r#type.to_typescript()),
+            FieldType::Union(inner) => format!(
+                "({} | null)",
+                inner
+                    .iter()
+                    .map(|t| t.to_partial_type_ref(ir, types_module, partial_module))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
+            FieldType::Tuple(inner) => format!(
+                "([{}] | null)",
+                inner
+                    .iter()
+                    .map(|t| t.to_partial_type_ref(ir, types_module, partial_module))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            FieldType::Optional(inner) => {
+                inner.to_partial_type_ref(ir, types_module, partial_module)
+            }
+            FieldType::Constrained { base, .. } => match field_type_attributes(self) {
+                Some(checks) => {
+                    let base_type_ref = base.to_partial_type_ref(ir, types_module, partial_module);
+                    let checks_type_ref = type_name_for_checks(&checks);
+                    format!("{types_module}Checked<{base_type_ref},{checks_type_ref}>")
+                }
+                None => base.to_partial_type_ref(ir, types_module, partial_module),
+            },
+        }
+    }
+
+    fn to_qualified_type_ref(&self, ir: &IntermediateRepr, types_module: &str) -> String {
         match self {
             FieldType::Enum(name) => {
                 if ir
@@ -261,21 +307,21 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                     .map(|e| e.item.attributes.get("dynamic_type").is_some())
                     .unwrap_or(false)
                 {
-                    format!("(string | {name})")
+                    format!("(string | {types_module}{name})")
                 } else {
-                    name.to_string()
+                    format!("{types_module}{name}")
                 }
             }
-            FieldType::Class(name) => name.to_string(),
+            FieldType::Class(name) => format!("{types_module}{name}"),
             FieldType::List(inner) => match inner.as_ref() {
                 FieldType::Union(_) | FieldType::Optional(_) => {
-                    format!("({})[]", inner.to_type_ref(ir))
+                    format!("({})[]", inner.to_qualified_type_ref(ir, types_module))
                 }
-                _ => format!("{}[]", inner.to_type_ref(ir)),
+                _ => format!("{}[]", inner.to_qualified_type_ref(ir, types_module)),
             },
             FieldType::Map(key, value) => {
-                let k = key.to_type_ref(ir);
-                let v = value.to_type_ref(ir);
+                let k = key.to_qualified_type_ref(ir, types_module);
+                let v = value.to_qualified_type_ref(ir, types_module);
 
                 match key.as_ref() {
                     FieldType::Enum(_)
@@ -291,7 +337,7 @@ impl ToTypeReferenceInClientDefinition for FieldType {
             FieldType::Literal(value) => value.to_string(),
             FieldType::Union(inner) => inner
                 .iter()
-                .map(|t| t.to_type_ref(ir))
+                .map(|t| t.to_qualified_type_ref(ir, types_module))
                 .collect::<Vec<_>>()
                 .join(" | ")
                 .to_string(),
@@ -299,18 +345,20 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                 "[{}]",
                 inner
                     .iter()
-                    .map(|t| t.to_type_ref(ir))
+                    .map(|t| t.to_qualified_type_ref(ir, types_module))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            FieldType::Optional(inner) => format!("{} | null", inner.to_type_ref(ir)),
+            FieldType::Optional(inner) => {
+                format!("{} | null", inner.to_qualified_type_ref(ir, types_module))
+            }
             FieldType::Constrained { base, .. } => match field_type_attributes(self) {
                 Some(checks) => {
-                    let base_type_ref = base.to_type_ref(ir);
+                    let base_type_ref = base.to_qualified_type_ref(ir, types_module);
                     let checks_type_ref = type_name_for_checks(&checks);
-                    format!("Checked<{base_type_ref},{checks_type_ref}>")
+                    format!("{types_module}Checked<{base_type_ref},{checks_type_ref}>")
                 }
-                None => base.to_type_ref(ir),
+                None => base.to_qualified_type_ref(ir, types_module),
             },
         }
     }
@@ -1,5 +1,5 @@
 use crate::dir_writer::LanguageFeatures;
-use baml_types::{BamlMediaType, TypeValue};
+use baml_types::{BamlMediaType, BamlTemporalType, TypeValue};
 
 #[derive(Default)]
 pub(super) struct TypescriptLanguageFeatures {}
@@ -40,6 +40,12 @@ impl ToTypescript for TypeValue {
             TypeValue::Null => "null",
             TypeValue::Media(BamlMediaType::Image) => "Image",
             TypeValue::Media(BamlMediaType::Audio) => "Audio",
+            TypeValue::Media(BamlMediaType::Pdf) => "Pdf",
+            TypeValue::Media(BamlMediaType::Video) => "Video",
+            TypeValue::Message => "Message",
+            TypeValue::Temporal(BamlTemporalType::Date | BamlTemporalType::DateTime) => "Date",
+            // Durations are passed around as a number of seconds.
+            TypeValue::Temporal(BamlTemporalType::Duration) => "number",
         };
         var_name.to_string()
     }
@@ -11,8 +11,12 @@ from .baml_py import (
     BamlRuntime,
     FunctionResult,
     FunctionResultStream,
+    HttpRequest,
     BamlImagePy as Image,
     BamlAudioPy as Audio,
+    BamlPdfPy as Pdf,
+    BamlVideoPy as Video,
+    BamlMessagePy as Message,
     invoke_runtime_cli,
     ClientRegistry,
 )
@@ -27,7 +31,11 @@ __all__ = [
     "BamlCtxManager",
     "FunctionResult",
     "FunctionResultStream",
+    "HttpRequest",
     "Image",
     "Audio",
+    "Pdf",
+    "Video",
+    "Message",
     "invoke_runtime_cli",
 ]
@@ -1,4 +1,4 @@
-from typing import Any, Callable, Dict, Optional, Tuple
+from typing import Any, Callable, Dict, List, Optional, Set, Tuple, Union
 
 class FunctionResult:
     """The result of a BAML function call.
@@ -17,6 +17,20 @@ class FunctionResult:
     def __str__(self) -> str: ...
     # Returns True if the function call was successful, False otherwise
     def is_ok(self) -> bool: ...
+    # The cost in USD across all retries and fallbacks, or None if unknown
+    def cost(self) -> Optional[float]: ...
+    # The tokens used across all retries and fallbacks, as
+    # {"input_tokens": int, "output_tokens": int,
+    #  "cache_creation_input_tokens": int, "cache_read_input_tokens": int},
+    # where a count is None if no provider reported it
+    def usage(self) -> Dict[str, Optional[int]]: ...
+    # The name of the prompt variant that was used, or None if the function has
+    # no variants
+    def variant(self) -> Optional[str]: ...
+    # The fixes the parser applied to each JSON path of the parsed value, as
+    # {"score": int, "entries": [{"path": str, "flags": [str], "score": int}]},
+    # or None if the response could not be parsed
+    def parse_report(self) -> Optional[Dict[str, Any]]: ...
     def cast_to(self, enum_module: Any, class_module: Any) -> Any: ...
 
     # This is a debug function that returns the internal representation of the response
@@ -27,6 +41,18 @@ class FunctionResult:
     #   print(val)
     def unstable_internal_repr(self) -> str: ...
 
+class HttpRequest:
+    """The HTTP request BAML would send to an LLM provider, built but not sent."""
+
+    @property
+    def url(self) -> str: ...
+    @property
+    def method(self) -> str: ...
+    @property
+    def headers(self) -> Dict[str, str]: ...
+    @property
+    def body(self) -> Any: ...
+
 class FunctionResultStream:
     """The result of a BAML function stream.
 
@@ -75,8 +101,50 @@ class BamlAudioPy:
     def as_url(self) -> str: ...
     def as_base64(self) -> Tuple[str, str]: ...
 
+class BamlPdfPy:
+    @staticmethod
+    def from_url(url: str) -> BamlPdfPy: ...
+    @staticmethod
+    def from_base64(media_type: str, base64: str) -> BamlPdfPy: ...
+    def is_url(self) -> bool: ...
+    def is_base64(self) -> bool: ...
+    def as_url(self) -> str: ...
+    def as_base64(self) -> Tuple[str, str]: ...
+
+class BamlVideoPy:
+    @staticmethod
+    def from_url(url: str) -> BamlVideoPy: ...
+    @staticmethod
+    def from_base64(media_type: str, base64: str) -> BamlVideoPy: ...
+    def is_url(self) -> bool: ...
+    def is_base64(self) -> bool: ...
+    def as_url(self) -> str: ...
+    def as_base64(self) -> Tuple[str, str]: ...
+
+BamlMessagePart = Union[str, BamlImagePy, BamlAudioPy, BamlPdfPy, BamlVideoPy]
+
+class BamlMessagePy:
+    def __init__(
+        self,
+        role: str,
+        content: Union[BamlMessagePart, List[BamlMessagePart]],
+        name: Optional[str] = None,
+        metadata: Optional[Dict[str, Any]] = None,
+    ) -> None: ...
+    @property
+    def role(self) -> str: ...
+    @property
+    def content(self) -> List[BamlMessagePart]: ...
+    @property
+    def name(self) -> Optional[str]: ...
+    @property
+    def metadata(self) -> Dict[str, Any]: ...
+
 class RuntimeContextManager:
     def upsert_tags(self, tags: Dict[str, Any]) -> None: ...
+    def set_cost_budget(
+        self, per_call: Optional[float] = None, per_context: Optional[float] = None
+    ) -> None: ...
     def deep_clone(self) -> RuntimeContextManager: ...
     def context_depth(self) -> int: ...
 
@@ -116,6 +184,36 @@ class BamlRuntime:
         tb: Optional[TypeBuilder],
         cr: Optional[ClientRegistry],
     ) -> SyncFunctionResultStream: ...
+    async def build_request(
+        self,
+        function_name: str,
+        args: Dict[str, Any],
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        stream: bool,
+    ) -> HttpRequest: ...
+    def build_request_sync(
+        self,
+        function_name: str,
+        args: Dict[str, Any],
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        stream: bool,
+    ) -> HttpRequest: ...
+    # Pass the partial_types module as class_module when allow_partials is True
+    def parse_llm_response(
+        self,
+        function_name: str,
+        llm_response: str,
+        enum_module: Any,
+        class_module: Any,
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+        cr: Optional[ClientRegistry],
+        allow_partials: bool,
+    ) -> Any: ...
     def create_context_manager(self) -> RuntimeContextManager: ...
     def flush(self) -> None: ...
     def drain_stats(self) -> TraceStats: ...
@@ -188,6 +286,11 @@ class TypeBuilder:
     def optional(self, inner_type: FieldType) -> FieldType: ...
     def map(self, key_type: FieldType, value_type: FieldType) -> FieldType: ...
     def union(self, *types: FieldType) -> FieldType: ...
+    def add_json_schema(
+        self, schema: str, root_name: str = "Root", taken: Set[str] = ...
+    ) -> Tuple[
+        Optional[FieldType], List[Tuple[str, List[str]]], List[Tuple[str, List[str]]]
+    ]: ...
 
 class ClientRegistry:
     def __init__(self) -> None: ...
@@ -199,6 +302,7 @@ class ClientRegistry:
         retry_policy: Optional[str] = None,
     ) -> None: ...
     def set_primary(self, name: str) -> None: ...
+    def set_variant(self, name: str) -> None: ...
 
 class FieldType:
     def list(self) -> FieldType: ...
@@ -66,6 +66,14 @@ class CtxManager:
         mngr = self.__ctx()
         mngr.upsert_tags(tags)
 
+    def set_cost_budget(
+        self,
+        per_call: typing.Optional[float] = None,
+        per_context: typing.Optional[float] = None,
+    ) -> None:
+        mngr = self.__ctx()
+        mngr.set_cost_budget(per_call, per_context)
+
     def get(self) -> RuntimeContextManager:
         return self.__ctx()
 
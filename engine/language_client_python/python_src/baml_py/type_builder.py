@@ -1,3 +1,4 @@
+import json
 import typing
 from .baml_py import (
     ClassBuilder,
@@ -14,6 +15,17 @@ class TypeBuilder:
         self.__classes = classes
         self.__enums = enums
         self.__tb = _TypeBuilder()
+        self.__imported: typing.Dict[
+            str, typing.Union["NewClassBuilder", "NewEnumBuilder"]
+        ] = {}
+
+    def __getattr__(self, name: str) -> typing.Union["NewClassBuilder", "NewEnumBuilder"]:
+        # Only called for names that aren't attributes, e.g. types added
+        # with add_json_schema.
+        imported = self.__dict__.get("_TypeBuilder__imported", {})
+        if name not in imported:
+            raise AttributeError(f"Type {name} not found.")
+        return imported[name]
 
     @property
     def _tb(self) -> _TypeBuilder:
@@ -52,6 +64,30 @@ class TypeBuilder:
     def union(self, types: typing.List[FieldType]):
         return self._tb.union(*types)
 
+    def add_json_schema(
+        self, schema: typing.Union[str, typing.Dict[str, typing.Any]], root_name: str = "Root"
+    ) -> typing.Optional[FieldType]:
+        """Adds the classes and enums defined by a JSON Schema (or OpenAPI) document.
+
+        Types named like an existing class or enum get a numbered name instead,
+        e.g. `Person2`. The added types can then be looked up like any other,
+        e.g. `tb.Person2`.
+
+        Returns the type of the root schema, if the document describes one.
+        """
+        if not isinstance(schema, str):
+            schema = json.dumps(schema)
+        root, classes, enums = self._tb.add_json_schema(
+            schema, root_name, self.__classes | self.__enums
+        )
+        for name, properties in classes:
+            self.__classes.add(name)
+            self.__imported[name] = NewClassBuilder(self._tb, name, properties)
+        for name, values in enums:
+            self.__enums.add(name)
+            self.__imported[name] = NewEnumBuilder(self._tb, name, values)
+        return root
+
     def add_class(self, name: str) -> "NewClassBuilder":
         if name in self.__classes:
             raise ValueError(f"Class with name {name} already exists.")
@@ -70,9 +106,11 @@ class TypeBuilder:
 
 
 class NewClassBuilder:
-    def __init__(self, tb: _TypeBuilder, name: str):
+    def __init__(
+        self, tb: _TypeBuilder, name: str, properties: typing.Iterable[str] = ()
+    ):
         self.__bldr = tb.class_(name)
-        self.__properties: typing.Set[str] = set()
+        self.__properties: typing.Set[str] = set(properties)
         self.__props = NewClassProperties(self.__bldr, self.__properties)
 
     def type(self) -> FieldType:
@@ -121,9 +159,9 @@ class NewClassProperties:
 
 
 class NewEnumBuilder:
-    def __init__(self, tb: _TypeBuilder, name: str):
+    def __init__(self, tb: _TypeBuilder, name: str, values: typing.Iterable[str] = ()):
         self.__bldr = tb.enum(name)
-        self.__values: typing.Set[str] = set()
+        self.__values: typing.Set[str] = set(values)
         self.__vals = NewEnumValues(self.__bldr, self.__values)
 
     def type(self) -> FieldType:
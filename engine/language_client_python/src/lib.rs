@@ -59,9 +59,13 @@ fn baml_py(m: Bound<'_, PyModule>) -> PyResult<()> {
 
     m.add_class::<types::FunctionResult>()?;
     m.add_class::<types::FunctionResultStream>()?;
+    m.add_class::<types::HttpRequest>()?;
     m.add_class::<types::SyncFunctionResultStream>()?;
     m.add_class::<types::BamlImagePy>()?;
     m.add_class::<types::BamlAudioPy>()?;
+    m.add_class::<types::BamlPdfPy>()?;
+    m.add_class::<types::BamlVideoPy>()?;
+    m.add_class::<types::BamlMessagePy>()?;
     m.add_class::<types::RuntimeContextManager>()?;
     m.add_class::<types::BamlSpan>()?;
     m.add_class::<types::TypeBuilder>()?;
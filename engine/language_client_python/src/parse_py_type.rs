@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use baml_types::{BamlMap, BamlValue};
+use baml_types::{BamlMap, BamlTemporal, BamlTemporalType, BamlValue};
 use pyo3::{
     exceptions::{PyRuntimeError, PyTypeError},
     prelude::{PyAnyMethods, PyTypeMethods},
@@ -9,7 +9,7 @@ use pyo3::{
     IntoPyObjectExt, PyErr, PyObject, PyResult, Python,
 };
 
-use crate::types::{BamlAudioPy, BamlImagePy};
+use crate::types::{BamlAudioPy, BamlImagePy, BamlMessagePy, BamlPdfPy, BamlVideoPy};
 
 struct SerializationError {
     position: Vec<String>,
@@ -60,6 +60,8 @@ enum MappedPyType {
     Bool(bool),
     None,
     BamlMedia(baml_types::BamlMedia),
+    BamlMessage(baml_types::BamlMessage),
+    Temporal(BamlTemporal),
     Unsupported(String),
 }
 
@@ -190,6 +192,8 @@ where
         MappedPyType::Float(v) => BamlValue::Float(v),
         MappedPyType::Bool(v) => BamlValue::Bool(v),
         MappedPyType::BamlMedia(media) => BamlValue::Media(media),
+        MappedPyType::BamlMessage(message) => BamlValue::Message(message),
+        MappedPyType::Temporal(t) => BamlValue::Temporal(t),
         MappedPyType::None => BamlValue::Null,
         MappedPyType::Unsupported(r#type) => {
             return if matches!(handle_unknown_types, UnknownTypeHandler::Ignore) {
@@ -211,6 +215,12 @@ pub fn parse_py_type(
     Python::with_gil(|py| {
         let enum_type = py.import("enum").and_then(|m| m.getattr("Enum"))?;
         let base_model = py.import("pydantic").and_then(|m| m.getattr("BaseModel"))?;
+        let datetime = py.import("datetime")?;
+        let (date_type, datetime_type, timedelta_type) = (
+            datetime.getattr("date")?,
+            datetime.getattr("datetime")?,
+            datetime.getattr("timedelta")?,
+        );
 
         let mut get_type = |py: Python<'_>,
                             any: PyObject,
@@ -282,6 +292,24 @@ pub fn parse_py_type(
                 // }
                 Ok(MappedPyType::Class(name, fields))
                 // use downcast only
+            } else if t.is_subclass(&timedelta_type).unwrap_or(false) {
+                let seconds = any.call_method0(py, "total_seconds")?.extract::<f64>(py)?;
+                Ok(MappedPyType::Temporal(BamlTemporal::from_number(
+                    BamlTemporalType::Duration,
+                    seconds,
+                )?))
+            } else if t.is_subclass(&date_type).unwrap_or(false) {
+                // `datetime.datetime` is a subclass of `datetime.date`.
+                let temporal_type = if t.is_subclass(&datetime_type).unwrap_or(false) {
+                    BamlTemporalType::DateTime
+                } else {
+                    BamlTemporalType::Date
+                };
+                let iso = any.call_method0(py, "isoformat")?.extract::<String>(py)?;
+                Ok(MappedPyType::Temporal(BamlTemporal::parse(
+                    temporal_type,
+                    &iso,
+                )?))
             } else if let Ok(list) = any.downcast_bound::<PyList>(py) {
                 let mut items = vec![];
                 let len = list.len()?;
@@ -309,6 +337,15 @@ pub fn parse_py_type(
             } else if let Ok(b) = any.downcast_bound::<BamlAudioPy>(py) {
                 let b = b.borrow();
                 Ok(MappedPyType::BamlMedia(b.inner.clone()))
+            } else if let Ok(b) = any.downcast_bound::<BamlPdfPy>(py) {
+                let b = b.borrow();
+                Ok(MappedPyType::BamlMedia(b.inner.clone()))
+            } else if let Ok(b) = any.downcast_bound::<BamlVideoPy>(py) {
+                let b = b.borrow();
+                Ok(MappedPyType::BamlMedia(b.inner.clone()))
+            } else if let Ok(b) = any.downcast_bound::<BamlMessagePy>(py) {
+                let b = b.borrow();
+                Ok(MappedPyType::BamlMessage(b.inner.clone()))
             } else if matches!(unknown_type_handler, UnknownTypeHandler::SerializeAsStr) {
                 // Call the __str__ method on the object
                 // Call the type() function on the object
@@ -1,6 +1,7 @@
 use crate::errors::{BamlError, BamlInvalidArgumentError};
 use crate::parse_py_type::parse_py_type;
-use crate::types::function_results::FunctionResult;
+use crate::types::function_results::{pythonize_strict, FunctionResult};
+use crate::types::http_request::HttpRequest;
 use crate::types::trace_stats::TraceStats;
 
 use crate::types::function_result_stream::{FunctionResultStream, SyncFunctionResultStream};
@@ -10,7 +11,8 @@ use crate::types::ClientRegistry;
 use baml_runtime::runtime_interface::ExperimentalTracingInterface;
 use baml_runtime::BamlRuntime as CoreBamlRuntime;
 use pyo3::prelude::{pymethods, PyResult};
-use pyo3::{pyclass, IntoPyObjectExt, PyObject, Python};
+use pyo3::types::PyModule;
+use pyo3::{pyclass, Bound, IntoPyObjectExt, PyObject, Python};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -274,6 +276,125 @@ impl BamlRuntime {
         ))
     }
 
+    #[pyo3(signature = (function_name, args, ctx, tb, cb, stream))]
+    fn build_request(
+        &self,
+        py: Python<'_>,
+        function_name: String,
+        args: PyObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> PyResult<PyObject> {
+        let Some(args) = parse_py_type(args.into_bound(py).into_py_any(py)?, false)? else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args, perhaps you used a non-serializable type?",
+            ));
+        };
+        let Some(args_map) = args.as_map_owned() else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args. Expect kwargs",
+            ));
+        };
+        log::debug!("pyo3 build_request parsed args into: {:#?}", args_map);
+
+        let baml_runtime = self.inner.clone();
+        let ctx_mng = ctx.inner.clone();
+        let tb = tb.map(|tb| tb.inner.clone());
+        let cb = cb.map(|cb| cb.inner.clone());
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            baml_runtime
+                .build_request(
+                    &function_name,
+                    &args_map,
+                    &ctx_mng,
+                    tb.as_ref(),
+                    cb.as_ref(),
+                    stream,
+                )
+                .await
+                .map(HttpRequest::from)
+                .map_err(BamlError::from_anyhow)
+        })
+        .map(|f| f.into())
+    }
+
+    #[pyo3(signature = (function_name, args, ctx, tb, cb, stream))]
+    fn build_request_sync(
+        &self,
+        function_name: String,
+        args: PyObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> PyResult<HttpRequest> {
+        let Some(args) = parse_py_type(args, false)? else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args, perhaps you used a non-serializable type?",
+            ));
+        };
+        let Some(args_map) = args.as_map_owned() else {
+            return Err(BamlInvalidArgumentError::new_err(
+                "Failed to parse args as a map",
+            ));
+        };
+        log::debug!("pyo3 build_request_sync parsed args into: {:#?}", args_map);
+
+        self.inner
+            .build_request_sync(
+                &function_name,
+                &args_map,
+                &ctx.inner,
+                tb.map(|tb| tb.inner.clone()).as_ref(),
+                cb.map(|cb| cb.inner.clone()).as_ref(),
+                stream,
+            )
+            .map(HttpRequest::from)
+            .map_err(BamlError::from_anyhow)
+    }
+
+    /// Parses `llm_response` into the output type of `function_name`. Pass the
+    /// `partial_types` module as `cls_module` when `allow_partials` is set.
+    #[pyo3(signature = (function_name, llm_response, enum_module, cls_module, ctx, tb, cb, allow_partials))]
+    fn parse_llm_response(
+        &self,
+        py: Python<'_>,
+        function_name: String,
+        llm_response: String,
+        enum_module: Bound<'_, PyModule>,
+        cls_module: Bound<'_, PyModule>,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        allow_partials: bool,
+    ) -> PyResult<PyObject> {
+        let tb = tb.map(|tb| tb.inner.clone());
+        let cb = cb.map(|cb| cb.inner.clone());
+        let parsed = if allow_partials {
+            self.inner.parse_llm_response_partial(
+                &function_name,
+                &llm_response,
+                &ctx.inner,
+                tb.as_ref(),
+                cb.as_ref(),
+            )
+        } else {
+            self.inner.parse_llm_response(
+                &function_name,
+                &llm_response,
+                &ctx.inner,
+                tb.as_ref(),
+                cb.as_ref(),
+            )
+        }
+        .map_err(BamlError::from_anyhow)?;
+
+        pythonize_strict(py, parsed, &enum_module, &cls_module)
+    }
+
     #[pyo3()]
     fn flush(&self) -> PyResult<()> {
         self.inner.flush().map_err(BamlError::from_anyhow)
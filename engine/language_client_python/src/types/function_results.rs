@@ -1,11 +1,11 @@
-use baml_types::{BamlValueWithMeta, ResponseCheck};
+use baml_types::{BamlTemporal, BamlValueWithMeta, ResponseCheck};
 use pyo3::prelude::{pymethods, PyResult};
 use pyo3::types::{PyAnyMethods, PyDict, PyModule, PyTuple, PyType};
 use pyo3::{Bound, IntoPyObject, IntoPyObjectExt, PyAny, PyObject, Python};
 
 use crate::errors::BamlError;
 
-use super::{BamlAudioPy, BamlImagePy};
+use super::{BamlAudioPy, BamlImagePy, BamlMessagePy, BamlPdfPy, BamlVideoPy};
 
 crate::lang_wrapper!(FunctionResult, baml_runtime::FunctionResult);
 
@@ -19,6 +19,27 @@ impl FunctionResult {
         self.inner.result_with_constraints_content().is_ok()
     }
 
+    /// The cost in USD across all retries and fallbacks, if known
+    fn cost(&self) -> Option<f64> {
+        self.inner.cost()
+    }
+
+    /// The input, output and cached tokens across all retries and fallbacks
+    fn usage(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(pythonize::pythonize(py, &self.inner.usage())?.into())
+    }
+
+    /// The name of the prompt variant that was used, if the function has variants
+    fn variant(&self) -> Option<String> {
+        self.inner.variant().map(|v| v.to_string())
+    }
+
+    /// The fixes the parser applied to each path of the parsed value, if it
+    /// could be parsed
+    fn parse_report(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(pythonize::pythonize(py, &self.inner.parse_report())?.into())
+    }
+
     /// This is a debug function that returns the internal representation of the response
     /// This is not to be relied upon and is subject to change
     fn unstable_internal_repr(&self) -> String {
@@ -72,7 +93,7 @@ fn pythonize_checks<'a>(
     Ok(dict)
 }
 
-fn pythonize_strict(
+pub(crate) fn pythonize_strict(
     py: Python<'_>,
     parsed: BamlValueWithMeta<Vec<ResponseCheck>>,
     enum_module: &Bound<'_, PyModule>,
@@ -107,7 +128,33 @@ fn pythonize_strict(
             baml_types::BamlMediaType::Audio => {
                 BamlAudioPy::from(baml_media.clone()).into_py_any(py)
             }
+            baml_types::BamlMediaType::Pdf => BamlPdfPy::from(baml_media.clone()).into_py_any(py),
+            baml_types::BamlMediaType::Video => {
+                BamlVideoPy::from(baml_media.clone()).into_py_any(py)
+            }
         },
+        BamlValueWithMeta::Message(message, _) => BamlMessagePy::from(message).into_py_any(py),
+        BamlValueWithMeta::Temporal(t, _) => {
+            let datetime = py.import("datetime")?;
+            match t {
+                BamlTemporal::Date(_) => datetime
+                    .getattr("date")?
+                    .call_method1("fromisoformat", (t.to_iso8601(),))?
+                    .into_py_any(py),
+                BamlTemporal::DateTime(_) => datetime
+                    .getattr("datetime")?
+                    .call_method1("fromisoformat", (t.to_iso8601(),))?
+                    .into_py_any(py),
+                BamlTemporal::Duration(d) => {
+                    let kwargs = PyDict::new(py);
+                    kwargs.set_item("milliseconds", d.num_milliseconds())?;
+                    datetime
+                        .getattr("timedelta")?
+                        .call((), Some(&kwargs))?
+                        .into_py_any(py)
+                }
+            }
+        }
         BamlValueWithMeta::Enum(enum_name, ref value, _) => {
             let enum_type = match enum_module.getattr(enum_name.as_str()) {
                 Ok(e) => e,
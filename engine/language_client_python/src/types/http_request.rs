@@ -0,0 +1,40 @@
+use pyo3::prelude::{pymethods, PyResult};
+use pyo3::{PyObject, Python};
+use std::collections::HashMap;
+
+crate::lang_wrapper!(HttpRequest, baml_runtime::HttpRequest);
+
+#[pymethods]
+impl HttpRequest {
+    #[getter]
+    pub fn url(&self) -> String {
+        self.inner.url.clone()
+    }
+
+    #[getter]
+    pub fn method(&self) -> String {
+        self.inner.method.clone()
+    }
+
+    #[getter]
+    pub fn headers(&self) -> HashMap<String, String> {
+        self.inner
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    /// The JSON body, as Python dicts and lists.
+    #[getter]
+    pub fn body(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(pythonize::pythonize(py, &self.inner.body)?.into())
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "HttpRequest(method={}, url={}, headers={:?}, body={})",
+            self.inner.method, self.inner.url, self.inner.headers, self.inner.body
+        )
+    }
+}
@@ -5,7 +5,7 @@ use baml_types::{BamlMedia, BamlMediaContent, BamlMediaType, MediaBase64, MediaU
 use pyo3::{
     ffi::c_str,
     types::{PyAnyMethods, PyModule, PyType},
-    Bound, IntoPyObjectExt, PyAny, PyObject, PyResult, Python,
+    Bound, IntoPyObjectExt, PyAny, PyObject, PyResult,
 };
 use serde::{Deserialize, Serialize};
 
@@ -67,9 +67,10 @@ impl TryInto<UserFacingBamlMedia> for &BamlMedia {
 
 /// This function is used for Pydantic compatibility in three ways:
 ///
-///   - allows constructing Pydantic models containing a BamlImagePy instance
-///   - allows FastAPI requests to deserialize BamlImagePy instances in JSON format
-///   - allows serializing BamlImagePy instances in JSON format
+///   - allows constructing Pydantic models containing a media instance
+///     (BamlImagePy, BamlAudioPy, BamlPdfPy, BamlVideoPy)
+///   - allows FastAPI requests to deserialize media instances in JSON format
+///   - allows serializing media instances in JSON format
 ///
 /// Ideally this belongs in baml_py.internal_monkeypatch, so that we can get
 /// ruff-based type checking, but this depends on the pydantic libraries, so we
@@ -80,18 +81,17 @@ impl TryInto<UserFacingBamlMedia> for &BamlMedia {
 /// See docs:
 /// https://docs.pydantic.dev/latest/concepts/types/#customizing-validation-with-__get_pydantic_core_schema__
 pub fn __get_pydantic_core_schema__(
-    _cls: Bound<'_, PyType>,
+    cls: Bound<'_, PyType>,
     _source_type: Bound<'_, PyAny>,
     _handler: Bound<'_, PyAny>,
 ) -> PyResult<PyObject> {
-    Python::with_gil(|py| {
-        let code = c_str!(
-            r#"
+    let py = cls.py();
+    let code = c_str!(
+        r#"
 from pydantic_core import core_schema, SchemaValidator
 
-def deserialize(data):
-    from baml_py.baml_py import BamlImagePy
-    if isinstance(data, BamlImagePy):
+def deserialize(cls, data):
+    if isinstance(data, cls):
         return data
     else:
         SchemaValidator(
@@ -122,27 +122,25 @@ def deserialize(data):
                 }),
             ])
         ).validate_python(data)
-        return BamlImagePy.baml_deserialize(data)
+        return cls.baml_deserialize(data)
 
-def get_schema():
+def get_schema(cls):
     return core_schema.no_info_after_validator_function(
-        deserialize,
+        lambda data: deserialize(cls, data),
         core_schema.any_schema(),
         serialization=core_schema.plain_serializer_function_ser_schema(
             lambda v: v.baml_serialize(),
         )
     )
-
-ret = get_schema()
-    "#
-        );
-        PyModule::from_code(
-            py,
-            code,
-            c_str!(file!()),
-            CString::new(crate::MODULE_NAME).unwrap().as_c_str(),
-        )?
-        .getattr("ret")?
-        .into_py_any(py)
-    })
+"#
+    );
+    PyModule::from_code(
+        py,
+        code,
+        c_str!(file!()),
+        CString::new(crate::MODULE_NAME).unwrap().as_c_str(),
+    )?
+    .getattr("get_schema")?
+    .call1((cls,))?
+    .into_py_any(py)
 }
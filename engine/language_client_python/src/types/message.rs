@@ -0,0 +1,290 @@
+use std::ffi::CString;
+
+use anyhow::Result;
+use baml_types::{BamlMap, BamlMedia, BamlMediaType, BamlMessage, BamlMessagePart};
+use pyo3::exceptions::PyTypeError;
+use pyo3::ffi::c_str;
+use pyo3::prelude::{pymethods, PyAnyMethods, PyResult};
+use pyo3::types::{PyList, PyListMethods, PyModule, PyTuple, PyType};
+use pyo3::{Bound, IntoPyObjectExt, PyAny, PyObject, Python};
+use pythonize::{depythonize, pythonize};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::BamlError;
+
+use super::media_repr::UserFacingBamlMedia;
+use super::{BamlAudioPy, BamlImagePy, BamlPdfPy, BamlVideoPy};
+
+crate::lang_wrapper!(BamlMessagePy, BamlMessage);
+
+/// The JSON form of a message, the same one that test args and
+/// BAML-over-HTTP accept:
+/// `{"role": "user", "content": ["Hi", {"type": "image", "url": "..."}]}`
+#[derive(Debug, Serialize, Deserialize)]
+struct UserFacingBamlMessage {
+    role: String,
+    content: UserFacingBamlMessageContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "BamlMap::is_empty")]
+    metadata: BamlMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum UserFacingBamlMessageContent {
+    Parts(Vec<UserFacingBamlMessagePart>),
+    Part(UserFacingBamlMessagePart),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum UserFacingBamlMessagePart {
+    Text(String),
+    Media {
+        #[serde(rename = "type")]
+        media_type: String,
+        #[serde(flatten)]
+        media: UserFacingBamlMedia,
+    },
+}
+
+impl UserFacingBamlMessagePart {
+    fn into_baml_part(self) -> Result<BamlMessagePart> {
+        Ok(match self {
+            UserFacingBamlMessagePart::Text(text) => BamlMessagePart::Text(text),
+            UserFacingBamlMessagePart::Media { media_type, media } => {
+                let media_type = match media_type.as_str() {
+                    "image" => BamlMediaType::Image,
+                    "audio" => BamlMediaType::Audio,
+                    "pdf" => BamlMediaType::Pdf,
+                    "video" => BamlMediaType::Video,
+                    other => anyhow::bail!(
+                        "Invalid message part type `{other}`: expected image, audio, pdf or video"
+                    ),
+                };
+                BamlMessagePart::Media(media.into_baml_media(media_type))
+            }
+        })
+    }
+}
+
+impl UserFacingBamlMessage {
+    fn into_baml_message(self) -> Result<BamlMessage> {
+        let parts = match self.content {
+            UserFacingBamlMessageContent::Parts(parts) => parts,
+            UserFacingBamlMessageContent::Part(part) => vec![part],
+        };
+        let mut message = BamlMessage::new(
+            self.role,
+            parts
+                .into_iter()
+                .map(UserFacingBamlMessagePart::into_baml_part)
+                .collect::<Result<_>>()?,
+        );
+        message.name = self.name;
+        message.metadata = self.metadata;
+        Ok(message)
+    }
+}
+
+impl TryInto<UserFacingBamlMessage> for &BamlMessage {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<UserFacingBamlMessage> {
+        Ok(UserFacingBamlMessage {
+            role: self.role.clone(),
+            content: UserFacingBamlMessageContent::Parts(
+                self.content
+                    .iter()
+                    .map(|part| {
+                        Ok(match part {
+                            BamlMessagePart::Text(text) => {
+                                UserFacingBamlMessagePart::Text(text.clone())
+                            }
+                            BamlMessagePart::Media(media) => UserFacingBamlMessagePart::Media {
+                                media_type: media.media_type.to_string(),
+                                media: media.try_into()?,
+                            },
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            name: self.name.clone(),
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
+/// A part of `content`: a string, an `Image`, `Audio`, `Pdf` or `Video`, or
+/// the dict form of a media part.
+fn message_part(part: &Bound<'_, PyAny>) -> PyResult<BamlMessagePart> {
+    if let Ok(text) = part.extract::<String>() {
+        return Ok(BamlMessagePart::Text(text));
+    }
+    let media = if let Ok(b) = part.downcast::<BamlImagePy>() {
+        b.borrow().inner.clone()
+    } else if let Ok(b) = part.downcast::<BamlAudioPy>() {
+        b.borrow().inner.clone()
+    } else if let Ok(b) = part.downcast::<BamlPdfPy>() {
+        b.borrow().inner.clone()
+    } else if let Ok(b) = part.downcast::<BamlVideoPy>() {
+        b.borrow().inner.clone()
+    } else {
+        let part: UserFacingBamlMessagePart = depythonize(part).map_err(|_| {
+            PyTypeError::new_err(format!(
+                "Message content must be a str, Image, Audio, Pdf or Video, got {}",
+                part.get_type()
+            ))
+        })?;
+        return part.into_baml_part().map_err(BamlError::from_anyhow);
+    };
+    Ok(BamlMessagePart::Media(media))
+}
+
+fn media_into_py(py: Python<'_>, media: &BamlMedia) -> PyResult<PyObject> {
+    match media.media_type {
+        BamlMediaType::Image => BamlImagePy::from(media.clone()).into_py_any(py),
+        BamlMediaType::Audio => BamlAudioPy::from(media.clone()).into_py_any(py),
+        BamlMediaType::Pdf => BamlPdfPy::from(media.clone()).into_py_any(py),
+        BamlMediaType::Video => BamlVideoPy::from(media.clone()).into_py_any(py),
+    }
+}
+
+#[pymethods]
+impl BamlMessagePy {
+    /// `content` is a single part or a list of parts.
+    #[new]
+    #[pyo3(signature = (role, content, name = None, metadata = None))]
+    pub fn py_new(
+        role: String,
+        content: Bound<'_, PyAny>,
+        name: Option<String>,
+        metadata: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let content = match content.downcast::<PyList>() {
+            Ok(parts) => parts
+                .iter()
+                .map(|part| message_part(&part))
+                .collect::<PyResult<Vec<_>>>()?,
+            Err(_) => vec![message_part(&content)?],
+        };
+        let mut inner = BamlMessage::new(role, content);
+        inner.name = name;
+        if let Some(metadata) = metadata {
+            inner.metadata = depythonize(&metadata)?;
+        }
+        Ok(Self { inner })
+    }
+
+    #[getter]
+    pub fn role(&self) -> String {
+        self.inner.role.clone()
+    }
+
+    #[getter]
+    pub fn content(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let parts = self
+            .inner
+            .content
+            .iter()
+            .map(|part| match part {
+                BamlMessagePart::Text(text) => text.into_py_any(py),
+                BamlMessagePart::Media(media) => media_into_py(py, media),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, parts)?.into_py_any(py)
+    }
+
+    #[getter]
+    pub fn name(&self) -> Option<String> {
+        self.inner.name.clone()
+    }
+
+    #[getter]
+    pub fn metadata(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(pythonize(py, &self.inner.metadata)?.into())
+    }
+
+    /// Used for `pickle.dump`: https://docs.python.org/3/library/pickle.html#object.__getnewargs__
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        PyTuple::new(
+            py,
+            vec![
+                self.role().into_py_any(py)?,
+                self.content(py)?,
+                self.name().into_py_any(py)?,
+                self.metadata(py)?,
+            ],
+        )
+    }
+
+    pub fn __repr__(&self) -> String {
+        match &self.inner.name {
+            Some(name) => format!(
+                "BamlMessagePy(role={}, name={}, parts={})",
+                self.inner.role,
+                name,
+                self.inner.content.len()
+            ),
+            None => format!(
+                "BamlMessagePy(role={}, parts={})",
+                self.inner.role,
+                self.inner.content.len()
+            ),
+        }
+    }
+
+    /// Lets pydantic models (and FastAPI requests) contain messages, in the
+    /// same JSON form as `baml_serialize`. See `media_repr` for why this lives
+    /// here.
+    #[classmethod]
+    pub fn __get_pydantic_core_schema__(
+        cls: Bound<'_, PyType>,
+        _source_type: Bound<'_, PyAny>,
+        _handler: Bound<'_, PyAny>,
+    ) -> PyResult<PyObject> {
+        let py = cls.py();
+        let code = c_str!(
+            r#"
+from pydantic_core import core_schema
+
+def get_schema(cls):
+    return core_schema.no_info_after_validator_function(
+        lambda data: data if isinstance(data, cls) else cls.baml_deserialize(data),
+        core_schema.any_schema(),
+        serialization=core_schema.plain_serializer_function_ser_schema(
+            lambda v: v.baml_serialize(),
+        )
+    )
+"#
+        );
+        PyModule::from_code(
+            py,
+            code,
+            c_str!(file!()),
+            CString::new(crate::MODULE_NAME).unwrap().as_c_str(),
+        )?
+        .getattr("get_schema")?
+        .call1((cls,))?
+        .into_py_any(py)
+    }
+
+    #[staticmethod]
+    fn baml_deserialize(data: Bound<'_, PyAny>) -> PyResult<Self> {
+        let data: UserFacingBamlMessage = depythonize(&data)?;
+        Ok(Self {
+            inner: data.into_baml_message().map_err(BamlError::from_anyhow)?,
+        })
+    }
+
+    pub fn baml_serialize(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let s: UserFacingBamlMessage = (&self.inner).try_into().map_err(BamlError::from_anyhow)?;
+        let s = serde_json::to_value(&s).map_err(|e| BamlError::from_anyhow(e.into()))?;
+        Ok(pythonize(py, &s)?.into())
+    }
+
+    pub fn __eq__(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
@@ -5,17 +5,25 @@ pub(crate) mod audio;
 pub(crate) mod client_registry;
 pub(crate) mod function_result_stream;
 pub(crate) mod function_results;
+pub(crate) mod http_request;
 pub(crate) mod image;
 pub(super) mod media_repr;
+pub(crate) mod message;
+pub(crate) mod pdf;
 pub(crate) mod runtime_ctx_manager;
 pub(crate) mod span;
 pub(crate) mod trace_stats;
 pub(crate) mod type_builder;
+pub(crate) mod video;
 
 pub use audio::BamlAudioPy;
 pub use function_result_stream::{FunctionResultStream, SyncFunctionResultStream};
 pub use function_results::FunctionResult;
+pub use http_request::HttpRequest;
 pub use image::BamlImagePy;
+pub use message::BamlMessagePy;
+pub use pdf::BamlPdfPy;
+pub use video::BamlVideoPy;
 
 pub use runtime_ctx_manager::RuntimeContextManager;
 pub use span::BamlSpan;
@@ -21,6 +21,11 @@ impl RuntimeContextManager {
         Ok(true)
     }
 
+    #[pyo3(signature = (per_call = None, per_context = None))]
+    fn set_cost_budget(&self, per_call: Option<f64>, per_context: Option<f64>) {
+        self.inner.set_cost_budget(per_call, per_context);
+    }
+
     #[pyo3()]
     fn deep_clone(&self) -> Self {
         RuntimeContextManager {
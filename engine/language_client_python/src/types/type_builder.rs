@@ -1,5 +1,9 @@
+use std::collections::HashSet;
+
 use baml_runtime::type_builder::{self, WithMeta};
 use baml_types::BamlValue;
+
+use crate::errors::{BamlError, BamlInvalidArgumentError};
 use pyo3::{
     prelude::PyAnyMethods,
     pymethods,
@@ -22,6 +26,13 @@ crate::lang_wrapper!(
 );
 crate::lang_wrapper!(FieldType, baml_types::FieldType, sync_thread_safe);
 
+/// The root type, classes and enums added by `TypeBuilder.add_json_schema`.
+type ImportedTypes = (
+    Option<FieldType>,
+    Vec<(String, Vec<String>)>,
+    Vec<(String, Vec<String>)>,
+);
+
 impl Default for TypeBuilder {
     fn default() -> Self {
         Self::new()
@@ -109,6 +120,50 @@ impl TypeBuilder {
         }
         Ok(baml_types::FieldType::union(rs_types).into())
     }
+
+    /// Adds the classes and enums from a JSON Schema (or OpenAPI) document,
+    /// given as a JSON string. Types named like one in `taken` are renamed.
+    ///
+    /// Returns the root schema's type, if any, and the added classes and
+    /// enums with the names of their properties and values.
+    #[pyo3(signature = (schema, root_name = "Root", taken = HashSet::new()))]
+    pub fn add_json_schema(
+        &self,
+        schema: &str,
+        root_name: &str,
+        taken: HashSet<String>,
+    ) -> PyResult<ImportedTypes> {
+        let schema: serde_json::Value = serde_json::from_str(schema).map_err(|e| {
+            BamlInvalidArgumentError::new_err(format!("Invalid JSON Schema: {}", e))
+        })?;
+        let import = self
+            .inner
+            .add_json_schema(&schema, root_name, &taken)
+            .map_err(BamlError::from_anyhow)?;
+        Ok((
+            import.root.map(FieldType::from),
+            import
+                .classes
+                .iter()
+                .map(|(name, c)| {
+                    (
+                        name.clone(),
+                        c.fields.iter().map(|f| f.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+            import
+                .enums
+                .iter()
+                .map(|(name, e)| {
+                    (
+                        name.clone(),
+                        e.values.iter().map(|v| v.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+        ))
+    }
 }
 
 #[pymethods]
@@ -31,6 +31,22 @@ impl FunctionResult {
         }
     }
 
+    fn cost(&self) -> Option<f64> {
+        self.inner.cost()
+    }
+
+    fn usage(&self) -> Result<Value> {
+        serde_magnus::serialize(&self.inner.usage())
+    }
+
+    fn variant(&self) -> Option<String> {
+        self.inner.variant().map(|v| v.to_string())
+    }
+
+    fn parse_report(&self) -> Result<Value> {
+        serde_magnus::serialize(&self.inner.parse_report())
+    }
+
     pub fn parsed_using_types(
         ruby: &Ruby,
         rb_self: &FunctionResult,
@@ -61,6 +77,10 @@ impl FunctionResult {
             "parsed_using_types",
             method!(FunctionResult::parsed_using_types, 1),
         )?;
+        cls.define_method("cost", method!(FunctionResult::cost, 0))?;
+        cls.define_method("usage", method!(FunctionResult::usage, 0))?;
+        cls.define_method("variant", method!(FunctionResult::variant, 0))?;
+        cls.define_method("parse_report", method!(FunctionResult::parse_report, 0))?;
 
         Ok(())
     }
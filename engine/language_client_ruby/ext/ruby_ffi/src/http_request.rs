@@ -0,0 +1,42 @@
+use magnus::{class, method, prelude::*, value::Value, RModule};
+
+use crate::Result;
+
+#[magnus::wrap(class = "Baml::Ffi::HttpRequest", free_immediately, size)]
+pub struct HttpRequest {
+    inner: baml_runtime::HttpRequest,
+}
+
+impl HttpRequest {
+    pub fn new(inner: baml_runtime::HttpRequest) -> Self {
+        Self { inner }
+    }
+
+    fn url(&self) -> String {
+        self.inner.url.clone()
+    }
+
+    fn method(&self) -> String {
+        self.inner.method.clone()
+    }
+
+    fn headers(&self) -> Result<Value> {
+        serde_magnus::serialize(&self.inner.headers)
+    }
+
+    fn body(&self) -> Result<Value> {
+        serde_magnus::serialize(&self.inner.body)
+    }
+
+    /// For usage in magnus::init
+    pub fn define_in_ruby(module: &RModule) -> Result<()> {
+        let cls = module.define_class("HttpRequest", class::object())?;
+
+        cls.define_method("url", method!(HttpRequest::url, 0))?;
+        cls.define_method("method", method!(HttpRequest::method, 0))?;
+        cls.define_method("headers", method!(HttpRequest::headers, 0))?;
+        cls.define_method("body", method!(HttpRequest::body, 0))?;
+
+        Ok(())
+    }
+}
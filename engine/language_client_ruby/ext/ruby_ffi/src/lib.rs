@@ -1,6 +1,6 @@
 use baml_runtime::BamlRuntime;
 use baml_types::BamlValue;
-use magnus::{class, function, method, prelude::*, Error, RHash, Ruby};
+use magnus::{class, function, method, prelude::*, value::Value, Error, RHash, RModule, Ruby};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -8,10 +8,12 @@ use tracing_subscriber::EnvFilter;
 
 use function_result::FunctionResult;
 use function_result_stream::FunctionResultStream;
+use http_request::HttpRequest;
 use types::runtime_ctx_manager::RuntimeContextManager;
 
 mod function_result;
 mod function_result_stream;
+mod http_request;
 mod ruby_to_json;
 mod types;
 
@@ -183,6 +185,89 @@ impl BamlRuntimeFfi {
 
         retval
     }
+
+    fn build_request(
+        ruby: &Ruby,
+        rb_self: &BamlRuntimeFfi,
+        function_name: String,
+        args: RHash,
+        ctx: &RuntimeContextManager,
+        type_registry: Option<&types::type_builder::TypeBuilder>,
+        client_registry: Option<&types::client_registry::ClientRegistry>,
+        stream: bool,
+    ) -> Result<HttpRequest> {
+        let args = match ruby_to_json::RubyToJson::convert_hash_to_json(args) {
+            Ok(args) => args.into_iter().collect(),
+            Err(e) => {
+                return Err(Error::new(
+                    ruby.exception_syntax_error(),
+                    format!("error while parsing build_request args:\n{}", e),
+                ));
+            }
+        };
+
+        match rb_self.t.block_on(rb_self.inner.build_request(
+            &function_name,
+            &args,
+            &ctx.inner,
+            type_registry.map(|t| &t.inner),
+            client_registry.map(|c| c.inner.borrow_mut()).as_deref(),
+            stream,
+        )) {
+            Ok(request) => Ok(HttpRequest::new(request)),
+            Err(e) => Err(Error::new(
+                ruby.exception_runtime_error(),
+                format!(
+                    "{:?}",
+                    e.context(format!("error while building request for {function_name}"))
+                ),
+            )),
+        }
+    }
+
+    /// Parses `llm_response` into the output type of `function_name`. Pass
+    /// `Baml::PartialTypes` as `types` when `allow_partials` is set.
+    fn parse_llm_response(
+        ruby: &Ruby,
+        rb_self: &BamlRuntimeFfi,
+        function_name: String,
+        llm_response: String,
+        types: RModule,
+        ctx: &RuntimeContextManager,
+        type_registry: Option<&types::type_builder::TypeBuilder>,
+        client_registry: Option<&types::client_registry::ClientRegistry>,
+        allow_partials: bool,
+    ) -> Result<Value> {
+        let client_registry = client_registry.map(|c| c.inner.borrow_mut());
+        let parsed = if allow_partials {
+            rb_self.inner.parse_llm_response_partial(
+                &function_name,
+                &llm_response,
+                &ctx.inner,
+                type_registry.map(|t| &t.inner),
+                client_registry.as_deref(),
+            )
+        } else {
+            rb_self.inner.parse_llm_response(
+                &function_name,
+                &llm_response,
+                &ctx.inner,
+                type_registry.map(|t| &t.inner),
+                client_registry.as_deref(),
+            )
+        };
+
+        match parsed {
+            Ok(parsed) => ruby_to_json::RubyToJson::serialize_baml(ruby, types, parsed),
+            Err(e) => Err(Error::new(
+                ruby.exception_runtime_error(),
+                format!(
+                    "{:?}",
+                    e.context(format!("error while parsing response for {function_name}"))
+                ),
+            )),
+        }
+    }
 }
 
 fn invoke_runtime_cli(ruby: &Ruby, argv0: String, argv: Vec<String>) -> Result<()> {
@@ -256,9 +341,15 @@ fn init(ruby: &Ruby) -> Result<()> {
         "stream_function",
         method!(BamlRuntimeFfi::stream_function, 5),
     )?;
+    runtime_class.define_method("build_request", method!(BamlRuntimeFfi::build_request, 6))?;
+    runtime_class.define_method(
+        "parse_llm_response",
+        method!(BamlRuntimeFfi::parse_llm_response, 7),
+    )?;
 
     FunctionResult::define_in_ruby(&module)?;
     FunctionResultStream::define_in_ruby(&module)?;
+    HttpRequest::define_in_ruby(&module)?;
 
     RuntimeContextManager::define_in_ruby(&module)?;
 
@@ -272,6 +363,9 @@ fn init(ruby: &Ruby) -> Result<()> {
     types::client_registry::ClientRegistry::define_in_ruby(&module)?;
     types::media::Audio::define_in_ruby(&module)?;
     types::media::Image::define_in_ruby(&module)?;
+    types::media::Pdf::define_in_ruby(&module)?;
+    types::media::Video::define_in_ruby(&module)?;
+    types::message::Message::define_in_ruby(&module)?;
 
     // everything below this is for our own testing purposes
     module.define_module_function(
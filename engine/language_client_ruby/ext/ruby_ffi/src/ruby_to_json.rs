@@ -1,4 +1,6 @@
-use baml_types::{BamlMap, BamlValue, BamlValueWithMeta, ResponseCheck};
+use baml_types::{
+    BamlMap, BamlTemporal, BamlTemporalType, BamlValue, BamlValueWithMeta, ResponseCheck,
+};
 use indexmap::IndexMap;
 use magnus::{
     prelude::*, typed_data::Obj, value::Value, Error, Float, Integer, IntoValue, RArray, RClass,
@@ -8,7 +10,8 @@ use std::result::Result;
 
 use crate::types::{
     self,
-    media::{Audio, Image},
+    media::{Audio, Image, Pdf, Video},
+    message::Message,
 };
 
 struct SerializationError {
@@ -120,6 +123,21 @@ impl<'rb> RubyToJson<'rb> {
                     }
                     Ok(arr.into_value_with(ruby))
                 }
+                BamlValueWithMeta::Temporal(t, _) => match t {
+                    BamlTemporal::Date(_) => {
+                        ruby.require("date")?;
+                        ruby.eval::<RClass>("Date")?
+                            .funcall("iso8601", (t.to_iso8601(),))
+                    }
+                    BamlTemporal::DateTime(_) => {
+                        ruby.require("time")?;
+                        ruby.eval::<RClass>("Time")?
+                            .funcall("iso8601", (t.to_iso8601(),))
+                    }
+                    BamlTemporal::Duration(d) => {
+                        Ok((d.num_milliseconds() as f64 / 1000.0).into_value_with(ruby))
+                    }
+                },
                 _ => serde_magnus::serialize(&from),
             }
         }
@@ -236,10 +254,26 @@ impl<'rb> RubyToJson<'rb> {
             return self.to_type::<Audio>(any, field_pos);
         }
 
+        if self.is_type::<Pdf>(any) {
+            return self.to_type::<Pdf>(any, field_pos);
+        }
+
+        if self.is_type::<Video>(any) {
+            return self.to_type::<Video>(any, field_pos);
+        }
+
+        if let Some(temporal) = self.to_temporal(any, &field_pos)? {
+            return Ok(BamlValue::Temporal(temporal));
+        }
+
         if self.is_type::<Image>(any) {
             return self.to_type::<Image>(any, field_pos);
         }
 
+        if self.is_type::<Message>(any) {
+            return self.to_type::<Message>(any, field_pos);
+        }
+
         Err(vec![SerializationError {
             position: field_pos,
             message: format!(
@@ -249,6 +283,39 @@ impl<'rb> RubyToJson<'rb> {
         }])
     }
 
+    /// Converts `Time`, `DateTime` and `Date` instances.
+    fn to_temporal(
+        &self,
+        any: Value,
+        field_pos: &[String],
+    ) -> Result<Option<BamlTemporal>, Vec<SerializationError>> {
+        let class_name = unsafe { any.class().name() }.into_owned();
+        let (temporal_type, iso) = match class_name.as_str() {
+            "Time" => (
+                BamlTemporalType::DateTime,
+                any.funcall::<_, _, String>("strftime", ("%Y-%m-%dT%H:%M:%S.%N%:z",)),
+            ),
+            "DateTime" => (
+                BamlTemporalType::DateTime,
+                any.funcall::<_, _, String>("iso8601", ()),
+            ),
+            "Date" => (
+                BamlTemporalType::Date,
+                any.funcall::<_, _, String>("iso8601", ()),
+            ),
+            _ => return Ok(None),
+        };
+        iso.map_err(|e| e.to_string())
+            .and_then(|iso| BamlTemporal::parse(temporal_type, &iso).map_err(|e| e.to_string()))
+            .map(Some)
+            .map_err(|message| {
+                vec![SerializationError {
+                    position: field_pos.to_vec(),
+                    message,
+                }]
+            })
+    }
+
     fn to_int(
         &self,
         any: Integer,
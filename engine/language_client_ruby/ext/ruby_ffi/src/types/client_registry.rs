@@ -57,6 +57,10 @@ impl ClientRegistry {
         self.inner.borrow_mut().set_primary(primary);
     }
 
+    pub fn set_variant(&self, variant: String) {
+        self.inner.borrow_mut().set_variant(variant);
+    }
+
     pub fn define_in_ruby(module: &magnus::RModule) -> Result<()> {
         let cls = module.define_class("ClientRegistry", class::object())?;
 
@@ -66,6 +70,7 @@ impl ClientRegistry {
             method!(ClientRegistry::add_llm_client, -1),
         )?;
         cls.define_method("set_primary", method!(ClientRegistry::set_primary, 1))?;
+        cls.define_method("set_variant", method!(ClientRegistry::set_variant, 1))?;
 
         Ok(())
     }
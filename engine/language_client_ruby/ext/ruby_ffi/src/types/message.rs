@@ -0,0 +1,84 @@
+use baml_types::{BamlMessage, BamlMessagePart, BamlValue};
+use magnus::{
+    class, function, method, scan_args::scan_args, Error, Module, Object, RHash, RModule, Ruby,
+    Value,
+};
+
+use super::media::CloneAsBamlValue;
+use crate::ruby_to_json::RubyToJson;
+use crate::Result;
+
+#[magnus::wrap(class = "Baml::Ffi::Message", free_immediately, size)]
+pub(crate) struct Message {
+    pub(crate) inner: BamlMessage,
+}
+
+impl Message {
+    /// `Baml::Message.new(role, content, name = nil, metadata = nil)`, where
+    /// `content` is a single part or an array of parts.
+    pub fn new(ruby: &Ruby, args: &[Value]) -> Result<Self> {
+        let args = scan_args::<_, _, (), (), (), ()>(args)?;
+        let (role, content): (String, Value) = args.required;
+        let (name, metadata): (Option<String>, Option<RHash>) = args.optional;
+
+        let content = match RubyToJson::convert(content)? {
+            BamlValue::List(parts) => parts
+                .into_iter()
+                .map(|part| Self::message_part(ruby, part))
+                .collect::<Result<Vec<_>>>()?,
+            part => vec![Self::message_part(ruby, part)?],
+        };
+
+        let mut inner = BamlMessage::new(role, content);
+        inner.name = name;
+        if let Some(metadata) = metadata {
+            for (key, value) in RubyToJson::convert_hash_to_json(metadata)? {
+                let value = serde_json::to_value(&value).map_err(|e| {
+                    Error::new(
+                        ruby.exception_type_error(),
+                        format!("Invalid metadata: {e}"),
+                    )
+                })?;
+                inner.metadata.insert(key, value);
+            }
+        }
+        Ok(Self { inner })
+    }
+
+    fn message_part(ruby: &Ruby, value: BamlValue) -> Result<BamlMessagePart> {
+        match value {
+            BamlValue::String(text) => Ok(BamlMessagePart::Text(text)),
+            BamlValue::Media(media) => Ok(BamlMessagePart::Media(media)),
+            other => Err(Error::new(
+                ruby.exception_type_error(),
+                format!(
+                    "Message content must be a String, Baml::Image, Baml::Audio, Baml::Pdf or Baml::Video, got {}",
+                    other.r#type()
+                ),
+            )),
+        }
+    }
+
+    pub fn role(&self) -> String {
+        self.inner.role.clone()
+    }
+
+    pub fn name(&self) -> Option<String> {
+        self.inner.name.clone()
+    }
+
+    pub fn define_in_ruby(module: &RModule) -> Result<()> {
+        let cls = module.define_class("Message", class::object())?;
+        cls.define_singleton_method("new", function!(Message::new, -1))?;
+        cls.define_method("role", method!(Message::role, 0))?;
+        cls.define_method("name", method!(Message::name, 0))?;
+
+        Ok(())
+    }
+}
+
+impl CloneAsBamlValue for Message {
+    fn clone_as_baml_value(&self) -> BamlValue {
+        BamlValue::Message(self.inner.clone())
+    }
+}
@@ -1,5 +1,6 @@
 pub(crate) mod client_registry;
 mod lang_wrapper;
 pub(crate) mod media;
+pub(crate) mod message;
 pub(crate) mod runtime_ctx_manager;
 pub(crate) mod type_builder;
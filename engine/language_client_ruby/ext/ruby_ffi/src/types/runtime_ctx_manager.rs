@@ -1,4 +1,4 @@
-use magnus::{class, prelude::*, RModule};
+use magnus::{class, method, prelude::*, RModule};
 
 use crate::Result;
 
@@ -7,8 +7,17 @@ pub struct RuntimeContextManager {
     pub inner: baml_runtime::RuntimeContextManager,
 }
 impl RuntimeContextManager {
+    fn set_cost_budget(&self, per_call: Option<f64>, per_context: Option<f64>) {
+        self.inner.set_cost_budget(per_call, per_context);
+    }
+
     pub fn define_in_ruby(module: &RModule) -> Result<()> {
-        module.define_class("RuntimeContextManager", class::object())?;
+        let cls = module.define_class("RuntimeContextManager", class::object())?;
+
+        cls.define_method(
+            "set_cost_budget",
+            method!(RuntimeContextManager::set_cost_budget, 2),
+        )?;
 
         //cls.define_method("upsert_tags", method!(RuntimeContextManager::upsert_tags, 1))?;
         //cls.define_method("deep_clone", method!(RuntimeContextManager::deep_clone, 0))?;
@@ -1,9 +1,11 @@
+use std::collections::HashSet;
+
 use crate::Result;
 use baml_runtime::type_builder::{self, WithMeta};
 use baml_types::BamlValue;
 use magnus::{
-    class, function, method, scan_args::scan_args, try_convert::TryConvertOwned, Module, Object,
-    RModule, Value,
+    class, function, method, scan_args::scan_args, try_convert::TryConvertOwned, Error, Module,
+    Object, RModule, Ruby, Value,
 };
 
 #[magnus::wrap(class = "Baml::Ffi::TypeBuilder", free_immediately, size)]
@@ -32,6 +34,13 @@ crate::lang_wrapper!(
     sync_thread_safe
 );
 
+/// The root type, classes and enums added by `TypeBuilder#add_json_schema`.
+type ImportedTypes = (
+    Option<FieldType>,
+    Vec<(String, Vec<String>)>,
+    Vec<(String, Vec<String>)>,
+);
+
 impl TypeBuilder {
     pub fn new() -> Self {
         Self {
@@ -113,6 +122,51 @@ impl TypeBuilder {
         .into())
     }
 
+    /// Returns the root schema's type, if any, and the added classes and enums
+    /// with the names of their properties and values.
+    pub fn add_json_schema(
+        ruby: &Ruby,
+        rb_self: &Self,
+        schema: String,
+        root_name: String,
+        taken: Vec<String>,
+    ) -> Result<ImportedTypes> {
+        let schema: serde_json::Value = serde_json::from_str(&schema).map_err(|e| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!("Invalid JSON Schema: {}", e),
+            )
+        })?;
+        let taken = taken.into_iter().collect::<HashSet<_>>();
+        let import = rb_self
+            .inner
+            .add_json_schema(&schema, &root_name, &taken)
+            .map_err(|e| Error::new(ruby.exception_runtime_error(), format!("{:?}", e)))?;
+        Ok((
+            import.root.map(FieldType::from),
+            import
+                .classes
+                .iter()
+                .map(|(name, c)| {
+                    (
+                        name.clone(),
+                        c.fields.iter().map(|f| f.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+            import
+                .enums
+                .iter()
+                .map(|(name, e)| {
+                    (
+                        name.clone(),
+                        e.values.iter().map(|v| v.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
     pub fn define_in_ruby(module: &RModule) -> Result<()> {
         let cls = module.define_class("TypeBuilder", class::object())?;
 
@@ -132,6 +186,7 @@ impl TypeBuilder {
         cls.define_method("literal_string", method!(TypeBuilder::literal_string, 1))?;
         cls.define_method("literal_int", method!(TypeBuilder::literal_int, 1))?;
         cls.define_method("literal_bool", method!(TypeBuilder::literal_bool, 1))?;
+        cls.define_method("add_json_schema", method!(TypeBuilder::add_json_schema, 3))?;
 
         Ok(())
     }
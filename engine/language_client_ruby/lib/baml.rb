@@ -8,11 +8,15 @@ end
 require_relative "stream"
 require_relative "struct"
 require_relative "checked"
+require_relative "stream_state"
 
 module Baml
   ClientRegistry = Baml::Ffi::ClientRegistry
   Image = Baml::Ffi::Image
   Audio = Baml::Ffi::Audio
+  Pdf = Baml::Ffi::Pdf
+  Video = Baml::Ffi::Video
+  Message = Baml::Ffi::Message
 
   # Reexport Checked types.
   Checked = Baml::Checks::Checked
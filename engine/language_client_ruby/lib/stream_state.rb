@@ -0,0 +1,21 @@
+require "sorbet-runtime"
+
+module Baml
+  # A streamed value together with how much of it has been received:
+  # "Pending", "Incomplete" or "Complete".
+  class StreamState < T::Struct
+    extend T::Sig
+
+    extend T::Generic
+
+    Value = type_member
+
+    const :value, Value
+    const :state, String
+
+    def initialize(props)
+      super(value: props[:value], state: props[:state])
+    end
+
+  end
+end
@@ -1,4 +1,4 @@
-export { BamlRuntime, FunctionResult, FunctionResultStream, BamlImage as Image, ClientBuilder, BamlAudio as Audio, invoke_runtime_cli, ClientRegistry, BamlLogEvent, } from "./native";
+export { BamlRuntime, FunctionResult, FunctionResultStream, HttpRequest, BamlImage as Image, ClientBuilder, BamlAudio as Audio, BamlPdf as Pdf, BamlVideo as Video, BamlMessage as Message, invoke_runtime_cli, ClientRegistry, BamlLogEvent, } from "./native";
 export { BamlStream } from "./stream";
 export { BamlCtxManager } from "./async_context_vars";
 export declare class BamlClientFinishReasonError extends Error {
@@ -1,13 +1,17 @@
 "use strict";
 Object.defineProperty(exports, "__esModule", { value: true });
-exports.createBamlValidationError = exports.BamlValidationError = exports.BamlClientFinishReasonError = exports.BamlCtxManager = exports.BamlStream = exports.BamlLogEvent = exports.ClientRegistry = exports.invoke_runtime_cli = exports.Audio = exports.ClientBuilder = exports.Image = exports.FunctionResultStream = exports.FunctionResult = exports.BamlRuntime = void 0;
+exports.createBamlValidationError = exports.BamlValidationError = exports.BamlClientFinishReasonError = exports.BamlCtxManager = exports.BamlStream = exports.BamlLogEvent = exports.ClientRegistry = exports.invoke_runtime_cli = exports.Message = exports.Video = exports.Pdf = exports.Audio = exports.ClientBuilder = exports.Image = exports.HttpRequest = exports.FunctionResultStream = exports.FunctionResult = exports.BamlRuntime = void 0;
 var native_1 = require("./native");
 Object.defineProperty(exports, "BamlRuntime", { enumerable: true, get: function () { return native_1.BamlRuntime; } });
 Object.defineProperty(exports, "FunctionResult", { enumerable: true, get: function () { return native_1.FunctionResult; } });
 Object.defineProperty(exports, "FunctionResultStream", { enumerable: true, get: function () { return native_1.FunctionResultStream; } });
+Object.defineProperty(exports, "HttpRequest", { enumerable: true, get: function () { return native_1.HttpRequest; } });
 Object.defineProperty(exports, "Image", { enumerable: true, get: function () { return native_1.BamlImage; } });
 Object.defineProperty(exports, "ClientBuilder", { enumerable: true, get: function () { return native_1.ClientBuilder; } });
 Object.defineProperty(exports, "Audio", { enumerable: true, get: function () { return native_1.BamlAudio; } });
+Object.defineProperty(exports, "Pdf", { enumerable: true, get: function () { return native_1.BamlPdf; } });
+Object.defineProperty(exports, "Video", { enumerable: true, get: function () { return native_1.BamlVideo; } });
+Object.defineProperty(exports, "Message", { enumerable: true, get: function () { return native_1.BamlMessage; } });
 Object.defineProperty(exports, "invoke_runtime_cli", { enumerable: true, get: function () { return native_1.invoke_runtime_cli; } });
 Object.defineProperty(exports, "ClientRegistry", { enumerable: true, get: function () { return native_1.ClientRegistry; } });
 Object.defineProperty(exports, "BamlLogEvent", { enumerable: true, get: function () { return native_1.BamlLogEvent; } });
@@ -18,6 +18,23 @@ export declare class BamlImage {
   toJSON(): any
 }
 
+export declare class BamlMessage {
+  /** `content` is a single part or a list of parts. */
+  static create(role: string, content: string | BamlImage | BamlAudio | BamlPdf | BamlVideo | Array<string | BamlImage | BamlAudio | BamlPdf | BamlVideo>, name?: string | undefined | null, metadata?: Record<string, any> | undefined | null): BamlMessage
+  get role(): string
+  get name(): string | null
+  toJSON(): any
+}
+
+export declare class BamlPdf {
+  static fromUrl(url: string): BamlPdf
+  static fromBase64(mediaType: string, base64: string): BamlPdf
+  isUrl(): boolean
+  asUrl(): string
+  asBase64(): [string, string]
+  toJSON(): any
+}
+
 export declare class BamlRuntime {
   static fromDirectory(directory: string, envVars: Record<string, string>): BamlRuntime
   static fromFiles(rootPath: string, files: Record<string, string>, envVars: Record<string, string>): BamlRuntime
@@ -25,6 +42,9 @@ export declare class BamlRuntime {
   createContextManager(): RuntimeContextManager
   callFunction(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null): Promise<FunctionResult>
   callFunctionSync(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null): FunctionResult
+  buildRequest(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb: TypeBuilder | undefined | null, cb: ClientRegistry | undefined | null, stream: boolean): Promise<HttpRequest>
+  buildRequestSync(functionName: string, args: { [string]: any }, ctx: RuntimeContextManager, tb: TypeBuilder | undefined | null, cb: ClientRegistry | undefined | null, stream: boolean): HttpRequest
+  parseLlmResponse(functionName: string, llmResponse: string, ctx: RuntimeContextManager, tb: TypeBuilder | undefined | null, cb: ClientRegistry | undefined | null, allowPartials: boolean): any
   streamFunction(functionName: string, args: { [string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null): FunctionResultStream
   streamFunctionSync(functionName: string, args: { [string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null): FunctionResultStream
   setLogEventCallback(func?: undefined | ((err: any, param: BamlLogEvent) => void)): void
@@ -37,6 +57,15 @@ export declare class BamlSpan {
   finish(result: any, ctx: RuntimeContextManager): any
 }
 
+export declare class BamlVideo {
+  static fromUrl(url: string): BamlVideo
+  static fromBase64(mediaType: string, base64: string): BamlVideo
+  isUrl(): boolean
+  asUrl(): string
+  asBase64(): [string, string]
+  toJSON(): any
+}
+
 export declare class ClassBuilder {
   field(): FieldType
   property(name: string): ClassPropertyBuilder
@@ -52,6 +81,7 @@ export declare class ClientRegistry {
   constructor()
   addLlmClient(name: string, provider: string, options: { [string]: any }, retryPolicy?: string | undefined | null): void
   setPrimary(primary: string): void
+  setVariant(variant: string): void
 }
 
 export declare class EnumBuilder {
@@ -73,6 +103,10 @@ export declare class FieldType {
 
 export declare class FunctionResult {
   isOk(): boolean
+  cost(): number | null
+  usage(): { input_tokens: number | null; output_tokens: number | null; cache_creation_input_tokens: number | null; cache_read_input_tokens: number | null }
+  variant(): string | null
+  parseReport(): { score: number; entries: { path: string; flags: string[]; score: number }[] } | null
   parsed(): any
 }
 
@@ -81,8 +115,26 @@ export declare class FunctionResultStream {
   done(rctx: RuntimeContextManager): Promise<FunctionResult>
 }
 
+export declare class HttpRequest {
+  get url(): string
+  get method(): string
+  get headers(): Record<string, string>
+  get body(): any
+}
+
+/** The types added by `TypeBuilder.addJsonSchema`. */
+export declare class ImportedTypes {
+  /** The type of the root schema, if the document describes one. */
+  get root(): FieldType | null
+  /** The names of the added classes and of their properties. */
+  get classes(): Record<string, Array<string>>
+  /** The names of the added enums and of their values. */
+  get enums(): Record<string, Array<string>>
+}
+
 export declare class RuntimeContextManager {
   upsertTags(tags: any): void
+  setCostBudget(perCall?: number | undefined | null, perContext?: number | undefined | null): void
   deepClone(): RuntimeContextManager
   contextDepth(): number
 }
@@ -113,6 +165,11 @@ export declare class TypeBuilder {
   null(): FieldType
   map(key: FieldType, value: FieldType): FieldType
   union(types: Array<FieldType>): FieldType
+  /**
+   * Adds the classes and enums from a JSON Schema (or OpenAPI) document,
+   * given as a JSON string. Types named like one in `taken` are renamed.
+   */
+  addJsonSchema(schema: string, rootName: string, taken: Array<string>): ImportedTypes
 }
 
 export interface BamlLogEvent {
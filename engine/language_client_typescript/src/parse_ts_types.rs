@@ -1,5 +1,6 @@
 use baml_types::BamlMap;
 use baml_types::BamlValue;
+use baml_types::{BamlTemporal, BamlTemporalType};
 use napi::bindgen_prelude::*;
 use napi::JsBoolean;
 
@@ -13,6 +14,9 @@ use napi::NapiRaw;
 
 use crate::types::audio::BamlAudio;
 use crate::types::image::BamlImage;
+use crate::types::message::BamlMessage;
+use crate::types::pdf::BamlPdf;
+use crate::types::video::BamlVideo;
 
 struct SerializationError {
     position: Vec<String>,
@@ -106,8 +110,9 @@ pub fn js_object_to_baml_value(env: Env, kwargs: JsObject) -> napi::Result<BamlV
     } else if kwargs.is_date()? {
         let date: JsDate = unsafe { kwargs.into_unknown().cast() };
         let timestamp = date.value_of()?;
-        // TODO: Convert timestamp to a DateTime
-        Ok(BamlValue::Float(timestamp))
+        BamlTemporal::from_number(BamlTemporalType::DateTime, timestamp / 1000.0)
+            .map(BamlValue::Temporal)
+            .map_err(|e| napi::Error::from_reason(e.to_string()))
     } else {
         let mut args = BamlMap::new();
 
@@ -197,6 +202,12 @@ pub fn jsunknown_to_baml_value(
                 BamlValue::Media(img.inner.clone())
             } else if let Ok(audio) = env.get_value_external::<BamlAudio>(&external) {
                 BamlValue::Media(audio.inner.clone())
+            } else if let Ok(pdf) = env.get_value_external::<BamlPdf>(&external) {
+                BamlValue::Media(pdf.inner.clone())
+            } else if let Ok(video) = env.get_value_external::<BamlVideo>(&external) {
+                BamlValue::Media(video.inner.clone())
+            } else if let Ok(message) = env.get_value_external::<BamlMessage>(&external) {
+                BamlValue::Message(message.inner.clone())
             } else {
                 if skip_unsupported {
                     return Ok(None);
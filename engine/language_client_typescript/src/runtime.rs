@@ -2,7 +2,8 @@ use crate::errors::{from_anyhow_error, invalid_argument_error};
 use crate::parse_ts_types;
 use crate::types::client_registry::ClientRegistry;
 use crate::types::function_result_stream::FunctionResultStream;
-use crate::types::function_results::FunctionResult;
+use crate::types::function_results::{to_js_value, FunctionResult};
+use crate::types::http_request::HttpRequest;
 use crate::types::runtime_ctx_manager::RuntimeContextManager;
 use crate::types::trace_stats::TraceStats;
 use crate::types::type_builder::TypeBuilder;
@@ -14,7 +15,7 @@ use napi::bindgen_prelude::ObjectFinalize;
 use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunctionCallMode};
 use napi::JsFunction;
 use napi::JsObject;
-use napi::{Env, JsUndefined};
+use napi::{Env, JsUndefined, JsUnknown};
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -163,6 +164,119 @@ impl BamlRuntime {
         result.map(FunctionResult::from).map_err(from_anyhow_error)
     }
 
+    #[napi(ts_return_type = "Promise<HttpRequest>")]
+    pub fn build_request(
+        &self,
+        env: Env,
+        function_name: String,
+        #[napi(ts_arg_type = "{ [string]: any }")] args: JsObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> napi::Result<JsObject> {
+        let args = parse_ts_types::js_object_to_baml_value(env, args)?;
+
+        if !args.is_map() {
+            return Err(invalid_argument_error(&format!(
+                "Expected a map of arguments, got: {}",
+                args.r#type()
+            )));
+        }
+        let args_map = args.as_map_owned().unwrap();
+
+        let baml_runtime = self.inner.clone();
+        let ctx_mng = ctx.inner.clone();
+        let tb = tb.map(|tb| tb.inner.clone());
+        let cb = cb.map(|cb| cb.inner.clone());
+
+        let fut = async move {
+            baml_runtime
+                .build_request(
+                    &function_name,
+                    &args_map,
+                    &ctx_mng,
+                    tb.as_ref(),
+                    cb.as_ref(),
+                    stream,
+                )
+                .await
+                .map(HttpRequest::from)
+                .map_err(from_anyhow_error)
+        };
+
+        env.execute_tokio_future(fut, |&mut _, data| Ok(data))
+    }
+
+    #[napi]
+    pub fn build_request_sync(
+        &self,
+        env: Env,
+        function_name: String,
+        #[napi(ts_arg_type = "{ [string]: any }")] args: JsObject,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        stream: bool,
+    ) -> napi::Result<HttpRequest> {
+        let args = parse_ts_types::js_object_to_baml_value(env, args)?;
+
+        if !args.is_map() {
+            return Err(invalid_argument_error(&format!(
+                "Expected a map of arguments, got: {}",
+                args.r#type()
+            )));
+        }
+        let args_map = args.as_map_owned().unwrap();
+
+        self.inner
+            .build_request_sync(
+                &function_name,
+                &args_map,
+                &ctx.inner,
+                tb.map(|tb| tb.inner.clone()).as_ref(),
+                cb.map(|cb| cb.inner.clone()).as_ref(),
+                stream,
+            )
+            .map(HttpRequest::from)
+            .map_err(from_anyhow_error)
+    }
+
+    #[napi(ts_return_type = "any")]
+    pub fn parse_llm_response(
+        &self,
+        env: Env,
+        function_name: String,
+        llm_response: String,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+        cb: Option<&ClientRegistry>,
+        allow_partials: bool,
+    ) -> napi::Result<JsUnknown> {
+        let tb = tb.map(|tb| tb.inner.clone());
+        let cb = cb.map(|cb| cb.inner.clone());
+        let parsed = if allow_partials {
+            self.inner.parse_llm_response_partial(
+                &function_name,
+                &llm_response,
+                &ctx.inner,
+                tb.as_ref(),
+                cb.as_ref(),
+            )
+        } else {
+            self.inner.parse_llm_response(
+                &function_name,
+                &llm_response,
+                &ctx.inner,
+                tb.as_ref(),
+                cb.as_ref(),
+            )
+        }
+        .map_err(from_anyhow_error)?;
+
+        to_js_value(env, &parsed)
+    }
+
     #[napi]
     pub fn stream_function(
         &self,
@@ -65,4 +65,9 @@ impl ClientRegistry {
     pub fn set_primary(&mut self, primary: String) {
         self.inner.set_primary(primary);
     }
+
+    #[napi]
+    pub fn set_variant(&mut self, variant: String) {
+        self.inner.set_variant(variant);
+    }
 }
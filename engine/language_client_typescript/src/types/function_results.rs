@@ -1,3 +1,8 @@
+use std::collections::HashMap;
+
+use baml_types::{BamlTemporal, BamlTemporalType, BamlValueWithMeta, ResponseCheck};
+use napi::bindgen_prelude::*;
+use napi::JsUnknown;
 use napi_derive::napi;
 
 use crate::errors::from_anyhow_error;
@@ -16,12 +21,107 @@ impl FunctionResult {
     }
 
     #[napi]
-    pub fn parsed(&self) -> napi::Result<serde_json::Value> {
+    pub fn cost(&self) -> Option<f64> {
+        self.inner.cost()
+    }
+
+    #[napi(
+        ts_return_type = "{ input_tokens: number | null; output_tokens: number | null; cache_creation_input_tokens: number | null; cache_read_input_tokens: number | null }"
+    )]
+    pub fn usage(&self, env: Env) -> napi::Result<JsUnknown> {
+        env.to_js_value(&self.inner.usage())
+    }
+
+    #[napi]
+    pub fn variant(&self) -> Option<String> {
+        self.inner.variant().map(|v| v.to_string())
+    }
+
+    #[napi(
+        ts_return_type = "{ score: number; entries: { path: string; flags: string[]; score: number }[] } | null"
+    )]
+    pub fn parse_report(&self, env: Env) -> napi::Result<JsUnknown> {
+        env.to_js_value(&self.inner.parse_report())
+    }
+
+    #[napi(ts_return_type = "any")]
+    pub fn parsed(&self, env: Env) -> napi::Result<JsUnknown> {
         let parsed = self
             .inner
             .result_with_constraints_content()
             .map_err(from_anyhow_error)?;
 
-        Ok(serde_json::to_value(parsed)?)
+        to_js_value(env, parsed)
     }
 }
+
+/// Converts a parsed value the same way its JSON serialization would, except
+/// that dates and datetimes become `Date`s and durations become seconds.
+pub(crate) fn to_js_value(
+    env: Env,
+    value: &BamlValueWithMeta<Vec<ResponseCheck>>,
+) -> napi::Result<JsUnknown> {
+    if !value
+        .iter()
+        .any(|v| matches!(v, BamlValueWithMeta::Temporal(..)))
+    {
+        return env.to_js_value(value);
+    }
+
+    let js_value = match value {
+        BamlValueWithMeta::Temporal(BamlTemporal::Duration(d), _) => env
+            .create_double(d.num_milliseconds() as f64 / 1000.0)?
+            .into_unknown(),
+        BamlValueWithMeta::Temporal(t, _) => {
+            let Some(BamlTemporal::DateTime(dt)) = t.cast_to(BamlTemporalType::DateTime) else {
+                return Err(napi::Error::from_reason(format!("Invalid {t}")));
+            };
+            env.create_date(dt.timestamp_millis() as f64)?
+                .into_unknown()
+        }
+        BamlValueWithMeta::List(items, _) => {
+            let mut array = env.create_array_with_length(items.len())?;
+            for (i, item) in items.iter().enumerate() {
+                array.set_element(i as u32, to_js_value(env, item)?)?;
+            }
+            array.into_unknown()
+        }
+        BamlValueWithMeta::Map(fields, checks) => {
+            // Maps carry their checks inline, like their JSON serialization.
+            let mut object = env.create_object()?;
+            for (key, value) in fields {
+                object.set_named_property(key, to_js_value(env, value)?)?;
+            }
+            if !checks.is_empty() {
+                object.set_named_property("checks", checks_to_js_value(env, checks)?)?;
+            }
+            return Ok(object.into_unknown());
+        }
+        BamlValueWithMeta::Class(_, fields, _) => {
+            let mut object = env.create_object()?;
+            for (key, value) in fields {
+                object.set_named_property(key, to_js_value(env, value)?)?;
+            }
+            object.into_unknown()
+        }
+        // Values without children can't contain temporals.
+        _ => return env.to_js_value(value),
+    };
+
+    let checks = value.meta();
+    if checks.is_empty() {
+        return Ok(js_value);
+    }
+    let mut object = env.create_object()?;
+    object.set_named_property("value", js_value)?;
+    object.set_named_property("checks", checks_to_js_value(env, checks)?)?;
+    Ok(object.into_unknown())
+}
+
+fn checks_to_js_value(env: Env, checks: &[ResponseCheck]) -> napi::Result<JsUnknown> {
+    let checks = checks
+        .iter()
+        .map(|check| (check.name.clone(), check))
+        .collect::<HashMap<_, _>>();
+    env.to_js_value(&checks)
+}
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use napi_derive::napi;
+
+crate::lang_wrapper!(HttpRequest, baml_runtime::HttpRequest);
+
+#[napi]
+impl HttpRequest {
+    #[napi(getter)]
+    pub fn get_url(&self) -> String {
+        self.inner.url.clone()
+    }
+
+    #[napi(getter)]
+    pub fn get_method(&self) -> String {
+        self.inner.method.clone()
+    }
+
+    #[napi(getter)]
+    pub fn get_headers(&self) -> HashMap<String, String> {
+        self.inner
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    #[napi(getter)]
+    pub fn get_body(&self) -> serde_json::Value {
+        self.inner.body.clone()
+    }
+}
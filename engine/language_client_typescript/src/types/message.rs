@@ -0,0 +1,95 @@
+use baml_types::{BamlMessagePart, BamlValue};
+use napi::bindgen_prelude::External;
+use napi::{Env, JsUnknown};
+use napi_derive::napi;
+use serde_json::json;
+
+use crate::errors::invalid_argument_error;
+use crate::parse_ts_types::jsunknown_to_baml_value;
+
+crate::lang_wrapper!(BamlMessage, baml_types::BamlMessage);
+
+fn message_part(value: BamlValue) -> napi::Result<BamlMessagePart> {
+    match value {
+        BamlValue::String(text) => Ok(BamlMessagePart::Text(text)),
+        BamlValue::Media(media) => Ok(BamlMessagePart::Media(media)),
+        other => Err(invalid_argument_error(&format!(
+            "Message content must be a string, Image, Audio, Pdf or Video, got {}",
+            other.r#type()
+        ))),
+    }
+}
+
+#[napi]
+impl BamlMessage {
+    /// `content` is a single part or a list of parts.
+    #[napi(
+        ts_args_type = "role: string, content: string | BamlImage | BamlAudio | BamlPdf | BamlVideo | Array<string | BamlImage | BamlAudio | BamlPdf | BamlVideo>, name?: string | undefined | null, metadata?: Record<string, any> | undefined | null",
+        ts_return_type = "BamlMessage"
+    )]
+    pub fn create(
+        env: Env,
+        role: String,
+        content: JsUnknown,
+        name: Option<String>,
+        metadata: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> napi::Result<External<BamlMessage>> {
+        let content = match jsunknown_to_baml_value(env, content, false)? {
+            Some(BamlValue::List(parts)) => parts
+                .into_iter()
+                .map(message_part)
+                .collect::<napi::Result<Vec<_>>>()?,
+            Some(part) => vec![message_part(part)?],
+            None => vec![],
+        };
+        let mut inner = baml_types::BamlMessage::new(role, content);
+        inner.name = name;
+        inner.metadata = metadata.unwrap_or_default().into_iter().collect();
+        Ok(External::new(BamlMessage { inner }))
+    }
+
+    #[napi(getter)]
+    pub fn role(&self) -> String {
+        self.inner.role.clone()
+    }
+
+    #[napi(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.inner.name.clone()
+    }
+
+    #[napi(js_name = "toJSON")]
+    pub fn to_json(&self) -> napi::Result<serde_json::Value> {
+        let content = self
+            .inner
+            .content
+            .iter()
+            .map(|part| match part {
+                BamlMessagePart::Text(text) => json!(text),
+                BamlMessagePart::Media(media) => match &media.content {
+                    baml_types::BamlMediaContent::Url(url) => json!({
+                        "type": media.media_type.to_string(),
+                        "url": url.url
+                    }),
+                    baml_types::BamlMediaContent::Base64(base64) => json!({
+                        "type": media.media_type.to_string(),
+                        "base64": base64.base64,
+                        "media_type": media.mime_type.clone().unwrap_or("".to_string())
+                    }),
+                    _ => "Unknown BamlMessage media part".into(),
+                },
+            })
+            .collect::<Vec<_>>();
+        let mut message = json!({
+            "role": self.inner.role,
+            "content": content,
+        });
+        if let Some(name) = &self.inner.name {
+            message["name"] = json!(name);
+        }
+        if !self.inner.metadata.is_empty() {
+            message["metadata"] = json!(self.inner.metadata);
+        }
+        Ok(message)
+    }
+}
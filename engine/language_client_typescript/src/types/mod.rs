@@ -4,8 +4,12 @@ pub mod audio;
 pub(crate) mod client_registry;
 pub(crate) mod function_result_stream;
 pub(crate) mod function_results;
+pub(crate) mod http_request;
 pub mod image;
+pub mod message;
+pub mod pdf;
 pub(crate) mod runtime_ctx_manager;
 pub(crate) mod span;
 pub(crate) mod trace_stats;
 pub(crate) mod type_builder;
+pub mod video;
@@ -23,6 +23,11 @@ impl RuntimeContextManager {
         Ok(())
     }
 
+    #[napi]
+    pub fn set_cost_budget(&self, per_call: Option<f64>, per_context: Option<f64>) {
+        self.inner.set_cost_budget(per_call, per_context);
+    }
+
     #[napi]
     pub fn deep_clone(&self) -> Self {
         RuntimeContextManager {
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use baml_runtime::type_builder::{self, WithMeta};
 use baml_types::BamlValue;
 use napi_derive::napi;
@@ -17,6 +19,35 @@ crate::lang_wrapper!(
 );
 crate::lang_wrapper!(FieldType, baml_types::FieldType, sync_thread_safe);
 
+/// The types added by `TypeBuilder.addJsonSchema`.
+#[napi]
+pub struct ImportedTypes {
+    root: Option<baml_types::FieldType>,
+    classes: HashMap<String, Vec<String>>,
+    enums: HashMap<String, Vec<String>>,
+}
+
+#[napi]
+impl ImportedTypes {
+    /// The type of the root schema, if the document describes one.
+    #[napi(getter)]
+    pub fn get_root(&self) -> Option<FieldType> {
+        self.root.clone().map(FieldType::from)
+    }
+
+    /// The names of the added classes and of their properties.
+    #[napi(getter)]
+    pub fn get_classes(&self) -> HashMap<String, Vec<String>> {
+        self.classes.clone()
+    }
+
+    /// The names of the added enums and of their values.
+    #[napi(getter)]
+    pub fn get_enums(&self) -> HashMap<String, Vec<String>> {
+        self.enums.clone()
+    }
+}
+
 impl Default for TypeBuilder {
     fn default() -> Self {
         Self::new()
@@ -115,6 +146,48 @@ impl TypeBuilder {
         )
         .into()
     }
+
+    /// Adds the classes and enums from a JSON Schema (or OpenAPI) document,
+    /// given as a JSON string. Types named like one in `taken` are renamed.
+    #[napi]
+    pub fn add_json_schema(
+        &self,
+        schema: String,
+        root_name: String,
+        taken: Vec<String>,
+    ) -> napi::Result<ImportedTypes> {
+        let schema: serde_json::Value = serde_json::from_str(&schema).map_err(|e| {
+            crate::errors::invalid_argument_error(&format!("Invalid JSON Schema: {}", e))
+        })?;
+        let taken = taken.into_iter().collect::<HashSet<_>>();
+        let import = self
+            .inner
+            .add_json_schema(&schema, &root_name, &taken)
+            .map_err(crate::errors::from_anyhow_error)?;
+        Ok(ImportedTypes {
+            root: import.root,
+            classes: import
+                .classes
+                .iter()
+                .map(|(name, c)| {
+                    (
+                        name.clone(),
+                        c.fields.iter().map(|f| f.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+            enums: import
+                .enums
+                .iter()
+                .map(|(name, e)| {
+                    (
+                        name.clone(),
+                        e.values.iter().map(|v| v.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+        })
+    }
 }
 
 #[napi]
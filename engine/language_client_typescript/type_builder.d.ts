@@ -8,6 +8,8 @@ export declare class TypeBuilder {
     private tb;
     protected classes: Set<string>;
     protected enums: Set<string>;
+    private importedClasses;
+    private importedEnums;
     constructor({ classes, enums }: {
         classes: Set<string>;
         enums: Set<string>;
@@ -24,6 +26,18 @@ export declare class TypeBuilder {
     list(type: FieldType): FieldType;
     map(keyType: FieldType, valueType: FieldType): FieldType;
     union(types: FieldType[]): FieldType;
+    /**
+     * Adds the classes and enums defined by a JSON Schema (or OpenAPI) document.
+     *
+     * Types named like an existing class or enum get a numbered name instead,
+     * e.g. `Person2`. The added types can then be looked up with `importedClass`
+     * and `importedEnum`.
+     *
+     * Returns the type of the root schema, if the document describes one.
+     */
+    addJsonSchema(schema: string | object, rootName?: string): FieldType | null;
+    importedClass(name: string): ClassBuilder<string>;
+    importedEnum(name: string): EnumBuilder<string>;
     classBuilder<Name extends string, Properties extends string>(name: Name, properties: Properties[]): ClassBuilder<Name, Properties>;
     enumBuilder<Name extends string, T extends string>(name: Name, values: T[]): EnumBuilder<Name, T>;
     addClass<Name extends string>(name: Name): ClassBuilder<Name>;
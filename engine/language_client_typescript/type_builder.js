@@ -6,6 +6,8 @@ class TypeBuilder {
     tb;
     classes;
     enums;
+    importedClasses = new Map();
+    importedEnums = new Map();
     constructor({ classes, enums }) {
         this.classes = classes;
         this.enums = enums;
@@ -47,6 +49,41 @@ class TypeBuilder {
     union(types) {
         return this.tb.union(types);
     }
+    /**
+     * Adds the classes and enums defined by a JSON Schema (or OpenAPI) document.
+     *
+     * Types named like an existing class or enum get a numbered name instead,
+     * e.g. `Person2`. The added types can then be looked up with `importedClass`
+     * and `importedEnum`.
+     *
+     * Returns the type of the root schema, if the document describes one.
+     */
+    addJsonSchema(schema, rootName = 'Root') {
+        const imported = this.tb.addJsonSchema(typeof schema === 'string' ? schema : JSON.stringify(schema), rootName, [...this.classes, ...this.enums]);
+        for (const [name, properties] of Object.entries(imported.classes)) {
+            this.classes.add(name);
+            this.importedClasses.set(name, new ClassBuilder(this.tb, name, new Set(properties)));
+        }
+        for (const [name, values] of Object.entries(imported.enums)) {
+            this.enums.add(name);
+            this.importedEnums.set(name, new EnumBuilder(this.tb, name, new Set(values)));
+        }
+        return imported.root;
+    }
+    importedClass(name) {
+        const cls = this.importedClasses.get(name);
+        if (!cls) {
+            throw new Error(`Class ${name} not found`);
+        }
+        return cls;
+    }
+    importedEnum(name) {
+        const enm = this.importedEnums.get(name);
+        if (!enm) {
+            throw new Error(`Enum ${name} not found`);
+        }
+        return enm;
+    }
     classBuilder(name, properties) {
         return new ClassBuilder(this.tb, name, new Set(properties));
     }
@@ -36,6 +36,11 @@ export class BamlCtxManager {
     manager.upsertTags(tags)
   }
 
+  setCostBudget(budget: { perCall?: number; perContext?: number }): void {
+    const manager = this.ctx.getStore()!
+    manager.setCostBudget(budget.perCall, budget.perContext)
+  }
+
   cloneContext(): RuntimeContextManager {
     let store = this.ctx.getStore()
     if (store === undefined) {
@@ -2,9 +2,13 @@ export {
   BamlRuntime,
   FunctionResult,
   FunctionResultStream,
+  HttpRequest,
   BamlImage as Image,
   ClientBuilder,
   BamlAudio as Audio,
+  BamlPdf as Pdf,
+  BamlVideo as Video,
+  BamlMessage as Message,
   invoke_runtime_cli,
   ClientRegistry,
   BamlLogEvent,
@@ -21,6 +21,8 @@ export class TypeBuilder {
   private tb: _TypeBuilder
   protected classes: Set<string>
   protected enums: Set<string>
+  private importedClasses = new Map<string, ClassBuilder<string>>()
+  private importedEnums = new Map<string, EnumBuilder<string>>()
 
   constructor({ classes, enums }: { classes: Set<string>; enums: Set<string> }) {
     this.classes = classes
@@ -76,6 +78,48 @@ export class TypeBuilder {
     return this.tb.union(types)
   }
 
+  /**
+   * Adds the classes and enums defined by a JSON Schema (or OpenAPI) document.
+   *
+   * Types named like an existing class or enum get a numbered name instead,
+   * e.g. `Person2`. The added types can then be looked up with `importedClass`
+   * and `importedEnum`.
+   *
+   * Returns the type of the root schema, if the document describes one.
+   */
+  addJsonSchema(schema: string | object, rootName = 'Root'): FieldType | null {
+    const imported = this.tb.addJsonSchema(
+      typeof schema === 'string' ? schema : JSON.stringify(schema),
+      rootName,
+      [...this.classes, ...this.enums],
+    )
+    for (const [name, properties] of Object.entries(imported.classes)) {
+      this.classes.add(name)
+      this.importedClasses.set(name, new ClassBuilder(this.tb, name, new Set(properties)))
+    }
+    for (const [name, values] of Object.entries(imported.enums)) {
+      this.enums.add(name)
+      this.importedEnums.set(name, new EnumBuilder(this.tb, name, new Set(values)))
+    }
+    return imported.root
+  }
+
+  importedClass(name: string): ClassBuilder<string> {
+    const cls = this.importedClasses.get(name)
+    if (!cls) {
+      throw new Error(`Class ${name} not found`)
+    }
+    return cls
+  }
+
+  importedEnum(name: string): EnumBuilder<string> {
+    const enm = this.importedEnums.get(name)
+    if (!enm) {
+      throw new Error(`Enum ${name} not found`)
+    }
+    return enm
+  }
+
   classBuilder<Name extends string, Properties extends string>(
     name: Name,
     properties: Properties[],
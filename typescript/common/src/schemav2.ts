@@ -13,6 +13,8 @@ export const llmOutputSchema = z.object({
     prompt_tokens: z.number().int().optional().nullable(),
     output_tokens: z.number().int().optional().nullable(),
     total_tokens: z.number().int().optional().nullable(),
+    cache_creation_input_tokens: z.number().int().optional().nullable(),
+    cache_read_input_tokens: z.number().int().optional().nullable(),
     finish_reason: z.string().optional().nullable(),
   }),
   override: z.any().optional().nullable(),
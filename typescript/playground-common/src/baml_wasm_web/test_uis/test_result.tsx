@@ -315,6 +315,10 @@ const LLMTestResult: React.FC<{ test: WasmTestResponse; doneStatus: DoneTestStat
       llm_response?.input_tokens && llm_response?.output_tokens,
       `${llm_response?.input_tokens} input tokens → ${llm_response?.output_tokens} output tokens`,
     ],
+    [
+      llm_response?.cache_read_input_tokens ?? llm_response?.cache_creation_input_tokens,
+      `${llm_response?.cache_read_input_tokens ?? 0} cached / ${llm_response?.cache_creation_input_tokens ?? 0} written to cache`,
+    ],
   ]
     .filter((x) => x[0] !== undefined)
     .map((x) => x[1])
@@ -133,7 +133,7 @@ const CurlSnippet: React.FC = () => {
   )
 }
 
-const WebviewMedia: React.FC<{ bamlMediaType: 'image' | 'audio'; media: WasmChatMessagePartMedia }> = ({
+const WebviewMedia: React.FC<{ bamlMediaType: 'image' | 'audio' | 'pdf' | 'video'; media: WasmChatMessagePartMedia }> = ({
   bamlMediaType,
   media,
 }) => {
@@ -194,6 +194,15 @@ const WebviewMedia: React.FC<{ bamlMediaType: 'image' | 'audio'; media: WasmChat
                 Your browser does not support the audio element.
               </audio>
             )
+          case 'pdf':
+            return <iframe src={mediaUrl} className='h-[400px] w-[400px]' />
+          case 'video':
+            return (
+              <video controls className='max-h-[400px] max-w-[400px]'>
+                <source src={mediaUrl} />
+                Your browser does not support the video element.
+              </video>
+            )
         }
       })()}
     </div>
@@ -275,6 +284,20 @@ const PromptPreview: React.FC = () => {
                   return <div key={idx}>Error loading audio: {media.content}</div>
                 return <WebviewMedia key={idx} bamlMediaType='audio' media={media} />
               }
+              if (part.is_pdf()) {
+                const media = part.as_media()
+                if (!media) return <div key={idx}>Error loading pdf: this chat message part is not media</div>
+                if (media.type === wasm?.WasmChatMessagePartMediaType.Error)
+                  return <div key={idx}>Error loading pdf: {media.content}</div>
+                return <WebviewMedia key={idx} bamlMediaType='pdf' media={media} />
+              }
+              if (part.is_video()) {
+                const media = part.as_media()
+                if (!media) return <div key={idx}>Error loading video: this chat message part is not media</div>
+                if (media.type === wasm?.WasmChatMessagePartMediaType.Error)
+                  return <div key={idx}>Error loading video: {media.content}</div>
+                return <WebviewMedia key={idx} bamlMediaType='video' media={media} />
+              }
               return null
             }
 